@@ -4,11 +4,11 @@ use heck::TitleCase;
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    self, DeriveInput, Fields, Variant, parse_macro_input,
+    self, DeriveInput, Field, Fields, Lit, Meta, NestedMeta, Variant, parse_macro_input,
     punctuated::Punctuated, token::Comma,
 };
 
-#[proc_macro_derive(RawUi)]
+#[proc_macro_derive(RawUi, attributes(rawui))]
 pub fn raw_ui_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
@@ -19,6 +19,52 @@ pub fn raw_ui_derive(input: TokenStream) -> TokenStream {
     }.into()
 }
 
+// #[rawui(skip)], #[rawui(readonly)], #[rawui(rename = "Nice Name")]
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    readonly: bool,
+    rename: Option<String>,
+}
+
+fn field_attrs(field: &Field) -> FieldAttrs {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("rawui")) {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => panic!("expected #[rawui(...)]"),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => attrs.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("readonly") => {
+                    attrs.readonly = true
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("rename") =>
+                {
+                    match name_value.lit {
+                        Lit::Str(rename) => attrs.rename = Some(rename.value()),
+                        _ => panic!("expected #[rawui(rename = \"...\")]"),
+                    }
+                }
+                _ => panic!("unknown rawui attribute, expected skip, readonly or rename"),
+            }
+        }
+    }
+    attrs
+}
+
+fn is_dummy(field: &Field) -> bool {
+    let is_hidden = field.ident.as_ref().unwrap().to_string().starts_with('_');
+    match &field.ty {
+        syn::Type::Path(type_path) if is_hidden => {
+            type_path.path.segments.last().is_some_and(|segment| segment.ident == "Dummy")
+        }
+        _ => false,
+    }
+}
+
 fn impl_raw_ui_struct(ast: &syn::DeriveInput, fields: &Fields) -> proc_macro2::TokenStream {
     let fields = match *fields {
         syn::Fields::Named(ref fields) => &fields.named,
@@ -27,24 +73,244 @@ fn impl_raw_ui_struct(ast: &syn::DeriveInput, fields: &Fields) -> proc_macro2::T
 
     let name = &ast.ident;
 
-    let draw_fields = fields.iter().filter_map(|f| {
-        if f.ident.as_ref().unwrap().to_string().starts_with('_') {
-            None
+    // (champ, label, lecture seule)
+    let drawn_fields: Vec<_> = fields
+        .iter()
+        .filter(|f| !f.ident.as_ref().unwrap().to_string().starts_with('_'))
+        .filter_map(|f| {
+            let attrs = field_attrs(f);
+            if attrs.skip {
+                return None;
+            }
+            let field_string = attrs
+                .rename
+                .unwrap_or_else(|| f.ident.as_ref().unwrap().to_string().to_title_case());
+            Some((f, field_string, attrs.readonly))
+        })
+        .collect();
+
+    // Les octets inconnus (`_osef: Dummy<4>`) ne sont dessinés qu'avec le profil développeur
+    let has_dummies = fields.iter().any(is_dummy);
+
+    // Une copie est dessinée à la place des champs en lecture seule, les modifications sont perdues
+    let readonly_copies = drawn_fields.iter().filter(|(_, _, readonly)| *readonly).map(|(f, _, _)| {
+        let field_name = &f.ident;
+        let copy_name = quote::format_ident!("readonly_{}", field_name.as_ref().unwrap());
+        quote! {
+            let mut #copy_name = crate::save_data::ReadOnly(self.#field_name.clone());
+        }
+    });
+
+    let revert_fields = drawn_fields.iter().filter(|(_, _, readonly)| !*readonly).map(|(f, field_string, _)| {
+        let field_name = &f.ident;
+        quote! {
+            #field_string => crate::save_data::RawUi::revert(&mut self.#field_name, &original.#field_name, path)
+        }
+    });
+
+    let watch_fields = drawn_fields.iter().map(|(f, field_string, readonly)| {
+        let field_name = &f.ident;
+        if *readonly {
+            quote! {
+                #field_string => crate::save_data::RawUi::draw_watch(&mut crate::save_data::ReadOnly(self.#field_name.clone()), gui, ident, path)
+            }
         } else {
-            let field_name = &f.ident;
-            let field_string = field_name.as_ref().unwrap().to_string().to_title_case();
-            Some(quote! {
+            quote! {
+                #field_string => crate::save_data::RawUi::draw_watch(&mut self.#field_name, gui, ident, path)
+            }
+        }
+    });
+
+    let collect_fields = drawn_fields.iter().map(|(f, field_string, _)| {
+        let field_name = &f.ident;
+        quote! {
+            path.push(String::from(#field_string));
+            crate::save_data::RawUi::collect_values(&self.#field_name, path, values);
+            path.pop();
+        }
+    });
+
+    let draw_fields = drawn_fields.iter().map(|(f, field_string, readonly)| {
+        let field_name = &f.ident;
+        if *readonly {
+            let copy_name = quote::format_ident!("readonly_{}", field_name.as_ref().unwrap());
+            quote! {
+                (&mut #copy_name as &mut dyn crate::save_data::RawUi, #field_string)
+            }
+        } else {
+            quote! {
                 (&mut self.#field_name as &mut dyn crate::save_data::RawUi, #field_string)
-            })
+            }
+        }
+    });
+
+    let visit_fields = drawn_fields.iter().filter(|(_, _, readonly)| !*readonly).map(|(f, field_string, _)| {
+        let field_name = &f.ident;
+        quote! {
+            path.push(String::from(#field_string));
+            crate::save_data::RawUi::visit_strings(&mut self.#field_name, path, visit)?;
+            path.pop();
         }
     });
 
+    // Vue tableau, une colonne par champ
+    let column_names = drawn_fields.iter().map(|(_, field_string, _)| field_string);
+
+    let cell_fields = drawn_fields.iter().enumerate().map(|(i, (f, _, readonly))| {
+        let field_name = &f.ident;
+        if *readonly {
+            quote! {
+                #i => crate::save_data::RawUi::draw_raw_ui(&mut crate::save_data::ReadOnly(self.#field_name.clone()), gui, ident)
+            }
+        } else {
+            quote! {
+                #i => crate::save_data::RawUi::draw_raw_ui(&mut self.#field_name, gui, ident)
+            }
+        }
+    });
+
+    let cell_values = drawn_fields.iter().enumerate().map(|(i, (f, _, _))| {
+        let field_name = &f.ident;
+        quote! {
+            #i => crate::save_data::RawUi::collect_values(&self.#field_name, &mut Vec::new(), &mut values)
+        }
+    });
+
+    // Tous les champs dans l'ordre du fichier, pour l'export du schéma
+    let describe_fields = fields.iter().map(|f| {
+        let field_name = f.ident.as_ref().unwrap().to_string();
+        let ty = &f.ty;
+        let drawn = drawn_fields.iter().find(|(drawn, _, _)| drawn.ident == f.ident);
+        let (title, readonly, type_desc) = match drawn {
+            Some((_, field_string, readonly)) => (
+                quote! { Some(#field_string) },
+                *readonly,
+                quote! { <#ty as crate::save_data::RawUi>::describe(definitions) },
+            ),
+            None if is_dummy(f) => (
+                quote! { None },
+                false,
+                quote! { <#ty as crate::save_data::RawUi>::describe(definitions) },
+            ),
+            None => (
+                quote! { None },
+                false,
+                quote! { crate::save_data::schema::TypeDesc::Opaque(std::any::type_name::<#ty>()) },
+            ),
+        };
+        quote! {
+            crate::save_data::schema::FieldDesc {
+                name: #field_name,
+                title: #title,
+                readonly: #readonly,
+                type_desc: #type_desc,
+            }
+        }
+    });
+
+    let draw_struct = if has_dummies {
+        let mut draw_fields = draw_fields;
+        let push_fields = fields.iter().filter_map(|f| {
+            let field_name = &f.ident;
+            if is_dummy(f) {
+                let field_string = field_name.as_ref().unwrap().to_string();
+                Some(quote! {
+                    if developer {
+                        fields.push((&mut self.#field_name as &mut dyn crate::save_data::RawUi, #field_string));
+                    }
+                })
+            } else if drawn_fields.iter().any(|(drawn, _, _)| drawn.ident == f.ident) {
+                let draw_field = draw_fields.next().unwrap();
+                Some(quote! {
+                    fields.push(#draw_field);
+                })
+            } else {
+                None
+            }
+        }).collect::<Vec<_>>();
+
+        quote! {
+            let developer = gui.shows(crate::gui::settings::Widgets::Developer);
+            let mut fields: Vec<(&mut dyn crate::save_data::RawUi, &str)> = Vec::new();
+            #(#push_fields)*
+            gui.draw_struct(ident, &mut fields);
+        }
+    } else {
+        quote! {
+            let mut fields = [#(#draw_fields),*];
+            gui.draw_struct(ident, &mut fields);
+        }
+    };
+
     quote! {
         #[automatically_derived]
         impl crate::save_data::RawUi for #name {
             fn draw_raw_ui(&mut self, gui: &crate::gui::Gui, ident: &str) {
-                let mut fields = [#(#draw_fields),*];
-                gui.draw_struct(ident, &mut fields);
+                #(#readonly_copies)*
+                #draw_struct
+            }
+
+            fn revert(&mut self, original: &Self, path: &[String]) {
+                match path.split_first() {
+                    Some((field, path)) => match field.as_str() {
+                        #(#revert_fields,)*
+                        _ => (),
+                    },
+                    None => *self = original.clone(),
+                }
+            }
+
+            fn draw_watch(&mut self, gui: &crate::gui::Gui, ident: &str, path: &[String]) {
+                match path.split_first() {
+                    Some((field, path)) => match field.as_str() {
+                        #(#watch_fields,)*
+                        _ => (),
+                    },
+                    None => crate::save_data::RawUi::draw_raw_ui(self, gui, ident),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn visit_strings(
+                &mut self, path: &mut Vec<String>,
+                visit: &mut dyn FnMut(&[String], &mut imgui::ImString),
+            ) -> anyhow::Result<()> {
+                #(#visit_fields)*
+                Ok(())
+            }
+
+            fn table_columns() -> Option<&'static [&'static str]> {
+                Some(&[#(#column_names),*])
+            }
+
+            fn draw_table_cell(&mut self, gui: &crate::gui::Gui, ident: &str, column: usize) {
+                match column {
+                    #(#cell_fields,)*
+                    _ => (),
+                }
+            }
+
+            // Seulement pour les champs simples, les structures n'ont pas de valeur à la racine
+            fn table_cell_value(&self, column: usize) -> Option<String> {
+                let mut values = crate::save_data::OriginalValues::new();
+                match column {
+                    #(#cell_values,)*
+                    _ => (),
+                }
+                values.remove(&Vec::new())
+            }
+
+            #[allow(unused_variables)]
+            fn collect_values(
+                &self, path: &mut Vec<String>, values: &mut crate::save_data::OriginalValues,
+            ) {
+                #(#collect_fields)*
+            }
+
+            fn describe(
+                definitions: &mut crate::save_data::schema::Definitions,
+            ) -> crate::save_data::schema::TypeDesc {
+                definitions.define_struct::<Self>(|definitions| vec![#(#describe_fields),*])
             }
         }
     }
@@ -64,6 +330,9 @@ fn impl_raw_ui_enum(
 
     let variant_number = array_variants.len();
 
+    let variant_names = variants.iter().map(|v| v.ident.to_string());
+    let describe_variants = variants.iter().map(|v| v.ident.to_string());
+
     let edit_variants = variants.iter().enumerate().map(|(i, v)| {
         let variant = &v.ident;
         quote! {
@@ -86,6 +355,23 @@ fn impl_raw_ui_enum(
                     };
                 }
             }
+
+            fn collect_values(
+                &self, path: &mut Vec<String>, values: &mut crate::save_data::OriginalValues,
+            ) {
+                const ITEMS: [&str; #variant_number] = [#(#variant_names),*];
+                values.insert(path.clone(), ITEMS[self.clone() as usize].to_owned());
+            }
+
+            fn is_flat() -> bool {
+                true
+            }
+
+            fn describe(
+                definitions: &mut crate::save_data::schema::Definitions,
+            ) -> crate::save_data::schema::TypeDesc {
+                definitions.define_enum::<Self>(&[#(#describe_variants),*])
+            }
         }
     }
 }
@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::{fs, sync::Semaphore, task};
+
+// Comme la compression des saves ME1, global car réglé dans les paramètres
+pub const DEFAULT_WORKER_THREADS: usize = 4;
+pub const MAX_WORKER_THREADS: usize = 32;
+static WORKER_THREADS: AtomicUsize = AtomicUsize::new(DEFAULT_WORKER_THREADS);
+
+// 0 pour la valeur par défaut
+pub fn set_worker_threads(threads: usize) {
+    let threads = match threads {
+        0 => DEFAULT_WORKER_THREADS,
+        threads => threads.min(MAX_WORKER_THREADS),
+    };
+    WORKER_THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn worker_threads() -> usize {
+    WORKER_THREADS.load(Ordering::Relaxed)
+}
+
+// Traite chaque fichier sur le pool de threads bloquants de tokio, `worker_threads()` à la fois.
+// L'erreur ou le panic d'un fichier n'interrompt pas les autres, les résultats suivent l'ordre de `paths`.
+// `progress` reçoit le nombre de fichiers terminés, une erreur annule les fichiers restants.
+pub async fn process_files<T, F>(
+    paths: Vec<PathBuf>, job: F, mut progress: impl FnMut(usize) -> Result<()>,
+) -> Result<Vec<(PathBuf, Result<T>)>>
+where
+    T: Send + 'static,
+    F: Fn(&Path, Vec<u8>) -> Result<T> + Send + Sync + 'static,
+{
+    let job = Arc::new(job);
+    let semaphore = Arc::new(Semaphore::new(worker_threads()));
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let handles: Vec<_> = paths
+        .iter()
+        .cloned()
+        .map(|path| {
+            let job = Arc::clone(&job);
+            let semaphore = Arc::clone(&semaphore);
+            let cancelled = Arc::clone(&cancelled);
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                if cancelled.load(Ordering::Relaxed) {
+                    return Ok(None);
+                }
+                let input = fs::read(&path)
+                    .await
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let result = task::spawn_blocking(move || job(&path, input)).await?;
+                result.map(Some)
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(paths.len());
+    for (i, (path, handle)) in paths.into_iter().zip(handles).enumerate() {
+        let result = match handle.await {
+            Ok(result) => result,
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = progress(i + 1) {
+            cancelled.store(true, Ordering::Relaxed);
+            return Err(err);
+        }
+        // `None` seulement après une annulation
+        if let Some(result) = result.transpose() {
+            results.push((path, result));
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::{bail, Result};
+
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn process_files_isolates_errors() -> Result<()> {
+        let paths = vec![
+            PathBuf::from("test/ME3Save.pcsav"),
+            PathBuf::from("test/ME2Save.pcsav"),
+            PathBuf::from("test/missing.pcsav"),
+            PathBuf::from("test/ME3Save.pcsav"),
+        ];
+
+        let mut done = Vec::new();
+        let results = process_files(
+            paths.clone(),
+            |_, input| {
+                let _: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+                Ok(())
+            },
+            |count| {
+                done.push(count);
+                Ok(())
+            },
+        )
+        .await?;
+
+        assert_eq!(done, [1, 2, 3, 4]);
+        let read: Vec<_> = results.iter().map(|(_, result)| result.is_ok()).collect();
+        assert_eq!(read, [true, false, false, true]);
+        assert!(results.iter().map(|(path, _)| path).eq(&paths));
+
+        // Un panic reste limité à son fichier
+        let results = process_files(
+            paths[..2].to_vec(),
+            |path, _| {
+                if path.ends_with("ME2Save.pcsav") {
+                    panic!("corrupt save");
+                }
+                Ok(())
+            },
+            |_| Ok(()),
+        )
+        .await?;
+        assert!(results[0].1.is_ok() && results[1].1.is_err());
+
+        // Annulé après le premier fichier
+        let cancelled = process_files(
+            paths,
+            |_, _| Ok(()),
+            |count| match count {
+                1 => Ok(()),
+                _ => bail!("Cancelled"),
+            },
+        )
+        .await;
+        assert!(cancelled.is_err());
+        Ok(())
+    }
+}
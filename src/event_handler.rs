@@ -1,66 +1,342 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use crc::{Crc, CRC_32_BZIP2};
 use flume::{Receiver, Sender};
 use ron::ser::PrettyConfig;
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
 use tokio::{
     fs::{self, File},
     io::{AsyncReadExt, AsyncWriteExt},
+    task::JoinHandle,
+    time,
 };
+use tracing::{debug, error, info, warn};
 
 use crate::{
-    gui::UiEvent,
+    batch,
+    gui::{help::HelpDb, presets, settings::Settings, UiEvent},
     save_data::{
-        mass_effect_1::{item_db::Me1ItemDb, plot_db::Me1PlotDb, Me1SaveGame},
-        mass_effect_1_leg::Me1LegSaveGame,
-        mass_effect_2::{
-            plot_db::Me2PlotDb, Me2LegSaveGame, Me2LegVersion, Me2SaveGame, Me2Version,
+        mass_effect_1::{
+            item_db::Me1ItemDb, plot_db::Me1PlotDb, plot_labels::Me1PlotLabels, zip_writer,
+            Me1SaveGame, ZipMember,
+        },
+        mass_effect_1_leg::{Me1LegSaveData, Me1LegSaveGame},
+        mass_effect_2::{plot_db::Me2PlotDb, Me2LegSaveGame, Me2LegVersion, Me2SaveGame},
+        mass_effect_3::{
+            career_audit::{CareerAudit, CareerSave},
+            gear_db::Me3GearDb,
+            known_issues::KnownIssuesDb,
+            plot_db::Me3PlotDb,
+            Me3SaveGame,
+        },
+        shared::{
+            appearance::HeadMorph,
+            cloud_sync,
+            folder_watch::FolderWatch,
+            forward_compat::{self, ForwardCompat},
+            launcher_config::{LauncherConfig, LAUNCHER_CONFIG_FILE},
+            map_db::MapDb,
+            patch,
+            plot::BoolVec,
+            recipe::{Recipe, RecipeDb},
+            romance::RomanceDb,
+            save_format::{self, SaveFormat},
+            save_slot::{SaveSlot, SlotNaming},
+            squad::SquadDb,
+            synthetic,
+            tlk::TlkFile,
+            SaveTimeStamp,
         },
-        mass_effect_3::{plot_db::Me3PlotDb, Me3SaveGame},
-        shared::appearance::HeadMorph,
+        RawUi,
     },
     unreal,
 };
 
+const SETTINGS_PATH: &str = "settings.ron";
+const DATABASES_DIR: &str = "databases";
+const AUTOSAVE_SUFFIX: &str = ".tse-autosave";
+const UNKNOWN_FORMAT: &str =
+    "Unknown save format, only the latest version of each game is supported";
+const WATCH_FOLDER_INTERVAL: Duration = Duration::from_secs(2);
+pub const NOTES_SUFFIX: &str = ".tse-notes";
+// Une save fait moins d'1 Mo, au-delà ce n'en est pas une
+const MAX_DOWNLOAD_SIZE: usize = 8 * 1024 * 1024;
+
 pub enum MainEvent {
     OpenSave(String),
+    OpenSaveForwardCompat(String),
+    OpenSaveAs(String, SaveFormat),
+    OpenStdin,
+    OpenUrl(String),
     SaveSave(String, SaveGame),
+    SaveNewSlot(SaveGame),
     LoadDatabases,
     ImportHeadMorph(String),
     ExportHeadMorph(String, Box<HeadMorph>),
+    ImportAppearanceCode(String),
+    ExportAppearanceCode(Box<HeadMorph>),
+    LoadSettings,
+    SaveSettings(Settings),
+    ExportTextFile(String, String),
+    ExportBinaryFile(String, Vec<u8>),
+    ExportPatch(String, SaveGame),
+    ApplyPatch(String, String),
+    ListSaveSlots(PathBuf),
+    DuplicateSave(PathBuf, PathBuf),
+    RenameSave(PathBuf, PathBuf),
+    DeleteSave(PathBuf),
+    OpenTlk(String),
+    TestInGame(SaveGame, Option<PathBuf>),
+    PromoteTestSlot(SaveGame, PathBuf),
+    DiscardTestSlot(PathBuf),
+    Autosave(SaveGame),
+    OpenMe1CopySource(String),
+    ImportMe1PlotLabels(String),
+    OpenMergeSource(String),
+    OpenSquadBuildSource(String),
+    ImportMe1Properties(String, i32),
+    ImportMe1ZipMember(String, ZipMember),
+    LoadNotes(String),
+    SaveNotes(String, String),
+    CreateWorkingCopy(String),
+    SaveWorkingCopy(String, SaveGame, PathBuf),
+    OpenLauncherConfig(PathBuf),
+    SaveLauncherConfig(PathBuf, LauncherConfig),
+    ExportDatabases(PathBuf),
+    GenerateTestSaves(PathBuf),
+    AuditCareer(PathBuf),
+    StartWatchFolder(PathBuf, Vec<Recipe>),
+    StopWatchFolder,
+    #[cfg(target_os = "windows")]
+    RegisterShellMenu,
+    #[cfg(target_os = "windows")]
+    UnregisterShellMenu,
 }
 
 #[derive(Clone)]
 pub enum SaveGame {
-    MassEffect1 { file_path: String, save_game: Box<Me1SaveGame> },
-    MassEffect1Leg { file_path: String, save_game: Box<Me1LegSaveGame> },
-    MassEffect2 { file_path: String, save_game: Box<Me2SaveGame> },
-    MassEffect2Leg { file_path: String, save_game: Box<Me2LegSaveGame> },
-    MassEffect3 { file_path: String, save_game: Box<Me3SaveGame> },
+    MassEffect1 {
+        file_path: String,
+        save_game: Box<Me1SaveGame>,
+    },
+    MassEffect1Leg {
+        file_path: String,
+        save_game: Box<Me1LegSaveGame>,
+    },
+    MassEffect2 {
+        file_path: String,
+        save_game: Box<Me2SaveGame>,
+    },
+    MassEffect2Leg {
+        file_path: String,
+        save_game: Box<Me2LegSaveGame>,
+        forward_compat: Option<ForwardCompat>,
+    },
+    MassEffect3 {
+        file_path: String,
+        save_game: Box<Me3SaveGame>,
+        forward_compat: Option<ForwardCompat>,
+    },
+}
+
+impl SaveGame {
+    pub fn file_path(&self) -> &str {
+        match self {
+            SaveGame::MassEffect1 { file_path, .. }
+            | SaveGame::MassEffect1Leg { file_path, .. }
+            | SaveGame::MassEffect2 { file_path, .. }
+            | SaveGame::MassEffect2Leg { file_path, .. }
+            | SaveGame::MassEffect3 { file_path, .. } => file_path,
+        }
+    }
+
+    pub fn file_path_mut(&mut self) -> &mut String {
+        match self {
+            SaveGame::MassEffect1 { file_path, .. }
+            | SaveGame::MassEffect1Leg { file_path, .. }
+            | SaveGame::MassEffect2 { file_path, .. }
+            | SaveGame::MassEffect2Leg { file_path, .. }
+            | SaveGame::MassEffect3 { file_path, .. } => file_path,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SaveGame::MassEffect1 { .. } => "Mass Effect 1",
+            SaveGame::MassEffect1Leg { .. } => "Mass Effect 1 Legendary",
+            SaveGame::MassEffect2 { .. } => "Mass Effect 2",
+            SaveGame::MassEffect2Leg { .. } => "Mass Effect 2 Legendary",
+            SaveGame::MassEffect3 { .. } => "Mass Effect 3",
+        }
+    }
+
+    // La date affichée par le chargement du jeu, pour qu'une copie ne se confonde pas avec l'original
+    pub fn renew_timestamp(&mut self) {
+        let timestamp = match self {
+            // Pas de date dans les données lues de la save ME1
+            SaveGame::MassEffect1 { .. } => return,
+            SaveGame::MassEffect1Leg { save_game, .. } => &mut save_game.save_data.timestamp,
+            SaveGame::MassEffect2 { save_game, .. } => &mut save_game.timestamp,
+            SaveGame::MassEffect2Leg { save_game, .. } => &mut save_game.timestamp,
+            SaveGame::MassEffect3 { save_game, .. } => &mut save_game.timestamp,
+        };
+        *timestamp = SaveTimeStamp::now();
+    }
+
+    pub fn forward_compat(&self) -> Option<&ForwardCompat> {
+        match self {
+            SaveGame::MassEffect2Leg { forward_compat, .. }
+            | SaveGame::MassEffect3 { forward_compat, .. } => forward_compat.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn plot_booleans_mut(&mut self) -> &mut BoolVec {
+        match self {
+            SaveGame::MassEffect1 { save_game, .. } => &mut save_game.state.plot.bool_variables,
+            SaveGame::MassEffect1Leg { save_game, .. } => {
+                &mut save_game.save_data.plot.bool_variables
+            }
+            SaveGame::MassEffect2 { save_game, .. } => &mut save_game.plot.bool_variables,
+            SaveGame::MassEffect2Leg { save_game, .. } => &mut save_game.plot.bool_variables,
+            SaveGame::MassEffect3 { save_game, .. } => &mut save_game.plot.bool_variables,
+        }
+    }
+
+    // Ce qui est affiché dans l'onglet Raw
+    pub fn raw_data_mut(&mut self) -> &mut dyn RawUi {
+        match self {
+            SaveGame::MassEffect1 { save_game, .. } => &mut save_game.state,
+            SaveGame::MassEffect1Leg { save_game, .. } => &mut save_game.save_data,
+            SaveGame::MassEffect2 { save_game, .. } => save_game.as_mut(),
+            SaveGame::MassEffect2Leg { save_game, .. } => save_game.as_mut(),
+            SaveGame::MassEffect3 { save_game, .. } => save_game.as_mut(),
+        }
+    }
+
+    // Une save d'essai / démo est ensuite écrite au format du jeu complet
+    pub fn rescue_variant(&mut self) {
+        if let SaveGame::MassEffect2Leg { forward_compat, .. }
+        | SaveGame::MassEffect3 { forward_compat, .. } = self
+        {
+            if forward_compat.as_ref().map(ForwardCompat::is_variant).unwrap_or_default() {
+                *forward_compat = None;
+            }
+        }
+    }
+}
+
+// Les octets lus à l'ouverture, la save d'origine n'est désérialisée que quand le Raw ou un revert
+// en a besoin plutôt que clonée à chaque ouverture
+pub struct OriginalSave {
+    input: Vec<u8>,
+    format: OriginalFormat,
+}
+
+enum OriginalFormat {
+    Known(SaveFormat),
+    Detected,
+    ForwardCompat(i32),
+}
+
+impl OriginalSave {
+    pub fn deserialize(&self, file_path: &str) -> Result<SaveGame> {
+        let input = &self.input;
+        match self.format {
+            OriginalFormat::Known(format) => {
+                deserialize_save_as(file_path.to_owned(), input, format)
+            }
+            OriginalFormat::Detected => deserialize_save(file_path, input)?
+                .context("The original save is newer than the editor"),
+            OriginalFormat::ForwardCompat(known_version) => {
+                deserialize_forward_compat(file_path.to_owned(), input, known_version)
+            }
+        }
+    }
 }
 
 pub async fn event_loop(rx: Receiver<MainEvent>, ui_addr: Sender<UiEvent>) {
+    // Tâche de fond du dossier surveillé
+    let mut auto_patch: Option<JoinHandle<()>> = None;
     while let Ok(event) = rx.recv_async().await {
         let result = async {
             let ui_addr = Sender::clone(&ui_addr);
             match event {
-                MainEvent::OpenSave(path) => tokio::spawn(open_save(path, ui_addr)).await?,
+                MainEvent::OpenSave(path) => tokio::spawn(open_save(path, false, ui_addr)).await?,
+                MainEvent::OpenSaveForwardCompat(path) => {
+                    tokio::spawn(open_save(path, true, ui_addr)).await?
+                }
+                MainEvent::OpenSaveAs(path, format) => {
+                    tokio::spawn(open_save_as(path, format, ui_addr)).await?
+                }
+                MainEvent::OpenStdin => tokio::spawn(open_stdin(ui_addr)).await?,
+                MainEvent::OpenUrl(url) => tokio::spawn(open_url(url, ui_addr)).await?,
                 MainEvent::SaveSave(path, save_game) => {
                     tokio::spawn(save_save(path, save_game, ui_addr)).await?
                 }
+                MainEvent::SaveNewSlot(save_game) => {
+                    tokio::spawn(save_new_slot(save_game, ui_addr)).await?
+                }
                 MainEvent::LoadDatabases => {
                     let me1_plot_handle = tokio::spawn(load_me1_plot_db(Sender::clone(&ui_addr)));
                     let me1_item_handle = tokio::spawn(load_me1_item_db(Sender::clone(&ui_addr)));
                     let me2_handle = tokio::spawn(load_me2_plot_db(Sender::clone(&ui_addr)));
-                    let me3_handle = tokio::spawn(load_me3_plot_db(ui_addr));
+                    let map_handle = tokio::spawn(load_map_db(Sender::clone(&ui_addr)));
+                    let me3_handle = tokio::spawn(load_me3_plot_db(Sender::clone(&ui_addr)));
+                    let recipe_handle = tokio::spawn(load_recipe_db(Sender::clone(&ui_addr)));
+                    let me3_known_issues_handle =
+                        tokio::spawn(load_me3_known_issues(Sender::clone(&ui_addr)));
+                    let me3_gear_handle = tokio::spawn(load_me3_gear_db(Sender::clone(&ui_addr)));
+                    let romance_handle = tokio::spawn(load_romance_db(Sender::clone(&ui_addr)));
+                    let squad_handle = tokio::spawn(load_squad_db(Sender::clone(&ui_addr)));
+                    let help_handle = tokio::spawn(load_help_db(ui_addr));
 
-                    let (me1_plot_result, me1_item_result, me2_result, me3_result) =
-                        tokio::join!(me1_plot_handle, me1_item_handle, me2_handle, me3_handle);
+                    let (
+                        me1_plot_result,
+                        me1_item_result,
+                        me2_result,
+                        map_result,
+                        me3_result,
+                        recipe_result,
+                        me3_known_issues_result,
+                        me3_gear_result,
+                        romance_result,
+                        squad_result,
+                        help_result,
+                    ) = tokio::join!(
+                        me1_plot_handle,
+                        me1_item_handle,
+                        me2_handle,
+                        map_handle,
+                        me3_handle,
+                        recipe_handle,
+                        me3_known_issues_handle,
+                        me3_gear_handle,
+                        romance_handle,
+                        squad_handle,
+                        help_handle
+                    );
 
                     me1_plot_result?.context("Failed to parse databases/me1_plot_db.ron")?;
                     me1_item_result?.context("Failed to parse databases/me1_item_db.ron")?;
                     me2_result?.context("Failed to parse databases/me2_plot_db.ron")?;
-                    me3_result?.context("Failed to parse databases/me3_plot_db.ron")
+                    map_result?.context("Failed to parse databases/map_db.ron")?;
+                    me3_result?.context("Failed to parse databases/me3_plot_db.ron")?;
+                    recipe_result?.context("Failed to parse databases/recipes.ron")?;
+                    me3_known_issues_result?
+                        .context("Failed to parse databases/me3_known_issues.ron")?;
+                    me3_gear_result?.context("Failed to parse databases/me3_gear_db.ron")?;
+                    romance_result?.context("Failed to parse databases/romance_rules.ron")?;
+                    squad_result?.context("Failed to parse databases/squad_db.ron")?;
+                    help_result?.context("Failed to parse databases/help.ron")
                 }
                 MainEvent::ImportHeadMorph(path) => {
                     tokio::spawn(import_head_morph(path, ui_addr)).await?
@@ -68,68 +344,358 @@ pub async fn event_loop(rx: Receiver<MainEvent>, ui_addr: Sender<UiEvent>) {
                 MainEvent::ExportHeadMorph(path, head_morph) => {
                     tokio::spawn(export_head_morph(path, head_morph, ui_addr)).await?
                 }
+                MainEvent::ImportAppearanceCode(code) => {
+                    tokio::spawn(import_appearance_code(code, ui_addr)).await?
+                }
+                MainEvent::ExportAppearanceCode(head_morph) => {
+                    tokio::spawn(export_appearance_code(head_morph, ui_addr)).await?
+                }
+                MainEvent::LoadSettings => tokio::spawn(load_settings(ui_addr)).await?,
+                MainEvent::SaveSettings(settings) => tokio::spawn(save_settings(settings)).await?,
+                MainEvent::ExportTextFile(path, text) => {
+                    tokio::spawn(export_text_file(path, text, ui_addr)).await?
+                }
+                MainEvent::ExportBinaryFile(path, data) => {
+                    tokio::spawn(export_binary_file(path, data, ui_addr)).await?
+                }
+                MainEvent::ExportPatch(path, save_game) => {
+                    tokio::spawn(export_patch(path, save_game, ui_addr)).await?
+                }
+                MainEvent::ApplyPatch(patch_path, file_path) => {
+                    tokio::spawn(apply_patch(patch_path, file_path, ui_addr)).await?
+                }
+                MainEvent::ListSaveSlots(dir) => {
+                    tokio::spawn(list_save_slots(dir, ui_addr)).await?
+                }
+                MainEvent::DuplicateSave(from, to) => {
+                    tokio::spawn(duplicate_save(from, to, ui_addr)).await?
+                }
+                MainEvent::RenameSave(from, to) => {
+                    tokio::spawn(rename_save(from, to, ui_addr)).await?
+                }
+                MainEvent::DeleteSave(path) => tokio::spawn(delete_save(path, ui_addr)).await?,
+                MainEvent::OpenTlk(path) => tokio::spawn(open_tlk(path, ui_addr)).await?,
+                MainEvent::TestInGame(save_game, test_path) => {
+                    tokio::spawn(test_in_game(save_game, test_path, ui_addr)).await?
+                }
+                MainEvent::PromoteTestSlot(save_game, test_path) => {
+                    tokio::spawn(promote_test_slot(save_game, test_path, ui_addr)).await?
+                }
+                MainEvent::DiscardTestSlot(test_path) => {
+                    tokio::spawn(discard_test_slot(test_path, ui_addr)).await?
+                }
+                MainEvent::Autosave(save_game) => tokio::spawn(autosave(save_game)).await?,
+                MainEvent::OpenMe1CopySource(path) => {
+                    tokio::spawn(open_me1_copy_source(path, ui_addr)).await?
+                }
+                MainEvent::ImportMe1PlotLabels(path) => {
+                    tokio::spawn(import_me1_plot_labels(path, ui_addr)).await?
+                }
+                MainEvent::OpenMergeSource(path) => {
+                    tokio::spawn(open_merge_source(path, ui_addr)).await?
+                }
+                MainEvent::OpenSquadBuildSource(path) => {
+                    tokio::spawn(open_squad_build_source(path, ui_addr)).await?
+                }
+                MainEvent::ImportMe1Properties(path, object_id) => {
+                    tokio::spawn(import_me1_properties(path, object_id, ui_addr)).await?
+                }
+                MainEvent::ImportMe1ZipMember(path, member) => {
+                    tokio::spawn(import_me1_zip_member(path, member, ui_addr)).await?
+                }
+                MainEvent::LoadNotes(file_path) => {
+                    tokio::spawn(load_notes(file_path, ui_addr)).await?
+                }
+                MainEvent::SaveNotes(file_path, notes) => {
+                    tokio::spawn(save_notes(file_path, notes, ui_addr)).await?
+                }
+                MainEvent::CreateWorkingCopy(file_path) => {
+                    tokio::spawn(create_working_copy(file_path, ui_addr)).await?
+                }
+                MainEvent::SaveWorkingCopy(path, save_game, original) => {
+                    tokio::spawn(save_working_copy(path, save_game, original, ui_addr)).await?
+                }
+                MainEvent::OpenLauncherConfig(career_dir) => {
+                    tokio::spawn(open_launcher_config(career_dir, ui_addr)).await?
+                }
+                MainEvent::SaveLauncherConfig(path, config) => {
+                    tokio::spawn(save_launcher_config(path, config, ui_addr)).await?
+                }
+                MainEvent::ExportDatabases(dir) => {
+                    tokio::spawn(export_databases(dir, ui_addr)).await?
+                }
+                MainEvent::GenerateTestSaves(dir) => {
+                    tokio::spawn(generate_test_saves(dir, ui_addr)).await?
+                }
+                MainEvent::AuditCareer(dir) => tokio::spawn(audit_career(dir, ui_addr)).await?,
+                MainEvent::StartWatchFolder(dir, recipes) => {
+                    if let Some(handle) = auto_patch.take() {
+                        handle.abort();
+                    }
+                    info!("Watching {}", dir.display());
+                    auto_patch = Some(tokio::spawn(auto_patch_folder(dir, recipes, ui_addr)));
+                    Ok(())
+                }
+                MainEvent::StopWatchFolder => {
+                    if let Some(handle) = auto_patch.take() {
+                        handle.abort();
+                        info!("Stopped watching");
+                    }
+                    Ok(())
+                }
+                #[cfg(target_os = "windows")]
+                MainEvent::RegisterShellMenu => register_shell_menu(true, ui_addr).await,
+                #[cfg(target_os = "windows")]
+                MainEvent::UnregisterShellMenu => register_shell_menu(false, ui_addr).await,
             }
         };
 
         if let Err(err) = result.await {
-            let _ = ui_addr.send_async(UiEvent::Error(err)).await;
+            if err.is::<Cancelled>() {
+                info!("Cancelled");
+                let _ = ui_addr.send_async(UiEvent::Notification("Cancelled")).await;
+            } else {
+                error!("{:#}", err);
+                let _ = ui_addr.send_async(UiEvent::Error(err)).await;
+            }
         }
     }
 }
 
-async fn open_save(file_path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
-    let mut input = Vec::new();
-    {
-        let mut file = File::open(&file_path).await?;
-        file.read_to_end(&mut input).await?;
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Cancelled")
     }
+}
 
-    if let Some(ext) = Path::new(&file_path).extension() {
-        let save_game = if unicase::eq(ext.to_string_lossy().to_string().as_str(), "MassEffectSave")
-        {
-            // ME1
-            SaveGame::MassEffect1 {
-                file_path,
-                save_game: Box::new(unreal::Deserializer::from_bytes(&input)?),
-            }
-        } else if input[0..4] == [0xC1, 0x83, 0x2A, 0x9E] {
-            // ME1 Legendary
-            SaveGame::MassEffect1Leg {
-                file_path,
-                save_game: Box::new(unreal::Deserializer::from_bytes(&input)?),
-            }
-        } else if unreal::Deserializer::from_bytes::<Me2Version>(&input).is_ok() {
-            // ME2
-            SaveGame::MassEffect2 {
-                file_path,
-                save_game: Box::new(unreal::Deserializer::from_bytes(&input)?),
-            }
-        } else if unreal::Deserializer::from_bytes::<Me2LegVersion>(&input).is_ok() {
-            // ME2 Legendary
-            SaveGame::MassEffect2Leg {
-                file_path,
-                save_game: Box::new(unreal::Deserializer::from_bytes(&input)?),
-            }
-        } else {
-            // ME3
-            SaveGame::MassEffect3 {
-                file_path,
-                save_game: Box::new(unreal::Deserializer::from_bytes(&input)?),
+impl std::error::Error for Cancelled {}
+
+// Progression d'une opération longue, la modale est fermée au drop
+struct Progress {
+    ui_addr: Sender<UiEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Progress {
+    fn start(ui_addr: &Sender<UiEvent>, label: &'static str) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _ = ui_addr.send(UiEvent::ProgressStarted(label, Arc::clone(&cancelled)));
+        Progress { ui_addr: Sender::clone(ui_addr), cancelled }
+    }
+
+    fn set(&self, fraction: f32) -> Result<()> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Cancelled.into());
+        }
+        let _ = self.ui_addr.send(UiEvent::Progress(fraction));
+        Ok(())
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let _ = self.ui_addr.send(UiEvent::ProgressFinished);
+    }
+}
+
+async fn read_with_progress(path: &str, progress: &Progress, end: f32) -> Result<Vec<u8>> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let mut file = File::open(path).await?;
+    let len = file.metadata().await?.len() as usize;
+
+    let mut input = Vec::with_capacity(len);
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        input.extend_from_slice(&chunk[..read]);
+        progress.set(end * input.len() as f32 / len.max(1) as f32)?;
+    }
+    Ok(input)
+}
+
+async fn open_save(file_path: String, compat_mode: bool, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Opening {}", file_path);
+    let progress = Progress::start(&ui_addr, "Opening save...");
+    let input = read_with_progress(&file_path, &progress, 0.5).await?;
+    debug!("Read {} bytes", input.len());
+    progress.set(0.5)?;
+
+    let formats = SaveFormat::detect(&input);
+    let newer_version =
+        forward_compat::newer_version(&input).or_else(|| forward_compat::variant_version(&input));
+    let (save_game, format) = match (formats.as_slice(), newer_version) {
+        ([format], _) => (
+            deserialize_save_as(original_path(file_path), &input, *format)?,
+            OriginalFormat::Known(*format),
+        ),
+        // ME2 Legendary / ME3 plus récents que l'éditeur, ou versions d'essai / démos
+        ([], Some((version, known_version))) => {
+            let file_path = original_path(file_path);
+            if !compat_mode {
+                let _ = ui_addr
+                    .send_async(UiEvent::NewerSaveVersion(file_path, version, known_version))
+                    .await;
+                return Ok(());
             }
-        };
 
-        let _ = ui_addr.send_async(UiEvent::OpenedSave(save_game)).await;
-        let _ = ui_addr.send_async(UiEvent::Notification("Opened")).await;
+            warn!("Opening save version {} as version {}", version, known_version);
+            let save_game = deserialize_forward_compat(file_path, &input, known_version)
+                .with_context(|| {
+                    format!("Failed to open version {} in compatibility mode", version)
+                })?;
+            (save_game, OriginalFormat::ForwardCompat(known_version))
+        }
+        // Plusieurs formats possibles ou aucun reconnu, au choix de l'utilisateur
+        (formats, _) => {
+            warn!("{} : {}", UNKNOWN_FORMAT, file_path);
+            let _ =
+                ui_addr.send_async(UiEvent::AmbiguousSaveFormat(file_path, formats.to_vec())).await;
+            return Ok(());
+        }
+    };
+
+    progress.set(1.0)?;
+    info!("Opened {}", save_game.kind());
+    let original = OriginalSave { input, format };
+    let _ = ui_addr.send_async(UiEvent::OpenedSave(save_game, original)).await;
+    let _ = ui_addr.send_async(UiEvent::Notification("Opened")).await;
+    Ok(())
+}
+
+// Format choisi par l'utilisateur, voir `SaveFormat::detect`
+async fn open_save_as(
+    file_path: String, format: SaveFormat, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    info!("Opening {} as {}", file_path, format);
+    let input = fs::read(&file_path).await?;
+    let save_game = deserialize_save_as(original_path(file_path), &input, format)
+        .with_context(|| format!("Failed to open the save as {}", format))?;
+
+    let original = OriginalSave { input, format: OriginalFormat::Known(format) };
+    let _ = ui_addr.send_async(UiEvent::OpenedSave(save_game, original)).await;
+    let _ = ui_addr.send_async(UiEvent::Notification("Opened")).await;
+    Ok(())
+}
+
+// Une autosave se réouvre à la place de la save d'origine
+fn original_path(file_path: String) -> String {
+    match file_path.strip_suffix(AUTOSAVE_SUFFIX) {
+        Some(original_path) => original_path.to_owned(),
+        None => file_path,
+    }
+}
+
+// `None` pour les versions plus récentes que l'éditeur, voir `forward_compat`
+fn deserialize_save(file_path: &str, input: &[u8]) -> Result<Option<SaveGame>> {
+    let mut formats = SaveFormat::detect(input);
+    if formats.is_empty() {
+        if forward_compat::newer_version(input)
+            .or_else(|| forward_compat::variant_version(input))
+            .is_some()
+        {
+            return Ok(None);
+        }
+        // Pour l'erreur du format attendu
+        formats.extend(SaveFormat::from_extension(Path::new(file_path)));
     }
 
+    // Le premier format qui se lit
+    let mut results =
+        formats.into_iter().map(|format| deserialize_save_as(file_path.to_owned(), input, format));
+    let first = results.next().context(UNKNOWN_FORMAT)?;
+    first.or_else(|err| results.find(Result::is_ok).unwrap_or(Err(err))).map(Some)
+}
+
+fn deserialize_forward_compat(
+    file_path: String, input: &[u8], known_version: i32,
+) -> Result<SaveGame> {
+    let save_game = if known_version == Me2LegVersion::LATEST {
+        let (save_game, forward_compat) = ForwardCompat::deserialize(input, known_version)?;
+        SaveGame::MassEffect2Leg {
+            file_path,
+            save_game: Box::new(save_game),
+            forward_compat: Some(forward_compat),
+        }
+    } else {
+        let (save_game, forward_compat) = ForwardCompat::deserialize(input, known_version)?;
+        SaveGame::MassEffect3 {
+            file_path,
+            save_game: Box::new(save_game),
+            forward_compat: Some(forward_compat),
+        }
+    };
+    Ok(save_game)
+}
+
+fn deserialize_save_as(file_path: String, input: &[u8], format: SaveFormat) -> Result<SaveGame> {
+    let save_game = match format {
+        SaveFormat::MassEffect1 => SaveGame::MassEffect1 {
+            file_path,
+            save_game: Box::new(unreal::Deserializer::from_bytes(input)?),
+        },
+        SaveFormat::MassEffect1Leg => SaveGame::MassEffect1Leg {
+            file_path,
+            save_game: Box::new(unreal::Deserializer::from_bytes(input)?),
+        },
+        SaveFormat::MassEffect1LegChunk => SaveGame::MassEffect1Leg {
+            file_path,
+            save_game: Box::new(Me1LegSaveGame::from_standalone_chunk(
+                unreal::Deserializer::from_bytes(input)?,
+            )),
+        },
+        SaveFormat::MassEffect2 => SaveGame::MassEffect2 {
+            file_path,
+            save_game: Box::new(unreal::Deserializer::from_bytes(input)?),
+        },
+        SaveFormat::MassEffect2Leg => SaveGame::MassEffect2Leg {
+            file_path,
+            save_game: Box::new(unreal::Deserializer::from_bytes(input)?),
+            forward_compat: None,
+        },
+        SaveFormat::MassEffect3 => SaveGame::MassEffect3 {
+            file_path,
+            save_game: Box::new(unreal::Deserializer::from_bytes(input)?),
+            forward_compat: None,
+        },
+    };
+    Ok(save_game)
+}
+
+async fn open_merge_source(file_path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Opening {} to merge", file_path);
+    let input = fs::read(&file_path).await?;
+    let save_game = deserialize_save(&file_path, &input)?
+        .with_context(|| format!("{} is newer than the editor and can't be merged", file_path))?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMergeSource(save_game)).await;
     Ok(())
 }
 
-async fn save_save(path: String, save_game: SaveGame, ui_addr: Sender<UiEvent>) -> Result<()> {
+async fn open_squad_build_source(file_path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Opening {} to copy a squadmate build", file_path);
+    let input = fs::read(&file_path).await?;
+    let save_game = deserialize_save(&file_path, &input)?
+        .with_context(|| format!("{} is newer than the editor", file_path))?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedSquadBuildSource(save_game)).await;
+    Ok(())
+}
+
+fn serialize_save(save_game: &SaveGame) -> Result<Vec<u8>> {
     let output = match save_game {
-        SaveGame::MassEffect1 { save_game, .. } => unreal::Serializer::to_byte_buf(&save_game)?,
+        SaveGame::MassEffect1 { save_game, .. } => unreal::Serializer::to_byte_buf(save_game)?,
+        SaveGame::MassEffect1Leg { save_game, .. } if save_game.is_standalone() => {
+            unreal::Serializer::to_byte_buf(save_game)?
+        }
         SaveGame::MassEffect1Leg { save_game, .. } => {
-            let mut output = unreal::Serializer::to_byte_buf(&save_game)?;
+            let mut output = unreal::Serializer::to_byte_buf(save_game)?;
 
             // Checksum
             let checksum_offset = output.len() - 12;
@@ -142,23 +708,29 @@ async fn save_save(path: String, save_game: SaveGame, ui_addr: Sender<UiEvent>)
             output
         }
         SaveGame::MassEffect2 { save_game, .. } => {
-            let mut output = unreal::Serializer::to_byte_buf(&save_game)?;
+            let mut output = unreal::Serializer::to_byte_buf(save_game)?;
 
             let crc = Crc::<u32>::new(&CRC_32_BZIP2);
             let checksum = crc.checksum(&output);
             output.extend(&u32::to_le_bytes(checksum));
             output
         }
-        SaveGame::MassEffect2Leg { save_game, .. } => {
-            let mut output = unreal::Serializer::to_byte_buf(&save_game)?;
+        SaveGame::MassEffect2Leg { save_game, forward_compat, .. } => {
+            let mut output = unreal::Serializer::to_byte_buf(save_game)?;
+            if let Some(forward_compat) = forward_compat {
+                forward_compat.restore(&mut output);
+            }
 
             let crc = Crc::<u32>::new(&CRC_32_BZIP2);
             let checksum = crc.checksum(&output);
             output.extend(&u32::to_le_bytes(checksum));
             output
         }
-        SaveGame::MassEffect3 { save_game, .. } => {
-            let mut output = unreal::Serializer::to_byte_buf(&save_game)?;
+        SaveGame::MassEffect3 { save_game, forward_compat, .. } => {
+            let mut output = unreal::Serializer::to_byte_buf(save_game)?;
+            if let Some(forward_compat) = forward_compat {
+                forward_compat.restore(&mut output);
+            }
 
             let crc = Crc::<u32>::new(&CRC_32_BZIP2);
             let checksum = crc.checksum(&output);
@@ -166,20 +738,270 @@ async fn save_save(path: String, save_game: SaveGame, ui_addr: Sender<UiEvent>)
             output
         }
     };
+    Ok(output)
+}
+
+// Relit la save produite comme à l'ouverture, pour ne jamais écrire un fichier que l'éditeur ne sait pas rouvrir
+fn verify_save(save_game: &SaveGame, output: &[u8]) -> Result<()> {
+    match save_game {
+        SaveGame::MassEffect1 { .. } => {
+            unreal::Deserializer::from_bytes::<Me1SaveGame>(output)?;
+        }
+        SaveGame::MassEffect1Leg { save_game, .. } if save_game.is_standalone() => {
+            unreal::Deserializer::from_bytes::<Me1LegSaveData>(output)?;
+        }
+        SaveGame::MassEffect1Leg { .. } => {
+            unreal::Deserializer::from_bytes::<Me1LegSaveGame>(output)?;
+        }
+        SaveGame::MassEffect2 { .. } => {
+            unreal::Deserializer::from_bytes::<Me2SaveGame>(output)?;
+        }
+        SaveGame::MassEffect2Leg { forward_compat: Some(forward_compat), .. } => {
+            ForwardCompat::deserialize::<Me2LegSaveGame>(output, forward_compat.known_version)?;
+        }
+        SaveGame::MassEffect2Leg { .. } => {
+            unreal::Deserializer::from_bytes::<Me2LegSaveGame>(output)?;
+        }
+        SaveGame::MassEffect3 { forward_compat: Some(forward_compat), .. } => {
+            ForwardCompat::deserialize::<Me3SaveGame>(output, forward_compat.known_version)?;
+        }
+        SaveGame::MassEffect3 { .. } => {
+            unreal::Deserializer::from_bytes::<Me3SaveGame>(output)?;
+        }
+    }
+    Ok(())
+}
+
+// `tse -`, la save est écrite dans un fichier temporaire pour garder le même chemin d'ouverture
+async fn open_stdin(ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Reading save from stdin");
+    let input = tokio::task::spawn_blocking(|| -> Result<Vec<u8>> {
+        use std::io::Read;
+        let mut input = Vec::new();
+        std::io::stdin().read_to_end(&mut input)?;
+        Ok(input)
+    })
+    .await??;
+
+    let file_name =
+        if save_format::is_me1_save(&input) { "stdin.MassEffectSave" } else { "stdin.pcsav" };
+    let path = std::env::temp_dir().join(file_name);
+    fs::write(&path, &input).await?;
+
+    open_save(path.to_string_lossy().into_owned(), false, ui_addr).await
+}
+
+async fn open_url(url: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Downloading {}", url);
+    let progress = Progress::start(&ui_addr, "Downloading save...");
+
+    let file_name = download_file_name(&url);
+
+    let input = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+        use std::io::Read;
+        let response =
+            ureq::get(&url).call().with_context(|| format!("Failed to download {}", url))?;
+        let len: Option<usize> = response.header("Content-Length").and_then(|len| len.parse().ok());
+        if let Some(len) = len.filter(|&len| len > MAX_DOWNLOAD_SIZE) {
+            bail!("{} is too big for a save ({} bytes)", url, len);
+        }
+
+        // Le Content-Length n'est qu'indicatif, la limite vaut aussi pendant la lecture
+        let mut reader = response.into_reader().take(MAX_DOWNLOAD_SIZE as u64 + 1);
+        let mut input = Vec::new();
+        let mut chunk = vec![0; 64 * 1024];
+        loop {
+            let read = reader.read(&mut chunk)?;
+            if read == 0 {
+                break;
+            }
+            input.extend_from_slice(&chunk[..read]);
+            if input.len() > MAX_DOWNLOAD_SIZE {
+                bail!("{} is too big for a save (over {} bytes)", url, MAX_DOWNLOAD_SIZE);
+            }
+            if let Some(len) = len {
+                progress.set(input.len() as f32 / len.max(1) as f32)?;
+            }
+        }
+        Ok(input)
+    })
+    .await??;
+    debug!("Downloaded {} bytes", input.len());
+
+    let path = std::env::temp_dir().join(file_name);
+    fs::write(&path, &input).await?;
+
+    open_save(path.to_string_lossy().into_owned(), false, ui_addr).await
+}
+
+// Le nom vient de l'URL, il ne doit pas pouvoir sortir du dossier temporaire
+fn download_file_name(url: &str) -> String {
+    let name = url.split(&['?', '#'][..]).next().and_then(|url| url.rsplit('/').next());
+    let name: String = name
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+
+    if name.trim_start_matches('.').is_empty() {
+        String::from("download.pcsav")
+    } else {
+        name
+    }
+}
+
+async fn save_save(path: String, save_game: SaveGame, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Saving {} to {}", save_game.kind(), path);
+    let progress = Progress::start(&ui_addr, "Saving...");
+    let output = serialize_save(&save_game)?;
+    verify_save(&save_game, &output)
+        .context("The saved file could not be read back, it was not written")?;
+    // Plus d'annulation possible une fois l'écriture commencée
+    progress.set(0.5)?;
+
+    write_with_backup(Path::new(&path), &output).await?;
+    drop(progress);
 
+    info!("Saved {} bytes", output.len());
+    let _ = ui_addr.send_async(UiEvent::Notification("Saved, verified re-readable")).await;
+    Ok(())
+}
+
+async fn write_with_backup(path: &Path, output: &[u8]) -> Result<()> {
     // Backup si fichier existe
-    let path = PathBuf::from(path);
-    if fs::metadata(&path).await.is_ok() {
+    if fs::metadata(path).await.is_ok() {
         if let Some(ext) = path.extension() {
-            let to = Path::with_extension(&path, ext.to_string_lossy().into_owned() + ".bak");
-            fs::copy(&path, to).await?;
+            let to = Path::with_extension(path, ext.to_string_lossy().into_owned() + ".bak");
+            debug!("Backup to {}", to.display());
+            fs::copy(path, to).await?;
         }
     }
 
+    let mut file = File::create(path).await?;
+    file.write_all(output).await?;
+    Ok(())
+}
+
+// Pas de backup ni de notification, le fichier d'à côté est écrasé à chaque fois
+async fn autosave(save_game: SaveGame) -> Result<()> {
+    let path = format!("{}{}", save_game.file_path(), AUTOSAVE_SUFFIX);
+    let output = serialize_save(&save_game)?;
+
     let mut file = File::create(&path).await?;
     file.write_all(&output).await?;
 
-    let _ = ui_addr.send_async(UiEvent::Notification("Saved")).await;
+    info!("Autosaved {} bytes to {}", output.len(), path);
+    Ok(())
+}
+
+async fn create_working_copy(file_path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let original = PathBuf::from(file_path);
+    let working_copy = cloud_sync::working_copy_path(&original);
+    if let Some(dir) = working_copy.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+    fs::copy(&original, &working_copy).await?;
+
+    info!("Working on {}", working_copy.display());
+    let _ = ui_addr
+        .send_async(UiEvent::CreatedWorkingCopy(
+            original,
+            working_copy.to_string_lossy().into_owned(),
+        ))
+        .await;
+    Ok(())
+}
+
+// La save synchronisée n'est remplacée que si la copie de travail a bien été écrite
+async fn save_working_copy(
+    path: String, save_game: SaveGame, original: PathBuf, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    save_save(path.clone(), save_game, Sender::clone(&ui_addr)).await?;
+    copy_back_working_copy(PathBuf::from(path), original, ui_addr).await
+}
+
+// Remplacée d'un coup, avec un backup
+async fn copy_back_working_copy(
+    working_copy: PathBuf, original: PathBuf, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    if let Some(ext) = original.extension() {
+        let to = Path::with_extension(&original, ext.to_string_lossy().into_owned() + ".bak");
+        debug!("Backup to {}", to.display());
+        fs::copy(&original, to).await?;
+    }
+    fs::copy(&working_copy, &original).await?;
+
+    info!("Copied {} back to {}", working_copy.display(), original.display());
+    let _ = ui_addr.send_async(UiEvent::Notification("Saved and copied back")).await;
+    Ok(())
+}
+
+// Cherché dans le dossier de la carrière et ses parents
+async fn open_launcher_config(career_dir: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut path = None;
+    for dir in career_dir.ancestors().take(4) {
+        let candidate = dir.join(LAUNCHER_CONFIG_FILE);
+        if fs::metadata(&candidate).await.is_ok() {
+            path = Some(candidate);
+            break;
+        }
+    }
+    let path = path.with_context(|| {
+        format!("No {} found near {}", LAUNCHER_CONFIG_FILE, career_dir.display())
+    })?;
+
+    let mut input = String::new();
+    {
+        let mut file = File::open(&path).await?;
+        file.read_to_string(&mut input).await?;
+    }
+
+    let config = LauncherConfig::parse(&input);
+    let _ = ui_addr.send_async(UiEvent::LoadedLauncherConfig(path, config)).await;
+    Ok(())
+}
+
+async fn save_launcher_config(
+    path: PathBuf, config: LauncherConfig, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    let to = Path::with_extension(&path, "cfg.bak");
+    debug!("Backup to {}", to.display());
+    fs::copy(&path, to).await?;
+
+    let mut file = File::create(&path).await?;
+    file.write_all(config.to_string().as_bytes()).await?;
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Launcher profile saved")).await;
+    Ok(())
+}
+
+async fn load_notes(file_path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let path = format!("{}{}", file_path, NOTES_SUFFIX);
+
+    // Pas de fichier => pas de notes
+    let mut notes = String::new();
+    if fs::metadata(&path).await.is_ok() {
+        let mut file = File::open(&path).await?;
+        file.read_to_string(&mut notes).await?;
+    }
+
+    let _ = ui_addr.send_async(UiEvent::LoadedNotes(file_path, notes)).await;
+    Ok(())
+}
+
+async fn save_notes(file_path: String, notes: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let path = format!("{}{}", file_path, NOTES_SUFFIX);
+
+    if notes.is_empty() {
+        if fs::metadata(&path).await.is_ok() {
+            fs::remove_file(&path).await?;
+        }
+    } else {
+        let mut file = File::create(&path).await?;
+        file.write_all(notes.as_bytes()).await?;
+    }
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Notes saved")).await;
     Ok(())
 }
 
@@ -189,6 +1011,7 @@ async fn load_me1_plot_db(ui_addr: Sender<UiEvent>) -> Result<()> {
         let mut file = File::open("databases/me1_plot_db.ron").await?;
         file.read_to_string(&mut input).await?;
     }
+    debug!("Parsing databases/me1_plot_db.ron");
 
     let me1_plot_db: Me1PlotDb = ron::from_str(&input)?;
 
@@ -202,6 +1025,7 @@ async fn load_me1_item_db(ui_addr: Sender<UiEvent>) -> Result<()> {
         let mut file = File::open("databases/me1_item_db.ron").await?;
         file.read_to_string(&mut input).await?;
     }
+    debug!("Parsing databases/me1_item_db.ron");
 
     let me1_item_db: Me1ItemDb = ron::from_str(&input)?;
 
@@ -215,6 +1039,7 @@ async fn load_me2_plot_db(ui_addr: Sender<UiEvent>) -> Result<()> {
         let mut file = File::open("databases/me2_plot_db.ron").await?;
         file.read_to_string(&mut input).await?;
     }
+    debug!("Parsing databases/me2_plot_db.ron");
 
     let me2_plot_db: Me2PlotDb = ron::from_str(&input)?;
 
@@ -228,6 +1053,7 @@ async fn load_me3_plot_db(ui_addr: Sender<UiEvent>) -> Result<()> {
         let mut file = File::open("databases/me3_plot_db.ron").await?;
         file.read_to_string(&mut input).await?;
     }
+    debug!("Parsing databases/me3_plot_db.ron");
 
     let me3_plot_db: Me3PlotDb = ron::from_str(&input)?;
 
@@ -235,6 +1061,156 @@ async fn load_me3_plot_db(ui_addr: Sender<UiEvent>) -> Result<()> {
     Ok(())
 }
 
+async fn load_recipe_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/recipes.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/recipes.ron");
+
+    let recipe_db: RecipeDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedRecipeDb(recipe_db)).await;
+    Ok(())
+}
+
+async fn load_me3_known_issues(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/me3_known_issues.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/me3_known_issues.ron");
+
+    let known_issues: KnownIssuesDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMe3KnownIssues(known_issues)).await;
+    Ok(())
+}
+
+async fn load_map_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/map_db.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/map_db.ron");
+
+    let map_db: MapDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMapDb(map_db)).await;
+    Ok(())
+}
+
+async fn load_me3_gear_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/me3_gear_db.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/me3_gear_db.ron");
+
+    let me3_gear_db: Me3GearDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMe3GearDb(me3_gear_db)).await;
+    Ok(())
+}
+
+async fn load_romance_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/romance_rules.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/romance_rules.ron");
+
+    let romance_db: RomanceDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedRomanceDb(romance_db)).await;
+    Ok(())
+}
+
+async fn load_squad_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/squad_db.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/squad_db.ron");
+
+    let squad_db: SquadDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedSquadDb(squad_db)).await;
+    Ok(())
+}
+
+async fn load_help_db(ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open("databases/help.ron").await?;
+        file.read_to_string(&mut input).await?;
+    }
+    debug!("Parsing databases/help.ron");
+
+    let help_db: HelpDb = ron::from_str(&input)?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedHelpDb(help_db)).await;
+    Ok(())
+}
+
+async fn open_me1_copy_source(path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Opening {} as copy source", path);
+    let input = fs::read(&path).await?;
+    let save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)
+        .with_context(|| format!("Failed to open {} as a Mass Effect 1 save", path))?;
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMe1CopySource(path, Box::new(save_game))).await;
+    Ok(())
+}
+
+async fn import_me1_plot_labels(path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let mut input = String::new();
+    {
+        let mut file = File::open(&path).await?;
+        file.read_to_string(&mut input).await?;
+    }
+
+    let labels = Me1PlotLabels::parse(path, &input)?;
+    info!("Imported {} ME1 plot labels", labels.booleans.len());
+
+    let _ = ui_addr.send_async(UiEvent::LoadedMe1PlotLabels(labels)).await;
+    Ok(())
+}
+
+async fn import_me1_properties(
+    path: String, object_id: i32, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    let text = fs::read_to_string(&path).await?;
+
+    let _ = ui_addr.send_async(UiEvent::ImportedMe1Properties(object_id, text)).await;
+    Ok(())
+}
+
+async fn import_me1_zip_member(
+    path: String, member: ZipMember, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    let data = fs::read(&path).await?;
+
+    let _ = ui_addr.send_async(UiEvent::ImportedMe1ZipMember(member, data)).await;
+    Ok(())
+}
+
+async fn open_tlk(path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Opening {}", path);
+    let input = fs::read(&path).await?;
+    let tlk = TlkFile::parse(&input).with_context(|| format!("Failed to parse {}", path))?;
+    debug!("Decoded {} strings", tlk.len());
+
+    let _ = ui_addr.send_async(UiEvent::LoadedTlk(tlk)).await;
+    Ok(())
+}
+
 async fn import_head_morph(path: String, ui_addr: Sender<UiEvent>) -> Result<()> {
     let mut import = String::new();
     {
@@ -264,3 +1240,444 @@ async fn export_head_morph(
     let _ = ui_addr.send_async(UiEvent::Notification("Exported")).await;
     Ok(())
 }
+
+async fn import_appearance_code(code: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let head_morph = HeadMorph::from_appearance_code(&code)
+        .context("Failed to import the appearance code from the clipboard")?;
+
+    let _ = ui_addr.send_async(UiEvent::ImportedHeadMorph(head_morph)).await;
+    let _ = ui_addr.send_async(UiEvent::Notification("Imported")).await;
+    Ok(())
+}
+
+async fn export_appearance_code(
+    head_morph: Box<HeadMorph>, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    let code = head_morph.to_appearance_code()?;
+
+    let _ = ui_addr.send_async(UiEvent::CopyToClipboard(code)).await;
+    let _ = ui_addr.send_async(UiEvent::Notification("Copied to clipboard")).await;
+    Ok(())
+}
+
+async fn load_settings(ui_addr: Sender<UiEvent>) -> Result<()> {
+    // Pas de fichier => paramètres par défaut
+    if fs::metadata(SETTINGS_PATH).await.is_err() {
+        return Ok(());
+    }
+
+    let mut input = String::new();
+    {
+        let mut file = File::open(SETTINGS_PATH).await?;
+        file.read_to_string(&mut input).await?;
+    }
+
+    let settings: Settings =
+        ron::from_str(&input).with_context(|| format!("Failed to parse {}", SETTINGS_PATH))?;
+    zip_writer::set_zip_compression(settings.me1_zip_compression);
+    batch::set_worker_threads(settings.worker_threads);
+
+    let _ = ui_addr.send_async(UiEvent::LoadedSettings(settings)).await;
+    Ok(())
+}
+
+async fn save_settings(settings: Settings) -> Result<()> {
+    zip_writer::set_zip_compression(settings.me1_zip_compression);
+    batch::set_worker_threads(settings.worker_threads);
+
+    let pretty_config = PrettyConfig::new().with_new_line(String::from('\n'));
+
+    let output = ron::ser::to_string_pretty(&settings, pretty_config)?;
+    {
+        let mut file = File::create(SETTINGS_PATH).await?;
+        file.write_all(output.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+// Copie les `.ron` tels quels, les fichiers déjà présents ne sont pas écrasés
+async fn export_databases(dir: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let dir = dir.join(DATABASES_DIR);
+    fs::create_dir_all(&dir).await?;
+
+    let mut entries = fs::read_dir(DATABASES_DIR).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension() != Some("ron".as_ref()) {
+            continue;
+        }
+
+        let to = dir.join(entry.file_name());
+        if fs::metadata(&to).await.is_ok() {
+            info!("{} already exists, skipped", to.display());
+            continue;
+        }
+        fs::copy(&path, &to)
+            .await
+            .with_context(|| format!("Failed to export {}", path.display()))?;
+    }
+
+    info!("Exported databases to {}", dir.display());
+    let _ = ui_addr.send_async(UiEvent::Notification("Databases exported")).await;
+    Ok(())
+}
+
+// Saves minimales de chaque format, voir `synthetic`
+async fn generate_test_saves(dir: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let path = |file_name: &str| dir.join(file_name).to_string_lossy().into_owned();
+    let save_games = vec![
+        SaveGame::MassEffect1 {
+            file_path: path("Synthetic_ME1.MassEffectSave"),
+            save_game: Box::new(synthetic::me1()?),
+        },
+        SaveGame::MassEffect1Leg {
+            file_path: path("Synthetic_ME1LE.pcsav"),
+            save_game: Box::new(synthetic::me1_leg()?),
+        },
+        SaveGame::MassEffect2 {
+            file_path: path("Synthetic_ME2.pcsav"),
+            save_game: Box::new(synthetic::me2()?),
+        },
+        SaveGame::MassEffect2Leg {
+            file_path: path("Synthetic_ME2LE.pcsav"),
+            save_game: Box::new(synthetic::me2_leg()?),
+            forward_compat: None,
+        },
+        SaveGame::MassEffect3 {
+            file_path: path("Synthetic_ME3.pcsav"),
+            save_game: Box::new(synthetic::me3()?),
+            forward_compat: None,
+        },
+    ];
+
+    let count = save_games.len();
+    fs::create_dir_all(&dir).await?;
+    for save_game in save_games {
+        let file_path = save_game.file_path();
+        let output = serialize_save(&save_game)?;
+
+        // Reconnue comme une save du même jeu
+        let kind = deserialize_save(file_path, &output)?.map(|read| read.kind());
+        if kind != Some(save_game.kind()) {
+            bail!("{} is not read back as a {} save", file_path, save_game.kind());
+        }
+
+        fs::write(&file_path, &output).await?;
+        debug!("Generated {} ({} bytes)", file_path, output.len());
+    }
+
+    info!("Generated {} test saves in {}", count, dir.display());
+    let _ = ui_addr.send_async(UiEvent::Notification("Test saves generated")).await;
+    Ok(())
+}
+
+async fn export_text_file(path: String, text: String, ui_addr: Sender<UiEvent>) -> Result<()> {
+    {
+        let mut file = File::create(&path).await?;
+        file.write_all(text.as_bytes()).await?;
+    }
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Exported")).await;
+    Ok(())
+}
+
+async fn export_binary_file(path: String, data: Vec<u8>, ui_addr: Sender<UiEvent>) -> Result<()> {
+    {
+        let mut file = File::create(&path).await?;
+        file.write_all(&data).await?;
+    }
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Exported")).await;
+    Ok(())
+}
+
+// Modifications de la session par rapport au fichier d'origine sur le disque
+async fn export_patch(path: String, save_game: SaveGame, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let original = fs::read(save_game.file_path())
+        .await
+        .with_context(|| format!("Failed to read the original file {}", save_game.file_path()))?;
+    let output = serialize_save(&save_game)?;
+    let patch = patch::create(&original, &output);
+
+    {
+        let mut file = File::create(&path).await?;
+        file.write_all(&patch).await?;
+    }
+
+    info!("Exported a {} bytes patch to {}", patch.len(), path);
+    let _ = ui_addr.send_async(UiEvent::Notification("Exported")).await;
+    Ok(())
+}
+
+// La save patchée est ouverte à la place, il reste à l'enregistrer
+async fn apply_patch(
+    patch_path: String, file_path: String, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    info!("Applying {} to {}", patch_path, file_path);
+    let patch = fs::read(&patch_path).await?;
+    let input = fs::read(&file_path).await?;
+    let output =
+        patch::apply(&input, &patch).with_context(|| format!("Failed to apply {}", patch_path))?;
+
+    let save_game = deserialize_save(&file_path, &output)?
+        .context("The patched save is newer than the editor")?;
+    let original = OriginalSave { input: output, format: OriginalFormat::Detected };
+    let _ = ui_addr.send_async(UiEvent::OpenedSave(save_game, original)).await;
+    let _ = ui_addr.send_async(UiEvent::Notification("Patch applied")).await;
+    Ok(())
+}
+
+fn is_save_file(path: &Path) -> bool {
+    match path.extension() {
+        Some(ext) => {
+            let ext = ext.to_string_lossy();
+            unicase::eq(ext.as_ref(), "pcsav") || unicase::eq(ext.as_ref(), "MassEffectSave")
+        }
+        None => false,
+    }
+}
+
+async fn read_save_slots(dir: &Path) -> Result<Vec<SaveSlot>> {
+    let mut slots = Vec::new();
+    let mut entries = fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if is_save_file(&entry.path()) && entry.file_type().await?.is_file() {
+            slots.push(SaveSlot::new(entry.file_name().to_string_lossy().into_owned()));
+        }
+    }
+    slots.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(slots)
+}
+
+#[cfg(target_os = "windows")]
+async fn register_shell_menu(register: bool, ui_addr: Sender<UiEvent>) -> Result<()> {
+    if register {
+        crate::shell_menu::register()?;
+        info!("Added the Explorer context menu entry");
+        let _ = ui_addr.send_async(UiEvent::Notification("Added to the context menu")).await;
+    } else {
+        crate::shell_menu::unregister()?;
+        info!("Removed the Explorer context menu entry");
+        let _ = ui_addr.send_async(UiEvent::Notification("Removed from the context menu")).await;
+    }
+    Ok(())
+}
+
+// Les saves illisibles (autre jeu, autre version) sont ignorées
+async fn audit_career(dir: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let progress = Progress::start(&ui_addr, "Scanning career...");
+    let slots: Vec<_> = read_save_slots(&dir)
+        .await?
+        .into_iter()
+        .filter(|slot| slot.extension() == "pcsav")
+        .collect();
+
+    let paths = slots.iter().map(|slot| dir.join(&slot.file_name)).collect();
+    let results = batch::process_files(
+        paths,
+        |_, input| Ok(unreal::Deserializer::from_bytes::<Me3SaveGame>(&input)?),
+        |done| progress.set(done as f32 / slots.len() as f32),
+    )
+    .await?;
+
+    let mut saves = Vec::new();
+    for (slot, (_, result)) in slots.iter().zip(results) {
+        match result {
+            Ok(save_game) => saves.push(CareerSave::new(slot.file_name.clone(), save_game)),
+            Err(err) => warn!("{} skipped : {:#}", slot.file_name, err),
+        }
+    }
+
+    if saves.is_empty() {
+        bail!("No Mass Effect 3 save found in {}", dir.display());
+    }
+    let _ = ui_addr.send_async(UiEvent::CareerAudit(dir, CareerAudit::new(saves))).await;
+    Ok(())
+}
+
+// Tourne jusqu'à `StopWatchFolder`, une save qui ne peut pas être patchée est seulement journalisée
+async fn auto_patch_folder(dir: PathBuf, recipes: Vec<Recipe>, ui_addr: Sender<UiEvent>) {
+    if let Err(err) = watch_folder(&dir, Arc::new(recipes), &ui_addr).await {
+        error!("{:#}", err);
+        let _ = ui_addr.send_async(UiEvent::WatchFolderStopped(err)).await;
+    }
+}
+
+async fn watch_folder(
+    dir: &Path, recipes: Arc<Vec<Recipe>>, ui_addr: &Sender<UiEvent>,
+) -> Result<()> {
+    let mut watch = FolderWatch::new(scan_saves(dir).await?);
+    loop {
+        time::sleep(WATCH_FOLDER_INTERVAL).await;
+        let paths = watch.update(scan_saves(dir).await?);
+        let recipes = Arc::clone(&recipes);
+        let patched = batch::process_files(
+            paths,
+            move |path, input| patch_save(path, &input, &recipes),
+            |_| Ok(()),
+        )
+        .await?;
+
+        for (path, result) in patched {
+            let result = match result {
+                Ok((applied, output)) => write_with_backup(&path, &output).await.map(|_| applied),
+                Err(err) => Err(err),
+            };
+            match &result {
+                Ok(applied) => info!("Applied {} preset(s) to {}", applied, path.display()),
+                Err(err) => warn!("{} not patched : {:#}", path.display(), err),
+            }
+            if let Ok(modified) = fs::metadata(&path).await.and_then(|metadata| metadata.modified())
+            {
+                watch.set_modified(path.clone(), modified);
+            }
+
+            let result = result.map_err(|err| format!("{:#}", err));
+            let _ = ui_addr.send_async(UiEvent::WatchFolderPatched(path, result)).await;
+        }
+    }
+}
+
+async fn scan_saves(dir: &Path) -> Result<HashMap<PathBuf, SystemTime>> {
+    let mut saves = HashMap::new();
+    let mut entries =
+        fs::read_dir(dir).await.with_context(|| format!("Failed to read {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let metadata = entry.metadata().await?;
+        if is_save_file(&path) && metadata.is_file() {
+            saves.insert(path, metadata.modified()?);
+        }
+    }
+    Ok(saves)
+}
+
+// Retourne le nombre de presets appliqués et la save patchée, écrite ensuite
+fn patch_save(path: &Path, input: &[u8], recipes: &[Recipe]) -> Result<(usize, Vec<u8>)> {
+    let file_path = path.to_string_lossy().into_owned();
+    let mut save_game =
+        deserialize_save(&file_path, input)?.context("The save is newer than the editor")?;
+
+    let game = presets::game_of(&save_game);
+    let recipes: Vec<_> = recipes.iter().filter(|recipe| recipe.is_applicable(game)).collect();
+    if recipes.is_empty() {
+        bail!("No preset for {}", save_game.kind());
+    }
+    for recipe in &recipes {
+        presets::check_recipe(&save_game, recipe)?;
+        presets::apply_recipe(&mut save_game, recipe);
+    }
+
+    let output = serialize_save(&save_game)?;
+    verify_save(&save_game, &output)
+        .context("The patched save could not be read back, it was not written")?;
+    Ok((recipes.len(), output))
+}
+
+async fn list_save_slots(dir: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let slots = read_save_slots(&dir).await?;
+    let _ = ui_addr.send_async(UiEvent::SaveSlots(dir, slots)).await;
+    Ok(())
+}
+
+async fn new_slot_path(file_path: &str) -> Result<PathBuf> {
+    let file_path = Path::new(file_path);
+    let dir = file_path.parent().context("Invalid save path")?;
+    let extension = match file_path.extension() {
+        Some(ext) if ext == "pcsav" || ext == "MassEffectSave" => ext.to_string_lossy(),
+        _ => bail!("Only regular saves can be saved to a new slot"),
+    };
+    let career_name = dir.file_name().unwrap_or_default().to_string_lossy();
+
+    let slots = read_save_slots(dir).await?;
+    let file_name = SlotNaming::detect(&slots, &extension, &career_name).next_free(&slots)?;
+    info!("New slot : {}", file_name);
+    Ok(dir.join(file_name))
+}
+
+async fn save_new_slot(mut save_game: SaveGame, ui_addr: Sender<UiEvent>) -> Result<()> {
+    let path = new_slot_path(save_game.file_path()).await?;
+    save_game.renew_timestamp();
+
+    save_save(path.to_string_lossy().into_owned(), save_game, Sender::clone(&ui_addr)).await?;
+    refresh_save_slots(&path, ui_addr).await
+}
+
+// Le slot de test est réutilisé tant qu'il n'est pas promu ou supprimé
+async fn test_in_game(
+    save_game: SaveGame, test_path: Option<PathBuf>, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    let path = match test_path {
+        Some(path) => path,
+        None => new_slot_path(save_game.file_path()).await?,
+    };
+    info!("Testing {} in {}", save_game.file_path(), path.display());
+
+    save_save(path.to_string_lossy().into_owned(), save_game, Sender::clone(&ui_addr)).await?;
+    let _ = ui_addr.send_async(UiEvent::TestSlot(Some(path.clone()))).await;
+    refresh_save_slots(&path, ui_addr).await
+}
+
+async fn promote_test_slot(
+    save_game: SaveGame, test_path: PathBuf, ui_addr: Sender<UiEvent>,
+) -> Result<()> {
+    info!("Promoting {} to {}", test_path.display(), save_game.file_path());
+    save_save(save_game.file_path().to_owned(), save_game, Sender::clone(&ui_addr)).await?;
+    discard_test_slot(test_path, ui_addr).await
+}
+
+async fn discard_test_slot(test_path: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Deleting test slot {}", test_path.display());
+    if fs::metadata(&test_path).await.is_ok() {
+        fs::remove_file(&test_path).await?;
+    }
+
+    let _ = ui_addr.send_async(UiEvent::TestSlot(None)).await;
+    refresh_save_slots(&test_path, ui_addr).await
+}
+
+async fn duplicate_save(from: PathBuf, to: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    if fs::metadata(&to).await.is_ok() {
+        bail!("{} already exists", to.display());
+    }
+    info!("Duplicating {} to {}", from.display(), to.display());
+    let from = from.to_string_lossy();
+    let input = fs::read(from.as_ref()).await?;
+    let mut save_game = deserialize_save(&from, &input)?
+        .with_context(|| format!("{} is newer than the editor and can't be duplicated", from))?;
+    save_game.renew_timestamp();
+
+    let output = serialize_save(&save_game)?;
+    verify_save(&save_game, &output)
+        .context("The duplicated save could not be read back, it was not written")?;
+    fs::write(&to, output).await?;
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Duplicated")).await;
+    refresh_save_slots(&to, ui_addr).await
+}
+
+async fn rename_save(from: PathBuf, to: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    if fs::metadata(&to).await.is_ok() {
+        bail!("{} already exists", to.display());
+    }
+    info!("Renaming {} to {}", from.display(), to.display());
+    fs::rename(&from, &to).await?;
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Renamed")).await;
+    refresh_save_slots(&to, ui_addr).await
+}
+
+async fn delete_save(path: PathBuf, ui_addr: Sender<UiEvent>) -> Result<()> {
+    info!("Deleting {}", path.display());
+    fs::remove_file(&path).await?;
+
+    let _ = ui_addr.send_async(UiEvent::Notification("Deleted")).await;
+    refresh_save_slots(&path, ui_addr).await
+}
+
+async fn refresh_save_slots(path: &Path, ui_addr: Sender<UiEvent>) -> Result<()> {
+    match path.parent() {
+        Some(dir) => list_save_slots(dir.to_owned(), ui_addr).await,
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Error;
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+use std::path::PathBuf;
+
+use crate::{event_handler::MainEvent, save_data::shared::recipe::RecipeDb};
+
+use super::Gui;
+
+// Applique des presets à chaque nouvelle save écrite par le jeu dans un dossier de carrière
+#[derive(Default)]
+pub struct AutoPatchState {
+    pub opened: bool,
+    dir: Option<PathBuf>,
+    recipes: Vec<String>,
+    running: bool,
+    log: Vec<(String, Result<usize, String>)>,
+}
+
+impl AutoPatchState {
+    pub fn add_entry(&mut self, path: PathBuf, result: Result<usize, String>) {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        self.log.push((file_name, result));
+    }
+
+    pub fn stopped(&mut self, err: Error) {
+        self.running = false;
+        self.log.push((String::from("Stopped"), Err(format!("{:#}", err))));
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_auto_patch(&self, state: &mut AutoPatchState, recipe_db: Option<&RecipeDb>) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Watch folder"))
+            .size([500.0, 450.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let recipe_db = match recipe_db {
+                Some(recipe_db) => recipe_db,
+                None => return,
+            };
+
+            if state.running {
+                if ui.button(im_str!("Stop")) {
+                    let _ = self.event_addr.send(MainEvent::StopWatchFolder);
+                    state.running = false;
+                }
+                ui.same_line();
+                if let Some(dir) = &state.dir {
+                    ui.text(format!("Watching {}", dir.display()));
+                }
+            } else {
+                if ui.button(im_str!("Folder")) {
+                    let default = match &state.dir {
+                        Some(dir) => dir.clone(),
+                        None => Self::get_document_dir(),
+                    };
+                    let dir = tinyfiledialogs::select_folder_dialog(
+                        "Career folder",
+                        &default.to_string_lossy(),
+                    );
+                    if let Some(dir) = dir {
+                        state.dir = Some(PathBuf::from(dir));
+                    }
+                }
+                ui.same_line();
+                match &state.dir {
+                    Some(dir) => ui.text(dir.to_string_lossy()),
+                    None => ui.text_disabled(im_str!("No folder")),
+                }
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Applies the ticked presets to every save the game writes in the folder, as long as the editor is running.\nOnly the presets of the save's game are applied, the previous file is kept as a `.bak` backup.",
+            );
+
+            // Presets
+            let can_start = !state.running && state.dir.is_some() && !state.recipes.is_empty();
+            if let Some(_t) = ChildWindow::new("presets").size([0.0, 150.0]).border(true).begin(ui)
+            {
+                for recipe in recipe_db.iter() {
+                    let position = state.recipes.iter().position(|name| *name == recipe.name);
+                    let mut ticked = position.is_some();
+                    if ui.checkbox(&ImString::new(&recipe.name), &mut ticked) && !state.running {
+                        match position {
+                            Some(position) => {
+                                state.recipes.remove(position);
+                            }
+                            None => state.recipes.push(recipe.name.clone()),
+                        }
+                    }
+                    if ui.is_item_hovered() && !recipe.description.is_empty() {
+                        ui.tooltip_text(&recipe.description);
+                    }
+                }
+            }
+
+            if ui.button(im_str!("Start")) && can_start {
+                if let Some(dir) = &state.dir {
+                    let recipes = recipe_db
+                        .iter()
+                        .filter(|recipe| state.recipes.contains(&recipe.name))
+                        .cloned()
+                        .collect();
+                    let _ = self.event_addr.send(MainEvent::StartWatchFolder(dir.clone(), recipes));
+                    state.running = true;
+                }
+            }
+            ui.same_line();
+            if ui.button(im_str!("Clear log")) {
+                state.log.clear();
+            }
+
+            // Log
+            ui.separator();
+            if state.log.is_empty() {
+                ui.text_disabled(im_str!("No patched save"));
+                return;
+            }
+            if let Some(_t) = ChildWindow::new("log").begin(ui) {
+                for (file_name, result) in state.log.iter().rev() {
+                    match result {
+                        Ok(applied) => {
+                            ui.text(format!("{} : {} preset(s) applied", file_name, applied))
+                        }
+                        Err(err) => ui
+                            .text_colored([0.9, 0.4, 0.3, 1.0], format!("{} : {}", file_name, err)),
+                    }
+                }
+            }
+        }
+    }
+}
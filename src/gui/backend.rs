@@ -28,7 +28,8 @@ use imgui::{
 use imgui_wgpu::{Renderer, RendererConfig};
 use imgui_winit_support::{HiDpiMode, WinitPlatform};
 use std::{
-    panic, thread,
+    panic::{self, AssertUnwindSafe},
+    thread,
     time::{Duration, Instant},
 };
 use tokio::runtime::Handle;
@@ -60,39 +61,58 @@ const MIN_WIDTH: u32 = 480;
 const MIN_HEIGHT: u32 = 270;
 
 // Backend
-pub fn init(title: &str, width: f64, height: f64, args: &ArgMatches) -> Backend {
-    let backend = if args.is_present("directx12") {
-        wgpu::BackendBit::DX12
+const RENDERERS: [(&str, wgpu::BackendBit); 6] = [
+    ("auto", wgpu::BackendBit::PRIMARY),
+    ("vulkan", wgpu::BackendBit::VULKAN),
+    ("dx12", wgpu::BackendBit::DX12),
+    ("dx11", wgpu::BackendBit::DX11),
+    ("metal", wgpu::BackendBit::METAL),
+    ("gl", wgpu::BackendBit::GL),
+];
+
+pub fn renderer_names() -> Vec<&'static str> {
+    RENDERERS.iter().map(|(name, _)| *name).collect()
+}
+
+fn renderers_by_priority(args: &ArgMatches) -> Vec<(&'static str, wgpu::BackendBit)> {
+    let requested = if args.is_present("directx12") {
+        "dx12"
     } else if args.is_present("directx11") {
-        wgpu::BackendBit::DX11
+        "dx11"
     } else if args.is_present("metal") {
-        wgpu::BackendBit::METAL
+        "metal"
     } else if args.is_present("vulkan") {
-        wgpu::BackendBit::VULKAN
+        "vulkan"
     } else {
-        wgpu::BackendBit::PRIMARY
+        args.value_of("renderer").unwrap_or("auto")
     };
 
+    // Celui demandé en premier puis les fallbacks
+    let mut priority = vec![requested, "auto"];
     #[cfg(target_os = "windows")]
-    {
-        panic::catch_unwind(|| init_with_backend(title, width, height, backend)).unwrap_or_else(
-            |_| {
-                eprintln!("Fallback to DirectX 11");
-                eprintln!("If it works for you, you can ignore this error or run TSE with --dx11 argument");
-                init_with_backend(title, width, height, wgpu::BackendBit::DX11)
-            },
-        )
+    priority.push("dx11");
+    priority.push("gl");
+
+    let mut renderers = Vec::new();
+    for name in priority {
+        if let Some(renderer) = RENDERERS.iter().find(|(n, _)| *n == name) {
+            if !renderers.contains(renderer) {
+                renderers.push(*renderer);
+            }
+        }
     }
-
-    #[cfg(not(target_os = "windows"))]
-    init_with_backend(title, width, height, backend)
+    renderers
 }
 
-fn init_with_backend(title: &str, width: f64, height: f64, backend: wgpu::BackendBit) -> Backend {
-    let rt = Handle::current();
-
-    let instance = wgpu::Instance::new(backend);
+struct Gpu {
+    surface: Surface,
+    device: Device,
+    queue: Queue,
+    swap_chain: SwapChain,
+    texture_format: wgpu::TextureFormat,
+}
 
+pub fn init(title: &str, width: f64, height: f64, args: &ArgMatches) -> Backend {
     let event_loop = EventLoop::new();
     let window = WindowBuilder::new()
         .with_resizable(true)
@@ -103,29 +123,22 @@ fn init_with_backend(title: &str, width: f64, height: f64, backend: wgpu::Backen
         .build(&event_loop)
         .expect("Failed to create a window");
 
-    let surface = unsafe { instance.create_surface(&window) };
-
-    let adapter = rt
-        .block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            compatible_surface: Some(&surface),
-        }))
-        .unwrap();
-
-    let (device, queue) =
-        rt.block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).unwrap();
-
-    // Set up swap chain
-    let size = window.inner_size();
-    let sc_desc = wgpu::SwapChainDescriptor {
-        usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
-        format: wgpu::TextureFormat::Bgra8UnormSrgb,
-        width: size.width as u32,
-        height: size.height as u32,
-        present_mode: wgpu::PresentMode::Fifo,
+    let mut renderers = renderers_by_priority(args).into_iter();
+    let gpu = loop {
+        let (name, backend) = renderers.next().expect(
+            "Failed to initialize any renderer, try another one with the --renderer argument",
+        );
+
+        // Certains backends paniquent au lieu de renvoyer une erreur
+        match panic::catch_unwind(AssertUnwindSafe(|| init_gpu(&window, backend))) {
+            Ok(Some(gpu)) => break gpu,
+            _ => {
+                eprintln!("Failed to initialize `{}` renderer", name);
+                eprintln!("If the fallback works for you, you can ignore this error or run TSE with --renderer <RENDERER> argument");
+            }
+        }
     };
-
-    let swap_chain = device.create_swap_chain(&surface, &sc_desc);
+    let Gpu { surface, device, queue, swap_chain, texture_format } = gpu;
 
     let mut imgui = Context::create();
     imgui.set_ini_filename(None);
@@ -169,12 +182,41 @@ fn init_with_backend(title: &str, width: f64, height: f64, backend: wgpu::Backen
 
     imgui.io_mut().font_global_scale = (1.0 / hidpi_factor) as f32;
 
-    let renderer_config = RendererConfig { texture_format: sc_desc.format, ..Default::default() };
+    let renderer_config = RendererConfig { texture_format, ..Default::default() };
     let renderer = Renderer::new(&mut imgui, &device, &queue, renderer_config);
 
     Backend { window, event_loop, surface, device, queue, swap_chain, imgui, platform, renderer }
 }
 
+fn init_gpu(window: &Window, backend: wgpu::BackendBit) -> Option<Gpu> {
+    let rt = Handle::current();
+
+    let instance = wgpu::Instance::new(backend);
+    let surface = unsafe { instance.create_surface(window) };
+
+    let adapter = rt.block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        compatible_surface: Some(&surface),
+    }))?;
+
+    let (device, queue) =
+        rt.block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).ok()?;
+
+    // Set up swap chain
+    let size = window.inner_size();
+    let sc_desc = wgpu::SwapChainDescriptor {
+        usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
+        format: wgpu::TextureFormat::Bgra8UnormSrgb,
+        width: size.width as u32,
+        height: size.height as u32,
+        present_mode: wgpu::PresentMode::Fifo,
+    };
+
+    let swap_chain = device.create_swap_chain(&surface, &sc_desc);
+
+    Some(Gpu { surface, device, queue, swap_chain, texture_format: sc_desc.format })
+}
+
 pub struct Backend {
     window: Window,
     event_loop: EventLoop<()>,
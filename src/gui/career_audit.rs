@@ -0,0 +1,106 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::mass_effect_3::{career_audit::CareerAudit, plot_db::Me3PlotDb},
+};
+
+use super::Gui;
+
+#[derive(Default)]
+pub struct CareerAuditState {
+    pub opened: bool,
+    audit: Option<(PathBuf, CareerAudit)>,
+    // Calculées une seule fois, le plot DB de ME3 est gros
+    losses: Vec<Vec<String>>,
+}
+
+impl CareerAuditState {
+    pub fn set_audit(&mut self, dir: PathBuf, audit: CareerAudit, plot_db: Option<&Me3PlotDb>) {
+        self.losses = match plot_db {
+            Some(plot_db) => (0..audit.saves.len()).map(|i| audit.losses(i, plot_db)).collect(),
+            None => Vec::new(),
+        };
+        self.audit = Some((dir, audit));
+        self.opened = true;
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    // Le dossier de la save de ME3 ouverte, sinon au choix
+    pub fn audit_career(&self, save_game: Option<&SaveGame>) {
+        let dir = match save_game {
+            Some(SaveGame::MassEffect3 { file_path, .. }) => {
+                Path::new(file_path).parent().map(Path::to_owned)
+            }
+            _ => tinyfiledialogs::select_folder_dialog(
+                "Mass Effect 3 career",
+                &Self::get_document_dir().to_string_lossy(),
+            )
+            .map(PathBuf::from),
+        };
+        if let Some(dir) = dir {
+            let _ = self.event_addr.send(MainEvent::AuditCareer(dir));
+        }
+    }
+
+    pub fn draw_career_audit(&self, state: &mut CareerAuditState) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let (dir, audit) = match &state.audit {
+            Some(audit) => audit,
+            None => return,
+        };
+
+        let window = Window::new(im_str!("ME3 career audit"))
+            .size([600.0, 450.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            ui.text(dir.to_string_lossy());
+            ui.same_line();
+            self.draw_help_marker(
+                "Saves sorted by time played.\nLists the plot database variables that went down since the previous save.",
+            );
+
+            let ems: Vec<_> = audit.saves.iter().map(|save| save.ems as f32).collect();
+            ui.plot_lines(im_str!("##ems"), &ems)
+                .graph_size([ui.content_region_avail()[0], 100.0])
+                .overlay_text(im_str!("Effective Military Strength"))
+                .scale_min(0.0)
+                .build();
+            ui.separator();
+
+            if let Some(_t) = ChildWindow::new("saves").begin(ui) {
+                if state.losses.is_empty() {
+                    ui.text_disabled(im_str!("ME3 plot database not loaded"));
+                }
+
+                for (i, save) in audit.saves.iter().enumerate() {
+                    let hours = save.seconds_played / 3600.0;
+                    let change = match i.checked_sub(1) {
+                        Some(previous) => format!("{:+}", save.ems - audit.saves[previous].ems),
+                        None => String::new(),
+                    };
+                    let label =
+                        format!("{} - {:.1}h - EMS {} {}", save.file_name, hours, save.ems, change);
+
+                    let losses = state.losses.get(i).map(Vec::as_slice).unwrap_or_default();
+                    if losses.is_empty() {
+                        ui.bullet_text(&ImString::new(label));
+                    } else if let Some(_t) = self.push_tree_node(&format!("{}##save-{}", label, i))
+                    {
+                        for loss in losses {
+                            ui.text_colored([0.9, 0.8, 0.3, 1.0], loss);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
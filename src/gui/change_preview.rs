@@ -0,0 +1,129 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+
+use crate::event_handler::SaveGame;
+
+use super::{
+    merge::{self, Difference},
+    Gui,
+};
+
+// Modifications d'un preset appliquées à une copie de la save, à valider une par une.
+// Mêmes différences que la fusion de saves, l'autre save étant le résultat du preset.
+pub struct ChangePreview {
+    title: String,
+    preview: SaveGame,
+    changes: Vec<Difference>,
+}
+
+impl ChangePreview {
+    pub fn new(title: String, save_game: &SaveGame, edit: impl FnOnce(&mut SaveGame)) -> Self {
+        let mut preview = save_game.clone();
+        edit(&mut preview);
+
+        // Même type de save des deux côtés, la comparaison ne peut pas échouer
+        let mut changes = merge::differences(save_game, &preview).unwrap_or_default();
+        for change in &mut changes {
+            change.take_theirs = true;
+        }
+        ChangePreview { title, preview, changes }
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    // Renvoie le titre une fois les modifications appliquées
+    pub fn draw_change_preview(
+        &self, change_preview: &mut Option<ChangePreview>, save_game: &mut Option<SaveGame>,
+    ) -> Option<String> {
+        let ui = self.ui;
+
+        let (preview, save_game) = match (change_preview.as_mut(), save_game) {
+            (Some(preview), Some(save_game)) => (preview, save_game),
+            _ => {
+                *change_preview = None;
+                return None;
+            }
+        };
+
+        let mut opened = true;
+        let mut close = false;
+        let mut applied = None;
+        let title = ImString::new(format!("Preview : {}###change_preview", preview.title));
+        let window =
+            Window::new(&title).size([650.0, 400.0], Condition::FirstUseEver).opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let selected = preview.changes.iter().filter(|change| change.take_theirs).count();
+            if ui.button(&ImString::new(format!("Apply {} change(s)", selected))) {
+                merge::merge(save_game, &preview.preview, &preview.changes);
+                applied = Some(preview.title.clone());
+                close = true;
+            }
+            ui.same_line();
+            if ui.button(im_str!("Cancel")) {
+                close = true;
+            }
+            ui.same_line();
+            if ui.button(im_str!("Select all")) {
+                preview.changes.iter_mut().for_each(|change| change.take_theirs = true);
+            }
+            ui.same_line();
+            if ui.button(im_str!("Select none")) {
+                preview.changes.iter_mut().for_each(|change| change.take_theirs = false);
+            }
+            ui.separator();
+
+            if preview.changes.is_empty() {
+                ui.text_disabled(im_str!("Nothing would change"));
+            } else if let Some(_t) = ChildWindow::new(im_str!("changes")).begin(ui) {
+                let _t = self.begin_table(im_str!("changes-table"), 3)?;
+                for (i, change) in preview.changes.iter_mut().enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    self.table_next_row();
+                    ui.checkbox(im_str!("##apply"), &mut change.take_theirs);
+                    self.table_next_column();
+                    ui.text(change.path.join("/"));
+                    self.table_next_column();
+                    let text = format!("{} -> {}", change.ours, change.theirs);
+                    if change.take_theirs {
+                        ui.text(text);
+                    } else {
+                        ui.text_disabled(text);
+                    }
+                }
+            }
+        }
+
+        if !opened || close {
+            *change_preview = None;
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn preview_changes() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_game =
+            SaveGame::MassEffect2 { file_path: String::new(), save_game: Box::new(save_game) };
+
+        let preview = ChangePreview::new("Rich".to_owned(), &save_game, |preview| {
+            if let SaveGame::MassEffect2 { save_game, .. } = preview {
+                save_game.player.credits = 999_999;
+            }
+        });
+        let paths: Vec<_> = preview.changes.iter().map(|change| change.path.join("/")).collect();
+        assert_eq!(paths, ["Player/Credits"]);
+        assert!(preview.changes[0].take_theirs);
+        assert_eq!(preview.changes[0].theirs, "999999");
+        Ok(())
+    }
+}
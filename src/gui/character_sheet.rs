@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::{
+        mass_effect_1::item_db::{DbItem, Me1ItemDb},
+        mass_effect_1_leg::player::Player as Me1LegPlayer,
+        mass_effect_2::player::Player as Me2Player,
+        mass_effect_3::player::Player as Me3Player,
+        shared::character_sheet::CharacterSheet,
+    },
+};
+
+use super::{DatabasesState, Gui};
+
+impl<'ui> Gui<'ui> {
+    pub fn export_character_sheet(&self, save_game: &SaveGame, databases: &DatabasesState) {
+        let file = tinyfiledialogs::save_file_dialog_with_filter(
+            "",
+            "character_sheet.md",
+            &["*.md", "*.html"],
+            "Markdown or HTML (*.md, *.html)",
+        );
+
+        if let Some(path) = file {
+            let sheet = character_sheet(save_game, databases);
+            let is_html = matches!(
+                Path::new(&path).extension(),
+                Some(ext) if unicase::eq(ext.to_string_lossy().as_ref(), "html")
+            );
+            let text = if is_html { sheet.to_html() } else { sheet.to_markdown() };
+            let _ = self.event_addr.send(MainEvent::ExportTextFile(path, text));
+        }
+    }
+}
+
+pub fn character_sheet(save_game: &SaveGame, databases: &DatabasesState) -> CharacterSheet {
+    let mut sheet = match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            // Le joueur de ME1 n'est pas encore décodé, seulement le plot
+            let mut sheet = CharacterSheet::new("Commander Shepard");
+            if let Some(plot_db) = &databases.me1_plot_db {
+                sheet.decisions(&save_game.state.plot.bool_variables, vec![&plot_db.missions]);
+            }
+            sheet
+        }
+        SaveGame::MassEffect1Leg { save_game, .. } => {
+            let save_data = &save_game.save_data;
+            let mut sheet = me1_leg_sheet(&save_data.player, databases.me1_item_db.as_ref());
+            if let Some(plot_db) = &databases.me1_plot_db {
+                sheet.decisions(&save_data.plot.bool_variables, vec![&plot_db.missions]);
+            }
+            sheet
+        }
+        SaveGame::MassEffect2 { save_game, .. } => {
+            let mut sheet = me2_sheet(&save_game.player);
+            if let Some(plot_db) = &databases.me2_plot_db {
+                sheet.decisions(
+                    &save_game.plot.bool_variables,
+                    vec![&plot_db.missions, &plot_db.loyalty_missions, &plot_db.romance],
+                );
+            }
+            sheet
+        }
+        SaveGame::MassEffect2Leg { save_game, .. } => {
+            let mut sheet = me2_sheet(&save_game.player);
+            if let Some(plot_db) = &databases.me2_plot_db {
+                sheet.decisions(
+                    &save_game.plot.bool_variables,
+                    vec![&plot_db.missions, &plot_db.loyalty_missions, &plot_db.romance],
+                );
+            }
+            sheet
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            let mut sheet = me3_sheet(&save_game.player);
+            if let Some(plot_db) = &databases.me3_plot_db {
+                sheet.decisions(
+                    &save_game.plot.bool_variables,
+                    vec![&plot_db.missions, &plot_db.romance],
+                );
+            }
+            sheet
+        }
+    };
+    sheet.details.insert(0, ("Game", save_game.kind().to_owned()));
+    sheet
+}
+
+fn me1_leg_sheet(player: &Me1LegPlayer, item_db: Option<&Me1ItemDb>) -> CharacterSheet {
+    let mut sheet = CharacterSheet::new(player.first_name.to_str());
+    sheet.detail("Level", player.level);
+    sheet.detail("Origin", &player.origin);
+    sheet.detail("Notoriety", &player.notoriety);
+    sheet.detail("Credits", player.credits);
+
+    let talents = player
+        .complex_talents
+        .iter()
+        .filter(|talent| talent.ranks > 0)
+        .map(|talent| {
+            format!("Talent {} : {} / {}", talent.talent_id, talent.ranks, talent.max_rank)
+        })
+        .collect();
+    sheet.section("Talents", talents);
+
+    let gear = player
+        .inventory
+        .equipped
+        .iter()
+        .map(|item| {
+            let db_item = DbItem { item_id: item.item_id, manufacturer_id: item.manufacturer_id };
+            match item_db.and_then(|item_db| item_db.get(&db_item)) {
+                Some(name) => name.clone(),
+                None => format!("Unknown item {} ({})", item.item_id, item.manufacturer_id),
+            }
+        })
+        .collect();
+    sheet.section("Gear", gear);
+    sheet
+}
+
+fn me2_sheet(player: &Me2Player) -> CharacterSheet {
+    let mut sheet = CharacterSheet::new(player.first_name.to_str());
+    sheet.detail("Class", &player.class_name);
+    sheet.detail("Level", player.level);
+    sheet.detail("Origin", &player.origin);
+    sheet.detail("Notoriety", &player.notoriety);
+    sheet.detail("Credits", player.credits);
+
+    let powers = player
+        .powers
+        .iter()
+        .filter(|power| power.rank > 0.0)
+        .map(|power| format!("{} : rank {}", power.name, power.rank))
+        .collect();
+    sheet.section("Powers", powers);
+
+    let weapons = player.weapons.iter().map(|weapon| weapon.class_name.to_string()).collect();
+    sheet.section("Weapons", weapons);
+    sheet
+}
+
+fn me3_sheet(player: &Me3Player) -> CharacterSheet {
+    let mut sheet = CharacterSheet::new(player.first_name.to_str());
+    sheet.detail("Class", &player.class_name);
+    sheet.detail("Level", player.level);
+    sheet.detail("Origin", &player.origin);
+    sheet.detail("Notoriety", &player.notoriety);
+    sheet.detail("Credits", player.credits);
+
+    let powers = player
+        .powers
+        .iter()
+        .filter(|power| power.rank > 0.0)
+        .map(|power| format!("{} : rank {}", power.name, power.rank))
+        .collect();
+    sheet.section("Powers", powers);
+
+    let weapons = player.weapons.iter().map(|weapon| weapon.class_name.to_string()).collect();
+    sheet.section("Weapons", weapons);
+    sheet
+}
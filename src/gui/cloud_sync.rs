@@ -0,0 +1,57 @@
+use imgui::im_str;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::shared::cloud_sync::cloud_provider,
+};
+
+use super::Gui;
+
+#[derive(Default)]
+pub struct CloudSyncState {
+    // Save d'origine quand la save ouverte est une copie de travail
+    pub working_copy_of: Option<PathBuf>,
+    pub dismissed: bool,
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_cloud_sync_warning(&self, save_game: &SaveGame, cloud_sync: &mut CloudSyncState) {
+        let ui = self.ui;
+
+        if let Some(original) = &cloud_sync.working_copy_of {
+            ui.text_disabled(format!(
+                "Working on a local copy, saving it also replaces {}",
+                original.display()
+            ));
+            ui.separator();
+            return;
+        }
+
+        let provider = match cloud_provider(Path::new(save_game.file_path())) {
+            Some(provider) if !cloud_sync.dismissed => provider,
+            _ => return,
+        };
+
+        ui.text_colored(
+            [0.9, 0.8, 0.3, 1.0],
+            format!(
+                "This save is in a {} synced folder, the sync client may overwrite your edits.",
+                provider
+            ),
+        );
+        if ui.button(im_str!("Work on a local copy")) {
+            let _ = self
+                .event_addr
+                .send(MainEvent::CreateWorkingCopy(save_game.file_path().to_owned()));
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("The save is copied to `working_copies` and copied back on save");
+        }
+        ui.same_line();
+        if ui.button(im_str!("Dismiss##cloud_sync")) {
+            cloud_sync.dismissed = true;
+        }
+        ui.separator();
+    }
+}
@@ -0,0 +1,122 @@
+use imgui::{im_str, PopupModal};
+
+use crate::event_handler::MainEvent;
+
+use super::{
+    settings::{Confirmations, Settings},
+    Gui,
+};
+
+#[derive(Clone, Copy)]
+pub enum Confirmation {
+    RemoveItem,
+    ResetTalents,
+}
+
+impl Confirmation {
+    fn is_enabled(self, confirmations: &Confirmations) -> bool {
+        match self {
+            Confirmation::RemoveItem => confirmations.remove_item,
+            Confirmation::ResetTalents => confirmations.reset_talents,
+        }
+    }
+
+    fn disable(self, confirmations: &mut Confirmations) {
+        match self {
+            Confirmation::RemoveItem => confirmations.remove_item = false,
+            Confirmation::ResetTalents => confirmations.reset_talents = false,
+        }
+    }
+}
+
+struct Pending {
+    kind: Confirmation,
+    id: String,
+    value: usize,
+    message: String,
+    dont_ask_again: bool,
+}
+
+// Une seule confirmation à la fois, l'action est rejouée par son widget à la frame suivante
+#[derive(Default)]
+pub struct ConfirmationState {
+    pending: Option<Pending>,
+    confirmed: Option<(String, usize)>,
+}
+
+impl<'ui> Gui<'ui> {
+    // Renvoie la valeur (index, etc.) une fois l'action confirmée, ou tout de suite si le prompt est désactivé.
+    // `id` doit rester le même d'une frame à l'autre.
+    pub fn confirm(
+        &self, kind: Confirmation, id: &str, request: Option<usize>,
+        message: impl FnOnce(usize) -> String,
+    ) -> Option<usize> {
+        let mut state = self.confirmation.borrow_mut();
+
+        if let Some(value) = request {
+            if !kind.is_enabled(&self.confirmations) {
+                return Some(value);
+            }
+            state.pending = Some(Pending {
+                kind,
+                id: id.to_owned(),
+                value,
+                message: message(value),
+                dont_ask_again: false,
+            });
+            return None;
+        }
+
+        match state.confirmed.take() {
+            Some((confirmed_id, value)) if confirmed_id == id => Some(value),
+            confirmed => {
+                state.confirmed = confirmed;
+                None
+            }
+        }
+    }
+
+    pub fn draw_confirmation(&self, settings: &mut Settings) {
+        let ui = self.ui;
+        let mut state = self.confirmation.borrow_mut();
+        let ConfirmationState { pending, confirmed } = &mut *state;
+
+        // Pas réclamée par son widget (onglet fermé, etc.)
+        *confirmed = None;
+
+        let current = match pending {
+            Some(current) => current,
+            None => return,
+        };
+
+        ui.open_popup(im_str!("Confirm###confirmation"));
+        if let Some(_t) = PopupModal::new(im_str!("Confirm###confirmation"))
+            .always_auto_resize(true)
+            .begin_popup(ui)
+        {
+            ui.text(&current.message);
+            ui.text_disabled(im_str!("This cannot be undone."));
+            ui.checkbox(im_str!("Don't ask again"), &mut current.dont_ask_again);
+            ui.separator();
+
+            let mut close = false;
+            if ui.button_with_size(im_str!("Confirm"), [70.0, 0.0]) {
+                if current.dont_ask_again {
+                    current.kind.disable(&mut settings.confirmations);
+                    let _ = self.event_addr.send(MainEvent::SaveSettings(settings.clone()));
+                }
+                *confirmed = Some((current.id.clone(), current.value));
+                close = true;
+            }
+            ui.same_line();
+            if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                close = true;
+            }
+
+            if close {
+                *pending = None;
+                ui.close_current_popup();
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use imgui::{im_str, ChildWindow, ImString, MenuItem, TabBar, TabItem};
+
+use crate::save_data::{
+    mass_effect_1::Me1SaveGame,
+    mass_effect_1_leg::Me1LegSaveData,
+    mass_effect_2::{Me2LegSaveGame, Me2SaveGame},
+    mass_effect_3::Me3SaveGame,
+    shared::validation::{Issues, Tab},
+    RawUi,
+};
+
+use super::{
+    mass_effect_1::me1_editor, mass_effect_1_leg::me1_leg_editor, mass_effect_2::me2_editor,
+    mass_effect_3::me3_editor, settings::Widgets, validation::tab_label, DatabasesState, Gui,
+};
+
+// Un onglet de l'éditeur d'un jeu, `S` est la save de ce jeu
+pub trait EditorTab<S> {
+    fn title(&self) -> &'static str;
+
+    // Marque (!) du titre et problèmes affichés en haut de l'onglet
+    fn validation_tab(&self) -> Option<Tab> {
+        None
+    }
+
+    // `false` pour les onglets qui ont leur propre barre d'onglets
+    fn scrolls(&self) -> bool {
+        true
+    }
+
+    // Profil d'affichage, database chargée...
+    fn is_available(&self, _gui: &Gui, _databases: &DatabasesState) -> bool {
+        true
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut S, databases: &DatabasesState);
+}
+
+// Onglets d'un jeu dans l'ordre d'affichage, suivis des notes
+pub struct EditorRegistry<S> {
+    id: &'static str,
+    tabs: Vec<Box<dyn EditorTab<S>>>,
+}
+
+// Les onglets de chaque jeu, construits une fois au lancement
+pub struct Editors {
+    pub me1: EditorRegistry<Me1SaveGame>,
+    pub me1_leg: EditorRegistry<Me1LegSaveData>,
+    pub me2: EditorRegistry<Me2SaveGame>,
+    pub me2_leg: EditorRegistry<Me2LegSaveGame>,
+    pub me3: EditorRegistry<Me3SaveGame>,
+}
+
+impl Default for Editors {
+    fn default() -> Self {
+        Editors {
+            me1: me1_editor(),
+            me1_leg: me1_leg_editor(),
+            me2: me2_editor(),
+            me2_leg: me2_editor(),
+            me3: me3_editor(),
+        }
+    }
+}
+
+impl<S: Clone> EditorRegistry<S> {
+    pub fn new(id: &'static str) -> Self {
+        EditorRegistry { id, tabs: Vec::new() }
+    }
+
+    pub fn register(mut self, tab: impl EditorTab<S> + 'static) -> Self {
+        self.tabs.push(Box::new(tab));
+        self
+    }
+
+    pub fn draw(
+        &self, gui: &Gui, save_game: &mut S, issues: &Issues, databases: &DatabasesState,
+    ) -> Option<()> {
+        let ui = gui.ui;
+
+        let _t = TabBar::new(&ImString::new(self.id)).begin(ui)?;
+        for tab in self.tabs.iter().filter(|tab| tab.is_available(gui, databases)) {
+            let has_issues = matches!(tab.validation_tab(), Some(tab) if issues.has(tab));
+            let locked = gui.is_tab_locked(tab.title());
+            let tab_item = TabItem::new(&tab_label(tab.title(), has_issues, locked)).begin(ui);
+            gui.draw_tab_lock_menu(tab.title(), locked);
+
+            if let Some(_t) = tab_item {
+                let draw = |save_game: &mut S| {
+                    if tab.scrolls() {
+                        if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui) {
+                            if let Some(validation_tab) = tab.validation_tab() {
+                                gui.draw_validation_issues(issues, validation_tab);
+                            }
+                            tab.draw(gui, save_game, databases);
+                        }
+                    } else {
+                        tab.draw(gui, save_game, databases);
+                    }
+                };
+
+                // Une copie est dessinée, les modifications sont perdues
+                if locked {
+                    let mut copy = save_game.clone();
+                    gui.draw_read_only(|| draw(&mut copy));
+                } else {
+                    draw(save_game);
+                }
+            }
+        }
+
+        gui.draw_notes_tab();
+        Some(())
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    fn is_tab_locked(&self, title: &str) -> bool {
+        self.locked_tabs.borrow().iter().any(|locked| locked == title)
+    }
+
+    // Clic droit sur le titre d'un onglet
+    fn draw_tab_lock_menu(&self, title: &str, locked: bool) {
+        let ui = self.ui;
+
+        if let Some(_t) = self.begin_popup_context_item() {
+            let label = if locked { im_str!("Unlock tab") } else { im_str!("Lock tab") };
+            if MenuItem::new(label).build(ui) {
+                let mut locked_tabs = self.locked_tabs.borrow_mut();
+                if locked {
+                    locked_tabs.retain(|locked| locked != title);
+                } else {
+                    locked_tabs.push(title.to_owned());
+                }
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("A locked tab is read only, its edits are discarded");
+            }
+        }
+    }
+}
+
+// Toute la save dans l'onglet Raw
+pub struct RawTab {
+    pub ident: &'static str,
+}
+
+impl<S: RawUi> EditorTab<S> for RawTab {
+    fn title(&self) -> &'static str {
+        "Raw"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::Raw)
+    }
+
+    fn is_available(&self, gui: &Gui, _: &DatabasesState) -> bool {
+        gui.shows(Widgets::Raw)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut S, _: &DatabasesState) {
+        gui.set_next_item_open(true);
+        gui.draw_revertable_raw_ui(save_game, self.ident);
+    }
+}
@@ -0,0 +1,176 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Selectable, Window};
+
+use crate::save_data::schema::{self, Definition, Definitions, TypeDesc};
+
+use super::Gui;
+
+// Structures des saves telles que l'éditeur les lit, voir `RawUi::describe`,
+// avec les notes de `databases/help.ron`
+#[derive(Default)]
+pub struct FormatDocsState {
+    pub opened: bool,
+    // Générées à la première ouverture
+    docs: Option<(Vec<(&'static str, TypeDesc)>, Definitions)>,
+    selected: Option<String>,
+    history: Vec<String>,
+    filter: ImString,
+}
+
+impl FormatDocsState {
+    fn select(&mut self, key: String) {
+        if let Some(previous) = self.selected.replace(key) {
+            self.history.push(previous);
+        }
+    }
+}
+
+// Le type documenté d'un champ, `Player` pour `Vec<Player>`
+fn referenced(type_desc: &TypeDesc) -> Option<&str> {
+    match type_desc {
+        TypeDesc::Ref(key) => Some(key),
+        TypeDesc::Array(items) | TypeDesc::FixedArray(items, _) | TypeDesc::Optional(items) => {
+            referenced(items)
+        }
+        TypeDesc::Map(key, value) => referenced(value).or_else(|| referenced(key)),
+        _ => None,
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_format_docs(&self, state: &mut FormatDocsState) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Save format"))
+            .size([800.0, 500.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let (roots, definitions) = state.docs.get_or_insert_with(schema::save_definitions);
+            let mut select = None;
+
+            // Types
+            if let Some(_t) = ChildWindow::new("types").size([260.0, 0.0]).border(true).begin(ui) {
+                if Selectable::new(im_str!("Overview")).selected(state.selected.is_none()).build(ui)
+                {
+                    state.selected = None;
+                    state.history.clear();
+                }
+                ui.separator();
+                for (title, type_desc) in roots.iter() {
+                    if let Some(key) = referenced(type_desc) {
+                        let selected = state.selected.as_deref() == Some(key);
+                        if Selectable::new(&ImString::new(*title)).selected(selected).build(ui) {
+                            select = Some(key.to_owned());
+                        }
+                    }
+                }
+                ui.separator();
+
+                ui.set_next_item_width(-1.0);
+                ui.input_text(im_str!("##format-filter"), &mut state.filter)
+                    .hint(im_str!("Filter"))
+                    .resize_buffer(true)
+                    .build();
+                let filter = state.filter.to_str().to_lowercase();
+                for key in definitions.keys().filter(|key| key.to_lowercase().contains(&filter)) {
+                    let selected = state.selected.as_ref() == Some(key);
+                    if Selectable::new(&ImString::new(key)).selected(selected).build(ui) {
+                        select = Some(key.clone());
+                    }
+                }
+            }
+
+            // Documentation
+            ui.same_line();
+            if let Some(_t) = ChildWindow::new("docs").begin(ui) {
+                match &state.selected {
+                    Some(key) => {
+                        if !state.history.is_empty() {
+                            if ui.button(im_str!("< Back")) {
+                                state.selected = state.history.pop();
+                                return;
+                            }
+                            ui.same_line();
+                        }
+                        if let Some(key) = self.draw_format_type(key, definitions) {
+                            select = Some(key);
+                        }
+                    }
+                    None => self.draw_format_overview(),
+                }
+            }
+
+            if let Some(key) = select {
+                state.select(key);
+            }
+        }
+    }
+
+    fn draw_format_overview(&self) {
+        let ui = self.ui;
+
+        if let Some(help_db) = &self.help.borrow().db {
+            ui.text_wrapped(&ImString::new(&help_db.format_overview));
+            ui.separator();
+        }
+        ui.text_wrapped(im_str!("Pick a game to browse its save structure, the field types link to their own structure. The same structures can be exported as a JSON schema with `--export-schema`."));
+    }
+
+    // Retourne le type cliqué
+    fn draw_format_type(&self, key: &str, definitions: &Definitions) -> Option<String> {
+        let ui = self.ui;
+
+        ui.text(key);
+        if let Some(help_db) = &self.help.borrow().db {
+            if let Some(notes) = help_db.format_notes.get(key) {
+                ui.text_wrapped(&ImString::new(notes));
+            }
+        }
+        ui.separator();
+
+        let mut select = None;
+        match definitions.get(key) {
+            Some(Definition::Struct(fields)) => {
+                let _t = self.begin_table(im_str!("format-fields"), 3)?;
+                for (i, field) in fields.iter().enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    self.table_next_row();
+                    match field.title {
+                        Some(_) => ui.text(field.name),
+                        None => ui.text_disabled(field.name),
+                    }
+
+                    self.table_next_column();
+                    let type_name = ImString::new(schema::type_name(&field.type_desc));
+                    match referenced(&field.type_desc) {
+                        Some(key) => {
+                            if ui.small_button(&type_name) {
+                                select = Some(key.to_owned());
+                            }
+                        }
+                        None => ui.text(&type_name),
+                    }
+
+                    self.table_next_column();
+                    match field.title {
+                        Some(title) if field.readonly => ui.text(format!("{} (read only)", title)),
+                        Some(title) => ui.text(title),
+                        None => ui.text_disabled(im_str!("Not shown by the editor")),
+                    }
+                }
+            }
+            Some(Definition::Enum(variants)) => {
+                ui.text_disabled(im_str!("Stored as a byte, the index of the variant"));
+                for (i, variant) in variants.iter().enumerate() {
+                    ui.text(format!("{} : {}", i, variant));
+                }
+            }
+            None => ui.text_disabled(im_str!("Unknown type")),
+        }
+        select
+    }
+}
@@ -0,0 +1,98 @@
+use imgui::{im_str, PopupModal};
+
+use crate::event_handler::{MainEvent, SaveGame};
+
+use super::Gui;
+
+// Save plus récente que l'éditeur, en attente du choix de l'utilisateur
+pub struct NewerSaveVersion {
+    pub file_path: String,
+    pub version: i32,
+    pub known_version: i32,
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_newer_version_prompt(&self, newer_version: &mut Option<NewerSaveVersion>) {
+        let ui = self.ui;
+
+        let NewerSaveVersion { file_path, version, known_version } = match newer_version {
+            Some(newer_version) => newer_version,
+            None => return,
+        };
+
+        ui.open_popup(im_str!("Newer save version###newer_version"));
+        if let Some(_t) = PopupModal::new(im_str!("Newer save version###newer_version"))
+            .always_auto_resize(true)
+            .begin_popup(ui)
+        {
+            if version < known_version {
+                ui.text(format!(
+                    "This save is version {}, probably from a trial or a demo. The full game uses version {}.",
+                    version, known_version
+                ));
+            } else {
+                ui.text(format!(
+                    "This save is version {}, the editor only knows version {}.",
+                    version, known_version
+                ));
+            }
+            ui.text(format!(
+                "In compatibility mode, the save is read as version {} and the unknown data at its end is kept as is.",
+                known_version
+            ));
+            ui.text_colored(
+                [0.9, 0.8, 0.3, 1.0],
+                "Fields that changed in the new version may be misread. Keep a backup of your save.",
+            );
+            ui.separator();
+
+            let mut close = false;
+            if ui.button(im_str!("Open in compatibility mode")) {
+                let _ = self.event_addr.send(MainEvent::OpenSaveForwardCompat(file_path.clone()));
+                close = true;
+            }
+            ui.same_line();
+            if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                close = true;
+            }
+
+            if close {
+                *newer_version = None;
+                ui.close_current_popup();
+            }
+        }
+    }
+
+    pub fn draw_forward_compat_warning(&self, save_game: &mut SaveGame) {
+        let ui = self.ui;
+
+        let (is_variant, known_version) = match save_game.forward_compat() {
+            Some(forward_compat) => {
+                ui.text_colored(
+                    [0.9, 0.8, 0.3, 1.0],
+                    format!(
+                        "Compatibility mode: version {} read as version {}, {} unknown bytes kept as is. Some fields may be wrong.",
+                        forward_compat.version,
+                        forward_compat.known_version,
+                        forward_compat.trailing.len()
+                    ),
+                );
+                (forward_compat.is_variant(), forward_compat.known_version)
+            }
+            None => return,
+        };
+
+        if is_variant {
+            if ui.button(im_str!("Convert to full game format")) {
+                save_game.rescue_variant();
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!(
+                    "Saves as version {} without the unknown bytes",
+                    known_version
+                ));
+            }
+        }
+        ui.separator();
+    }
+}
@@ -0,0 +1,147 @@
+use imgui::{im_str, Condition, Window};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::event_handler::MainEvent;
+
+use super::{settings::Settings, Gui};
+
+#[derive(Deserialize)]
+pub struct HelpDb {
+    pub tour: Vec<TourStep>,
+    pub topics: IndexMap<String, String>,
+    // Documentation du format, en plus des structures décrites par `RawUi::describe`
+    #[serde(default)]
+    pub format_overview: String,
+    #[serde(default)]
+    pub format_notes: IndexMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct TourStep {
+    pub title: String,
+    pub text: String,
+    pub area: TourArea,
+}
+
+// Zone de la fenêtre présentée par une étape
+#[derive(Deserialize, Clone, Copy)]
+pub enum TourArea {
+    MenuBar,
+    Tabs,
+    Content,
+}
+
+// Partagé avec le Gui pour les (?) des différents onglets
+#[derive(Default)]
+pub struct HelpState {
+    pub db: Option<HelpDb>,
+    step: Option<usize>,
+}
+
+impl HelpState {
+    pub fn start_tour(&mut self) {
+        self.step = Some(0);
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    // (?) dont le texte vient de `databases/help.ron`, rien si le sujet n'existe pas
+    pub fn draw_help(&self, topic: &str) {
+        let help = self.help.borrow();
+        if let Some(text) = help.db.as_ref().and_then(|db| db.topics.get(topic)) {
+            self.draw_help_marker(text);
+        }
+    }
+
+    pub fn draw_tour(&self, settings: &mut Settings) {
+        let ui = self.ui;
+        let mut help = self.help.borrow_mut();
+        let HelpState { db, step } = &mut *help;
+
+        let (db, current) = match (db, *step) {
+            (Some(db), Some(current)) if current < db.tour.len() => (db, current),
+            _ => return,
+        };
+        let TourStep { title, text, area } = &db.tour[current];
+
+        let [width, height] = ui.io().display_size;
+        let position = match area {
+            TourArea::MenuBar => [20.0, 30.0],
+            TourArea::Tabs => [20.0, 60.0],
+            TourArea::Content => [width / 2.0 - 200.0, height / 3.0],
+        };
+
+        let mut next = None;
+        let mut finished = false;
+        let title_bar = im_str!("Guided tour ({}/{})###tour", current + 1, db.tour.len());
+        let window = Window::new(&title_bar)
+            .position(position, Condition::Always)
+            .size([400.0, 0.0], Condition::Always)
+            .collapsible(false)
+            .resizable(false);
+
+        if let Some(_t) = window.begin(ui) {
+            ui.text(title);
+            ui.separator();
+            ui.text_wrapped(&im_str!("{}", text));
+            ui.separator();
+
+            if current > 0 {
+                if ui.button(im_str!("Previous")) {
+                    next = Some(current - 1);
+                }
+                ui.same_line();
+            }
+            if current + 1 < db.tour.len() {
+                if ui.button(im_str!("Next")) {
+                    next = Some(current + 1);
+                }
+                ui.same_line();
+                finished = ui.button(im_str!("Skip"));
+            } else {
+                finished = ui.button(im_str!("Finish"));
+            }
+        }
+
+        if let Some(next) = next {
+            *step = Some(next);
+        }
+        if finished {
+            *step = None;
+            if !settings.tour_completed {
+                settings.tour_completed = true;
+                let _ = self.event_addr.send(MainEvent::SaveSettings(settings.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use super::*;
+    use crate::save_data::schema;
+
+    #[test]
+    fn deserialize_help_db() -> Result<()> {
+        let mut input = String::new();
+        {
+            let mut file = File::open("databases/help.ron")?;
+            file.read_to_string(&mut input)?;
+        }
+
+        let help_db: HelpDb = ron::from_str(&input)?;
+        assert!(!help_db.tour.is_empty());
+        assert!(help_db.topics.contains_key("raw"));
+
+        // Les notes portent sur des types existants
+        let (_, definitions) = schema::save_definitions();
+        for key in help_db.format_notes.keys() {
+            assert!(definitions.get(key).is_some(), "{}", key);
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use imgui::{im_str, sys, Condition, ImStr, ImString, TreeNode, TreeNodeFlags, TreeNodeToken, Ui};
-use std::marker::PhantomData;
+use std::{marker::PhantomData, ptr, slice};
 
 use super::Gui;
 
@@ -35,6 +35,17 @@ impl<'ui> Gui<'ui> {
         Self::begin_table_with_flags(&self, ident, column, FLAGS)
     }
 
+    // Plusieurs colonnes redimensionnables, pour les vues tableau
+    pub fn begin_grid(&self, ident: &ImStr, column: i32) -> Option<TableToken> {
+        const FLAGS: u32 = sys::ImGuiTableFlags_RowBg
+            | sys::ImGuiTableFlags_BordersOuterH
+            | sys::ImGuiTableFlags_BordersOuterV
+            | sys::ImGuiTableFlags_BordersInnerV
+            | sys::ImGuiTableFlags_Resizable;
+
+        Self::begin_table_with_flags(&self, ident, column, FLAGS)
+    }
+
     pub fn begin_columns(&self, column: i32) -> Option<TableToken> {
         const FLAGS: u32 = sys::ImGuiTableFlags_BordersInnerV;
 
@@ -61,6 +72,57 @@ impl<'ui> Gui<'ui> {
     pub fn table_next_column(&self) -> bool {
         unsafe { sys::igTableNextColumn() }
     }
+
+    // imgui le signale quand une colonne a été redimensionnée ou déplacée,
+    // l'ini n'est pas écrit sur le disque
+    pub fn take_table_layouts(&self) -> Option<String> {
+        if !self.ui.io().want_save_ini_settings {
+            return None;
+        }
+
+        let ini = unsafe {
+            (*sys::igGetIO()).WantSaveIniSettings = false;
+            let mut len = 0;
+            let ini = sys::igSaveIniSettingsToMemory(&mut len);
+            if ini.is_null() {
+                return None;
+            }
+            String::from_utf8_lossy(slice::from_raw_parts(ini as *const u8, len)).into_owned()
+        };
+        Some(table_sections(&ini))
+    }
+
+    pub fn begin_popup_context_item(&self) -> Option<PopupToken> {
+        if unsafe {
+            sys::igBeginPopupContextItem(ptr::null(), sys::ImGuiPopupFlags_MouseButtonRight as i32)
+        } {
+            Some(PopupToken::new(self.ui))
+        } else {
+            None
+        }
+    }
+}
+
+pub fn load_table_layouts(layouts: &str) {
+    if !layouts.is_empty() {
+        unsafe { sys::igLoadIniSettingsFromMemory(layouts.as_ptr() as *const _, layouts.len()) }
+    }
+}
+
+// Seules les sections `[Table]` sont gardées, pas la position des fenêtres
+fn table_sections(ini: &str) -> String {
+    let mut sections = String::new();
+    let mut is_table = false;
+    for line in ini.lines() {
+        if line.starts_with('[') {
+            is_table = line.starts_with("[Table]");
+        }
+        if is_table {
+            sections.push_str(line);
+            sections.push('\n');
+        }
+    }
+    sections
 }
 
 #[must_use]
@@ -77,3 +139,32 @@ impl Drop for TableToken<'_> {
         unsafe { sys::igEndTable() }
     }
 }
+
+#[must_use]
+pub struct PopupToken<'ui>(PhantomData<Ui<'ui>>);
+
+impl<'ui> PopupToken<'ui> {
+    pub fn new(_: &Ui<'ui>) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl Drop for PopupToken<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::igEndPopup() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keep_table_sections() {
+        let ini = "[Window][Trilogy Save Editor]\nPos=0,0\nSize=1000,670\n\n[Table][0x4A2B1C3D,3]\nColumn 0  Width=120\nColumn 1  Width=80 Sort=0v\n\n[Docking][Data]\n";
+        assert_eq!(
+            table_sections(ini),
+            "[Table][0x4A2B1C3D,3]\nColumn 0  Width=120\nColumn 1  Width=80 Sort=0v\n\n"
+        );
+    }
+}
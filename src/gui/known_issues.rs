@@ -0,0 +1,59 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+
+use crate::{event_handler::SaveGame, save_data::mass_effect_3::known_issues::KnownIssuesDb};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    // Réévalué à chaque frame, un problème corrigé disparaît de la liste
+    pub fn draw_known_issues(
+        &self, opened: &mut bool, save_game: &mut Option<SaveGame>,
+        known_issues: Option<&KnownIssuesDb>,
+    ) {
+        let ui = self.ui;
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Known issues found"))
+            .size([450.0, 350.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let (plot, known_issues) = match (save_game, known_issues) {
+                (Some(SaveGame::MassEffect3 { save_game, .. }), Some(known_issues)) => {
+                    (&mut save_game.plot, known_issues)
+                }
+                _ => {
+                    ui.text_disabled(im_str!("Open a Mass Effect 3 save"));
+                    return;
+                }
+            };
+
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                let mut fix = None;
+                for (i, issue) in known_issues.found(plot).enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    ui.text_colored([0.9, 0.8, 0.3, 1.0], &issue.title);
+                    ui.text_wrapped(&ImString::new(&issue.description));
+                    for issue_fix in &issue.fixes {
+                        if ui.button(&ImString::new(&issue_fix.label)) {
+                            fix = Some(issue_fix);
+                        }
+                        ui.same_line();
+                    }
+                    ui.new_line();
+                    ui.separator();
+                }
+
+                match fix {
+                    Some(fix) => fix.apply(plot),
+                    None if known_issues.found(plot).next().is_none() => {
+                        ui.text_disabled(im_str!("No known issue found"));
+                    }
+                    None => (),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+use std::fmt::Write;
+use tracing::Level;
+
+use crate::logger::LogBuffer;
+
+use super::Gui;
+
+pub struct LogViewerState {
+    pub opened: bool,
+    level: usize,
+}
+
+impl Default for LogViewerState {
+    fn default() -> Self {
+        // Info par défaut
+        LogViewerState { opened: false, level: 2 }
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_log_viewer(&self, state: &mut LogViewerState, log_buffer: &LogBuffer) {
+        const LEVELS: [Level; 4] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG];
+        const ITEMS: [&imgui::ImStr; 4] =
+            [im_str!("Error"), im_str!("Warning"), im_str!("Info"), im_str!("Debug")];
+
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Logs"))
+            .size([600.0, 400.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            self.draw_edit_enum("Level", &mut state.level, &ITEMS);
+            let max_level = LEVELS[state.level];
+
+            ui.same_line();
+            if ui.button(im_str!("Copy")) {
+                let mut text = String::new();
+                for entry in log_buffer.entries().iter().filter(|entry| entry.level <= max_level) {
+                    let _ =
+                        writeln!(text, "{:>5} {}: {}", entry.level, entry.target, entry.message);
+                }
+                ui.set_clipboard_text(&ImString::new(text));
+            }
+            ui.same_line();
+            if ui.button(im_str!("Clear")) {
+                log_buffer.entries().clear();
+            }
+            ui.separator();
+
+            if let Some(_t) = ChildWindow::new("scroll").horizontal_scrollbar(true).begin(ui) {
+                // Suit les nouveaux messages si on est déjà en bas
+                let follow = ui.scroll_y() >= ui.scroll_max_y();
+
+                for entry in log_buffer.entries().iter().filter(|entry| entry.level <= max_level) {
+                    let color = match entry.level {
+                        Level::ERROR => [0.9, 0.4, 0.3, 1.0],
+                        Level::WARN => [0.9, 0.8, 0.3, 1.0],
+                        Level::INFO => [1.0, 1.0, 1.0, 1.0],
+                        _ => [0.6, 0.6, 0.6, 1.0],
+                    };
+                    ui.text_colored(color, format!("{:>5}", entry.level));
+                    ui.same_line();
+                    ui.text_disabled(entry.target);
+                    ui.same_line();
+                    ui.text(&entry.message);
+                }
+
+                if follow {
+                    ui.set_scroll_here_y();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use imgui::{im_str, ComboBox, ImString, Selectable};
+use indexmap::IndexMap;
+
+use crate::save_data::{
+    shared::map_db::{self, KnownMap, MapLocation},
+    RawUi,
+};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    // ME2 / ME3 : la carte où Shepard réapparaît au chargement de la save
+    pub fn draw_map_location(
+        &self, mut map_location: MapLocation, known_maps: Option<&IndexMap<String, KnownMap>>,
+    ) -> Option<()> {
+        let ui = self.ui;
+
+        let _t = self.begin_table(im_str!("location-table"), 1)?;
+        self.table_next_row();
+        let _t = self.push_tree_node("Location")?;
+        ui.same_line();
+        self.draw_help_marker(
+            "Where Shepard is when the save is loaded.\n\
+            If Shepard is stuck, pick a known map to be moved to a safe place.\n\
+            Loaded areas of the previous map are forgotten, the game loads the default ones.",
+        );
+
+        if let Some(known_maps) = known_maps {
+            self.table_next_row();
+            let current = map_db::find_map(known_maps, map_location.base_level_name.to_str());
+            let preview = match current {
+                Some((_, map)) => ImString::new(&map.label),
+                None => ImString::new("Unknown map"),
+            };
+
+            let width = ui.push_item_width(200.0);
+            if let Some(_t) = ComboBox::new(im_str!("Known maps")).preview_value(&preview).begin(ui)
+            {
+                for (level_name, map) in known_maps {
+                    let selected = matches!(current, Some((name, _)) if name == level_name);
+                    let text = match map.safe_location {
+                        Some(_) => ImString::new(format!("{} ({})", map.label, level_name)),
+                        None => {
+                            ImString::new(format!("{} ({}, position kept)", map.label, level_name))
+                        }
+                    };
+                    if Selectable::new(&text).selected(selected).build(ui) {
+                        map_location.teleport(level_name, map.safe_location.as_ref());
+                    }
+                }
+            }
+            width.pop(ui);
+        }
+
+        self.table_next_row();
+        map_location.base_level_name.draw_raw_ui(self, "Map");
+        self.table_next_row();
+        map_location.location.draw_raw_ui(self, "Position");
+        self.table_next_row();
+        map_location.rotation.draw_raw_ui(self, "Rotation");
+        Some(())
+    }
+}
@@ -1,29 +1,167 @@
 use if_chain::if_chain;
-use imgui::{im_str, ChildWindow, ImStr, ImString, ListClipper, TabBar, TabItem};
+use imgui::{
+    im_str, ChildWindow, ComboBox, ImStr, ImString, InputInt, ListClipper, MenuItem, Selectable,
+    TabBar, TabItem,
+};
 use std::{
     cell::{RefCell, RefMut},
     cmp::Ordering,
 };
 
-use crate::save_data::{
-    mass_effect_1::{
-        data::{ArrayType, Data, Property, StructType},
-        player::{Name, Player},
-        plot_db::Me1PlotDb,
-        Me1SaveGame,
+use crate::{
+    event_handler::MainEvent,
+    save_data::{
+        mass_effect_1::{
+            data::{ArrayType, Data, Property, StructType},
+            inventory::{self, MAX_ITEMS},
+            item_db::{DbItem, Me1ItemDb},
+            player::{Name, Player},
+            plot_db::Me1PlotDb,
+            property_text::export_properties,
+            Me1SaveGame, ZipMember,
+        },
+        shared::{
+            plot::{
+                packed_plot_id, packed_position, BoolVec, Me1PlotTable, PlotBitfield, PlotCategory,
+            },
+            plot_references::PlotId,
+            recipe::Game,
+            validation::Issues,
+        },
+        ImguiString, List, RawUi,
     },
-    shared::plot::{Me1PlotTable, PlotCategory},
-    ImguiString, List, RawUi,
 };
 
-use super::{DatabasesState, Gui};
+use super::{
+    editor_tabs::{EditorRegistry, EditorTab},
+    mass_effect_1_leg::ITEM_LEVEL_LIST,
+    settings::Widgets,
+    DatabasesState, Gui,
+};
+
+// Page de la grille, ID du calculateur et bool mis en évidence
+#[derive(Clone, Copy, Default)]
+pub struct PlotGridState {
+    page: usize,
+    plot_id: usize,
+    highlighted: Option<usize>,
+}
+
+// Objet choisi pour être ajouté à l'inventaire
+#[derive(Default)]
+pub struct Me1InventoryState {
+    item: Option<DbItem>,
+    sophistication: usize,
+    error: Option<String>,
+}
+
+pub fn me1_editor() -> EditorRegistry<Me1SaveGame> {
+    EditorRegistry::new("mass_effect_1")
+        .register(GeneralTab)
+        .register(PlotTab)
+        .register(PlotGridTab)
+        .register(RawTab)
+        .register(AdvancedTab)
+}
+
+struct GeneralTab;
+
+impl EditorTab<Me1SaveGame> for GeneralTab {
+    fn title(&self) -> &'static str {
+        "General"
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1SaveGame, databases: &DatabasesState) {
+        gui.draw_me1_general(save_game);
+        gui.draw_me1_inventory(&mut save_game.player, databases.me1_item_db.as_ref());
+    }
+}
+
+struct PlotTab;
+
+impl EditorTab<Me1SaveGame> for PlotTab {
+    fn title(&self) -> &'static str {
+        "Plot"
+    }
+
+    fn scrolls(&self) -> bool {
+        false
+    }
+
+    fn is_available(&self, _: &Gui, databases: &DatabasesState) -> bool {
+        databases.me1_plot_db.is_some()
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1SaveGame, databases: &DatabasesState) {
+        if let Some(me1_plot_db) = &databases.me1_plot_db {
+            gui.draw_me1_plot_db(&mut save_game.state.plot, me1_plot_db);
+        }
+    }
+}
+
+// Tous les booléens de plot, le plot DB de ME1 est loin de tous les connaître
+struct PlotGridTab;
+
+impl EditorTab<Me1SaveGame> for PlotGridTab {
+    fn title(&self) -> &'static str {
+        "Plot grid"
+    }
+
+    fn is_available(&self, gui: &Gui, _: &DatabasesState) -> bool {
+        gui.shows(Widgets::Raw)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1SaveGame, databases: &DatabasesState) {
+        gui.draw_me1_plot_grid(&mut save_game.state.plot.bool_variables, databases);
+    }
+}
+
+// Seulement la partie State est éditable, le Player est dessiné à partir de ses objets
+struct RawTab;
+
+impl EditorTab<Me1SaveGame> for RawTab {
+    fn title(&self) -> &'static str {
+        "Raw"
+    }
+
+    fn is_available(&self, gui: &Gui, _: &DatabasesState) -> bool {
+        gui.shows(Widgets::Raw)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1SaveGame, _: &DatabasesState) {
+        // Player
+        gui.set_next_item_open(true);
+        gui.draw_raw_player(&save_game.player);
+        // State
+        gui.set_next_item_open(true);
+        gui.draw_revertable_raw_ui(&mut save_game.state, "State");
+        // Names
+        if gui.shows(Widgets::Developer) {
+            gui.draw_me1_name_table(&save_game.player);
+        }
+    }
+}
+
+struct AdvancedTab;
+
+impl EditorTab<Me1SaveGame> for AdvancedTab {
+    fn title(&self) -> &'static str {
+        "Advanced"
+    }
+
+    fn is_available(&self, gui: &Gui, _: &DatabasesState) -> bool {
+        gui.shows(Widgets::Raw)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1SaveGame, _: &DatabasesState) {
+        gui.draw_me1_zip_members(save_game);
+    }
+}
 
 impl<'ui> Gui<'ui> {
     pub fn draw_mass_effect_1(
-        &self, save_game: &mut Me1SaveGame, databases: &DatabasesState,
+        &self, save_game: &mut Me1SaveGame, issues: &Issues, databases: &DatabasesState,
     ) -> Option<()> {
-        let ui = self.ui;
-
         // Ajoute un Name dupliqué à la liste
         {
             let duplicate = &mut *save_game.player.duplicate.borrow_mut();
@@ -32,41 +170,58 @@ impl<'ui> Gui<'ui> {
             }
         }
 
-        // Tab bar
-        let _t = TabBar::new(im_str!("mass_effect_1")).begin(ui)?;
+        self.editors.me1.draw(self, save_game, issues, databases)
+    }
 
-        // General
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("General")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me1_general(save_game);
+    // Les fichiers de l'archive zip de la save, pour les outils externes
+    fn draw_me1_zip_members(&self, save_game: &Me1SaveGame) {
+        let ui = self.ui;
+
+        ui.text_colored(
+            [0.9, 0.8, 0.3, 1.0],
+            "Imported files replace the ones of the opened save, keep a backup.",
+        );
+        ui.same_line();
+        self.draw_help_marker(
+            "An imported file is only accepted if it can be read entirely,\nthe save is zipped again when saved.",
+        );
+        ui.separator();
+
+        for member in save_game.members() {
+            let file_name = member.file_name();
+            ui.text(file_name);
+            ui.same_line_with_pos(200.0);
+            if ui.small_button(&im_str!("Export##export-{}", file_name)) {
+                self.export_me1_zip_member(save_game, member);
             }
-        }
-        // Plot
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Plot")).begin(ui);
-            if let Some(me1_plot_db) = &databases.me1_plot_db;
-            then {
-                self.draw_me1_plot_db(&mut save_game.state.plot, me1_plot_db);
+            ui.same_line();
+            if ui.small_button(&im_str!("Import##import-{}", file_name)) {
+                let file = tinyfiledialogs::open_file_dialog(
+                    &format!("Import {}", file_name),
+                    file_name,
+                    Some((&["*.sav"], "Save member (*.sav)")),
+                );
+                if let Some(path) = file {
+                    let _ = self.event_addr.send(MainEvent::ImportMe1ZipMember(path, member));
+                }
             }
         }
-        // Raw
+    }
+
+    fn export_me1_zip_member(&self, save_game: &Me1SaveGame, member: ZipMember) {
+        let file = tinyfiledialogs::save_file_dialog_with_filter(
+            &format!("Export {}", member.file_name()),
+            member.file_name(),
+            &["*.sav"],
+            "Save member (*.sav)",
+        );
         if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Raw")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
+            if let Some(path) = file;
+            if let Ok(data) = save_game.export_member(member);
             then {
-                // Player
-                self.set_next_item_open(true);
-                self.draw_raw_player(&save_game.player);
-                // State
-                self.set_next_item_open(true);
-                save_game.state.draw_raw_ui(self, "State");
-
+                let _ = self.event_addr.send(MainEvent::ExportBinaryFile(path, data));
             }
         }
-
-        Some(())
     }
 
     fn draw_me1_general(&self, save_game: &mut Me1SaveGame) -> Option<()> {
@@ -298,6 +453,87 @@ impl<'ui> Gui<'ui> {
         Some(())
     }
 
+    fn draw_me1_inventory(&self, player: &mut Player, item_db: Option<&Me1ItemDb>) -> Option<()> {
+        let ui = self.ui;
+        let items = inventory::inventory_items(player).ok()?;
+
+        let _t = self.begin_table(im_str!("inventory-table"), 1)?;
+        self.table_next_row();
+        let _t = self.push_tree_node(&format!(
+            "Inventory ({} / {})##inventory",
+            items.len(),
+            MAX_ITEMS
+        ))?;
+        for item in &items {
+            self.table_next_row();
+            let name = item_db
+                .and_then(|item_db| item_db.get(&item.item))
+                .map(String::as_str)
+                .unwrap_or("Unknown item");
+            let level = ITEM_LEVEL_LIST.get(item.sophistication as usize).copied();
+            ui.text(name);
+            ui.same_line();
+            ui.text_disabled(level.unwrap_or(im_str!("?")));
+        }
+
+        // Add
+        self.table_next_row();
+        let item_db = match item_db {
+            Some(item_db) => item_db,
+            None => {
+                ui.text_disabled(im_str!("Loading the item database..."));
+                return Some(());
+            }
+        };
+        let mut state = self.me1_inventory.borrow_mut();
+
+        let preview = state
+            .item
+            .and_then(|item| item_db.get(&item))
+            .map(String::as_str)
+            .unwrap_or("Pick an item");
+        let width = ui.push_item_width(318.0);
+        if let Some(_t) = ComboBox::new(im_str!("##me1-add-item"))
+            .preview_value(&ImString::new(preview))
+            .begin(ui)
+        {
+            for (item, name) in item_db.iter() {
+                let selected = state.item == Some(*item);
+                if Selectable::new(&ImString::new(name)).selected(selected).build(ui) {
+                    state.item = Some(*item);
+                }
+            }
+        }
+        width.pop(ui);
+        ui.same_line();
+
+        let width = ui.push_item_width(60.0);
+        ComboBox::new(im_str!("##me1-add-item-level")).build_simple_string(
+            ui,
+            &mut state.sophistication,
+            &ITEM_LEVEL_LIST,
+        );
+        width.pop(ui);
+        ui.same_line();
+
+        if ui.button(im_str!("add")) {
+            if let Some(item) = state.item {
+                let sophistication = state.sophistication as u8;
+                state.error = inventory::grant_item(player, item_db, item, sophistication)
+                    .err()
+                    .map(|err| format!("{:#}", err));
+            }
+        }
+        ui.same_line();
+        self.draw_help_marker("The new item copies the structure of an item of the same kind already in the inventory, with its mod slots empty.");
+
+        if let Some(err) = &state.error {
+            self.table_next_row();
+            ui.text_colored([0.9, 0.4, 0.3, 1.0], err);
+        }
+        Some(())
+    }
+
     fn me1_find_object_property<'a>(
         player: &'a Player, properties: &[Property], property_name: &str,
     ) -> Option<RefMut<'a, Data>> {
@@ -418,28 +654,173 @@ impl<'ui> Gui<'ui> {
         Some(())
     }
 
+    fn draw_me1_plot_grid(&self, booleans: &mut BoolVec, databases: &DatabasesState) {
+        let ui = self.ui;
+        const PAGE_LEN: usize = 100;
+        const COLUMNS: usize = 10;
+
+        // Labels
+        if ui.button(im_str!("Import labels")) {
+            let file = tinyfiledialogs::open_file_dialog(
+                "Import labels",
+                "",
+                Some((&["*.txt"], "Plot labels (*.txt)")),
+            );
+            if let Some(path) = file {
+                let _ = self.event_addr.send(MainEvent::ImportMe1PlotLabels(path));
+            }
+        }
+        ui.same_line();
+        match &databases.me1_plot_labels {
+            Some(labels) => {
+                ui.text(format!("{} labels from {}", labels.booleans.len(), labels.file_path))
+            }
+            None => ui.text_disabled(im_str!("No imported labels")),
+        }
+        ui.same_line();
+        self.draw_help_marker(
+            "A text file with one `ID=label` line per boolean, lines starting with `#` are ignored.\nImported labels take precedence over the plot database.",
+        );
+
+        // Calculateur d'ID
+        let mut grid = self.plot_grid.get();
+        let (index, bit) = packed_position(grid.plot_id);
+        let (mut plot_id, mut index, mut bit) = (grid.plot_id as i32, index as i32, bit as i32);
+        let width = ui.push_item_width(120.0);
+        if InputInt::new(ui, im_str!("Plot ID"), &mut plot_id).build() {
+            grid.plot_id = plot_id.max(0) as usize;
+        }
+        ui.same_line();
+        if InputInt::new(ui, im_str!("Int index"), &mut index).build() {
+            grid.plot_id = packed_plot_id(index.max(0) as usize, bit as usize);
+        }
+        ui.same_line();
+        if InputInt::new(ui, im_str!("Bit"), &mut bit).build() {
+            grid.plot_id = packed_plot_id(index as usize, bit.clamp(0, 31) as usize);
+        }
+        width.pop(ui);
+        ui.same_line();
+        let (index, bit) = packed_position(grid.plot_id);
+        ui.text(format!("Mask 0x{:08X}", 1_u32 << bit));
+        ui.same_line();
+        if ui.button(im_str!("Go to")) && grid.plot_id < booleans.len() {
+            grid.page = grid.plot_id / PAGE_LEN;
+            grid.highlighted = Some(grid.plot_id);
+        }
+        ui.same_line();
+        self.draw_help_marker(&format!(
+            "Booleans are packed by 32 in the ints of `Bool Variables`, lowest bit first.\nPlot ID {} is bit {} of int {}.",
+            grid.plot_id, bit, index
+        ));
+
+        // Pages
+        let page_count = booleans.len().saturating_sub(1) / PAGE_LEN + 1;
+        let mut page = grid.page.min(page_count - 1);
+        if ui.button(im_str!("<")) && page > 0 {
+            page -= 1;
+        }
+        ui.same_line();
+        let mut go_to = (page * PAGE_LEN) as i32;
+        let width = ui.push_item_width(120.0);
+        if InputInt::new(ui, im_str!("First ID"), &mut go_to).step(PAGE_LEN as i32).build() {
+            page = (go_to.max(0) as usize / PAGE_LEN).min(page_count - 1);
+        }
+        width.pop(ui);
+        ui.same_line();
+        if ui.button(im_str!(">")) && page + 1 < page_count {
+            page += 1;
+        }
+        ui.same_line();
+        ui.text(format!("Page {} / {}", page + 1, page_count));
+        grid.page = page;
+        self.plot_grid.set(grid);
+
+        let label = |id: usize| {
+            let imported =
+                databases.me1_plot_labels.as_ref().and_then(|labels| labels.booleans.get(&id));
+            match imported {
+                Some(label) => Some(label.clone()),
+                None => databases.me1_plot_db.as_ref().and_then(|plot_db| {
+                    plot_db.categories().find_map(|category| {
+                        category.booleans.get(&id).map(|entry| entry.label.clone())
+                    })
+                }),
+            }
+        };
+
+        // Grille
+        let start = page * PAGE_LEN;
+        let end = (start + PAGE_LEN).min(booleans.len());
+        if let Some(_t) = self.begin_grid(im_str!("plot-grid"), COLUMNS as i32) {
+            for row in (start..end).step_by(COLUMNS) {
+                self.table_next_row();
+                for id in row..(row + COLUMNS).min(end) {
+                    if id > row {
+                        self.table_next_column();
+                    }
+
+                    let label = label(id);
+                    if let Some(mut value) = booleans.get_mut(id) {
+                        ui.checkbox(&im_str!("##plot-grid-{}", id), &mut value);
+                    }
+                    ui.same_line();
+                    match &label {
+                        _ if grid.highlighted == Some(id) => {
+                            ui.text_colored([1.0, 0.8, 0.2, 1.0], id.to_string())
+                        }
+                        Some(_) => ui.text(id.to_string()),
+                        None => ui.text_disabled(id.to_string()),
+                    }
+                    if ui.is_item_hovered() {
+                        let (index, bit) = packed_position(id);
+                        ui.tooltip_text(format!(
+                            "{}\nInt {}, bit {}",
+                            label.as_deref().unwrap_or("No label"),
+                            index,
+                            bit
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
     fn draw_me1_plot_category(&self, plot_table: &mut Me1PlotTable, plot_db: &PlotCategory) {
         let ui = self.ui;
-        let PlotCategory { booleans, ints } = plot_db;
+        let PlotCategory { booleans, ints, bitfields, .. } = plot_db;
 
-        if booleans.is_empty() && ints.is_empty() {
+        if booleans.is_empty() && ints.is_empty() && bitfields.is_empty() {
             return;
         }
 
         // Booleans
+        let list = format!("bool@{:p}", plot_db);
+        self.draw_plot_bool_selection(&list, booleans, &mut plot_table.bool_variables);
         let mut clipper = ListClipper::new(booleans.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
                 let (plot_id, plot_desc) = booleans.get_index(i as usize).unwrap();
-                let plot = plot_table.bool_variables.get_mut(*plot_id);
-                if let Some(mut plot) = plot {
+                if let Some(mut plot) = plot_table.bool_variables.get_mut(*plot_id) {
                     self.table_next_row();
-                    plot.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc));
+                    self.draw_selectable_row(&list, i as usize, &mut *plot, |value| {
+                        value.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc))
+                    });
+                    self.draw_plot_context_menu(Game::MassEffect1, PlotId::Bool(*plot_id));
+                    self.draw_plot_entry_details(&format!("bool-{}", plot_id), plot_desc);
                 }
+                self.draw_plot_requirements(plot_db, *plot_id, &mut plot_table.bool_variables);
             }
         }
 
         // Integers
+        let list = format!("int@{:p}", plot_db);
+        if let Some((value, rows)) = self.draw_int_selection_actions(&list) {
+            for (plot_id, _) in rows.into_iter().filter_map(|row| ints.get_index(row)) {
+                if let Some(plot) = plot_table.int_variables.get_mut(*plot_id) {
+                    *plot = value;
+                }
+            }
+        }
         let mut clipper = ListClipper::new(ints.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
@@ -447,10 +828,30 @@ impl<'ui> Gui<'ui> {
                 let plot = plot_table.int_variables.get_mut(*plot_id);
                 if let Some(plot) = plot {
                     self.table_next_row();
-                    plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc));
+                    self.draw_selectable_row(&list, i as usize, plot, |plot| {
+                        match plot_desc.options() {
+                            Some(options) => self.draw_edit_plot_options(
+                                &format!("int-{}", plot_id),
+                                plot,
+                                &options,
+                            ),
+                            None => {
+                                plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc))
+                            }
+                        }
+                    });
+                    self.draw_plot_context_menu(Game::MassEffect1, PlotId::Int(*plot_id));
+                    self.draw_plot_entry_details(&format!("int-{}", plot_id), plot_desc);
                 }
             }
         }
+        // Bitfields
+        for (plot_id, PlotBitfield { label, bits }) in bitfields {
+            if let Some(plot) = plot_table.int_variables.get_mut(*plot_id) {
+                self.table_next_row();
+                self.draw_edit_bitfield(&format!("{}##bitfield-{}", label, plot_id), plot, bits);
+            }
+        }
     }
 
     fn draw_raw_player(&self, player: &Player) -> Option<()> {
@@ -463,6 +864,25 @@ impl<'ui> Gui<'ui> {
         Some(())
     }
 
+    // Noms auxquels renvoient les objets et les propriétés, par index
+    fn draw_me1_name_table(&self, player: &Player) -> Option<()> {
+        let ui = self.ui;
+
+        let _t = self.begin_table(im_str!("name-table"), 1)?;
+        self.table_next_row();
+        let _t = self.push_tree_node("Name Table")?;
+
+        let mut clipper = ListClipper::new(player.names.len() as i32).begin(ui);
+        while clipper.step() {
+            for i in clipper.display_start()..clipper.display_end() {
+                self.table_next_row();
+                let name = player.names[i as usize].borrow();
+                ui.text(format!("{} : {}", i, name.to_str()));
+            }
+        }
+        Some(())
+    }
+
     fn draw_object(
         &self, player: &Player, ident: usize, property_name: Option<&ImStr>, object_id: i32,
     ) {
@@ -474,8 +894,10 @@ impl<'ui> Gui<'ui> {
             Option::None => object_name.to_owned(),
         };
 
+        let tree_node = self.push_tree_node(&format!("{}##{}", property_name, ident));
+        self.draw_object_context_menu(player, object_id);
         if_chain! {
-            if let Some(_t) = self.push_tree_node(&format!("{}##{}", property_name, ident));
+            if let Some(_t) = tree_node;
             if let Some(_t) = self.begin_table(im_str!("object-table"), 1);
             then {
                 let mut data = player.get_data(object_id).borrow_mut();
@@ -486,6 +908,35 @@ impl<'ui> Gui<'ui> {
         }
     }
 
+    fn draw_object_context_menu(&self, player: &Player, object_id: i32) -> Option<()> {
+        let ui = self.ui;
+
+        let _t = self.begin_popup_context_item()?;
+        if MenuItem::new(im_str!("Export properties")).build(ui) {
+            let file = tinyfiledialogs::save_file_dialog_with_filter(
+                "",
+                "properties.ron",
+                &["*.ron"],
+                "Properties (*.ron)",
+            );
+            if_chain! {
+                if let Some(path) = file;
+                if let Ok(text) = export_properties(player, object_id);
+                then {
+                    let _ = self.event_addr.send(MainEvent::ExportTextFile(path, text));
+                }
+            }
+        }
+        if MenuItem::new(im_str!("Import properties")).build(ui) {
+            let file =
+                tinyfiledialogs::open_file_dialog("", "", Some((&["*.ron"], "Properties (*.ron)")));
+            if let Some(path) = file {
+                let _ = self.event_addr.send(MainEvent::ImportMe1Properties(path, object_id));
+            }
+        }
+        Some(())
+    }
+
     fn draw_property(&self, player: &Player, ident: usize, property: &mut Property) -> Option<()> {
         match property {
             Property::Byte { .. } | Property::None { .. } => return None,
@@ -1,75 +1,118 @@
 use std::ops::IndexMut;
 
-use if_chain::if_chain;
-use imgui::{
-    im_str, ChildWindow, ComboBox, ImStr, ImString, ListClipper, Selectable, TabBar, TabItem,
-};
+use imgui::{im_str, ComboBox, ImStr, ImString, ListClipper, Selectable};
 
 use crate::save_data::{
     mass_effect_1::item_db::{DbItem, Me1ItemDb},
     mass_effect_1_leg::{
-        player::{ComplexTalent, Item, ItemLevel, Player},
+        player::{self, ComplexTalent, Item, ItemLevel, Player},
         squad::Henchman,
         Me1LegSaveData,
     },
-    shared::player::{Notoriety, Origin},
+    shared::{
+        player::{Notoriety, Origin},
+        plot::PlotOptions,
+        validation::{Issues, Tab},
+    },
     RawUi,
 };
 
-use super::{DatabasesState, Gui};
+use super::{
+    confirmation::Confirmation,
+    editor_tabs::{EditorRegistry, EditorTab, RawTab},
+    DatabasesState, Gui,
+};
 
-impl<'ui> Gui<'ui> {
-    pub fn draw_mass_effect_1_leg(
-        &self, save_game: &mut Me1LegSaveData, databases: &DatabasesState,
-    ) -> Option<()> {
-        let ui = self.ui;
+pub fn me1_leg_editor() -> EditorRegistry<Me1LegSaveData> {
+    EditorRegistry::new("mass_effect_1_leg")
+        .register(GeneralTab)
+        .register(PlotTab)
+        .register(InventoryTab)
+        .register(HeadMorphTab)
+        .register(RawTab { ident: "Mass Effect 1" })
+}
 
-        // Tab bar
-        let _t = TabBar::new(im_str!("mass_effect_1_leg")).begin(ui)?;
+struct GeneralTab;
 
-        // General
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("General")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me1_leg_general(save_game);
-            }
-        }
-        // Plot
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Plot")).begin(ui);
-            if let Some(me1_plot_db) = &databases.me1_plot_db;
-            then {
-                self.draw_me1_plot_db(&mut save_game.plot, me1_plot_db);
-            }
-        }
-        // Inventory
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Inventory")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            if let Some(me1_item_db) = &databases.me1_item_db;
-            then {
-                self.draw_me1_le_inventory_tab(save_game, me1_item_db);
-            }
-        }
-        // Head Morph
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Head Morph")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me3_and_le_head_morph(&mut save_game.player.head_morph);
-            }
+impl EditorTab<Me1LegSaveData> for GeneralTab {
+    fn title(&self) -> &'static str {
+        "General"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::General)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1LegSaveData, _: &DatabasesState) {
+        gui.draw_me1_leg_general(save_game);
+    }
+}
+
+struct PlotTab;
+
+impl EditorTab<Me1LegSaveData> for PlotTab {
+    fn title(&self) -> &'static str {
+        "Plot"
+    }
+
+    fn scrolls(&self) -> bool {
+        false
+    }
+
+    fn is_available(&self, _: &Gui, databases: &DatabasesState) -> bool {
+        databases.me1_plot_db.is_some()
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1LegSaveData, databases: &DatabasesState) {
+        if let Some(me1_plot_db) = &databases.me1_plot_db {
+            gui.draw_me1_plot_db(&mut save_game.plot, me1_plot_db);
         }
-        // Raw
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Raw")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.set_next_item_open(true);
-                save_game.draw_raw_ui(self, "Mass Effect 1");
-            }
+    }
+}
+
+struct InventoryTab;
+
+impl EditorTab<Me1LegSaveData> for InventoryTab {
+    fn title(&self) -> &'static str {
+        "Inventory"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::Inventory)
+    }
+
+    fn is_available(&self, _: &Gui, databases: &DatabasesState) -> bool {
+        databases.me1_item_db.is_some()
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1LegSaveData, databases: &DatabasesState) {
+        if let Some(me1_item_db) = &databases.me1_item_db {
+            gui.draw_me1_le_inventory_tab(save_game, me1_item_db);
         }
-        Some(())
+    }
+}
+
+struct HeadMorphTab;
+
+impl EditorTab<Me1LegSaveData> for HeadMorphTab {
+    fn title(&self) -> &'static str {
+        "Head Morph"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::HeadMorph)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me1LegSaveData, _: &DatabasesState) {
+        gui.draw_me3_and_le_head_morph(&mut save_game.player.head_morph);
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_mass_effect_1_leg(
+        &self, save_game: &mut Me1LegSaveData, issues: &Issues, databases: &DatabasesState,
+    ) -> Option<()> {
+        self.editors.me1_leg.draw(self, save_game, issues, databases)
     }
 
     fn draw_me1_leg_general(&self, save_game: &mut Me1LegSaveData) -> Option<()> {
@@ -82,6 +125,7 @@ impl<'ui> Gui<'ui> {
             first_name,
             origin,
             notoriety,
+            specialization_bonus_id,
             talent_points,
             credits,
             medigel,
@@ -89,6 +133,7 @@ impl<'ui> Gui<'ui> {
             omnigel,
             face_code,
             complex_talents,
+            auto_levelup_template_id,
             ..
         } = player;
 
@@ -169,12 +214,7 @@ impl<'ui> Gui<'ui> {
                 }
 
                 self.table_next_row();
-                face_code.draw_raw_ui(self, "Identity Code");
-                ui.same_line();
-                self.draw_help_marker(
-                    "If you change this you can display whatever you want in the menus\n\
-                    in place of your `Identity Code`, which is pretty cool !",
-                );
+                self.draw_identity_code(face_code);
             }
         }
 
@@ -224,6 +264,40 @@ impl<'ui> Gui<'ui> {
             }
         }
 
+        // Options
+        if let Some(_t) = self.begin_table(im_str!("options-table"), 1) {
+            self.table_next_row();
+            if let Some(_t) = self.push_tree_node("Options") {
+                self.table_next_row();
+                self.draw_edit_plot_options(
+                    "auto-levelup-player",
+                    auto_levelup_template_id,
+                    &player::auto_levelup_options("Player auto level up"),
+                );
+                ui.same_line();
+                self.draw_help_marker(
+                    "Template used to spend talent points when leveling up automatically",
+                );
+
+                for henchman in squad.iter_mut() {
+                    let character_name = match henchman.character_name() {
+                        Some(character_name) => character_name,
+                        None => continue,
+                    };
+                    self.table_next_row();
+                    self.draw_edit_plot_options(
+                        &format!("auto-levelup-{}", character_name),
+                        &mut henchman.auto_levelup_template_id,
+                        &player::auto_levelup_options(character_name),
+                    );
+                    ui.same_line();
+                    henchman
+                        .helmet_visible
+                        .draw_raw_ui(self, &format!("Helmet##{}", character_name));
+                }
+            }
+        }
+
         // Gameplay
         if let Some(_t) = self.begin_table(im_str!("gameplay-table"), 1) {
             self.table_next_row();
@@ -237,9 +311,9 @@ impl<'ui> Gui<'ui> {
                 self.table_next_row();
                 current_xp.draw_raw_ui(self, "Current XP");
                 self.table_next_row();
-                talent_points.draw_raw_ui(self, "Talent Points");
+                self.draw_me1_le_bonus_talent(specialization_bonus_id, complex_talents, squad);
                 self.table_next_row();
-                self.draw_me1_le_reset_talents("player", talent_points, complex_talents);
+                self.draw_me1_le_talents("Player", talent_points, complex_talents);
             }
         }
 
@@ -248,19 +322,15 @@ impl<'ui> Gui<'ui> {
             self.table_next_row();
             self.set_next_item_open(true);
             if let Some(_t) = self.push_tree_node("Squad") {
-                for Henchman { tag, talent_points, complex_talents, .. } in squad {
-                    let character_name = match tag.to_str() {
-                        "hench_asari" => "Liara",
-                        "hench_humanfemale" => "Ashley",
-                        "hench_humanmale" => "Kaidan",
-                        "hench_krogan" => "Wrex",
-                        "hench_quarian" => "Tali'Zorah",
-                        "hench_turian" => "Garrus",
-                        _ => continue,
+                for henchman in squad {
+                    let character_name = match henchman.character_name() {
+                        Some(character_name) => character_name,
+                        None => continue,
                     };
+                    let Henchman { talent_points, complex_talents, .. } = henchman;
 
                     self.table_next_row();
-                    self.draw_me1_le_reset_talents(character_name, talent_points, complex_talents);
+                    self.draw_me1_le_talents(character_name, talent_points, complex_talents);
                 }
             }
         }
@@ -268,12 +338,135 @@ impl<'ui> Gui<'ui> {
         Some(())
     }
 
+    fn draw_me1_le_bonus_talent(
+        &self, bonus_talent_id: &mut i32, complex_talents: &mut Vec<ComplexTalent>,
+        squad: &[Henchman],
+    ) {
+        let ui = self.ui;
+
+        // Talents débloqués par les succès, ids tirés des talents de l'équipe
+        const BONUS_TALENTS: [(i32, &str); 15] = [
+            (7, "Assault Rifles"),
+            (14, "Shotguns"),
+            (21, "Sniper Rifles"),
+            (49, "Throw"),
+            (50, "Lift"),
+            (56, "Warp"),
+            (57, "Singularity"),
+            (63, "Barrier"),
+            (64, "Stasis"),
+            (84, "Electronics"),
+            (86, "Damping"),
+            (91, "Hacking"),
+            (93, "Decryption"),
+            (98, "First Aid"),
+            (99, "Medicine"),
+        ];
+
+        let options = PlotOptions { label: "Bonus Talent", options: BONUS_TALENTS.to_vec() };
+        self.draw_edit_plot_options("bonus-talent", bonus_talent_id, &options);
+        ui.same_line();
+        self.draw_help_marker("Talent granted by an achievement in addition to the class talents.");
+
+        // Le talent doit aussi être dans la liste du joueur pour être utilisable
+        let is_bonus = BONUS_TALENTS.iter().any(|(id, _)| id == bonus_talent_id);
+        let is_unlocked = complex_talents.iter().any(|talent| talent.talent_id == *bonus_talent_id);
+        if is_bonus && !is_unlocked {
+            let template = squad
+                .iter()
+                .flat_map(|henchman| &henchman.complex_talents)
+                .find(|talent| talent.talent_id == *bonus_talent_id);
+
+            if let Some(template) = template {
+                ui.same_line();
+                if ui.small_button(im_str!("Unlock")) {
+                    let mut talent = template.clone();
+                    talent.ranks = 0;
+                    complex_talents.push(talent);
+                }
+            }
+        }
+    }
+
+    fn draw_me1_le_talents(
+        &self, character_name: &str, talent_points: &mut i32, complex_talents: &mut [ComplexTalent],
+    ) -> Option<()> {
+        let ui = self.ui;
+
+        let _t = self.push_tree_node(&format!("{}##{}-talents", character_name, character_name))?;
+        let _t = self.begin_table(&im_str!("{}-talents-table", character_name), 1)?;
+
+        self.table_next_row();
+        talent_points.draw_raw_ui(self, "Talent Points");
+
+        for i in 0..complex_talents.len() {
+            let missing = player::missing_prerequisites(complex_talents, &complex_talents[i]);
+            let plan = player::unlock_plan(complex_talents, i);
+
+            self.table_next_row();
+            let ComplexTalent { talent_id, ranks, max_rank, .. } = &mut complex_talents[i];
+            let old_ranks = *ranks;
+            self.draw_edit_i32(&format!("Talent {}##talent-{}", talent_id, talent_id), ranks);
+            *ranks = (*ranks).clamp(0, *max_rank);
+            // Verrouillé tant que les prérequis ne sont pas remplis
+            if !missing.is_empty() && *ranks > old_ranks {
+                *ranks = old_ranks;
+            }
+            // Un point par rang, pas d'augmentation sans points disponibles
+            if *ranks > old_ranks {
+                *ranks = old_ranks + (*ranks - old_ranks).min((*talent_points).max(0));
+            }
+            *talent_points -= *ranks - old_ranks;
+            ui.same_line();
+            ui.text_disabled(im_str!("/ {}", max_rank));
+            let talent_id = *talent_id;
+
+            if !missing.is_empty() {
+                let requires: Vec<_> = missing
+                    .iter()
+                    .map(|(id, rank)| format!("Talent {} rank {}", id, rank))
+                    .collect();
+                ui.same_line();
+                ui.text_colored([0.9, 0.8, 0.3, 1.0], format!("requires {}", requires.join(", ")));
+
+                if let Some(plan) = plan {
+                    let cost = player::plan_cost(complex_talents, &plan);
+                    if cost <= *talent_points {
+                        ui.same_line();
+                        if ui.small_button(&im_str!(
+                            "Unlock ({} points)##unlock-talent-{}",
+                            cost,
+                            talent_id
+                        )) {
+                            for (index, rank) in plan {
+                                complex_talents[index].ranks = rank;
+                            }
+                            *talent_points -= cost;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.table_next_row();
+        self.draw_me1_le_reset_talents(character_name, talent_points, complex_talents);
+        Some(())
+    }
+
     fn draw_me1_le_reset_talents(
         &self, character_name: &str, talent_points: &mut i32, complex_talents: &mut [ComplexTalent],
     ) {
         let ui = self.ui;
 
-        if ui.button(&im_str!("Reset {} talents", character_name)) {
+        let request = ui.button(&im_str!("Reset {} talents", character_name)).then_some(0);
+        // Par personnage, l'adresse des talents n'est pas stable d'une frame à l'autre
+        let id = format!("reset-talents@{}", character_name);
+        if self
+            .confirm(Confirmation::ResetTalents, &id, request, |_| {
+                format!("Reset {} talents ?", character_name)
+            })
+            .is_some()
+        {
             for talent in complex_talents {
                 *talent_points += talent.ranks;
                 talent.ranks = 0;
@@ -368,7 +561,7 @@ impl<'ui> Gui<'ui> {
 
                     ui.align_text_to_frame_padding();
                     if ui.small_button(&im_str!("remove##remove-{}", i)) {
-                        remove = Some(i);
+                        remove = Some(i as usize);
                     }
                     ui.same_line();
 
@@ -381,8 +574,11 @@ impl<'ui> Gui<'ui> {
             }
 
             // Remove
-            if let Some(i) = remove {
-                inventory.remove(i as usize);
+            let id = format!("inventory@{:p}", inventory);
+            if let Some(i) = self.confirm(Confirmation::RemoveItem, &id, remove, |i| {
+                format!("Remove item {} from the inventory ?", i)
+            }) {
+                inventory.remove(i);
             }
         } else {
             self.table_next_row();
@@ -429,19 +625,6 @@ impl<'ui> Gui<'ui> {
 
         // Item level
         let mut item_level_idx = current_item.item_level.clone() as usize;
-        const ITEM_LEVEL_LIST: [&ImStr; 11] = [
-            im_str!("None"),
-            im_str!("I"),
-            im_str!("II"),
-            im_str!("III"),
-            im_str!("IV"),
-            im_str!("V"),
-            im_str!("VI"),
-            im_str!("VII"),
-            im_str!("VIII"),
-            im_str!("IX"),
-            im_str!("X"),
-        ];
         let width = ui.push_item_width(60.0);
         if ComboBox::new(&im_str!("##item-level-{}", ident)).build_simple_string(
             ui,
@@ -467,3 +650,18 @@ impl<'ui> Gui<'ui> {
         width.pop(ui);
     }
 }
+
+// Niveau d'un objet, partagé avec ME1
+pub(super) const ITEM_LEVEL_LIST: [&ImStr; 11] = [
+    im_str!("None"),
+    im_str!("I"),
+    im_str!("II"),
+    im_str!("III"),
+    im_str!("IV"),
+    im_str!("V"),
+    im_str!("VI"),
+    im_str!("VII"),
+    im_str!("VIII"),
+    im_str!("IX"),
+    im_str!("X"),
+];
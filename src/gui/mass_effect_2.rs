@@ -14,114 +14,137 @@ use crate::{
         },
         shared::{
             appearance::{HasHeadMorph, HeadMorph},
+            map_db::MapLocation,
             player::{Notoriety, Origin},
-            plot::{Me1PlotTable, PlotCategory},
+            plot::{Me1PlotTable, PlotBitfield, PlotCategory},
+            plot_references::PlotId,
+            recipe::Game,
+            validation::{Issues, Tab},
         },
         RawUi,
     },
 };
 
-use super::{DatabasesState, Gui, Theme};
+use super::{
+    editor_tabs::{EditorRegistry, EditorTab, RawTab},
+    settings::Widgets,
+    DatabasesState, Gui, Theme,
+};
 
-enum Me2Type<'a> {
+pub enum Me2Type<'a> {
     Vanilla(&'a mut Me2SaveGame),
     Legendary(&'a mut Me2LegSaveGame),
 }
 
-impl<'ui> Gui<'ui> {
-    pub fn draw_mass_effect_2(
-        &self, save_game: &mut Me2SaveGame, databases: &DatabasesState,
-    ) -> Option<()> {
-        let ui = self.ui;
+// Les onglets sont les mêmes pour ME2 et ME2 Legendary
+pub trait Me2Save: RawUi + Clone + 'static {
+    fn me2_type(&mut self) -> Me2Type<'_>;
+}
 
-        // Tab bar
-        let _t = TabBar::new(im_str!("mass_effect_2")).begin(ui)?;
+impl Me2Save for Me2SaveGame {
+    fn me2_type(&mut self) -> Me2Type<'_> {
+        Me2Type::Vanilla(self)
+    }
+}
 
-        // General
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("General")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me2_general(Me2Type::Vanilla(save_game));
-            }
-        }
-        // Plot
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Plot")).begin(ui);
-            if let Some(_t) = TabBar::new(im_str!("plot-tab")).begin(ui);
-            then {
-                self.draw_me2_plot_db(&mut save_game.plot, &mut save_game.me1_plot, &databases);
-            }
-        }
-        // Head Morph
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Head Morph")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me2_head_morph(&mut save_game.player.appearance.head_morph, save_game.player.is_female);
-            }
-        }
-        // Raw
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Raw")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.set_next_item_open(true);
-                save_game.draw_raw_ui(self, "Mass Effect 2");
-            }
-        }
-        Some(())
+impl Me2Save for Me2LegSaveGame {
+    fn me2_type(&mut self) -> Me2Type<'_> {
+        Me2Type::Legendary(self)
     }
+}
 
-    pub fn draw_mass_effect_2_leg(
-        &self, save_game: &mut Me2LegSaveGame, databases: &DatabasesState,
-    ) -> Option<()> {
-        let ui = self.ui;
+pub fn me2_editor<S: Me2Save>() -> EditorRegistry<S> {
+    EditorRegistry::new("mass_effect_2")
+        .register(GeneralTab)
+        .register(PlotTab)
+        .register(HeadMorphTab)
+        .register(RawTab { ident: "Mass Effect 2" })
+}
 
-        // Tab bar
-        let _t = TabBar::new(im_str!("mass_effect_2")).begin(ui)?;
+struct GeneralTab;
 
-        // General
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("General")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me2_general(Me2Type::Legendary(save_game));
-            }
-        }
-        // Plot
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Plot")).begin(ui);
-            if let Some(_t) = TabBar::new(im_str!("plot-tab")).begin(ui);
-            then {
-                self.draw_me2_plot_db(&mut save_game.plot, &mut save_game.me1_plot, &databases);
-            }
-        }
-        // Head Morph
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Head Morph")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me3_and_le_head_morph(&mut save_game.player.appearance.head_morph);
-            }
+impl<S: Me2Save> EditorTab<S> for GeneralTab {
+    fn title(&self) -> &'static str {
+        "General"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::General)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut S, databases: &DatabasesState) {
+        gui.draw_me2_general(save_game.me2_type(), databases);
+    }
+}
+
+struct PlotTab;
+
+impl<S: Me2Save> EditorTab<S> for PlotTab {
+    fn title(&self) -> &'static str {
+        "Plot"
+    }
+
+    fn scrolls(&self) -> bool {
+        false
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut S, databases: &DatabasesState) {
+        let (plot, me1_plot) = match save_game.me2_type() {
+            Me2Type::Vanilla(save_game) => (&mut save_game.plot, &mut save_game.me1_plot),
+            Me2Type::Legendary(save_game) => (&mut save_game.plot, &mut save_game.me1_plot),
+        };
+        if let Some(_t) = TabBar::new(im_str!("plot-tab")).begin(gui.ui) {
+            gui.draw_me2_plot_db(plot, me1_plot, databases);
         }
-        // Raw
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Raw")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.set_next_item_open(true);
-                save_game.draw_raw_ui(self, "Mass Effect 2");
+    }
+}
+
+struct HeadMorphTab;
+
+impl<S: Me2Save> EditorTab<S> for HeadMorphTab {
+    fn title(&self) -> &'static str {
+        "Head Morph"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::HeadMorph)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut S, _: &DatabasesState) {
+        match save_game.me2_type() {
+            Me2Type::Vanilla(save_game) => gui.draw_me2_head_morph(
+                &mut save_game.player.appearance.head_morph,
+                save_game.player.is_female,
+            ),
+            Me2Type::Legendary(save_game) => {
+                gui.draw_me3_and_le_head_morph(&mut save_game.player.appearance.head_morph)
             }
         }
-        Some(())
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_mass_effect_2(
+        &self, save_game: &mut Me2SaveGame, issues: &Issues, databases: &DatabasesState,
+    ) -> Option<()> {
+        self.editors.me2.draw(self, save_game, issues, databases)
+    }
+
+    pub fn draw_mass_effect_2_leg(
+        &self, save_game: &mut Me2LegSaveGame, issues: &Issues, databases: &DatabasesState,
+    ) -> Option<()> {
+        self.editors.me2_leg.draw(self, save_game, issues, databases)
     }
 
-    fn draw_me2_general(&self, save_game: Me2Type) -> Option<()> {
+    fn draw_me2_general(&self, save_game: Me2Type, databases: &DatabasesState) -> Option<()> {
         let ui = self.ui;
 
         match save_game {
             Me2Type::Vanilla(Me2SaveGame {
+                base_level_name,
+                location,
+                rotation,
+                levels,
                 difficulty,
                 end_game_state,
                 player,
@@ -130,6 +153,10 @@ impl<'ui> Gui<'ui> {
                 ..
             })
             | Me2Type::Legendary(Me2LegSaveGame {
+                base_level_name,
+                location,
+                rotation,
+                levels,
                 difficulty,
                 end_game_state,
                 player,
@@ -314,9 +341,7 @@ impl<'ui> Gui<'ui> {
                         }
 
                         self.table_next_row();
-                        face_code.draw_raw_ui(self, "Identity Code");
-                        ui.same_line();
-                        self.draw_help_marker("If you change this you can display whatever you want in the menus\nin place of your `Identity Code`, which is pretty cool !");
+                        self.draw_identity_code(face_code);
                     }
                 }
 
@@ -402,6 +427,11 @@ impl<'ui> Gui<'ui> {
                     }
                 }
 
+                // Location
+                let map_location = MapLocation { base_level_name, location, rotation, levels };
+                let known_maps = databases.map_db.as_ref().map(|map_db| &map_db.me2);
+                self.draw_map_location(map_location, known_maps);
+
                 // Bonus Powers
                 self.set_next_item_open(true);
                 self.draw_me2_bonus_powers(powers)
@@ -637,25 +667,39 @@ impl<'ui> Gui<'ui> {
 
     fn draw_me2_plot_category(&self, plot_table: &mut PlotTable, plot_db: &PlotCategory) {
         let ui = self.ui;
-        let PlotCategory { booleans, ints } = plot_db;
+        let PlotCategory { booleans, ints, bitfields, .. } = plot_db;
 
-        if booleans.is_empty() && ints.is_empty() {
+        if booleans.is_empty() && ints.is_empty() && bitfields.is_empty() {
             return;
         }
 
         // Booleans
+        let list = format!("bool@{:p}", plot_db);
+        self.draw_plot_bool_selection(&list, booleans, &mut plot_table.bool_variables);
         let mut clipper = ListClipper::new(booleans.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
                 let (plot_id, plot_desc) = booleans.get_index(i as usize).unwrap();
-                let plot = plot_table.bool_variables.get_mut(*plot_id);
-                if let Some(mut plot) = plot {
+                if let Some(mut plot) = plot_table.bool_variables.get_mut(*plot_id) {
                     self.table_next_row();
-                    plot.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc));
+                    self.draw_selectable_row(&list, i as usize, &mut *plot, |value| {
+                        value.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc))
+                    });
+                    self.draw_plot_context_menu(Game::MassEffect2, PlotId::Bool(*plot_id));
+                    self.draw_plot_entry_details(&format!("bool-{}", plot_id), plot_desc);
                 }
+                self.draw_plot_requirements(plot_db, *plot_id, &mut plot_table.bool_variables);
             }
         }
         // Integers
+        let list = format!("int@{:p}", plot_db);
+        if let Some((value, rows)) = self.draw_int_selection_actions(&list) {
+            for (plot_id, _) in rows.into_iter().filter_map(|row| ints.get_index(row)) {
+                if let Some(plot) = plot_table.int_variables.get_mut(*plot_id) {
+                    *plot = value;
+                }
+            }
+        }
         let mut clipper = ListClipper::new(ints.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
@@ -663,10 +707,30 @@ impl<'ui> Gui<'ui> {
                 let plot = plot_table.int_variables.get_mut(*plot_id);
                 if let Some(plot) = plot {
                     self.table_next_row();
-                    plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc));
+                    self.draw_selectable_row(&list, i as usize, plot, |plot| {
+                        match plot_desc.options() {
+                            Some(options) => self.draw_edit_plot_options(
+                                &format!("int-{}", plot_id),
+                                plot,
+                                &options,
+                            ),
+                            None => {
+                                plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc))
+                            }
+                        }
+                    });
+                    self.draw_plot_context_menu(Game::MassEffect2, PlotId::Int(*plot_id));
+                    self.draw_plot_entry_details(&format!("int-{}", plot_id), plot_desc);
                 }
             }
         }
+        // Bitfields
+        for (plot_id, PlotBitfield { label, bits }) in bitfields {
+            if let Some(plot) = plot_table.int_variables.get_mut(*plot_id) {
+                self.table_next_row();
+                self.draw_edit_bitfield(&format!("{}##bitfield-{}", label, plot_id), plot, bits);
+            }
+        }
     }
 
     fn draw_me2_head_morph(&self, head_morph: &mut HasHeadMorph, is_female: bool) {
@@ -682,6 +746,10 @@ impl<'ui> Gui<'ui> {
                 let _ = self.event_addr.send(MainEvent::ImportHeadMorph(path));
             }
         }
+        ui.same_line();
+        self.draw_appearance_code_buttons(head_morph.as_ref());
+        ui.same_line();
+        self.draw_help("head_morph");
         match head_morph {
             Some(head_morph) => {
                 // Export
@@ -708,7 +776,7 @@ impl<'ui> Gui<'ui> {
                 ui.separator();
 
                 // Raw
-                if *has_head_morph {
+                if *has_head_morph && self.shows(Widgets::Raw) {
                     let HeadMorph {
                         hair_mesh,
                         accessory_mesh,
@@ -3,6 +3,7 @@ use imgui::{
     im_str, ChildWindow, ComboBox, ImStr, ImString, ListClipper, Selectable, TabBar, TabItem,
 };
 use indexmap::IndexMap;
+use std::collections::BTreeMap;
 
 use crate::{
     event_handler::MainEvent,
@@ -10,67 +11,228 @@ use crate::{
         mass_effect_1::plot_db::Me1PlotDb,
         mass_effect_2::plot_db::Me2PlotDb,
         mass_effect_3::{
-            player::{Player, Power},
+            galaxy_map::GalaxyMap,
+            gear_db::{self, Me3GearDb},
+            player::{Player, Power, Weapon},
             plot::PlotTable,
             plot_db::{Me3PlotDb, PlotVariable},
             Me3SaveGame,
         },
         shared::{
-            appearance::{HasHeadMorph, HeadMorph},
-            player::{Notoriety, Origin},
-            plot::PlotCategory,
+            appearance::{normalize_face_code, HasHeadMorph, HeadMorph},
+            map_db::MapLocation,
+            player::{Notoriety, Origin, WeaponLoadout},
+            plot::{PlotBitfield, PlotCategory},
+            plot_references::PlotId,
+            recipe::{Game, PlotEdit},
+            validation::{Issues, Tab},
         },
         ImguiString, RawUi,
     },
 };
 
-use super::{DatabasesState, Gui, Theme};
+use super::{
+    editor_tabs::{EditorRegistry, EditorTab, RawTab},
+    settings::Widgets,
+    DatabasesState, Gui, Theme,
+};
+
+pub fn me3_editor() -> EditorRegistry<Me3SaveGame> {
+    EditorRegistry::new("mass_effect_3")
+        .register(GeneralTab)
+        .register(PlotTab)
+        .register(GalaxyAtWarTab)
+        .register(HeadMorphTab)
+        .register(RawTab { ident: "Mass Effect 3" })
+}
+
+struct GeneralTab;
+
+impl EditorTab<Me3SaveGame> for GeneralTab {
+    fn title(&self) -> &'static str {
+        "General"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::General)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me3SaveGame, databases: &DatabasesState) {
+        gui.draw_me3_general(save_game, databases);
+    }
+}
+
+struct PlotTab;
+
+impl EditorTab<Me3SaveGame> for PlotTab {
+    fn title(&self) -> &'static str {
+        "Plot"
+    }
+
+    fn scrolls(&self) -> bool {
+        false
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me3SaveGame, databases: &DatabasesState) {
+        gui.draw_me3_plot_db(&mut save_game.plot, &mut save_game.player_variables, databases);
+    }
+}
+
+struct GalaxyAtWarTab;
+
+impl EditorTab<Me3SaveGame> for GalaxyAtWarTab {
+    fn title(&self) -> &'static str {
+        "Galaxy at War"
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me3SaveGame, _: &DatabasesState) {
+        gui.draw_me3_galaxy_at_war(&mut save_game.player, &mut save_game.galaxy_map);
+    }
+}
+
+struct HeadMorphTab;
+
+impl EditorTab<Me3SaveGame> for HeadMorphTab {
+    fn title(&self) -> &'static str {
+        "Head Morph"
+    }
+
+    fn validation_tab(&self) -> Option<Tab> {
+        Some(Tab::HeadMorph)
+    }
+
+    fn draw(&self, gui: &Gui, save_game: &mut Me3SaveGame, _: &DatabasesState) {
+        gui.draw_me3_and_le_head_morph(&mut save_game.player.appearance.head_morph);
+    }
+}
 
 impl<'ui> Gui<'ui> {
     pub fn draw_mass_effect_3(
-        &self, save_game: &mut Me3SaveGame, databases: &DatabasesState,
+        &self, save_game: &mut Me3SaveGame, issues: &Issues, databases: &DatabasesState,
     ) -> Option<()> {
-        let ui = self.ui;
+        self.editors.me3.draw(self, save_game, issues, databases)
+    }
 
-        // Tab bar
-        let _t = TabBar::new(im_str!("mass_effect_3")).begin(ui)?;
+    // Ressources de la carte galactique et ce qui a déjà été scanné
+    fn draw_me3_galaxy_at_war(&self, player: &mut Player, galaxy_map: &mut GalaxyMap) {
+        let ui = self.ui;
+        let Player { war_assets, probes, current_fuel, .. } = player;
 
-        // General
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("General")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me3_general(save_game);
+        // Resources
+        if let Some(_t) = self.begin_table(im_str!("resources-table"), 1) {
+            self.table_next_row();
+            self.set_next_item_open(true);
+            if let Some(_t) = self.push_tree_node("Resources") {
+                self.table_next_row();
+                current_fuel.draw_raw_ui(self, "Current Fuel");
+                self.table_next_row();
+                probes.draw_raw_ui(self, "Probes");
+                ui.same_line();
+                self.draw_help_marker(
+                    "Mass Effect 3 scans without probes, this value is a leftover of Mass Effect 2.",
+                );
             }
         }
-        // Plot
-        if let Some(_t) = TabItem::new(im_str!("Plot")).begin(ui) {
-            self.draw_me3_plot_db(&mut save_game.plot, &mut save_game.player_variables, databases);
-        }
-        // Head Morph
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Head Morph")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.draw_me3_and_le_head_morph(&mut save_game.player.appearance.head_morph);
+
+        // Reapers
+        if let Some(_t) = self.begin_table(im_str!("reapers-table"), 1) {
+            self.table_next_row();
+            let systems = &galaxy_map.systems;
+            let alerted = systems
+                .iter()
+                .filter(|system| system.reaper_detected || system.reaper_alert_level > 0.0)
+                .count();
+            let label =
+                format!("Reaper alerts ({} / {} systems)###reaper-alerts", alerted, systems.len());
+            if let Some(_t) = self.push_tree_node(&label) {
+                self.table_next_row();
+                if ui.button(im_str!("Calm the Reapers")) {
+                    galaxy_map.calm_reapers();
+                }
+                ui.same_line();
+                self.draw_help_marker(
+                    "Scanning raises the alert level of a system until the Reapers come.\nResets the alert level of every system.",
+                );
+                for system in galaxy_map.systems.iter_mut() {
+                    self.table_next_row();
+                    system
+                        .reaper_alert_level
+                        .draw_raw_ui(self, &format!("Alert level##alert-{}", system.id));
+                    ui.same_line();
+                    ui.checkbox(
+                        &im_str!("Reapers##detected-{}", system.id),
+                        &mut system.reaper_detected,
+                    );
+                    ui.same_line();
+                    ui.text_disabled(format!("System {}", system.id));
+                }
             }
         }
-        // Raw
-        if_chain! {
-            if let Some(_t) = TabItem::new(im_str!("Raw")).begin(ui);
-            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
-            then {
-                self.set_next_item_open(true);
-                save_game.draw_raw_ui(self, "Mass Effect 3");
+
+        // Planets
+        if let Some(_t) = self.begin_table(im_str!("planets-table"), 1) {
+            self.table_next_row();
+            let planets = &mut galaxy_map.planets;
+            let scanned = planets.iter().filter(|planet| planet.show_as_scanned).count();
+            let label =
+                format!("Scanned planets ({} / {})###scanned-planets", scanned, planets.len());
+            if let Some(_t) = self.push_tree_node(&label) {
+                let mut by_system: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+                for (i, planet) in planets.iter().enumerate() {
+                    by_system.entry(planet.system_id()).or_default().push(i);
+                }
+
+                for (system_id, indices) in by_system {
+                    self.table_next_row();
+                    if let Some(_t) = self.push_tree_node(&format!("System {}", system_id)) {
+                        for i in indices {
+                            let planet = &mut planets[i];
+                            self.table_next_row();
+                            ui.text(format!("Planet {}", planet.id));
+                            ui.same_line_with_pos(150.0);
+                            ui.checkbox(
+                                &im_str!("Visited##visited-{}", planet.id),
+                                &mut planet.visited,
+                            );
+                            ui.same_line();
+                            ui.checkbox(
+                                &im_str!("Scanned##scanned-{}", planet.id),
+                                &mut planet.show_as_scanned,
+                            );
+                            ui.same_line();
+                            ui.text_disabled(format!("{} pickups", planet.probes.len()));
+                        }
+                    }
+                }
             }
         }
-        Some(())
+
+        // War assets
+        if let Some(_t) = self.begin_table(im_str!("war-assets-table"), 1) {
+            self.table_next_row();
+            war_assets.draw_raw_ui(self, "War Assets");
+            ui.same_line();
+            self.draw_help_marker("Strength of each war asset, by ID.");
+        }
     }
 
-    fn draw_me3_general(&self, save_game: &mut Me3SaveGame) -> Option<()> {
+    fn draw_me3_general(
+        &self, save_game: &mut Me3SaveGame, databases: &DatabasesState,
+    ) -> Option<()> {
         let ui = self.ui;
-        let Me3SaveGame { difficulty, end_game_state, conversation_mode, player, plot, .. } =
-            save_game;
+        let Me3SaveGame {
+            base_level_name,
+            location,
+            rotation,
+            levels,
+            difficulty,
+            end_game_state,
+            conversation_mode,
+            player,
+            plot,
+            player_variables,
+            ..
+        } = save_game;
         let Player {
             is_female,
             class_name,
@@ -86,6 +248,8 @@ impl<'ui> Gui<'ui> {
             current_fuel,
             grenades,
             face_code,
+            weapons,
+            weapons_loadout,
             ..
         } = player;
 
@@ -266,11 +430,7 @@ impl<'ui> Gui<'ui> {
                 }
 
                 self.table_next_row();
-                face_code.draw_raw_ui(self, "Identity Code");
-                ui.same_line();
-                self.draw_help_marker(
-                    "If you change this you can display whatever you want in the menus\nin place of your `Identity Code`, which is pretty cool !"
-                );
+                self.draw_identity_code(face_code);
             }
         }
 
@@ -349,11 +509,180 @@ impl<'ui> Gui<'ui> {
             }
         }
 
+        // Location
+        let map_location = MapLocation { base_level_name, location, rotation, levels };
+        let known_maps = databases.map_db.as_ref().map(|map_db| &map_db.me3);
+        self.draw_map_location(map_location, known_maps);
+
+        // Gear
+        if let Some(gear_db) = &databases.me3_gear_db {
+            self.draw_me3_gear(weapons, player_variables, gear_db);
+            self.draw_me3_loadout(class_name.to_str(), weapons_loadout, weapons, gear_db);
+        }
+
         // Bonus Powers
         self.set_next_item_open(true);
         self.draw_me3_bonus_powers(powers)
     }
 
+    fn draw_me3_gear(
+        &self, weapons: &mut [Weapon], player_variables: &mut IndexMap<ImguiString, i32>,
+        gear_db: &Me3GearDb,
+    ) -> Option<()> {
+        let ui = self.ui;
+
+        // Table
+        let _t = self.begin_table(im_str!("gear-table"), 1)?;
+
+        // Tree node
+        self.table_next_row();
+        let _t = self.push_tree_node("Gear")?;
+
+        // Ammo
+        for (i, weapon) in weapons.iter_mut().enumerate() {
+            self.table_next_row();
+            self.draw_me3_ammo(i, weapon, &gear_db.ammo_powers);
+        }
+
+        // Intel
+        self.table_next_row();
+        ui.text(im_str!("Intel bonuses"));
+        ui.same_line();
+        self.draw_help_marker("Permanent bonuses granted by the intel handed over to Traynor.");
+        for (bonus, name) in &gear_db.intel_bonuses {
+            self.table_next_row();
+            let mut active = gear_db::has_bonus(player_variables, bonus);
+            if ui.checkbox(&ImString::new(name), &mut active) {
+                gear_db::set_bonus(player_variables, bonus, active);
+            }
+        }
+        Some(())
+    }
+
+    // Seulement les armes possédées, pour estimer le bonus de recharge avant de lancer le jeu
+    fn draw_me3_loadout(
+        &self, class_name: &str, loadout: &mut WeaponLoadout, weapons: &[Weapon],
+        gear_db: &Me3GearDb,
+    ) -> Option<()> {
+        let ui = self.ui;
+
+        // Table
+        let _t = self.begin_table(im_str!("loadout-table"), 1)?;
+
+        // Tree node
+        self.table_next_row();
+        let _t = self.push_tree_node("Loadout")?;
+
+        let display_name = |class: &str| match gear_db.weapon(class) {
+            Some(weapon) => weapon.name.clone(),
+            None => class.rsplit('.').next().unwrap_or_default().to_owned(),
+        };
+
+        let WeaponLoadout { assault_rifle, shotgun, sniper_rifle, submachine_gun, pistol, .. } =
+            loadout;
+        let slots = [
+            ("Assault Rifle", "SFXWeapon_AssaultRifle_", assault_rifle),
+            ("Shotgun", "SFXWeapon_Shotgun_", shotgun),
+            ("Sniper Rifle", "SFXWeapon_SniperRifle_", sniper_rifle),
+            ("Submachine Gun", "SFXWeapon_SMG_", submachine_gun),
+            ("Pistol", "SFXWeapon_Pistol_", pistol),
+        ];
+        for (label, prefix, equipped) in slots {
+            self.table_next_row();
+            let preview = match equipped.to_str() {
+                class if gear_db::is_empty_slot(class) => ImString::new("None"),
+                class => ImString::new(display_name(class)),
+            };
+            let width = ui.push_item_width(200.0);
+            if let Some(_t) = ComboBox::new(&ImString::new(label)).preview_value(&preview).begin(ui)
+            {
+                let is_empty = gear_db::is_empty_slot(equipped.to_str());
+                if Selectable::new(im_str!("None")).selected(is_empty).build(ui) {
+                    *equipped = ImString::new("None").into();
+                }
+                let prefix = prefix.to_lowercase();
+                let owned =
+                    weapons.iter().map(|weapon| weapon.class_name.to_str()).filter(|class| {
+                        class
+                            .rsplit('.')
+                            .next()
+                            .unwrap_or_default()
+                            .to_lowercase()
+                            .starts_with(&prefix)
+                    });
+                for class in owned {
+                    let selected = unicase::eq(class, equipped.to_str());
+                    let name = ImString::new(display_name(class));
+                    if Selectable::new(&name).selected(selected).build(ui) {
+                        *equipped = ImString::new(class).into();
+                    }
+                }
+            }
+            width.pop(ui);
+        }
+
+        // Poids
+        self.table_next_row();
+        match gear_db.weight_stats(class_name, loadout) {
+            Some(stats) => {
+                ui.text(format!("Weight {:.2} / {:.2}", stats.weight, stats.capacity));
+                ui.same_line();
+                let bonus = format!("Power cooldown {:+.0}%", stats.cooldown_bonus);
+                if stats.cooldown_bonus < 0.0 {
+                    ui.text_colored([0.9, 0.4, 0.3, 1.0], bonus);
+                } else {
+                    ui.text_colored([0.3, 0.8, 0.4, 1.0], bonus);
+                }
+                ui.same_line();
+                self.draw_help_marker(
+                    "Estimate from the level I weight of the equipped weapons, the heavy weapon doesn't count.\nWeapon upgrades and weight mods lower the weight in game, so the actual bonus can only be better.",
+                );
+                if !stats.unknown.is_empty() {
+                    self.table_next_row();
+                    let unknown: Vec<_> =
+                        stats.unknown.iter().map(|class| display_name(class)).collect();
+                    ui.text_disabled(format!("Unknown weight : {}", unknown.join(", ")));
+                }
+            }
+            None => ui.text_disabled(im_str!("Unknown weight capacity for this class")),
+        }
+        Some(())
+    }
+
+    // Les munitions hors DB restent sélectionnées telles quelles
+    fn draw_me3_ammo(
+        &self, ident: usize, weapon: &mut Weapon, ammo_powers: &IndexMap<String, String>,
+    ) {
+        let ui = self.ui;
+        let Weapon { class_name, ammo_power_name, .. } = weapon;
+
+        let current = ammo_powers
+            .iter()
+            .find(|(class, _)| unicase::eq(class.as_str(), ammo_power_name.to_str()));
+        let preview = match current {
+            Some((_, name)) => ImString::new(name),
+            None if ammo_power_name.is_empty() => ImString::new("None"),
+            None => ImString::new(ammo_power_name.to_str()),
+        };
+
+        // Sans le package
+        let weapon_name = class_name.to_str().rsplit('.').next().unwrap_or_default();
+        let width = ui.push_item_width(200.0);
+        let label = ImString::new(format!("{}##ammo-{}", weapon_name, ident));
+        if let Some(_t) = ComboBox::new(&label).preview_value(&preview).begin(ui) {
+            if Selectable::new(im_str!("None")).selected(ammo_power_name.is_empty()).build(ui) {
+                ammo_power_name.clear();
+            }
+            for (class, name) in ammo_powers {
+                let selected = unicase::eq(class.as_str(), ammo_power_name.to_str());
+                if Selectable::new(&ImString::new(name)).selected(selected).build(ui) {
+                    *ammo_power_name = ImString::new(class).into();
+                }
+            }
+        }
+        width.pop(ui);
+    }
+
     fn draw_me3_class(&self, class_name: &mut ImString) {
         let ui = self.ui;
         const CLASS_LIST: [(&ImStr, &ImStr); 12] = [
@@ -527,7 +856,6 @@ impl<'ui> Gui<'ui> {
             (im_str!("Romance"), romance),
             (im_str!("Missions"), missions),
             (im_str!("Normandy"), normandy),
-            (im_str!("Citadel DLC"), citadel_dlc),
         ];
 
         for (title, plot_map) in &categories {
@@ -547,6 +875,15 @@ impl<'ui> Gui<'ui> {
             }
         }
 
+        // Citadel DLC
+        if_chain! {
+            if let Some(_t) = TabItem::new(im_str!("Citadel DLC")).begin(ui);
+            if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
+            then {
+                self.draw_me3_citadel_dlc(plot_table, citadel_dlc);
+            }
+        }
+
         if_chain! {
             if let Some(_t) = TabItem::new(im_str!("Intel")).begin(ui);
             if let Some(_t) = ChildWindow::new(im_str!("scroll")).begin(ui);
@@ -695,27 +1032,69 @@ impl<'ui> Gui<'ui> {
         Some(())
     }
 
+    // Souvent cassés par les imports, d'où les boutons pour tout débloquer
+    fn draw_me3_citadel_dlc(
+        &self, plot_table: &mut PlotTable,
+        citadel_dlc: &IndexMap<String, IndexMap<String, PlotCategory>>,
+    ) {
+        let ui = self.ui;
+
+        for (section, categories) in citadel_dlc {
+            ui.text_disabled(section);
+            for (category_name, plot_db) in categories {
+                let _t = ui.push_id(category_name.as_str());
+                if ui.small_button(im_str!("Unlock all")) {
+                    set_plot_booleans(plot_table, plot_db, true);
+                }
+                ui.same_line();
+                if ui.small_button(im_str!("Lock all")) {
+                    set_plot_booleans(plot_table, plot_db, false);
+                }
+
+                if let Some(_t) = self.begin_table(&im_str!("{}-table", category_name), 1) {
+                    self.table_next_row();
+                    if let Some(_t) = self.push_tree_node(category_name) {
+                        self.draw_me3_plot_category(plot_table, plot_db);
+                    }
+                }
+            }
+            ui.separator();
+        }
+    }
+
     fn draw_me3_plot_category(&self, plot_table: &mut PlotTable, plot_db: &PlotCategory) {
         let ui = self.ui;
-        let PlotCategory { booleans, ints } = plot_db;
+        let PlotCategory { booleans, ints, bitfields, .. } = plot_db;
 
-        if booleans.is_empty() && ints.is_empty() {
+        if booleans.is_empty() && ints.is_empty() && bitfields.is_empty() {
             return;
         }
 
         // Booleans
+        let list = format!("bool@{:p}", plot_db);
+        self.draw_plot_bool_selection(&list, booleans, &mut plot_table.bool_variables);
         let mut clipper = ListClipper::new(booleans.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
                 let (plot_id, plot_desc) = booleans.get_index(i as usize).unwrap();
-                let plot = plot_table.bool_variables.get_mut(*plot_id);
-                if let Some(mut plot) = plot {
+                if let Some(mut plot) = plot_table.bool_variables.get_mut(*plot_id) {
                     self.table_next_row();
-                    plot.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc));
+                    self.draw_selectable_row(&list, i as usize, &mut *plot, |value| {
+                        value.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc))
+                    });
+                    self.draw_plot_context_menu(Game::MassEffect3, PlotId::Bool(*plot_id));
+                    self.draw_plot_entry_details(&format!("bool-{}", plot_id), plot_desc);
                 }
+                self.draw_plot_requirements(plot_db, *plot_id, &mut plot_table.bool_variables);
             }
         }
         // Integers
+        let list = format!("int@{:p}", plot_db);
+        if let Some((value, rows)) = self.draw_int_selection_actions(&list) {
+            for (plot_id, _) in rows.into_iter().filter_map(|row| ints.get_index(row)) {
+                plot_table.int_variables.insert(*plot_id as i32, value);
+            }
+        }
         let mut clipper = ListClipper::new(ints.len() as i32).begin(ui);
         while clipper.step() {
             for i in clipper.display_start()..clipper.display_end() {
@@ -723,9 +1102,27 @@ impl<'ui> Gui<'ui> {
                 let plot = plot_table.int_variables.entry(*plot_id as i32).or_default();
 
                 self.table_next_row();
-                plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc));
+                self.draw_selectable_row(&list, i as usize, plot, |plot| {
+                    match plot_desc.options() {
+                        Some(options) => {
+                            self.draw_edit_plot_options(&format!("int-{}", plot_id), plot, &options)
+                        }
+                        None => {
+                            plot.draw_raw_ui(self, &format!("{}##int-{}", plot_desc, plot_desc))
+                        }
+                    }
+                });
+                self.draw_plot_context_menu(Game::MassEffect3, PlotId::Int(*plot_id));
+                self.draw_plot_entry_details(&format!("int-{}", plot_id), plot_desc);
             }
         }
+        // Bitfields
+        for (plot_id, PlotBitfield { label, bits }) in bitfields {
+            let plot = plot_table.int_variables.entry(*plot_id as i32).or_default();
+
+            self.table_next_row();
+            self.draw_edit_bitfield(&format!("{}##bitfield-{}", label, plot_id), plot, bits);
+        }
     }
 
     fn draw_me3_plot_variable(
@@ -748,6 +1145,7 @@ impl<'ui> Gui<'ui> {
                 if let Some(mut plot) = plot {
                     self.table_next_row();
                     plot.draw_raw_ui(self, &format!("{}##bool-{}", plot_desc, plot_desc));
+                    self.draw_plot_context_menu(Game::MassEffect3, PlotId::Bool(*plot_id));
                 }
             }
         }
@@ -771,6 +1169,59 @@ impl<'ui> Gui<'ui> {
         }
     }
 
+    pub fn draw_identity_code(&self, face_code: &mut ImguiString) {
+        let ui = self.ui;
+
+        face_code.draw_raw_ui(self, "Identity Code");
+        ui.same_line();
+        self.draw_help_marker(
+            "If you change this you can display whatever you want in the menus\n\
+            in place of your `Identity Code`, which is pretty cool !\n\
+            A pasted face code is only checked and formatted, it does not change\n\
+            the head morph as the mapping between face codes and head morphs is unknown.",
+        );
+
+        // Seulement si ça ressemble à un code
+        if face_code.to_str().contains('.') {
+            match normalize_face_code(face_code.to_str()) {
+                Ok(normalized) if normalized == face_code.to_str() => {
+                    ui.text_disabled(im_str!("Valid face code"));
+                }
+                Ok(normalized) => {
+                    if ui.small_button(im_str!("Format face code")) {
+                        *face_code = ImString::new(normalized).into();
+                    }
+                }
+                Err(err) => ui.text_colored([0.9, 0.4, 0.3, 1.0], err.to_string()),
+            }
+        }
+    }
+
+    pub fn draw_appearance_code_buttons(&self, head_morph: Option<&HeadMorph>) {
+        let ui = self.ui;
+
+        if ui.button(im_str!("Paste head morph")) {
+            if let Some(code) = ui.clipboard_text() {
+                let _ =
+                    self.event_addr.send(MainEvent::ImportAppearanceCode(code.to_str().to_owned()));
+            }
+        }
+        if let Some(head_morph) = head_morph {
+            ui.same_line();
+            if ui.button(im_str!("Copy head morph")) {
+                let _ = self
+                    .event_addr
+                    .send(MainEvent::ExportAppearanceCode(Box::new(head_morph.clone())));
+            }
+        }
+        ui.same_line();
+        self.draw_help_marker(
+            "Copies the whole head morph to the clipboard as a text code,\n\
+            to paste it in another save or another instance of the editor.\n\
+            It should only be used with a character of the same game and gender.",
+        );
+    }
+
     pub fn draw_me3_and_le_head_morph(&self, head_morph: &mut HasHeadMorph) {
         let ui = self.ui;
         let HasHeadMorph { has_head_morph, head_morph } = head_morph;
@@ -784,6 +1235,10 @@ impl<'ui> Gui<'ui> {
                 let _ = self.event_addr.send(MainEvent::ImportHeadMorph(path));
             }
         }
+        ui.same_line();
+        self.draw_appearance_code_buttons(head_morph.as_ref());
+        ui.same_line();
+        self.draw_help("head_morph");
         match head_morph {
             Some(head_morph) => {
                 // Export
@@ -808,7 +1263,7 @@ impl<'ui> Gui<'ui> {
                 ui.separator();
 
                 // Raw
-                if *has_head_morph {
+                if *has_head_morph && self.shows(Widgets::Raw) {
                     let HeadMorph {
                         hair_mesh,
                         accessory_mesh,
@@ -857,3 +1312,9 @@ impl<'ui> Gui<'ui> {
         }
     }
 }
+
+fn set_plot_booleans(plot_table: &mut PlotTable, plot_db: &PlotCategory, value: bool) {
+    for &plot_id in plot_db.booleans.keys() {
+        plot_table.set_bool(plot_id, value);
+    }
+}
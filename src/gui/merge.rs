@@ -0,0 +1,335 @@
+use anyhow::{bail, Result};
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashSet},
+    mem,
+};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::OriginalValues,
+};
+
+use super::{raw_ui, Gui};
+
+// Fusion de deux saves d'une même carrière, valeur par valeur
+#[derive(Default)]
+pub struct MergeState {
+    pub opened: bool,
+    theirs: Option<SaveGame>,
+    differences: Vec<Difference>,
+    filter: ImString,
+    result: Option<Result<String, String>>,
+}
+
+pub struct Difference {
+    pub path: Vec<String>,
+    pub ours: String,
+    pub theirs: String,
+    pub take_theirs: bool,
+}
+
+impl MergeState {
+    // L'autre save n'a de sens que face à la save ouverte
+    pub fn clear(&mut self) {
+        self.theirs = None;
+        self.differences.clear();
+        self.result = None;
+    }
+
+    pub fn set_source(&mut self, ours: Option<&SaveGame>, theirs: SaveGame) {
+        self.clear();
+        match ours.map(|ours| differences(ours, &theirs)) {
+            Some(Ok(differences)) => {
+                self.differences = differences;
+                self.theirs = Some(theirs);
+            }
+            Some(Err(err)) => self.result = Some(Err(format!("{:#}", err))),
+            None => self.result = Some(Err("Open a save first".to_owned())),
+        }
+    }
+}
+
+pub fn differences(ours: &SaveGame, theirs: &SaveGame) -> Result<Vec<Difference>> {
+    if mem::discriminant(ours) != mem::discriminant(theirs) {
+        bail!("Can't merge a {} save with a {} save", ours.kind(), theirs.kind());
+    }
+
+    let ours = raw_ui::original_values(ours);
+    let theirs = raw_ui::original_values(theirs);
+    let (our_items, their_items) = (list_items(&ours), list_items(&theirs));
+
+    // Une valeur d'un seul côté vient d'une liste de longueur différente,
+    // c'est toute la liste qui est fusionnée
+    let merge_unit = |path: &Vec<String>, other_items: &HashSet<Vec<String>>| {
+        let missing = (0..path.len())
+            .find(|&i| path[i].parse::<usize>().is_ok() && !other_items.contains(&path[..=i]));
+        match missing {
+            Some(i) => path[..i].to_vec(),
+            None => path.clone(),
+        }
+    };
+
+    let mut units = BTreeSet::new();
+    for (path, value) in &ours {
+        match theirs.get(path) {
+            Some(other) if other == value => (),
+            Some(_) => {
+                units.insert(PathKey(path.clone()));
+            }
+            None => {
+                units.insert(PathKey(merge_unit(path, &their_items)));
+            }
+        }
+    }
+    for path in theirs.keys().filter(|path| !ours.contains_key(*path)) {
+        units.insert(PathKey(merge_unit(path, &our_items)));
+    }
+
+    let describe = |values: &OriginalValues, path: &[String]| match values.get(path) {
+        Some(value) => value.clone(),
+        None => format!("{} items", list_len(values, path)),
+    };
+    Ok(units
+        .into_iter()
+        .map(|PathKey(path)| Difference {
+            ours: describe(&ours, &path),
+            theirs: describe(&theirs, &path),
+            path,
+            take_theirs: false,
+        })
+        .collect())
+}
+
+pub fn merge(ours: &mut SaveGame, theirs: &SaveGame, differences: &[Difference]) -> usize {
+    let mut merged = 0;
+    for difference in differences.iter().filter(|difference| difference.take_theirs) {
+        raw_ui::revert_save_game(ours, theirs, &difference.path);
+        merged += 1;
+    }
+    merged
+}
+
+// Chemins de tous les éléments de listes, `Squad/2` par exemple
+fn list_items(values: &OriginalValues) -> HashSet<Vec<String>> {
+    let mut items = HashSet::new();
+    for path in values.keys() {
+        for (i, segment) in path.iter().enumerate() {
+            if segment.parse::<usize>().is_ok() {
+                items.insert(path[..=i].to_vec());
+            }
+        }
+    }
+    items
+}
+
+fn list_len(values: &OriginalValues, list: &[String]) -> usize {
+    values
+        .keys()
+        .filter(|path| path.len() > list.len() && path.starts_with(list))
+        .map(|path| &path[list.len()])
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+// Les index sont triés numériquement
+#[derive(PartialEq, Eq)]
+struct PathKey(Vec<String>);
+
+impl Ord for PathKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (a, b) in self.0.iter().zip(&other.0) {
+            let ordering = match (a.parse::<usize>(), b.parse::<usize>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        self.0.len().cmp(&other.0.len())
+    }
+}
+
+impl PartialOrd for PathKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_merge(&self, state: &mut MergeState, save_game: &mut Option<SaveGame>) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Merge saves"))
+            .size([700.0, 500.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let ours = match save_game {
+                Some(save_game) => save_game,
+                None => {
+                    ui.text_disabled(im_str!("Open a save first"));
+                    return;
+                }
+            };
+
+            if ui.button(im_str!("Other save")) {
+                let file = tinyfiledialogs::open_file_dialog(
+                    "",
+                    &Self::get_document_dir().to_string_lossy(),
+                    Some((
+                        &["*.pcsav", "*.MassEffectSave", "*.sav"],
+                        "Mass Effect Trilogy Save (*.pcsav, *.MassEffectSave, *.sav)",
+                    )),
+                );
+                if let Some(path) = file {
+                    let _ = self.event_addr.send(MainEvent::OpenMergeSource(path));
+                }
+            }
+            ui.same_line();
+            match &state.theirs {
+                Some(theirs) => ui.text(theirs.file_path()),
+                None => ui.text_disabled(im_str!("No other save")),
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Lists every value that differs between the opened save and another save of the same game.\nTick the values to take from the other save, then merge them into the opened save and save it as usual.",
+            );
+
+            match &state.result {
+                Some(Ok(message)) => ui.text(message),
+                Some(Err(err)) => ui.text_colored([0.9, 0.4, 0.3, 1.0], err),
+                None => (),
+            }
+
+            let theirs = match &state.theirs {
+                Some(theirs) => theirs,
+                None => return,
+            };
+            ui.separator();
+
+            ui.set_next_item_width(200.0);
+            ui.input_text(im_str!("##merge-filter"), &mut state.filter)
+                .hint(im_str!("Filter"))
+                .resize_buffer(true)
+                .build();
+            let filter = state.filter.to_str().to_lowercase();
+            let matches_filter = |difference: &Difference| {
+                filter.is_empty() || difference.path.join("/").to_lowercase().contains(&filter)
+            };
+
+            ui.same_line();
+            if ui.button(im_str!("Keep all")) {
+                for difference in state.differences.iter_mut().filter(|d| matches_filter(d)) {
+                    difference.take_theirs = false;
+                }
+            }
+            ui.same_line();
+            if ui.button(im_str!("Take all")) {
+                for difference in state.differences.iter_mut().filter(|d| matches_filter(d)) {
+                    difference.take_theirs = true;
+                }
+            }
+            ui.same_line();
+            let taken = state.differences.iter().filter(|d| d.take_theirs).count();
+            if ui.button(&ImString::new(format!("Merge {} value(s)", taken))) && taken > 0 {
+                let merged = merge(ours, theirs, &state.differences);
+                state.result = Some(Ok(format!("Merged {} value(s)", merged)));
+                match differences(ours, theirs) {
+                    Ok(differences) => state.differences = differences,
+                    Err(err) => state.result = Some(Err(format!("{:#}", err))),
+                }
+            }
+
+            if state.differences.is_empty() {
+                ui.text_disabled(im_str!("The saves are identical"));
+                return;
+            }
+
+            if let Some(_t) = ChildWindow::new(im_str!("differences")).begin(ui) {
+                let _t = match self.begin_table(im_str!("merge-table"), 4) {
+                    Some(t) => t,
+                    None => return,
+                };
+                for (i, difference) in
+                    state.differences.iter_mut().enumerate().filter(|(_, d)| matches_filter(d))
+                {
+                    let _t = ui.push_id(i as i32);
+                    self.table_next_row();
+                    ui.checkbox(im_str!("##take"), &mut difference.take_theirs);
+                    self.table_next_column();
+                    ui.text(difference.path.join("/"));
+                    self.table_next_column();
+                    if difference.take_theirs {
+                        ui.text_disabled(&difference.ours);
+                    } else {
+                        ui.text(&difference.ours);
+                    }
+                    self.table_next_column();
+                    if difference.take_theirs {
+                        ui.text(&difference.theirs);
+                    } else {
+                        ui.text_disabled(&difference.theirs);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    fn me2_save() -> Result<SaveGame> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        Ok(SaveGame::MassEffect2 { file_path: String::new(), save_game: Box::new(save_game) })
+    }
+
+    #[test]
+    fn merge_saves() -> Result<()> {
+        let mut ours = me2_save()?;
+        let mut theirs = me2_save()?;
+        assert!(differences(&ours, &theirs)?.is_empty());
+
+        // Chacun a progressé de son côté
+        if let SaveGame::MassEffect2 { save_game, .. } = &mut ours {
+            save_game.player.credits = 1;
+        }
+        if let SaveGame::MassEffect2 { save_game, .. } = &mut theirs {
+            save_game.player.credits = 2;
+            save_game.plot.bool_variables.set(21, false);
+            save_game.levels.clear();
+        }
+
+        let mut differences = differences(&ours, &theirs)?;
+        let paths: Vec<_> = differences.iter().map(|d| d.path.join("/")).collect();
+        assert_eq!(paths, ["Levels", "Player/Credits", "Plot/Bool Variables/21"]);
+        assert_eq!(differences[0].theirs, "0 items");
+
+        for difference in &mut differences {
+            difference.take_theirs = difference.path[0] != "Player";
+        }
+        assert_eq!(merge(&mut ours, &theirs, &differences), 2);
+
+        match &ours {
+            SaveGame::MassEffect2 { save_game, .. } => {
+                assert_eq!(save_game.player.credits, 1);
+                assert!(!save_game.plot.bool_variables[21]);
+                assert!(save_game.levels.is_empty());
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
@@ -3,31 +3,136 @@ use clap::ArgMatches;
 use flume::{Receiver, Sender};
 use if_chain::if_chain;
 use imgui::{
-    im_str, ChildWindow, ColorStackToken, Condition, ImString, PopupModal, ProgressBar, StyleColor,
-    TabBar, TabItem, Ui, Window,
+    im_str, ChildWindow, ColorStackToken, Condition, ImString, MenuItem, MouseButton, PopupModal,
+    ProgressBar, StyleColor, TabBar, TabItem, Ui, Window,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
-use std::path::PathBuf;
 
+use self::{
+    auto_patch::AutoPatchState,
+    career_audit::CareerAuditState,
+    change_preview::ChangePreview,
+    cloud_sync::CloudSyncState,
+    confirmation::ConfirmationState,
+    editor_tabs::Editors,
+    format_docs::FormatDocsState,
+    forward_compat::NewerSaveVersion,
+    help::{HelpDb, HelpState},
+    log_viewer::LogViewerState,
+    mass_effect_1::{Me1InventoryState, PlotGridState},
+    merge::MergeState,
+    notes::NotesState,
+    plot_lookup::PlotLookupState,
+    plot_paste::PlotPaste,
+    plot_references::PlotReferencesState,
+    property_copy::PropertyCopyState,
+    raw_ui::TableSort,
+    save_format::SaveFormatChoice,
+    save_slots::SaveSlotsState,
+    selection::SelectionState,
+    settings::{
+        Accessibility, Confirmations, FloatRounding, Palette, Settings, UiProfile, ViewPreferences,
+        Widgets,
+    },
+    shortcuts::Shortcut,
+    snapshots::SnapshotsState,
+    squad_build::SquadBuildState,
+    string_search::StringSearchState,
+    tlk::TlkState,
+    validation::{save_issues, HealthState},
+    watch::WatchState,
+};
 use crate::{
-    event_handler::{MainEvent, SaveGame},
+    event_handler::{MainEvent, OriginalSave, SaveGame},
+    logger::LogBuffer,
     save_data::{
-        mass_effect_1::{item_db::Me1ItemDb, plot_db::Me1PlotDb},
-        mass_effect_2::plot_db::Me2PlotDb,
-        mass_effect_3::plot_db::Me3PlotDb,
-        shared::appearance::{HasHeadMorph, HeadMorph},
+        mass_effect_1::{
+            item_db::Me1ItemDb, plot_db::Me1PlotDb, plot_labels::Me1PlotLabels,
+            property_text::import_properties, Me1SaveGame, ZipMember,
+        },
+        mass_effect_2::{plot_db::Me2PlotDb, suicide_mission::Choices},
+        mass_effect_3::{
+            career_audit::CareerAudit, gear_db::Me3GearDb, known_issues::KnownIssuesDb,
+            plot_db::Me3PlotDb,
+        },
+        shared::{
+            appearance::{HasHeadMorph, HeadMorph},
+            launcher_config::LauncherConfig,
+            map_db::MapDb,
+            plot_coverage::PlotCoverage,
+            recipe::RecipeDb,
+            romance::RomanceDb,
+            save_format::SaveFormat,
+            save_slot::SaveSlot,
+            save_stats::SaveStats,
+            squad::SquadDb,
+            tlk::TlkFile,
+            validation::Issues,
+        },
+        OriginalValues,
     },
+    unreal,
 };
 
+mod auto_patch;
 mod backend;
+mod career_audit;
+mod change_preview;
 mod changelog;
+mod character_sheet;
+mod cloud_sync;
+mod confirmation;
+mod editor_tabs;
+mod format_docs;
+mod forward_compat;
+pub mod help;
 mod imgui_utils;
+mod known_issues;
+mod log_viewer;
+mod map_location;
 mod mass_effect_1;
 mod mass_effect_1_leg;
 mod mass_effect_2;
 mod mass_effect_3;
+mod merge;
+mod normandy_crew;
+mod notes;
+mod plot_coverage;
+mod plot_lookup;
+mod plot_paste;
+mod plot_references;
+pub mod presets;
+mod property_copy;
 mod raw_ui;
+mod romance;
+mod save_format;
+mod save_slots;
+mod save_stats;
+mod selection;
+pub mod settings;
+mod shortcuts;
+mod snapshots;
+mod squad;
+mod squad_build;
+mod string_search;
+mod suicide_mission;
+mod tlk;
+mod validation;
+mod watch;
+
+pub use backend::renderer_names;
 
 static NOTIFICATION_TIME: f64 = 1.5; // seconde
+static PROGRESS_DELAY: f64 = 0.3; // seconde
 
 // States
 #[derive(Default)]
@@ -36,38 +141,136 @@ struct NotificationState {
     close_time: f64,
 }
 
+struct ProgressState {
+    label: &'static str,
+    fraction: f32,
+    cancelled: Arc<AtomicBool>,
+    start_time: f64,
+    finished: bool,
+}
+
 #[derive(Default)]
 pub struct DatabasesState {
     me1_plot_db: Option<Me1PlotDb>,
+    me1_plot_labels: Option<Me1PlotLabels>,
     me1_item_db: Option<Me1ItemDb>,
     me2_plot_db: Option<Me2PlotDb>,
+    map_db: Option<MapDb>,
     me3_plot_db: Option<Me3PlotDb>,
+    me3_known_issues: Option<KnownIssuesDb>,
+    me3_gear_db: Option<Me3GearDb>,
+    romance_db: Option<RomanceDb>,
+    squad_db: Option<SquadDb>,
+    recipe_db: Option<RecipeDb>,
 }
 
 #[derive(Default)]
 struct State {
     save_game: Option<SaveGame>,
+    original_save: Option<OriginalSave>,
+    original_save_game: Option<SaveGame>,
+    original_values: Option<Rc<OriginalValues>>,
     error: Option<Error>,
     notification: Option<NotificationState>,
+    progress: Option<ProgressState>,
     databases: DatabasesState,
+    settings: Settings,
+    plot_coverage: Option<PlotCoverage>,
+    save_stats: Option<SaveStats>,
+    hex_fields: Rc<RefCell<HashSet<Vec<String>>>>,
+    table_views: Rc<RefCell<HashMap<Vec<String>, TableSort>>>,
+    locked_tabs: Rc<RefCell<Vec<String>>>,
+    plot_grid: Rc<Cell<PlotGridState>>,
+    me1_inventory: Rc<RefCell<Me1InventoryState>>,
+    raw_ui_shown: Rc<Cell<bool>>,
+    log_buffer: LogBuffer,
+    dev: bool,
+    log_viewer: LogViewerState,
+    save_slots: SaveSlotsState,
+    suicide_mission: Option<Choices>,
+    known_issues_opened: bool,
+    romance_checker_opened: bool,
+    normandy_crew_opened: bool,
+    squad_selection_opened: bool,
+    squad_build: SquadBuildState,
+    format_docs: FormatDocsState,
+    held_shortcut: Option<Shortcut>,
+    watch: Rc<RefCell<WatchState>>,
+    tlk: Rc<RefCell<TlkState>>,
+    help: Rc<RefCell<HelpState>>,
+    confirmation: Rc<RefCell<ConfirmationState>>,
+    selection: Rc<RefCell<SelectionState>>,
+    notes: Rc<RefCell<NotesState>>,
+    plot_references: Rc<RefCell<PlotReferencesState>>,
+    test_slot: Option<PathBuf>,
+    cloud_sync: CloudSyncState,
+    newer_save_version: Option<NewerSaveVersion>,
+    save_format_choice: Option<SaveFormatChoice>,
+    next_autosave: Option<f64>,
+    property_copy: PropertyCopyState,
+    snapshots: SnapshotsState,
+    merge: MergeState,
+    quick_actions_opened: bool,
+    change_preview: Option<ChangePreview>,
+    health: HealthState,
+    // Refaite seulement après une modification possible, voir `has_user_input`
+    issues: Option<Issues>,
+    editors: Rc<Editors>,
+    plot_lookup: PlotLookupState,
+    string_search: StringSearchState,
+    career_audit: CareerAuditState,
+    auto_patch: AutoPatchState,
+    plot_paste: Option<PlotPaste>,
 }
 
 // Events
 pub enum UiEvent {
     Error(Error),
     Notification(&'static str),
-    OpenedSave(SaveGame),
+    ProgressStarted(&'static str, Arc<AtomicBool>),
+    Progress(f32),
+    ProgressFinished,
+    OpenedSave(SaveGame, OriginalSave),
+    NewerSaveVersion(String, i32, i32),
+    AmbiguousSaveFormat(String, Vec<SaveFormat>),
     LoadedMe1PlotDb(Me1PlotDb),
     LoadedMe1ItemDb(Me1ItemDb),
     LoadedMe2PlotDb(Me2PlotDb),
+    LoadedMapDb(MapDb),
     LoadedMe3PlotDb(Me3PlotDb),
+    LoadedRecipeDb(RecipeDb),
+    LoadedMe3KnownIssues(KnownIssuesDb),
+    LoadedMe3GearDb(Me3GearDb),
+    LoadedRomanceDb(RomanceDb),
+    LoadedSquadDb(SquadDb),
+    LoadedHelpDb(HelpDb),
     ImportedHeadMorph(HeadMorph),
+    LoadedSettings(Settings),
+    CopyToClipboard(String),
+    SaveSlots(PathBuf, Vec<SaveSlot>),
+    LoadedTlk(TlkFile),
+    LoadedMe1CopySource(String, Box<Me1SaveGame>),
+    LoadedMe1PlotLabels(Me1PlotLabels),
+    LoadedMergeSource(SaveGame),
+    LoadedSquadBuildSource(SaveGame),
+    ImportedMe1Properties(i32, String),
+    ImportedMe1ZipMember(ZipMember, Vec<u8>),
+    LoadedNotes(String, String),
+    CreatedWorkingCopy(PathBuf, String),
+    LoadedLauncherConfig(PathBuf, LauncherConfig),
+    TestSlot(Option<PathBuf>),
+    CareerAudit(PathBuf, CareerAudit),
+    WatchFolderPatched(PathBuf, Result<usize, String>),
+    WatchFolderStopped(Error),
 }
 
 // UI
-pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatches) {
-    let mut state = State::default();
+pub fn run(
+    event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatches, log_buffer: LogBuffer,
+) {
+    let mut state = State { log_buffer, dev: args.is_present("dev"), ..Default::default() };
 
+    let _ = event_addr.send(MainEvent::LoadSettings);
     let _ = event_addr.send(MainEvent::LoadDatabases);
 
     // UI
@@ -80,7 +283,14 @@ pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatche
 
     // Open file from command line
     if let Some(path) = args.value_of("FILE") {
-        let _ = event_addr.send(MainEvent::OpenSave(path.to_owned()));
+        let event = if path == "-" {
+            MainEvent::OpenStdin
+        } else if path.starts_with("http://") || path.starts_with("https://") {
+            MainEvent::OpenUrl(path.to_owned())
+        } else {
+            MainEvent::OpenSave(path.to_owned())
+        };
+        let _ = event_addr.send(event);
     }
 
     system.main_loop(move |run, ui, dropped_file| {
@@ -89,7 +299,10 @@ pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatche
             let _ = event_addr.send(MainEvent::OpenSave(path));
         }
 
-        // Manage events
+        // Manage events, ils peuvent remplacer ou modifier la save
+        if !rx.is_empty() {
+            state.issues = None;
+        }
         rx.try_iter().for_each(|ui_event| match ui_event {
             UiEvent::Error(err) => {
                 state.error = Some(err);
@@ -100,8 +313,54 @@ pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatche
                     close_time: ui.time() + NOTIFICATION_TIME,
                 })
             }
-            UiEvent::OpenedSave(opened_save_game) => {
+            UiEvent::ProgressStarted(label, cancelled) => {
+                state.progress = Some(ProgressState {
+                    label,
+                    fraction: 0.0,
+                    cancelled,
+                    start_time: ui.time(),
+                    finished: false,
+                })
+            }
+            UiEvent::Progress(fraction) => {
+                if let Some(progress) = &mut state.progress {
+                    progress.fraction = fraction;
+                }
+            }
+            UiEvent::ProgressFinished => {
+                if let Some(progress) = &mut state.progress {
+                    progress.finished = true;
+                }
+            }
+            UiEvent::OpenedSave(opened_save_game, original_save) => {
+                state.plot_coverage = None;
+                state.save_stats = None;
+                state.suicide_mission = None;
+                state.test_slot = None;
+                state.next_autosave = None;
+                state.cloud_sync = CloudSyncState::default();
+                state.snapshots.clear();
+                state.merge.clear();
+                state.change_preview = None;
+                state.health.set_opened_save(&opened_save_game, ui.time());
+                state.string_search.clear();
+                state.plot_paste = None;
+                *state.notes.borrow_mut() = NotesState::default();
+                let _ =
+                    event_addr.send(MainEvent::LoadNotes(opened_save_game.file_path().to_owned()));
+                state.original_values = None;
+                state.raw_ui_shown.set(false);
+                state.original_save = Some(original_save);
+                state.original_save_game = None;
                 state.save_game = Some(opened_save_game);
+                restore_view_preferences(&mut state);
+            }
+            UiEvent::NewerSaveVersion(file_path, version, known_version) => {
+                state.newer_save_version =
+                    Some(NewerSaveVersion { file_path, version, known_version })
+            }
+            UiEvent::AmbiguousSaveFormat(file_path, detected) => {
+                state.save_format_choice = Some(SaveFormatChoice::new(file_path, detected))
             }
             UiEvent::LoadedMe1PlotDb(me1_plot_db) => {
                 state.databases.me1_plot_db = Some(me1_plot_db)
@@ -112,9 +371,39 @@ pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatche
             UiEvent::LoadedMe2PlotDb(me2_plot_db) => {
                 state.databases.me2_plot_db = Some(me2_plot_db)
             }
+            UiEvent::LoadedMapDb(map_db) => state.databases.map_db = Some(map_db),
             UiEvent::LoadedMe3PlotDb(me3_plot_db) => {
                 state.databases.me3_plot_db = Some(me3_plot_db)
             }
+            UiEvent::LoadedRecipeDb(recipe_db) => state.databases.recipe_db = Some(recipe_db),
+            UiEvent::LoadedMe3KnownIssues(known_issues) => {
+                state.databases.me3_known_issues = Some(known_issues)
+            }
+            UiEvent::LoadedMe3GearDb(me3_gear_db) => {
+                state.databases.me3_gear_db = Some(me3_gear_db)
+            }
+            UiEvent::LoadedRomanceDb(romance_db) => state.databases.romance_db = Some(romance_db),
+            UiEvent::LoadedSquadDb(squad_db) => state.databases.squad_db = Some(squad_db),
+            UiEvent::LoadedHelpDb(help_db) => {
+                // Les paramètres sont chargés avant les databases
+                let mut help = state.help.borrow_mut();
+                help.db = Some(help_db);
+                if !state.settings.tour_completed {
+                    help.start_tour();
+                }
+            }
+            UiEvent::CreatedWorkingCopy(original, working_copy) => {
+                // Les saves suivantes vont dans la copie, recopiée sur l'originale
+                for save_game in
+                    state.save_game.iter_mut().chain(state.original_save_game.iter_mut())
+                {
+                    *save_game.file_path_mut() = working_copy.clone();
+                }
+                state.cloud_sync.working_copy_of = Some(original);
+            }
+            UiEvent::LoadedNotes(file_path, notes) => {
+                state.notes.borrow_mut().loaded(file_path, notes)
+            }
             UiEvent::ImportedHeadMorph(head_morph) => {
                 let has_head_morph =
                     HasHeadMorph { has_head_morph: true, head_morph: Some(head_morph) };
@@ -134,21 +423,171 @@ pub fn run(event_addr: Sender<MainEvent>, rx: Receiver<UiEvent>, args: ArgMatche
                     Some(SaveGame::MassEffect1 { .. }) | None => unreachable!(),
                 }
             }
+            UiEvent::LoadedSettings(settings) => {
+                imgui_utils::load_table_layouts(&settings.table_layouts);
+                state.settings = settings;
+                restore_view_preferences(&mut state);
+            }
+            UiEvent::CopyToClipboard(text) => ui.set_clipboard_text(&ImString::new(text)),
+            UiEvent::SaveSlots(dir, slots) => state.save_slots.set_slots(dir, slots),
+            UiEvent::LoadedLauncherConfig(path, config) => {
+                state.save_slots.launcher_config = Some((path, config))
+            }
+            UiEvent::LoadedTlk(tlk) => state.tlk.borrow_mut().set_tlk(tlk),
+            UiEvent::TestSlot(test_slot) => state.test_slot = test_slot,
+            UiEvent::CareerAudit(dir, audit) => {
+                state.career_audit.set_audit(dir, audit, state.databases.me3_plot_db.as_ref())
+            }
+            UiEvent::WatchFolderPatched(path, result) => state.auto_patch.add_entry(path, result),
+            UiEvent::WatchFolderStopped(err) => state.auto_patch.stopped(err),
+            UiEvent::LoadedMe1CopySource(path, save_game) => {
+                state.property_copy.set_source(path, save_game)
+            }
+            UiEvent::LoadedMe1PlotLabels(labels) => state.databases.me1_plot_labels = Some(labels),
+            UiEvent::LoadedSquadBuildSource(save_game) => state.squad_build.set_source(save_game),
+            UiEvent::LoadedMergeSource(save_game) => {
+                state.merge.set_source(state.save_game.as_ref(), save_game)
+            }
+            UiEvent::ImportedMe1Properties(object_id, text) => {
+                if let Some(SaveGame::MassEffect1 { save_game, .. }) = state.save_game.as_mut() {
+                    match import_properties(&mut save_game.player, object_id, &text) {
+                        Ok(()) => {
+                            state.notification = Some(NotificationState {
+                                string: ImString::new("Imported"),
+                                close_time: ui.time() + NOTIFICATION_TIME,
+                            })
+                        }
+                        Err(err) => state.error = Some(err),
+                    }
+                }
+            }
+            UiEvent::ImportedMe1ZipMember(member, data) => {
+                if let Some(SaveGame::MassEffect1 { save_game, .. }) = state.save_game.as_mut() {
+                    match save_game.import_member(member, &data) {
+                        Ok(()) => {
+                            state.notification = Some(NotificationState {
+                                string: ImString::new("Imported"),
+                                close_time: ui.time() + NOTIFICATION_TIME,
+                            })
+                        }
+                        Err(err) => state.error = Some(err),
+                    }
+                }
+            }
         });
 
-        let ui = Gui::new(ui, &event_addr);
+        // Calculées une fois, au premier affichage d'un onglet Raw et non à l'ouverture
+        if state.settings.show_original_values
+            && state.raw_ui_shown.get()
+            && state.original_values.is_none()
+        {
+            state.original_values = original_save_game(&mut state)
+                .map(|original| Rc::new(raw_ui::original_values(original)));
+        }
+
+        let ui = Gui::new(ui, &event_addr, &state);
+        ui.update_autosave(&state.save_game, &state.settings, &mut state.next_autosave);
         ui.draw(run, &mut state);
+        ui.save_view_preferences(&mut state);
+        if ui.has_user_input() {
+            state.issues = None;
+        }
     });
 }
 
+// Désérialisée au premier besoin, voir `OriginalSave`
+fn original_save_game(state: &mut State) -> Option<&SaveGame> {
+    if state.original_save_game.is_none() {
+        let file_path = state.save_game.as_ref()?.file_path();
+        match state.original_save.as_ref()?.deserialize(file_path) {
+            Ok(original) => state.original_save_game = Some(original),
+            Err(err) => {
+                state.original_save = None;
+                state.error = Some(err);
+                return None;
+            }
+        }
+    }
+    state.original_save_game.as_ref()
+}
+
+// Vues tableau et filtres enregistrés pour ce type de save
+fn restore_view_preferences(state: &mut State) {
+    let views = match &state.save_game {
+        Some(save_game) => state.settings.views.get(save_game.kind()).cloned().unwrap_or_default(),
+        None => return,
+    };
+    *state.table_views.borrow_mut() = views.table_views.into_iter().collect();
+    *state.locked_tabs.borrow_mut() = views.locked_tabs;
+    state.tlk.borrow_mut().set_picker_filter(&views.tlk_filter);
+    state.plot_lookup.set_query(&views.plot_lookup_query);
+}
+
 pub struct Gui<'ui> {
     ui: &'ui Ui<'ui>,
     event_addr: Sender<MainEvent>,
+    ui_profile: UiProfile,
+    accessibility: Accessibility,
+    hex_integers: bool,
+    float_rounding: FloatRounding,
+    confirmations: Confirmations,
+    hex_fields: Rc<RefCell<HashSet<Vec<String>>>>,
+    table_views: Rc<RefCell<HashMap<Vec<String>, TableSort>>>,
+    locked_tabs: Rc<RefCell<Vec<String>>>,
+    plot_grid: Rc<Cell<PlotGridState>>,
+    me1_inventory: Rc<RefCell<Me1InventoryState>>,
+    raw_ui_shown: Rc<Cell<bool>>,
+    raw_path: RefCell<Option<Vec<String>>>,
+    revert_request: RefCell<Option<Vec<String>>>,
+    original_values: Option<Rc<OriginalValues>>,
+    hide_original_value: Cell<bool>,
+    watch: Rc<RefCell<WatchState>>,
+    tlk: Rc<RefCell<TlkState>>,
+    help: Rc<RefCell<HelpState>>,
+    confirmation: Rc<RefCell<ConfirmationState>>,
+    selection: Rc<RefCell<SelectionState>>,
+    notes: Rc<RefCell<NotesState>>,
+    plot_references: Rc<RefCell<PlotReferencesState>>,
+    editors: Rc<Editors>,
 }
 
 impl<'ui> Gui<'ui> {
-    fn new(ui: &'ui Ui<'ui>, event_addr: &Sender<MainEvent>) -> Self {
-        Self { ui, event_addr: Sender::clone(event_addr) }
+    fn new(ui: &'ui Ui<'ui>, event_addr: &Sender<MainEvent>, state: &State) -> Self {
+        let settings = &state.settings;
+        Self {
+            ui,
+            event_addr: Sender::clone(event_addr),
+            ui_profile: settings.ui_profile,
+            accessibility: settings.accessibility,
+            hex_integers: settings.hex_integers,
+            float_rounding: settings.float_rounding,
+            confirmations: settings.confirmations,
+            hex_fields: Rc::clone(&state.hex_fields),
+            table_views: Rc::clone(&state.table_views),
+            locked_tabs: Rc::clone(&state.locked_tabs),
+            plot_grid: Rc::clone(&state.plot_grid),
+            me1_inventory: Rc::clone(&state.me1_inventory),
+            raw_ui_shown: Rc::clone(&state.raw_ui_shown),
+            raw_path: RefCell::new(None),
+            revert_request: RefCell::new(None),
+            original_values: state
+                .original_values
+                .clone()
+                .filter(|_| settings.show_original_values),
+            hide_original_value: Cell::new(false),
+            watch: Rc::clone(&state.watch),
+            tlk: Rc::clone(&state.tlk),
+            help: Rc::clone(&state.help),
+            confirmation: Rc::clone(&state.confirmation),
+            selection: Rc::clone(&state.selection),
+            notes: Rc::clone(&state.notes),
+            plot_references: Rc::clone(&state.plot_references),
+            editors: Rc::clone(&state.editors),
+        }
+    }
+
+    pub fn shows(&self, widgets: Widgets) -> bool {
+        self.ui_profile.shows(widgets)
     }
 
     fn draw(&self, _: &mut bool, state: &mut State) {
@@ -166,7 +605,7 @@ impl<'ui> Gui<'ui> {
             .collapsible(false);
 
         // Pop on drop
-        let _colors = self.style_colors(match state.save_game {
+        let game_theme = match state.save_game {
             None => Theme::MassEffect3,
             Some(SaveGame::MassEffect1 { .. }) | Some(SaveGame::MassEffect1Leg { .. }) => {
                 Theme::MassEffect1
@@ -175,7 +614,9 @@ impl<'ui> Gui<'ui> {
                 Theme::MassEffect2
             }
             Some(SaveGame::MassEffect3 { .. }) => Theme::MassEffect3,
-        });
+        };
+        let _colors = self.style_colors(game_theme);
+        let _vars = self.style_vars();
 
         // Window
         if let Some(_t) = window.begin(ui) {
@@ -186,39 +627,553 @@ impl<'ui> Gui<'ui> {
                 }
                 if let Some(save_game) = &state.save_game {
                     if ui.button(im_str!("Save")) {
-                        self.save_dialog(save_game);
+                        self.save_dialog(save_game, state.cloud_sync.working_copy_of.as_deref());
                     }
+                    self.draw_help("save");
+                    if ui.button(im_str!("Save as new slot")) {
+                        let _ = self.event_addr.send(MainEvent::SaveNewSlot(save_game.clone()));
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text(
+                            "Saves next to the opened save, with the next free slot number",
+                        );
+                    }
+                    self.draw_test_slot_menu(save_game, &state.test_slot);
+                }
+                if_chain! {
+                    if let Some(save_game) = &mut state.save_game;
+                    if let Some(recipe_db) = &state.databases.recipe_db;
+                    if self.draw_presets_menu(
+                        save_game,
+                        recipe_db,
+                        &mut state.quick_actions_opened,
+                        &mut state.change_preview,
+                    );
+                    then {
+                        state.notification = Some(NotificationState {
+                            string: ImString::new("Preset applied"),
+                            close_time: ui.time() + NOTIFICATION_TIME,
+                        });
+                    }
+                }
+                if let Some(_t) = ui.begin_menu(im_str!("Tools")) {
+                    if_chain! {
+                        if MenuItem::new(im_str!("Plot DB coverage"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            state.plot_coverage = plot_coverage::plot_coverage(save_game, &state.databases);
+                        }
+                    }
+                    if_chain! {
+                        if MenuItem::new(im_str!("Save statistics"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            match save_stats::save_stats(save_game) {
+                                Ok(stats) => state.save_stats = Some(stats),
+                                Err(err) => state.error = Some(err),
+                            }
+                        }
+                    }
+                    let is_me2 = matches!(
+                        state.save_game,
+                        Some(SaveGame::MassEffect2 { .. }) | Some(SaveGame::MassEffect2Leg { .. })
+                    );
+                    if MenuItem::new(im_str!("Suicide Mission simulator")).enabled(is_me2).build(ui)
+                    {
+                        state.suicide_mission = Some(Choices::default());
+                    }
+                    MenuItem::new(im_str!("Normandy crew"))
+                        .enabled(is_me2)
+                        .build_with_ref(ui, &mut state.normandy_crew_opened);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Which crew members survive the Collector abduction");
+                    }
+                    let is_me3 = matches!(state.save_game, Some(SaveGame::MassEffect3 { .. }));
+                    MenuItem::new(im_str!("ME3 known issues"))
+                        .enabled(is_me3)
+                        .build_with_ref(ui, &mut state.known_issues_opened);
+                    if MenuItem::new(im_str!("ME3 career audit")).build(ui) {
+                        self.audit_career(state.save_game.as_ref());
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Compares the Mass Effect 3 saves of a career, to find where something was lost");
+                    }
+                    MenuItem::new(im_str!("Romance checker"))
+                        .build_with_ref(ui, &mut state.romance_checker_opened);
+                    MenuItem::new(im_str!("Squad selection"))
+                        .build_with_ref(ui, &mut state.squad_selection_opened);
+                    MenuItem::new(im_str!("Copy squadmate build"))
+                        .enabled(is_me2 || is_me3)
+                        .build_with_ref(ui, &mut state.squad_build.opened);
+                    MenuItem::new(im_str!("Watch folder"))
+                        .build_with_ref(ui, &mut state.auto_patch.opened);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Applies presets to every new save of a career folder");
+                    }
+                    if MenuItem::new(im_str!("Save slots")).build(ui) {
+                        let save_path = state.save_game.as_ref().map(SaveGame::file_path);
+                        state.save_slots.open(save_path, &self.event_addr);
+                    }
+                    if_chain! {
+                        if MenuItem::new(im_str!("Export character sheet"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            self.export_character_sheet(save_game, &state.databases);
+                        }
+                    }
+                    let is_me1 = matches!(state.save_game, Some(SaveGame::MassEffect1 { .. }));
+                    MenuItem::new(im_str!("Copy ME1 property"))
+                        .enabled(is_me1)
+                        .build_with_ref(ui, &mut state.property_copy.opened);
+                    if_chain! {
+                        if MenuItem::new(im_str!("Paste plot flags"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            let text = ui.clipboard_text().unwrap_or_default();
+                            match PlotPaste::from_text(text.to_str(), save_game, &state.databases) {
+                                Ok(paste) => state.plot_paste = Some(paste),
+                                Err(err) => state.error = Some(err),
+                            }
+                        }
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Previews the `ID=value` plot flags from the clipboard before applying them");
+                    }
+                    MenuItem::new(im_str!("Plot database lookup"))
+                        .build_with_ref(ui, &mut state.plot_lookup.opened);
+                    MenuItem::new(im_str!("Snapshots"))
+                        .build_with_ref(ui, &mut state.snapshots.opened);
+                    MenuItem::new(im_str!("Merge saves"))
+                        .build_with_ref(ui, &mut state.merge.opened);
+                    MenuItem::new(im_str!("Save health"))
+                        .build_with_ref(ui, &mut state.health.opened);
+                    if_chain! {
+                        if MenuItem::new(im_str!("Export patch"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            self.export_patch_dialog(save_game);
+                        }
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Exports the modifications as a small patch (.bps) of the original file, to share exact edits");
+                    }
+                    if_chain! {
+                        if MenuItem::new(im_str!("Apply patch"))
+                            .enabled(state.save_game.is_some())
+                            .build(ui);
+                        if let Some(save_game) = &state.save_game;
+                        then {
+                            self.apply_patch_dialog(save_game);
+                        }
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Applies a patch to the file of the opened save, as it is on disk.\nUnsaved modifications are replaced by the patched save.");
+                    }
+                    MenuItem::new(im_str!("Search strings"))
+                        .build_with_ref(ui, &mut state.string_search.opened);
+                    MenuItem::new(im_str!("TLK"))
+                        .build_with_ref(ui, &mut self.tlk.borrow_mut().opened);
+                    MenuItem::new(im_str!("Watch"))
+                        .build_with_ref(ui, &mut self.watch.borrow_mut().opened);
+                    MenuItem::new(im_str!("Logs")).build_with_ref(ui, &mut state.log_viewer.opened);
+                    ui.separator();
+                    if MenuItem::new(im_str!("Export databases")).build(ui) {
+                        self.export_databases_dialog();
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Copies the plot, item and preset databases to a folder, to edit or extend them");
+                    }
+                    if state.dev {
+                        if MenuItem::new(im_str!("Generate test saves")).build(ui) {
+                            self.generate_test_saves_dialog();
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Writes a minimal save of each game to a folder, with valid headers and empty tables, to test parsers and presets");
+                        }
+                    }
+                }
+                if let Some(_t) = ui.begin_menu(im_str!("Settings")) {
+                    self.draw_settings(&mut state.settings);
+                }
+                if let Some(_t) = ui.begin_menu(im_str!("Help")) {
+                    if MenuItem::new(im_str!("Guided tour")).build(ui) {
+                        self.help.borrow_mut().start_tour();
+                    }
+                    MenuItem::new(im_str!("Save format"))
+                        .build_with_ref(ui, &mut state.format_docs.opened);
                 }
                 if let Some(_t) = ui.begin_menu(im_str!("About")) {
                     self.draw_about();
                 }
             }
 
+            // Shortcuts
+            match self.pressed_shortcut(&state.settings.shortcuts, &mut state.held_shortcut) {
+                Some(Shortcut::Open) => self.open_dialog(),
+                Some(Shortcut::Save) => {
+                    if let Some(save_game) = &state.save_game {
+                        self.save_dialog(save_game, state.cloud_sync.working_copy_of.as_deref());
+                    }
+                }
+                Some(Shortcut::SaveNewSlot) => {
+                    if let Some(save_game) = &state.save_game {
+                        let _ = self.event_addr.send(MainEvent::SaveNewSlot(save_game.clone()));
+                    }
+                }
+                Some(Shortcut::SplitView) => {
+                    state.settings.split_view = !state.settings.split_view;
+                    let _ = self.event_addr.send(MainEvent::SaveSettings(state.settings.clone()));
+                }
+                None => (),
+            }
+
             // Error popup
             self.draw_error(&mut state.error);
 
             // Notification
             self.draw_notification_overlay(&mut state.notification);
+            self.draw_health_summary(&mut state.health);
+
+            // Progress
+            self.draw_progress(&mut state.progress);
+
+            // Newer save version
+            self.draw_newer_version_prompt(&mut state.newer_save_version);
+
+            // Save format
+            self.draw_save_format_prompt(&mut state.save_format_choice);
+
+            // Cloud sync
+            if let Some(save_game) = &mut state.save_game {
+                self.draw_forward_compat_warning(save_game);
+                self.draw_cloud_sync_warning(save_game, &mut state.cloud_sync);
+            }
+
+            // Quick actions
+            if_chain! {
+                if let Some(save_game) = &mut state.save_game;
+                if let Some(recipe_db) = &state.databases.recipe_db;
+                if let Some(label) = self.draw_quick_actions_bar(
+                    save_game,
+                    recipe_db,
+                    &state.settings.quick_actions,
+                    &mut state.change_preview,
+                );
+                then {
+                    state.notification = Some(NotificationState {
+                        string: ImString::new(format!("{} applied", label)),
+                        close_time: ui.time() + NOTIFICATION_TIME,
+                    });
+                }
+            }
 
             // Game
             match &mut state.save_game {
-                None => self.draw_change_log(),
-                Some(SaveGame::MassEffect1 { save_game, .. }) => {
-                    self.draw_mass_effect_1(save_game, &state.databases)
+                None => {
+                    self.draw_change_log();
+                }
+                Some(save_game) => {
+                    let issues = state.issues.get_or_insert_with(|| save_issues(save_game));
+                    if state.settings.split_view && self.shows(Widgets::Raw) {
+                        self.draw_split_view(save_game, issues, &state.databases)
+                    } else {
+                        self.draw_save_game(save_game, issues, &state.databases)
+                    }
+                }
+            }
+        }
+
+        // Plot coverage
+        self.draw_plot_coverage(&mut state.plot_coverage);
+
+        // Save statistics
+        self.draw_save_stats(&mut state.save_stats);
+
+        // Suicide Mission
+        self.draw_suicide_mission(&mut state.suicide_mission, &mut state.save_game);
+
+        // Normandy crew
+        self.draw_normandy_crew(&mut state.normandy_crew_opened, &mut state.save_game);
+
+        // ME3 known issues
+        self.draw_known_issues(
+            &mut state.known_issues_opened,
+            &mut state.save_game,
+            state.databases.me3_known_issues.as_ref(),
+        );
+
+        // ME3 career audit
+        self.draw_career_audit(&mut state.career_audit);
+
+        // Romance checker
+        self.draw_romance_checker(
+            &mut state.romance_checker_opened,
+            &mut state.save_game,
+            state.databases.romance_db.as_ref(),
+        );
+
+        // Squad selection
+        self.draw_squad_selection(
+            &mut state.squad_selection_opened,
+            &mut state.save_game,
+            state.databases.squad_db.as_ref(),
+        );
+
+        // Squadmate build
+        self.draw_squad_build(&mut state.squad_build, &mut state.save_game);
+
+        // Save slots
+        self.draw_save_slots(&mut state.save_slots);
+
+        // ME1 property copy
+        self.draw_property_copy(&mut state.property_copy, &mut state.save_game);
+
+        // Snapshots
+        self.draw_snapshots(&mut state.snapshots, &mut state.save_game);
+
+        // Merge
+        self.draw_merge(&mut state.merge, &mut state.save_game);
+
+        // Watch folder
+        self.draw_auto_patch(&mut state.auto_patch, state.databases.recipe_db.as_ref());
+
+        // Quick actions
+        self.draw_quick_actions(
+            &mut state.quick_actions_opened,
+            &mut state.settings,
+            state.databases.recipe_db.as_ref(),
+        );
+
+        // Preset preview
+        if let Some(title) =
+            self.draw_change_preview(&mut state.change_preview, &mut state.save_game)
+        {
+            state.notification = Some(NotificationState {
+                string: ImString::new(format!("{} applied", title)),
+                close_time: self.ui.time() + NOTIFICATION_TIME,
+            });
+        }
+
+        // Save health
+        self.draw_health(&mut state.health, &state.save_game, &mut state.issues);
+
+        // String search
+        self.draw_string_search(&mut state.string_search, &mut state.save_game);
+
+        // TLK
+        self.draw_tlk_viewer();
+
+        // Watch
+        self.draw_watch_panel(&mut state.save_game);
+
+        // Plot paste
+        self.draw_plot_paste(&mut state.plot_paste, &mut state.save_game);
+
+        // Plot lookup
+        self.draw_plot_lookup(&mut state.plot_lookup, &state.databases);
+
+        // Plot references
+        self.draw_plot_references(&state.databases);
+
+        // Logs
+        self.draw_log_viewer(&mut state.log_viewer, &state.log_buffer);
+
+        // Save format
+        self.draw_format_docs(&mut state.format_docs);
+
+        // Guided tour
+        self.draw_tour(&mut state.settings);
+
+        // Confirmation
+        self.draw_confirmation(&mut state.settings);
+
+        // Revert
+        if let Some(path) = self.revert_request.take() {
+            original_save_game(state);
+            if let (Some(save_game), Some(original)) =
+                (&mut state.save_game, &state.original_save_game)
+            {
+                raw_ui::revert_save_game(save_game, original, &path);
+            }
+        }
+    }
+
+    // Enregistrées quand plus rien n'est en cours d'édition, pas à chaque caractère tapé
+    fn save_view_preferences(&self, state: &mut State) {
+        let kind = match &state.save_game {
+            Some(save_game) => save_game.kind(),
+            None => return,
+        };
+
+        let mut changed = false;
+        if let Some(layouts) = self.take_table_layouts() {
+            if layouts != state.settings.table_layouts {
+                state.settings.table_layouts = layouts;
+                changed = true;
+            }
+        }
+
+        if !self.ui.is_any_item_active() {
+            let mut table_views: Vec<_> = state
+                .table_views
+                .borrow()
+                .iter()
+                .map(|(path, sort)| (path.clone(), *sort))
+                .collect();
+            table_views.sort();
+            let views = ViewPreferences {
+                table_views,
+                tlk_filter: state.tlk.borrow().picker_filter().to_owned(),
+                plot_lookup_query: state.plot_lookup.query().to_owned(),
+                locked_tabs: state.locked_tabs.borrow().clone(),
+            };
+            let is_saved = match state.settings.views.get(kind) {
+                Some(saved) => *saved == views,
+                None => views == ViewPreferences::default(),
+            };
+            if !is_saved {
+                state.settings.views.insert(kind.to_owned(), views);
+                changed = true;
+            }
+        }
+
+        if changed {
+            let _ = self.event_addr.send(MainEvent::SaveSettings(state.settings.clone()));
+        }
+    }
+
+    // Une modification passe par la souris ou le clavier, sinon par un événement
+    fn has_user_input(&self) -> bool {
+        let ui = self.ui;
+        ui.is_any_item_active()
+            || ui.is_any_mouse_down()
+            || ui.is_mouse_released(MouseButton::Left)
+            || ui.is_mouse_released(MouseButton::Right)
+            || ui.io().keys_down.iter().any(|&down| down)
+    }
+
+    fn update_autosave(
+        &self, save_game: &Option<SaveGame>, settings: &Settings, next_autosave: &mut Option<f64>,
+    ) {
+        let save_game = match save_game {
+            Some(save_game) if settings.autosave_interval > 0 => save_game,
+            _ => {
+                *next_autosave = None;
+                return;
+            }
+        };
+
+        let now = self.ui.time();
+        let interval = settings.autosave_interval as f64 * 60.0;
+        match *next_autosave {
+            Some(time) if now < time => (),
+            Some(_) => {
+                let _ = self.event_addr.send(MainEvent::Autosave(save_game.clone()));
+                *next_autosave = Some(now + interval);
+            }
+            None => *next_autosave = Some(now + interval),
+        }
+    }
+
+    fn draw_test_slot_menu(&self, save_game: &SaveGame, test_slot: &Option<PathBuf>) {
+        let ui = self.ui;
+
+        match test_slot {
+            None => {
+                if ui.button(im_str!("Test in game")) {
+                    let _ = self.event_addr.send(MainEvent::TestInGame(save_game.clone(), None));
                 }
-                Some(SaveGame::MassEffect1Leg { save_game, .. }) => {
-                    self.draw_mass_effect_1_leg(&mut save_game.save_data, &state.databases)
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(
+                        "Saves to a temporary slot, the opened save is left untouched until promoted",
+                    );
                 }
-                Some(SaveGame::MassEffect2 { save_game, .. }) => {
-                    self.draw_mass_effect_2(save_game, &state.databases)
+            }
+            Some(test_path) => {
+                if let Some(_t) = ui.begin_menu(im_str!("Test slot")) {
+                    let file_name = test_path.file_name().unwrap_or_default().to_string_lossy();
+                    ui.text_disabled(format!("Testing in {}", file_name));
+                    if MenuItem::new(im_str!("Update test slot")).build(ui) {
+                        let _ = self.event_addr.send(MainEvent::TestInGame(
+                            save_game.clone(),
+                            Some(test_path.clone()),
+                        ));
+                    }
+                    if MenuItem::new(im_str!("Promote to original save")).build(ui) {
+                        let _ = self
+                            .event_addr
+                            .send(MainEvent::PromoteTestSlot(save_game.clone(), test_path.clone()));
+                    }
+                    if MenuItem::new(im_str!("Discard test slot")).build(ui) {
+                        let _ = self.event_addr.send(MainEvent::DiscardTestSlot(test_path.clone()));
+                    }
                 }
-                Some(SaveGame::MassEffect2Leg { save_game, .. }) => {
-                    self.draw_mass_effect_2_leg(save_game, &state.databases)
+            }
+        }
+    }
+
+    fn draw_save_game(
+        &self, save_game: &mut SaveGame, issues: &Issues, databases: &DatabasesState,
+    ) {
+        match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                self.draw_mass_effect_1(save_game, issues, databases)
+            }
+            SaveGame::MassEffect1Leg { save_game, .. } => {
+                self.draw_mass_effect_1_leg(&mut save_game.save_data, issues, databases)
+            }
+            SaveGame::MassEffect2 { save_game, .. } => {
+                self.draw_mass_effect_2(save_game, issues, databases)
+            }
+            SaveGame::MassEffect2Leg { save_game, .. } => {
+                self.draw_mass_effect_2_leg(save_game, issues, databases)
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                self.draw_mass_effect_3(save_game, issues, databases)
+            }
+        };
+    }
+
+    // Les deux vues éditent la même save dans la même frame, elles restent synchronisées
+    fn draw_split_view(
+        &self, save_game: &mut SaveGame, issues: &Issues, databases: &DatabasesState,
+    ) {
+        let ui = self.ui;
+        let [width, _] = ui.content_region_avail();
+
+        if let Some(_t) = ChildWindow::new("structured").size([width / 2.0, 0.0]).begin(ui) {
+            self.draw_save_game(save_game, issues, databases);
+        }
+        ui.same_line();
+        if let Some(_t) = ChildWindow::new("raw").border(true).begin(ui) {
+            self.set_next_item_open(true);
+            match save_game {
+                SaveGame::MassEffect1 { save_game, .. } => {
+                    self.draw_revertable_raw_ui(&mut save_game.state, "State")
                 }
-                Some(SaveGame::MassEffect3 { save_game, .. }) => {
-                    self.draw_mass_effect_3(save_game, &state.databases)
+                SaveGame::MassEffect1Leg { save_game, .. } => {
+                    self.draw_revertable_raw_ui(&mut save_game.save_data, "Mass Effect 1")
                 }
-            };
+                SaveGame::MassEffect2 { save_game, .. } => {
+                    self.draw_revertable_raw_ui(save_game.as_mut(), "Mass Effect 2")
+                }
+                SaveGame::MassEffect2Leg { save_game, .. } => {
+                    self.draw_revertable_raw_ui(save_game.as_mut(), "Mass Effect 2")
+                }
+                SaveGame::MassEffect3 { save_game, .. } => {
+                    self.draw_revertable_raw_ui(save_game.as_mut(), "Mass Effect 3")
+                }
+            }
         }
     }
 
@@ -260,8 +1215,8 @@ impl<'ui> Gui<'ui> {
             "",
             &dir.to_string_lossy(),
             Some((
-                &["*.pcsav", "*.MassEffectSave"],
-                "Mass Effect Trilogy Save (*.pcsav, *.MassEffectSave)",
+                &["*.pcsav", "*.MassEffectSave", "*.sav", "*.tse-autosave"],
+                "Mass Effect Trilogy Save (*.pcsav, *.MassEffectSave, *.sav, *.tse-autosave)",
             )),
         );
 
@@ -270,11 +1225,34 @@ impl<'ui> Gui<'ui> {
         }
     }
 
-    fn save_dialog(&self, save_game: &SaveGame) {
+    fn export_databases_dialog(&self) {
+        let dir = Self::get_document_dir();
+
+        if let Some(dir) =
+            tinyfiledialogs::select_folder_dialog("Export databases", &dir.to_string_lossy())
+        {
+            let _ = self.event_addr.send(MainEvent::ExportDatabases(PathBuf::from(dir)));
+        }
+    }
+
+    fn generate_test_saves_dialog(&self) {
+        let dir = Self::get_document_dir();
+
+        if let Some(dir) =
+            tinyfiledialogs::select_folder_dialog("Generate test saves", &dir.to_string_lossy())
+        {
+            let _ = self.event_addr.send(MainEvent::GenerateTestSaves(PathBuf::from(dir)));
+        }
+    }
+
+    fn save_dialog(&self, save_game: &SaveGame, working_copy_of: Option<&Path>) {
         let (file_path, description, extension) = match save_game {
             SaveGame::MassEffect1 { file_path, .. } => {
                 (file_path, "Mass Effect 1 Save (*.MassEffectSave)", "*.MassEffectSave")
             }
+            SaveGame::MassEffect1Leg { file_path, save_game } if save_game.is_standalone() => {
+                (file_path, "Mass Effect 1 Legendary Chunk (*.sav)", "*.sav")
+            }
             SaveGame::MassEffect1Leg { file_path, .. } => {
                 (file_path, "Mass Effect 1 Legendary Save (*.pcsav)", "*.pcsav")
             }
@@ -291,7 +1269,39 @@ impl<'ui> Gui<'ui> {
             tinyfiledialogs::save_file_dialog_with_filter("", file_path, &[extension], description);
 
         if let Some(path) = file {
-            let _ = self.event_addr.send(MainEvent::SaveSave(path, save_game.clone()));
+            let event = match working_copy_of {
+                // Recopiée sur la save synchronisée seulement si la sauvegarde réussit
+                Some(original) if path == *file_path => {
+                    MainEvent::SaveWorkingCopy(path, save_game.clone(), original.to_owned())
+                }
+                _ => MainEvent::SaveSave(path, save_game.clone()),
+            };
+            let _ = self.event_addr.send(event);
+        }
+    }
+
+    fn export_patch_dialog(&self, save_game: &SaveGame) {
+        let default_path = format!("{}.bps", save_game.file_path());
+        let file = tinyfiledialogs::save_file_dialog_with_filter(
+            "Export patch",
+            &default_path,
+            &["*.bps"],
+            "BPS patch (*.bps)",
+        );
+        if let Some(path) = file {
+            let _ = self.event_addr.send(MainEvent::ExportPatch(path, save_game.clone()));
+        }
+    }
+
+    fn apply_patch_dialog(&self, save_game: &SaveGame) {
+        let file = tinyfiledialogs::open_file_dialog(
+            "Apply patch",
+            "",
+            Some((&["*.bps"], "BPS patch (*.bps)")),
+        );
+        if let Some(path) = file {
+            let file_path = save_game.file_path().to_owned();
+            let _ = self.event_addr.send(MainEvent::ApplyPatch(path, file_path));
         }
     }
 
@@ -334,11 +1344,19 @@ impl<'ui> Gui<'ui> {
             {
                 ui.text(error.to_string());
 
+                // Une cause par niveau d'indentation
                 let chain = error.chain().skip(1);
                 if chain.len() != 0 {
                     ui.separator();
-                    for error in chain {
-                        ui.text(error.to_string());
+                    if let Some(_t) = ChildWindow::new("error_chain")
+                        .size([500.0, 150.0])
+                        .horizontal_scrollbar(true)
+                        .begin(ui)
+                    {
+                        for error in chain {
+                            ui.indent();
+                            ui.text(error.to_string());
+                        }
                     }
                 }
                 ui.separator();
@@ -347,6 +1365,37 @@ impl<'ui> Gui<'ui> {
                     *option_error = None;
                     ui.close_current_popup();
                 }
+
+                if let Some(error) = option_error.as_ref() {
+                    ui.same_line();
+                    if ui.button(im_str!("Copy details")) {
+                        ui.set_clipboard_text(&ImString::new(error_details(error)));
+                    }
+                }
+
+                let context = option_error.as_ref().and_then(|error| {
+                    error.chain().find_map(|err| err.downcast_ref::<unreal::Error>()?.context())
+                });
+                if let Some(context) = context {
+                    ui.same_line();
+                    if ui.button(im_str!("Export report")) {
+                        let file = tinyfiledialogs::save_file_dialog_with_filter(
+                            "",
+                            "error_report.txt",
+                            &["*.txt"],
+                            "Error report (*.txt)",
+                        );
+                        if let Some(path) = file {
+                            let _ = self
+                                .event_addr
+                                .send(MainEvent::ExportTextFile(path, context.report()));
+                        }
+                    }
+                    ui.same_line();
+                    self.draw_help_marker(
+                        "Exports the bytes around the error without any names or strings.\nAttach it to your bug report.",
+                    );
+                }
             }
         }
     }
@@ -380,6 +1429,43 @@ impl<'ui> Gui<'ui> {
         }
     }
 
+    fn draw_progress(&self, option_progress: &mut Option<ProgressState>) {
+        let ui = self.ui;
+
+        if let Some(progress) = option_progress {
+            // Évite un clignotement pour les opérations rapides
+            let is_shown = ui.time() - progress.start_time >= PROGRESS_DELAY;
+            if !is_shown && !progress.finished {
+                return;
+            }
+            if !progress.finished {
+                ui.open_popup(im_str!("Please wait###progress"));
+            }
+
+            if let Some(_t) = PopupModal::new(im_str!("Please wait###progress"))
+                .always_auto_resize(true)
+                .begin_popup(ui)
+            {
+                if progress.finished {
+                    ui.close_current_popup();
+                } else {
+                    ui.text(progress.label);
+                    ProgressBar::new(progress.fraction).size([300.0, 0.0]).build(ui);
+
+                    if progress.cancelled.load(Ordering::Relaxed) {
+                        ui.text_disabled(im_str!("Cancelling..."));
+                    } else if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                        progress.cancelled.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+
+            if progress.finished {
+                *option_progress = None;
+            }
+        }
+    }
+
     fn draw_help_marker(&self, desc: &str) {
         let ui = self.ui;
 
@@ -393,30 +1479,62 @@ impl<'ui> Gui<'ui> {
     // Style
     fn style_colors(&self, game_theme: Theme) -> [ColorStackToken<'ui>; 23] {
         let ui = self.ui;
-        let theme = match game_theme {
-            Theme::MassEffect1 => ColorTheme {
+        let accessibility = &self.accessibility;
+        let theme = match (accessibility.palette, game_theme) {
+            (Palette::Default, Theme::MassEffect1) => ColorTheme {
                 bg_color: [0.11, 0.32, 0.43, 1.0],
                 color: [0.16, 0.42, 0.58, 1.0],
                 active_color: [0.28, 0.55, 0.67, 1.0],
                 hover_color: [0.83, 0.43, 0.17, 1.0],
             },
-            Theme::MassEffect2 => ColorTheme {
+            (Palette::Default, Theme::MassEffect2) => ColorTheme {
                 bg_color: [0.64, 0.32, 0.12, 1.0],
                 color: [0.70, 0.37, 0.16, 1.0],
                 active_color: [0.85, 0.49, 0.25, 1.0],
                 hover_color: [0.22, 0.52, 0.23, 1.0],
             },
-            Theme::MassEffect3 => ColorTheme {
+            (Palette::Default, Theme::MassEffect3) => ColorTheme {
                 bg_color: [0.40, 0.0, 0.0, 1.0],
                 color: [0.53, 0.0, 0.0, 1.0],
                 active_color: [0.70, 0.0, 0.0, 1.0],
                 hover_color: [0.02, 0.28, 0.43, 1.0],
             },
+            // Okabe-Ito, pas de paire rouge / vert
+            (Palette::ColorblindSafe, Theme::MassEffect1) => ColorTheme {
+                bg_color: [0.0, 0.30, 0.47, 1.0],
+                color: [0.0, 0.38, 0.60, 1.0],
+                active_color: [0.0, 0.45, 0.70, 1.0],
+                hover_color: [0.80, 0.55, 0.0, 1.0],
+            },
+            (Palette::ColorblindSafe, Theme::MassEffect2) => ColorTheme {
+                bg_color: [0.55, 0.37, 0.0, 1.0],
+                color: [0.65, 0.44, 0.0, 1.0],
+                active_color: [0.80, 0.54, 0.0, 1.0],
+                hover_color: [0.0, 0.45, 0.70, 1.0],
+            },
+            (Palette::ColorblindSafe, Theme::MassEffect3) => ColorTheme {
+                bg_color: [0.50, 0.22, 0.0, 1.0],
+                color: [0.62, 0.28, 0.0, 1.0],
+                active_color: [0.84, 0.37, 0.0, 1.0],
+                hover_color: [0.0, 0.45, 0.70, 1.0],
+            },
+            (Palette::HighContrast, _) => ColorTheme {
+                bg_color: [0.22, 0.22, 0.22, 1.0],
+                color: [0.32, 0.32, 0.32, 1.0],
+                active_color: [0.50, 0.50, 0.50, 1.0],
+                hover_color: [0.0, 0.35, 0.75, 1.0],
+            },
+        };
+
+        let (border_color, check_mark_color) = if accessibility.high_contrast_checkboxes {
+            ([0.85, 0.85, 0.85, 1.0], [1.0, 0.85, 0.0, 1.0])
+        } else {
+            ([0.20, 0.20, 0.20, 1.0], [1.0, 1.0, 1.0, 1.0])
         };
 
         [
             ui.push_style_color(StyleColor::WindowBg, [0.05, 0.05, 0.05, 1.0]),
-            ui.push_style_color(StyleColor::Border, [0.20, 0.20, 0.20, 1.0]),
+            ui.push_style_color(StyleColor::Border, border_color),
             ui.push_style_color(StyleColor::Separator, [0.20, 0.20, 0.20, 1.0]),
             ui.push_style_color(StyleColor::PopupBg, [0.07, 0.07, 0.07, 0.99]),
             ui.push_style_color(StyleColor::TitleBgActive, theme.active_color),
@@ -433,7 +1551,7 @@ impl<'ui> Gui<'ui> {
             ui.push_style_color(StyleColor::Header, theme.bg_color),
             ui.push_style_color(StyleColor::HeaderActive, theme.active_color),
             ui.push_style_color(StyleColor::HeaderHovered, theme.hover_color),
-            ui.push_style_color(StyleColor::CheckMark, [1.0, 1.0, 1.0, 1.0]),
+            ui.push_style_color(StyleColor::CheckMark, check_mark_color),
             ui.push_style_color(StyleColor::PlotHistogram, [1.0, 1.0, 1.0, 1.0]),
             ui.push_style_color(StyleColor::TableRowBg, [0.07, 0.07, 0.07, 1.0]),
             ui.push_style_color(StyleColor::TableRowBgAlt, [0.1, 0.1, 0.1, 1.0]),
@@ -454,3 +1572,23 @@ struct ColorTheme {
     active_color: [f32; 4],
     hover_color: [f32; 4],
 }
+
+// Pour les rapports de bug
+fn error_details(error: &Error) -> String {
+    let mut details = format!(
+        "Trilogy Save Editor v{} ({} {})\n\nError: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        error
+    );
+
+    let chain = error.chain().skip(1);
+    if chain.len() != 0 {
+        details.push_str("\nCaused by:\n");
+        for (i, error) in chain.enumerate() {
+            details.push_str(&format!("{:>5}: {}\n", i, error));
+        }
+    }
+    details
+}
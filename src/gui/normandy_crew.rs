@@ -0,0 +1,88 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, InputInt, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::mass_effect_2::{
+        crew::{self, CrewState},
+        suicide_mission::CrewOutcome,
+    },
+};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_normandy_crew(&self, opened: &mut bool, save_game: &mut Option<SaveGame>) {
+        let ui = self.ui;
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Normandy crew"))
+            .size([450.0, 450.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let plot = match save_game {
+                Some(SaveGame::MassEffect2 { save_game, .. }) => &mut save_game.plot,
+                Some(SaveGame::MassEffect2Leg { save_game, .. }) => &mut save_game.plot,
+                _ => {
+                    ui.text_disabled(im_str!("Open a Mass Effect 2 save"));
+                    return;
+                }
+            };
+
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                let state = CrewState::from_plot(plot);
+
+                ui.text_wrapped(im_str!("The Collectors abduct the crew the first time Shepard leaves the Normandy after the Reaper IFF is installed. Every mission done afterwards, before going through the Omega 4 Relay, delays the rescue :\n- none, everyone is saved\n- 1 to 3, Kelly and half of the crew die\n- 4 or more, only Dr. Chakwas survives\nSending no escort, or a disloyal one, with the crew during the Suicide Mission also gets them killed."));
+                ui.separator();
+
+                // Avant la mission suicide
+                ui.text_disabled(im_str!("Before the Suicide Mission"));
+                let mut delay = state.delay;
+                let width = ui.push_item_width(120.0);
+                let changed =
+                    InputInt::new(ui, im_str!("Missions after abduction"), &mut delay).build();
+                width.pop(ui);
+                if changed {
+                    crew::set_delay(plot, delay);
+                }
+                let expected = match crew::expected_outcome(delay) {
+                    CrewOutcome::AllSurvive => "Everyone will survive",
+                    CrewOutcome::HalfSurvive => "Kelly and half of the crew will die",
+                    _ => "Only Dr. Chakwas will survive",
+                };
+                ui.text(expected);
+                ui.separator();
+
+                // Après la mission suicide
+                ui.text_disabled(im_str!("After the Suicide Mission"));
+                ui.same_line();
+                self.draw_help_marker(
+                    "Written by the game at the end of the Suicide Mission, imported by Mass Effect 3.",
+                );
+                if !state.is_resolved() {
+                    ui.text_disabled(im_str!("The crew has not been rescued yet"));
+                }
+                for (member, died) in state.died {
+                    let mut survived = !died;
+                    if ui.checkbox(&ImString::new(format!("{} survived", member)), &mut survived) {
+                        crew::set_died(plot, member, !survived);
+                    }
+                }
+
+                if ui.button(im_str!("Everyone survives")) {
+                    crew::set_outcome(plot, CrewOutcome::AllSurvive);
+                }
+                ui.same_line();
+                if ui.button(im_str!("Only Kelly dies")) {
+                    crew::set_outcome(plot, CrewOutcome::HalfSurvive);
+                }
+                ui.same_line();
+                if ui.button(im_str!("Only Dr. Chakwas survives")) {
+                    crew::set_outcome(plot, CrewOutcome::OnlyChakwas);
+                }
+            }
+        }
+    }
+}
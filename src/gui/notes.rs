@@ -0,0 +1,57 @@
+use if_chain::if_chain;
+use imgui::{im_str, ImString, TabItem};
+use std::path::Path;
+
+use crate::event_handler::{MainEvent, NOTES_SUFFIX};
+
+use super::Gui;
+
+// Notes de la save ouverte, dans un fichier à côté de la save (`MySave.pcsav.tse-notes`)
+#[derive(Default)]
+pub struct NotesState {
+    file_path: String,
+    text: ImString,
+    modified: bool,
+}
+
+impl NotesState {
+    pub fn loaded(&mut self, file_path: String, text: String) {
+        *self = NotesState { file_path, text: ImString::new(text), modified: false };
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_notes_tab(&self) {
+        let ui = self.ui;
+        let mut notes = self.notes.borrow_mut();
+
+        let label =
+            if notes.modified { im_str!("Notes *###notes") } else { im_str!("Notes###notes") };
+        if_chain! {
+            if let Some(_t) = TabItem::new(label).begin(ui);
+            if !notes.file_path.is_empty();
+            then {
+                if ui.button(im_str!("Save notes")) {
+                    let _ = self.event_addr.send(MainEvent::SaveNotes(
+                        notes.file_path.clone(),
+                        notes.text.to_string(),
+                    ));
+                    notes.modified = false;
+                }
+                ui.same_line();
+                let file_name = Path::new(&notes.file_path).file_name().unwrap_or_default();
+                ui.text_disabled(format!(
+                    "Saved in {}{}, independently from the save",
+                    file_name.to_string_lossy(),
+                    NOTES_SUFFIX
+                ));
+
+                let size = ui.content_region_avail();
+                notes.modified |= ui
+                    .input_text_multiline(im_str!("##notes"), &mut notes.text, size)
+                    .resize_buffer(true)
+                    .build();
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use imgui::{im_str, ChildWindow, Condition, Window};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::shared::plot_coverage::{PlotCoverage, PlotLabels},
+};
+
+use super::{DatabasesState, Gui};
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_plot_coverage(&self, plot_coverage: &mut Option<PlotCoverage>) {
+        let ui = self.ui;
+
+        let mut opened = true;
+        if let Some(coverage) = plot_coverage {
+            let window = Window::new(im_str!("Plot DB coverage"))
+                .size([400.0, 450.0], Condition::FirstUseEver)
+                .opened(&mut opened);
+
+            if let Some(_t) = window.begin(ui) {
+                ui.text(format!(
+                    "Booleans : {} / {} labeled",
+                    coverage.labeled_booleans, coverage.bool_len
+                ));
+                ui.text(format!(
+                    "Integers : {} / {} labeled",
+                    coverage.labeled_ints, coverage.int_len
+                ));
+
+                if ui.button(im_str!("Export CSV")) {
+                    let file = tinyfiledialogs::save_file_dialog_with_filter(
+                        "",
+                        "plot_coverage.csv",
+                        &["*.csv"],
+                        "CSV (*.csv)",
+                    );
+                    if let Some(path) = file {
+                        let _ = self
+                            .event_addr
+                            .send(MainEvent::ExportTextFile(path, coverage.to_csv()));
+                    }
+                }
+                ui.same_line();
+                self.draw_help_marker(
+                    "Variables set in your save but missing from the plot databases.\nShare them to help extend the databases.",
+                );
+                ui.separator();
+
+                if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                    let categories = [
+                        ("Unlabeled booleans", &coverage.unlabeled_booleans),
+                        ("Unlabeled integers", &coverage.unlabeled_ints),
+                    ];
+                    for (title, ranges) in &categories {
+                        let ident = format!("{} ({} ranges)", title, ranges.len());
+                        if let Some(_t) = self.push_tree_node(&ident) {
+                            for range in ranges.iter() {
+                                if range.start() == range.end() {
+                                    ui.text(range.start().to_string());
+                                } else {
+                                    ui.text(format!("{} - {}", range.start(), range.end()));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if !opened {
+            *plot_coverage = None;
+        }
+    }
+}
+
+pub fn plot_coverage(save_game: &SaveGame, databases: &DatabasesState) -> Option<PlotCoverage> {
+    let coverage = match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            let labels: PlotLabels = databases.me1_plot_db.as_ref()?.categories().collect();
+            let plot = &save_game.state.plot;
+            PlotCoverage::new(
+                &plot.bool_variables,
+                plot.int_variables.iter().copied().enumerate(),
+                &labels,
+            )
+        }
+        SaveGame::MassEffect1Leg { save_game, .. } => {
+            let labels: PlotLabels = databases.me1_plot_db.as_ref()?.categories().collect();
+            let plot = &save_game.save_data.plot;
+            PlotCoverage::new(
+                &plot.bool_variables,
+                plot.int_variables.iter().copied().enumerate(),
+                &labels,
+            )
+        }
+        SaveGame::MassEffect2 { save_game, .. } => {
+            let labels: PlotLabels = databases.me2_plot_db.as_ref()?.categories().collect();
+            let plot = &save_game.plot;
+            PlotCoverage::new(
+                &plot.bool_variables,
+                plot.int_variables.iter().copied().enumerate(),
+                &labels,
+            )
+        }
+        SaveGame::MassEffect2Leg { save_game, .. } => {
+            let labels: PlotLabels = databases.me2_plot_db.as_ref()?.categories().collect();
+            let plot = &save_game.plot;
+            PlotCoverage::new(
+                &plot.bool_variables,
+                plot.int_variables.iter().copied().enumerate(),
+                &labels,
+            )
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            let me3_plot_db = databases.me3_plot_db.as_ref()?;
+
+            // Le plot de ME3 contient aussi les variables importées de ME2
+            let mut labels: PlotLabels = me3_plot_db.categories().collect();
+            if let Some(me2_plot_db) = &databases.me2_plot_db {
+                me2_plot_db.categories().for_each(|category| labels.add_category(category));
+            }
+            for variable in me3_plot_db.weapons_powers.values() {
+                labels.booleans.extend(variable.booleans.keys());
+            }
+
+            let plot = &save_game.plot;
+            let ints = plot
+                .int_variables
+                .iter()
+                .filter(|(&id, _)| id >= 0)
+                .map(|(&id, &value)| (id as usize, value));
+            PlotCoverage::new(&plot.bool_variables, ints, &labels)
+        }
+    };
+    Some(coverage)
+}
@@ -0,0 +1,122 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, ListClipper, MenuItem, Selectable, Window};
+
+use crate::save_data::shared::{
+    plot_references::{self, PlotId},
+    recipe::Game,
+};
+
+use super::{DatabasesState, Gui};
+
+const GAMES: [(Game, &str); 3] = [
+    (Game::MassEffect1, "Mass Effect 1"),
+    (Game::MassEffect2, "Mass Effect 2"),
+    (Game::MassEffect3, "Mass Effect 3"),
+];
+
+// Consultation du plot DB de n'importe quel jeu, indépendamment de la save ouverte
+#[derive(Default)]
+pub struct PlotLookupState {
+    pub opened: bool,
+    game: usize,
+    query: ImString,
+}
+
+impl PlotLookupState {
+    pub fn query(&self) -> &str {
+        self.query.to_str()
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.query = ImString::new(query);
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_plot_lookup(&self, state: &mut PlotLookupState, databases: &DatabasesState) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Plot database lookup"))
+            .size([600.0, 400.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let game_names: Vec<_> = GAMES.iter().map(|(_, name)| ImString::new(*name)).collect();
+            let game_names: Vec<_> = game_names.iter().map(ImString::as_ref).collect();
+            self.draw_edit_enum("Game", &mut state.game, &game_names);
+            ui.input_text(im_str!("Search"), &mut state.query).resize_buffer(true).build();
+            ui.same_line();
+            self.draw_help_marker(
+                "A plot ID, or a part of a label.\nRead only, nothing is edited.",
+            );
+            ui.separator();
+
+            let (game, _) = GAMES[state.game];
+            let query = state.query.to_str();
+            let entries = match game {
+                Game::MassEffect1 => databases
+                    .me1_plot_db
+                    .as_ref()
+                    .map(|plot_db| plot_references::lookup(plot_db.named_categories(), query)),
+                Game::MassEffect2 => databases
+                    .me2_plot_db
+                    .as_ref()
+                    .map(|plot_db| plot_references::lookup(plot_db.named_categories(), query)),
+                _ => databases
+                    .me3_plot_db
+                    .as_ref()
+                    .map(|plot_db| plot_references::lookup(plot_db.named_categories(), query)),
+            };
+            let entries = match entries {
+                Some(entries) => entries,
+                None => {
+                    ui.text_disabled(im_str!("Database not loaded"));
+                    return;
+                }
+            };
+
+            ui.text_disabled(format!("{} entries", entries.len()));
+            if let Some(_t) = ChildWindow::new("entries").begin(ui) {
+                let mut clipper = ListClipper::new(entries.len() as i32).begin(ui);
+                while clipper.step() {
+                    for i in clipper.display_start()..clipper.display_end() {
+                        let (category, plot_id, entry) = &entries[i as usize];
+                        let (kind, id) = match plot_id {
+                            PlotId::Bool(id) => ("Bool", id),
+                            PlotId::Int(id) => ("Int", id),
+                        };
+
+                        let _t = ui.push_id(i);
+                        Selectable::new(&im_str!("{} {}", kind, id)).size([90.0, 0.0]).build(ui);
+                        self.draw_plot_lookup_context_menu(game, *plot_id);
+                        ui.same_line_with_pos(100.0);
+                        ui.text(&entry.label);
+                        ui.same_line();
+                        ui.text_disabled(category);
+                        self.draw_plot_entry_details(&format!("lookup-{}", i), entry);
+                    }
+                }
+            }
+        }
+    }
+
+    // Pas de `Watch`, la variable n'est pas forcément du jeu de la save ouverte
+    fn draw_plot_lookup_context_menu(&self, game: Game, plot_id: PlotId) {
+        let ui = self.ui;
+
+        if let Some(_t) = self.begin_popup_context_item() {
+            if MenuItem::new(im_str!("Copy ID")).build(ui) {
+                let id = match plot_id {
+                    PlotId::Bool(id) | PlotId::Int(id) => id,
+                };
+                ui.set_clipboard_text(&ImString::new(id.to_string()));
+            }
+            if MenuItem::new(im_str!("Show references")).build(ui) {
+                self.plot_references.borrow_mut().shown = Some((game, plot_id));
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::shared::{
+        plot_clipboard,
+        plot_references::{self, PlotId},
+        recipe::Game,
+    },
+};
+
+use super::{presets::game_of, DatabasesState, Gui};
+
+// Flags collés, affichés avant d'être appliqués
+pub struct PlotPaste {
+    // (ID, valeur, label du plot DB)
+    flags: Vec<(usize, bool, Option<String>)>,
+}
+
+impl PlotPaste {
+    pub fn from_text(
+        text: &str, save_game: &SaveGame, databases: &DatabasesState,
+    ) -> anyhow::Result<Self> {
+        let flags = plot_clipboard::parse(text)?;
+        let game = game_of(save_game);
+        let flags = flags
+            .into_iter()
+            .map(|(id, value)| (id, value, bool_label(databases, game, id)))
+            .collect();
+        Ok(PlotPaste { flags })
+    }
+}
+
+fn bool_label(databases: &DatabasesState, game: Game, id: usize) -> Option<String> {
+    let query = id.to_string();
+    let entries = match game {
+        Game::MassEffect1 | Game::MassEffect1Leg => {
+            plot_references::lookup(databases.me1_plot_db.as_ref()?.named_categories(), &query)
+        }
+        Game::MassEffect2 | Game::MassEffect2Leg => {
+            plot_references::lookup(databases.me2_plot_db.as_ref()?.named_categories(), &query)
+        }
+        Game::MassEffect3 => {
+            plot_references::lookup(databases.me3_plot_db.as_ref()?.named_categories(), &query)
+        }
+    };
+    entries
+        .into_iter()
+        .find(|(_, plot_id, _)| *plot_id == PlotId::Bool(id))
+        .map(|(category, _, entry)| format!("{} > {}", category, entry))
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_plot_paste(
+        &self, plot_paste: &mut Option<PlotPaste>, save_game: &mut Option<SaveGame>,
+    ) {
+        let ui = self.ui;
+
+        let (paste, save_game) = match (plot_paste.as_ref(), save_game) {
+            (Some(paste), Some(save_game)) => (paste, save_game),
+            _ => {
+                *plot_paste = None;
+                return;
+            }
+        };
+
+        let mut opened = true;
+        let mut close = false;
+        let window = Window::new(im_str!("Paste plot flags"))
+            .size([550.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let booleans = save_game.plot_booleans_mut();
+
+            if ui.button(im_str!("Apply")) {
+                for &(id, value, _) in &paste.flags {
+                    if id >= booleans.len() {
+                        booleans.resize(id + 1, false);
+                    }
+                    booleans.set(id, value);
+                }
+                close = true;
+            }
+            ui.same_line();
+            if ui.button(im_str!("Cancel")) {
+                close = true;
+            }
+            ui.separator();
+
+            if let Some(_t) = ChildWindow::new("flags").begin(ui) {
+                for (id, value, label) in &paste.flags {
+                    let current = booleans.get(*id).map(|b| *b).unwrap_or_default();
+                    let text =
+                        ImString::new(format!("{} : {} -> {}", id, current as u8, *value as u8));
+                    if current == *value {
+                        ui.text_disabled(&text);
+                    } else {
+                        ui.text(&text);
+                    }
+                    ui.same_line_with_pos(150.0);
+                    match label {
+                        Some(label) => ui.text(label),
+                        None => ui.text_disabled(im_str!("Not in the plot database")),
+                    }
+                }
+            }
+        }
+
+        if !opened || close {
+            *plot_paste = None;
+        }
+    }
+}
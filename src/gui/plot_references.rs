@@ -0,0 +1,127 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, MenuItem, Window};
+
+use crate::save_data::shared::{
+    plot_references::{self, PlotId},
+    recipe::Game,
+};
+
+use super::{watch::Watch, DatabasesState, Gui};
+
+// Variable dont on affiche les références, le plot de ME1 et ME1LE est le même
+#[derive(Default)]
+pub struct PlotReferencesState {
+    pub shown: Option<(Game, PlotId)>,
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_plot_context_menu(&self, game: Game, plot_id: PlotId) {
+        let ui = self.ui;
+
+        if let Some(_t) = self.begin_popup_context_item() {
+            if MenuItem::new(im_str!("Watch")).build(ui) {
+                let watch = match plot_id {
+                    PlotId::Bool(id) => Watch::Bool(id),
+                    PlotId::Int(id) => Watch::Int(id),
+                };
+                self.watch.borrow_mut().add(watch);
+            }
+            if MenuItem::new(im_str!("Show references")).build(ui) {
+                self.plot_references.borrow_mut().shown = Some((game, plot_id));
+            }
+        }
+    }
+
+    pub fn draw_plot_references(&self, databases: &DatabasesState) {
+        let ui = self.ui;
+        let mut state = self.plot_references.borrow_mut();
+
+        let (game, plot_id) = match state.shown {
+            Some(shown) => shown,
+            None => return,
+        };
+
+        let mut opened = true;
+        let window = Window::new(im_str!("Plot references"))
+            .size([450.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let (kind, id) = match plot_id {
+                PlotId::Bool(id) => ("Bool", id),
+                PlotId::Int(id) => ("Int", id),
+            };
+            let game_name = match game {
+                Game::MassEffect1 | Game::MassEffect1Leg => "Mass Effect 1",
+                Game::MassEffect2 | Game::MassEffect2Leg => "Mass Effect 2",
+                Game::MassEffect3 => "Mass Effect 3",
+            };
+            ui.text(format!("{} {} of the {} plot", kind, id, game_name));
+            ui.separator();
+
+            let references = self.plot_references(databases, game, plot_id);
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                if references.is_empty() {
+                    ui.text_disabled(im_str!("Not referenced in the databases"));
+                }
+                for reference in references {
+                    ui.bullet_text(&ImString::new(reference));
+                }
+            }
+        }
+
+        if !opened {
+            state.shown = None;
+        }
+    }
+
+    // ME3 affiche aussi le plot DB de ME2 sur son propre plot
+    fn plot_references(
+        &self, databases: &DatabasesState, game: Game, plot_id: PlotId,
+    ) -> Vec<String> {
+        let mut references = Vec::new();
+        match game {
+            Game::MassEffect1 | Game::MassEffect1Leg => {
+                if let Some(me1_plot_db) = &databases.me1_plot_db {
+                    references.extend(plot_references::category_references(
+                        plot_id,
+                        me1_plot_db.named_categories(),
+                    ));
+                }
+            }
+            Game::MassEffect2 | Game::MassEffect2Leg => {
+                if let Some(me2_plot_db) = &databases.me2_plot_db {
+                    references.extend(plot_references::category_references(
+                        plot_id,
+                        me2_plot_db.named_categories(),
+                    ));
+                }
+            }
+            Game::MassEffect3 => {
+                if let Some(me3_plot_db) = &databases.me3_plot_db {
+                    references.extend(plot_references::category_references(
+                        plot_id,
+                        me3_plot_db.named_categories(),
+                    ));
+                    references.extend(plot_references::me3_weapons_powers_references(
+                        plot_id,
+                        me3_plot_db,
+                    ));
+                }
+                if let Some(me2_plot_db) = &databases.me2_plot_db {
+                    let categories = me2_plot_db
+                        .named_categories()
+                        .map(|(name, category)| (format!("Mass Effect 2 > {}", name), category));
+                    references.extend(plot_references::category_references(plot_id, categories));
+                }
+                if let Some(known_issues) = &databases.me3_known_issues {
+                    references
+                        .extend(plot_references::known_issue_references(plot_id, known_issues));
+                }
+            }
+        }
+        if let Some(recipe_db) = &databases.recipe_db {
+            references.extend(plot_references::recipe_references(plot_id, recipe_db, game));
+        }
+        references
+    }
+}
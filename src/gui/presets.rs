@@ -0,0 +1,311 @@
+use anyhow::Result;
+use imgui::{im_str, ComboBox, Condition, ImString, ItemHoveredFlags, MenuItem, Window};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::{
+        mass_effect_3::player::Player as Me3Player,
+        shared::recipe::{Game, Recipe, RecipeDb, WeaponSlot},
+    },
+};
+
+use super::{
+    change_preview::ChangePreview,
+    settings::{QuickAction, Settings},
+    Gui,
+};
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_presets_menu(
+        &self, save_game: &mut SaveGame, recipe_db: &RecipeDb, quick_actions_opened: &mut bool,
+        change_preview: &mut Option<ChangePreview>,
+    ) -> bool {
+        let ui = self.ui;
+        let game = game_of(save_game);
+
+        let mut request = None;
+        if let Some(_t) = ui.begin_menu(im_str!("Presets")) {
+            let mut is_empty = true;
+            for (i, recipe) in recipe_db.iter().enumerate() {
+                if !recipe.is_applicable(game) {
+                    continue;
+                }
+                is_empty = false;
+                let check = check_recipe(save_game, recipe);
+                if MenuItem::new(&ImString::new(&recipe.name)).enabled(check.is_ok()).build(ui) {
+                    request = Some(i);
+                }
+                if ui.is_item_hovered_with_flags(ItemHoveredFlags::ALLOW_WHEN_DISABLED) {
+                    if let Err(err) = &check {
+                        let _t = ui.begin_tooltip();
+                        ui.text(err.to_string());
+                    } else if !recipe.description.is_empty() {
+                        let _t = ui.begin_tooltip();
+                        ui.text(&recipe.description);
+                    }
+                }
+            }
+
+            if is_empty {
+                ui.text_disabled(im_str!("No preset for this game"));
+            }
+
+            ui.separator();
+            MenuItem::new(im_str!("Quick actions...")).build_with_ref(ui, quick_actions_opened);
+        }
+
+        match request.and_then(|i| recipe_db.get(i)) {
+            Some(recipe) if self.confirmations.apply_preset => {
+                *change_preview =
+                    Some(ChangePreview::new(recipe.name.clone(), save_game, |preview| {
+                        apply_recipe(preview, recipe)
+                    }));
+                false
+            }
+            Some(recipe) => {
+                apply_recipe(save_game, recipe);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Un clic, l'aperçu des modifications suit le même paramètre que le menu
+    pub fn draw_quick_actions_bar(
+        &self, save_game: &mut SaveGame, recipe_db: &RecipeDb, quick_actions: &[QuickAction],
+        change_preview: &mut Option<ChangePreview>,
+    ) -> Option<String> {
+        let ui = self.ui;
+        let game = game_of(save_game);
+
+        let mut applied = None;
+        let mut is_first = true;
+        for (i, action) in quick_actions.iter().enumerate() {
+            let recipes = action_recipes(action, recipe_db, game);
+            if recipes.is_empty() {
+                continue;
+            }
+
+            if !is_first {
+                ui.same_line();
+            }
+            is_first = false;
+
+            let _t = ui.push_id(i as i32);
+            let check = recipes.iter().try_for_each(|recipe| check_recipe(save_game, recipe));
+            match &check {
+                Ok(()) => {
+                    if ui.button(&ImString::new(&action.label)) {
+                        let apply_recipes = |save_game: &mut SaveGame| {
+                            for recipe in &recipes {
+                                apply_recipe(save_game, recipe);
+                            }
+                        };
+                        if self.confirmations.apply_preset {
+                            *change_preview = Some(ChangePreview::new(
+                                action.label.clone(),
+                                save_game,
+                                apply_recipes,
+                            ));
+                        } else {
+                            apply_recipes(save_game);
+                            applied = Some(action.label.clone());
+                        }
+                    }
+                }
+                Err(_) => ui.text_disabled(&action.label),
+            }
+            if ui.is_item_hovered() {
+                let _t = ui.begin_tooltip();
+                if let Err(err) = &check {
+                    ui.text(err.to_string());
+                }
+                for recipe in &recipes {
+                    ui.text(format!("- {}", recipe.name));
+                }
+            }
+        }
+
+        if !is_first {
+            ui.separator();
+        }
+        applied
+    }
+
+    pub fn draw_quick_actions(
+        &self, opened: &mut bool, settings: &mut Settings, recipe_db: Option<&RecipeDb>,
+    ) {
+        let ui = self.ui;
+
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Quick actions"))
+            .size([500.0, 300.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let recipe_db = match recipe_db {
+                Some(recipe_db) => recipe_db,
+                None => {
+                    ui.text_disabled(im_str!("The presets database is not loaded"));
+                    return;
+                }
+            };
+
+            ui.text_disabled(im_str!(
+                "Each action is a button above the editor that applies its presets in one click.\nOnly the presets for the opened game are applied."
+            ));
+
+            let mut changed = false;
+            let mut removed = None;
+            for (i, action) in settings.quick_actions.iter_mut().enumerate() {
+                let _t = ui.push_id(i as i32);
+                ui.separator();
+
+                let mut label = ImString::new(&action.label);
+                ui.set_next_item_width(200.0);
+                if ui.input_text(im_str!("Label"), &mut label).resize_buffer(true).build() {
+                    action.label = label.to_string();
+                    changed = true;
+                }
+
+                ui.same_line();
+                let preview = match action.recipes.len() {
+                    0 => ImString::new("No preset"),
+                    1 => ImString::new(&action.recipes[0]),
+                    len => ImString::new(format!("{} presets", len)),
+                };
+                ui.set_next_item_width(200.0);
+                if let Some(_t) =
+                    ComboBox::new(im_str!("Presets")).preview_value(&preview).begin(ui)
+                {
+                    for recipe in recipe_db.iter() {
+                        let position = action.recipes.iter().position(|name| *name == recipe.name);
+                        let mut pinned = position.is_some();
+                        if ui.checkbox(&ImString::new(&recipe.name), &mut pinned) {
+                            match position {
+                                Some(position) => {
+                                    action.recipes.remove(position);
+                                }
+                                None => action.recipes.push(recipe.name.clone()),
+                            }
+                            changed = true;
+                        }
+                    }
+                }
+
+                ui.same_line();
+                if ui.button(im_str!("Remove")) {
+                    removed = Some(i);
+                }
+            }
+
+            if let Some(i) = removed {
+                settings.quick_actions.remove(i);
+                changed = true;
+            }
+
+            ui.separator();
+            if ui.button(im_str!("Add action")) {
+                let label = format!("Action {}", settings.quick_actions.len() + 1);
+                settings.quick_actions.push(QuickAction { label, recipes: Vec::new() });
+                changed = true;
+            }
+
+            if changed {
+                let _ = self.event_addr.send(MainEvent::SaveSettings(settings.clone()));
+            }
+        }
+    }
+}
+
+// Les presets d'une action qui existent et concernent ce jeu
+fn action_recipes<'a>(
+    action: &QuickAction, recipe_db: &'a RecipeDb, game: Game,
+) -> Vec<&'a Recipe> {
+    action
+        .recipes
+        .iter()
+        .filter_map(|name| recipe_db.iter().find(|recipe| recipe.name == *name))
+        .filter(|recipe| recipe.is_applicable(game))
+        .collect()
+}
+
+pub fn game_of(save_game: &SaveGame) -> Game {
+    match save_game {
+        SaveGame::MassEffect1 { .. } => Game::MassEffect1,
+        SaveGame::MassEffect1Leg { .. } => Game::MassEffect1Leg,
+        SaveGame::MassEffect2 { .. } => Game::MassEffect2,
+        SaveGame::MassEffect2Leg { .. } => Game::MassEffect2Leg,
+        SaveGame::MassEffect3 { .. } => Game::MassEffect3,
+    }
+}
+
+// Vérifie que la classe peut porter les armes du preset
+pub fn check_recipe(save_game: &SaveGame, recipe: &Recipe) -> Result<()> {
+    let loadout = match &recipe.loadout {
+        Some(loadout) => loadout,
+        None => return Ok(()),
+    };
+
+    let class_name = match save_game {
+        SaveGame::MassEffect2 { save_game, .. } => save_game.player.class_name.to_str(),
+        SaveGame::MassEffect2Leg { save_game, .. } => save_game.player.class_name.to_str(),
+        SaveGame::MassEffect3 { save_game, .. } => save_game.player.class_name.to_str(),
+        SaveGame::MassEffect1 { .. } | SaveGame::MassEffect1Leg { .. } => return Ok(()),
+    };
+    loadout.check_class(game_of(save_game), class_name)
+}
+
+pub fn apply_recipe(save_game: &mut SaveGame, recipe: &Recipe) {
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => recipe.apply_plot(&mut save_game.state.plot),
+        SaveGame::MassEffect1Leg { save_game, .. } => {
+            let save_data = &mut save_game.save_data;
+            recipe.apply_plot(&mut save_data.plot);
+            recipe.grant_items(&mut save_data.player.inventory.inventory);
+        }
+        SaveGame::MassEffect2 { save_game, .. } => {
+            recipe.apply_plot(&mut save_game.plot);
+            if let Some(loadout) = &recipe.loadout {
+                let player = &mut save_game.player;
+                loadout.apply(&mut player.weapons_loadout, &mut player.weapons);
+            }
+        }
+        SaveGame::MassEffect2Leg { save_game, .. } => {
+            recipe.apply_plot(&mut save_game.plot);
+            if let Some(loadout) = &recipe.loadout {
+                let player = &mut save_game.player;
+                loadout.apply(&mut player.weapons_loadout, &mut player.weapons);
+            }
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            recipe.apply_plot(&mut save_game.plot);
+            if let Some(loadout) = &recipe.loadout {
+                let player = &mut save_game.player;
+                loadout.apply(&mut player.weapons_loadout, &mut player.weapons);
+                equip_me3_weapons(player, loadout.weapons());
+            }
+        }
+    }
+}
+
+// Les 2 premières armes du preset deviennent les armes équipées
+fn equip_me3_weapons<'a>(
+    player: &mut Me3Player, weapons: impl Iterator<Item = (WeaponSlot, &'a str)>,
+) {
+    let equipped: Vec<_> =
+        weapons.filter(|(slot, _)| *slot != WeaponSlot::HeavyWeapon).take(2).collect();
+
+    if let Some((_, weapon)) = equipped.first() {
+        player.primary_weapon = ImString::new(*weapon).into();
+    }
+    if let Some((_, weapon)) = equipped.get(1) {
+        player.secondary_weapon = ImString::new(*weapon).into();
+    }
+    if !equipped.is_empty() {
+        player.loadout_weapon_group = equipped.iter().map(|(slot, _)| *slot as i32).collect();
+    }
+}
@@ -0,0 +1,114 @@
+use imgui::{im_str, Condition, ImString, Window};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::mass_effect_1::{property_copy, Me1SaveGame},
+};
+
+use super::Gui;
+
+const PRESETS: [(&str, &str); 4] = [
+    ("Inventory", "m_Player.m_Squad.m_Inventory.m_aItem"),
+    ("Credits", "m_Player.m_Squad.m_Inventory.m_nResourceCredits"),
+    ("Grenades", "m_Player.m_Squad.m_Inventory.m_nResourceGrenades"),
+    ("Medi-gel", "m_Player.m_Squad.m_Inventory.m_fResourceMedigel"),
+];
+
+pub struct PropertyCopyState {
+    pub opened: bool,
+    source: Option<(String, Box<Me1SaveGame>)>,
+    path: ImString,
+    result: Option<Result<String, String>>,
+}
+
+impl Default for PropertyCopyState {
+    fn default() -> Self {
+        PropertyCopyState {
+            opened: false,
+            source: None,
+            path: ImString::new(PRESETS[0].1),
+            result: None,
+        }
+    }
+}
+
+impl PropertyCopyState {
+    pub fn set_source(&mut self, path: String, save_game: Box<Me1SaveGame>) {
+        self.source = Some((path, save_game));
+        self.result = None;
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_property_copy(
+        &self, state: &mut PropertyCopyState, save_game: &mut Option<SaveGame>,
+    ) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Copy ME1 property"))
+            .size([500.0, 220.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            if ui.button(im_str!("Source save")) {
+                let file = tinyfiledialogs::open_file_dialog(
+                    "",
+                    &Self::get_document_dir().to_string_lossy(),
+                    Some((&["*.MassEffectSave"], "Mass Effect 1 Save (*.MassEffectSave)")),
+                );
+                if let Some(path) = file {
+                    let _ = self.event_addr.send(MainEvent::OpenMe1CopySource(path));
+                }
+            }
+            ui.same_line();
+            match &state.source {
+                Some((path, _)) => ui.text(path),
+                None => ui.text_disabled(im_str!("No source save")),
+            }
+
+            ui.input_text(im_str!("Property"), &mut state.path).resize_buffer(true).build();
+            ui.same_line();
+            self.draw_help_marker(
+                "Path from the `CurrentGame` object, following object properties.\nNames and referenced objects are remapped to the opened save.",
+            );
+            for (i, (label, path)) in PRESETS.iter().enumerate() {
+                if i > 0 {
+                    ui.same_line();
+                }
+                if ui.small_button(&ImString::new(*label)) {
+                    state.path = ImString::new(*path);
+                }
+            }
+            ui.separator();
+
+            let target = match save_game {
+                Some(SaveGame::MassEffect1 { save_game, .. }) => Some(save_game),
+                _ => None,
+            };
+            match (&state.source, target) {
+                (Some((_, source)), Some(target)) => {
+                    if ui.button(im_str!("Copy to opened save")) {
+                        let path = state.path.to_str();
+                        state.result = Some(
+                            property_copy::copy_property(&source.player, &mut target.player, path)
+                                .map(|_| format!("Copied `{}`", path))
+                                .map_err(|err| format!("{:#}", err)),
+                        );
+                    }
+                }
+                (_, None) => ui.text_disabled(im_str!("Open a Mass Effect 1 save first")),
+                (None, _) => ui.text_disabled(im_str!("Choose a source save")),
+            }
+
+            match &state.result {
+                Some(Ok(message)) => ui.text(message),
+                Some(Err(err)) => ui.text_colored([0.9, 0.4, 0.3, 1.0], err),
+                None => (),
+            }
+        }
+    }
+}
@@ -1,22 +1,35 @@
 use if_chain::if_chain;
 use imgui::{
     im_str, ColorEdit, ComboBox, Condition, ImStr, ImString, InputFloat, InputInt, ListClipper,
-    TreeNode,
+    MenuItem, Selectable, StyleVar, TreeNode,
 };
 use indexmap::IndexMap;
-use std::{any::Any, fmt::Display, hash::Hash};
+use std::{any::Any, cmp::Ordering, fmt::Display, hash::Hash, ops::Range};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::{
+        shared::{
+            plot::{BoolSlice, PlotCategory, PlotEntry, PlotOptions},
+            Guid,
+        },
+        OriginalValues, RawUi,
+    },
+};
 
-use crate::save_data::{
-    shared::{plot::BoolSlice, Guid},
-    RawUi,
+use super::{
+    confirmation::Confirmation, imgui_utils::PopupToken, settings::Widgets, watch::Watch, Gui,
 };
 
-use super::Gui;
+// Tri de la vue tableau : (colonne, croissant)
+pub type TableSort = Option<(usize, bool)>;
 
 impl<'ui> Gui<'ui> {
     // Edit boxes
     pub fn draw_edit_string(&self, ident: &str, value: &mut ImString) {
         self.ui.input_text(&ImString::new(ident), value).resize_buffer(true).build();
+        self.draw_revert_context_menu();
+        self.draw_original_value(value);
     }
 
     pub fn draw_edit_bool(&self, ident: &str, value: &mut bool) {
@@ -25,22 +38,72 @@ impl<'ui> Gui<'ui> {
         let width = ui.push_item_width(120.0);
         ui.checkbox(&ImString::new(ident), value);
         width.pop(ui);
+        self.draw_revert_context_menu();
+        self.draw_original_value(value);
     }
 
     pub fn draw_edit_i32(&self, ident: &str, value: &mut i32) {
         let ui = self.ui;
+        let is_hex = self.is_hex_field();
 
         let width = ui.push_item_width(120.0);
-        InputInt::new(ui, &ImString::new(ident), value).build();
+        if is_hex {
+            let mut text = ImString::new(format!("0x{:08X}", value));
+            if ui.input_text(&ImString::new(ident), &mut text).resize_buffer(true).build() {
+                if let Some(parsed) = parse_int(text.to_str()) {
+                    *value = parsed;
+                }
+            }
+        } else {
+            InputInt::new(ui, &ImString::new(ident), value).build();
+        }
         width.pop(ui);
+        self.draw_tlk_picker(ident, value);
+
+        if let Some(_t) = self.begin_raw_context_menu() {
+            if self.shows(Widgets::Developer)
+                && MenuItem::new(im_str!("Hexadecimal")).selected(is_hex).build(ui)
+            {
+                self.toggle_hex_field();
+            }
+        }
+        self.draw_original_value(value);
     }
 
     pub fn draw_edit_f32(&self, ident: &str, value: &mut f32) {
         let ui = self.ui;
 
         let width = ui.push_item_width(120.0);
-        InputFloat::new(ui, &ImString::new(ident), value).build();
+        if InputFloat::new(ui, &ImString::new(ident), value).build() {
+            *value = self.float_rounding.round(*value);
+        }
         width.pop(ui);
+        self.draw_revert_context_menu();
+        self.draw_original_value(value);
+    }
+
+    // Grisé, les modifications sont faites sur une copie
+    pub fn draw_read_only(&self, draw: impl FnOnce()) {
+        let ui = self.ui;
+
+        let _t = ui.push_style_var(StyleVar::Alpha(0.5));
+        draw();
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Read only");
+        }
+    }
+
+    // Octets inconnus, affichés tels quels
+    pub fn draw_hex_bytes(&self, ident: &str, bytes: &[u8]) {
+        let ui = self.ui;
+
+        let hex: Vec<_> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        self.draw_read_only(|| {
+            ui.align_text_to_frame_padding();
+            ui.text(hex.join(" "));
+        });
+        ui.same_line();
+        ui.text(ident);
     }
 
     pub fn draw_edit_enum(&self, ident: &str, current_item: &mut usize, items: &[&ImStr]) -> bool {
@@ -50,15 +113,102 @@ impl<'ui> Gui<'ui> {
         let edited =
             ComboBox::new(&ImString::new(ident)).build_simple_string(ui, current_item, items);
         width.pop(ui);
+        self.draw_revert_context_menu();
+        if let Some(item) = items.get(*current_item) {
+            self.draw_original_value(item.to_str());
+        }
         edited
     }
 
+    // Les valeurs hors liste restent sélectionnées telles quelles
+    pub fn draw_edit_plot_options(&self, ident: &str, value: &mut i32, options: &PlotOptions) {
+        let ui = self.ui;
+
+        let preview = match options.options.iter().find(|(option, _)| option == value) {
+            Some((_, name)) => ImString::new(*name),
+            None => ImString::new(format!("Unknown ({})", value)),
+        };
+
+        let width = ui.push_item_width(200.0);
+        let label = ImString::new(format!("{}##{}", options.label, ident));
+        if let Some(_t) = ComboBox::new(&label).preview_value(&preview).begin(ui) {
+            for (option, name) in &options.options {
+                let text = ImString::new(format!("{} ({})", name, option));
+                if Selectable::new(&text).selected(option == value).build(ui) {
+                    *value = *option;
+                }
+            }
+        }
+        width.pop(ui);
+        self.draw_revert_context_menu();
+    }
+
+    // Description, acte et lien d'une entrée du plot DB
+    pub fn draw_plot_entry_details(&self, ident: &str, entry: &PlotEntry) {
+        let ui = self.ui;
+
+        if !entry.has_details() {
+            return;
+        }
+
+        ui.same_line();
+        ui.text_disabled(im_str!("(?)"));
+        if ui.is_item_hovered() {
+            let _t = ui.begin_tooltip();
+            if let Some(act) = &entry.act {
+                ui.text_disabled(act);
+            }
+            if let Some(description) = &entry.description {
+                ui.text(description);
+            }
+            if let Some(link) = &entry.link {
+                ui.text(link);
+            }
+        }
+
+        if let Some(link) = &entry.link {
+            ui.same_line();
+            if ui.small_button(&im_str!("Wiki##{}", ident)) {
+                ui.set_clipboard_text(&ImString::new(link));
+            }
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy the link to the clipboard");
+            }
+        }
+    }
+
+    // Indice à côté d'un booléen dont les prérequis ne sont pas remplis
+    pub fn draw_plot_requirements(
+        &self, plot_db: &PlotCategory, plot_id: usize, booleans: &mut BoolSlice,
+    ) {
+        let ui = self.ui;
+
+        let missing = plot_db.missing_requirements(plot_id, booleans);
+        if missing.is_empty() {
+            return;
+        }
+
+        let labels: Vec<_> = missing.iter().map(|&id| plot_db.label(id)).collect();
+        ui.same_line();
+        ui.text_colored([0.9, 0.8, 0.3, 1.0], format!("requires {}", labels.join(", ")));
+        ui.same_line();
+        if ui.small_button(&im_str!("Set##requires-{}", plot_id)) {
+            for id in missing {
+                booleans.set(id, true);
+            }
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Set the prerequisites of this flag");
+        }
+    }
+
     pub fn draw_edit_color(&self, ident: &str, color: &mut [f32; 4]) {
         let ui = self.ui;
 
         let width = ui.push_item_width(200.0);
         ColorEdit::new(&ImString::new(ident), color).build(ui);
         width.pop(ui);
+        self.draw_revert_context_menu();
     }
 
     pub fn draw_edit_guid(&self, ident: &str, guid: &mut Guid) {
@@ -110,15 +260,47 @@ impl<'ui> Gui<'ui> {
                 .build();
             width.pop(ui);
         }
+        self.draw_revert_context_menu();
+    }
+
+    pub fn draw_edit_bitfield(&self, ident: &str, value: &mut i32, bits: &IndexMap<u8, String>) {
+        let ui = self.ui;
+
+        let tree_node = self.push_tree_node(ident);
+        ui.same_line();
+        ui.text_disabled(format!("0x{:08X}", value));
+
+        if let Some(_t) = tree_node {
+            if let Some(_t) = self.begin_table(&ImString::new(ident), 1) {
+                for (&bit, name) in bits.iter().filter(|(&bit, _)| bit < 32) {
+                    let mask = 1 << bit;
+                    let mut is_set = *value & mask != 0;
+
+                    self.table_next_row();
+                    if ui.checkbox(&im_str!("{}##bit-{}", name, bit), &mut is_set) {
+                        if is_set {
+                            *value |= mask;
+                        } else {
+                            *value &= !mask;
+                        }
+                    }
+                }
+            }
+        }
     }
 
     // View widgets
     pub fn draw_struct(&self, ident: &str, fields: &mut [(&mut dyn RawUi, &str)]) {
-        if let Some(_t) = self.push_tree_node(ident) {
+        let tree_node = self.push_tree_node(ident);
+        self.draw_revert_context_menu();
+
+        if let Some(_t) = tree_node {
             if let Some(_t) = self.begin_table(&ImString::new(ident), 1) {
                 for (field, ident) in fields {
                     self.table_next_row();
+                    self.push_raw_path(ident);
                     field.draw_raw_ui(self, ident);
+                    self.pop_raw_path();
                 }
             }
         }
@@ -127,7 +309,9 @@ impl<'ui> Gui<'ui> {
     pub fn draw_boolvec(&self, ident: &str, list: &mut BoolSlice) {
         let ui = self.ui;
         // Tree node
-        let _t = match self.push_tree_node(ident) {
+        let tree_node = self.push_tree_node(ident);
+        self.draw_revert_context_menu();
+        let _t = match tree_node {
             Some(t) => t,
             None => return,
         };
@@ -139,11 +323,23 @@ impl<'ui> Gui<'ui> {
         };
 
         if !list.is_empty() {
+            let selection = format!("{}@{:p}", ident, list);
+            if let Some((value, rows)) = self.draw_bool_selection_actions(&selection) {
+                for i in rows {
+                    list.set(i, value);
+                }
+            }
+
             let mut clipper = ListClipper::new(list.len() as i32).begin(ui);
             while clipper.step() {
                 for i in clipper.display_start()..clipper.display_end() {
                     self.table_next_row();
-                    list.get_mut(i as usize).unwrap().draw_raw_ui(self, &i.to_string());
+                    self.push_raw_path(&i.to_string());
+                    let mut value = list.get_mut(i as usize).unwrap();
+                    self.draw_selectable_row(&selection, i as usize, &mut *value, |value| {
+                        value.draw_raw_ui(self, &i.to_string())
+                    });
+                    self.pop_raw_path();
                 }
             }
         } else {
@@ -152,6 +348,18 @@ impl<'ui> Gui<'ui> {
         }
     }
 
+    // Seules les lignes visibles sont dessinées, si elles ont toutes la même hauteur
+    fn draw_rows(&self, len: usize, fixed_height: bool, mut draw: impl FnMut(Range<usize>)) {
+        if fixed_height {
+            let mut clipper = ListClipper::new(len as i32).begin(self.ui);
+            while clipper.step() {
+                draw(clipper.display_start() as usize..clipper.display_end() as usize);
+            }
+        } else {
+            draw(0..len);
+        }
+    }
+
     pub fn draw_vec<T>(&self, ident: &str, list: &mut Vec<T>)
     where
         T: RawUi + Default,
@@ -159,11 +367,24 @@ impl<'ui> Gui<'ui> {
         let ui = self.ui;
 
         // Tree node
-        let _t = match self.push_tree_node(ident) {
+        let tree_node = self.push_tree_node(ident);
+        let table_view = self.table_view();
+        if let Some(_t) = self.begin_raw_context_menu() {
+            if T::table_columns().is_some()
+                && MenuItem::new(im_str!("Table view")).selected(table_view.is_some()).build(ui)
+            {
+                self.toggle_table_view();
+            }
+        }
+        let _t = match tree_node {
             Some(t) => t,
             None => return,
         };
 
+        if let (Some(columns), Some(sort)) = (T::table_columns(), table_view) {
+            return self.draw_vec_table(ident, list, columns, sort);
+        }
+
         // Table
         let _t = match self.begin_table(&ImString::new(ident), 1) {
             Some(t) => t,
@@ -173,18 +394,25 @@ impl<'ui> Gui<'ui> {
         if !list.is_empty() {
             // Item
             let mut remove = None;
-            for (i, item) in list.iter_mut().enumerate() {
-                self.table_next_row();
-                ui.align_text_to_frame_padding();
-                if ui.small_button(&im_str!("remove##remove-{}", i)) {
-                    remove = Some(i);
+            self.draw_rows(list.len(), T::is_flat(), |rows| {
+                for (i, item) in list.iter_mut().enumerate().skip(rows.start).take(rows.len()) {
+                    self.table_next_row();
+                    ui.align_text_to_frame_padding();
+                    if ui.small_button(&im_str!("remove##remove-{}", i)) {
+                        remove = Some(i);
+                    }
+                    ui.same_line();
+                    self.push_raw_path(&i.to_string());
+                    item.draw_raw_ui(self, &i.to_string());
+                    self.pop_raw_path();
                 }
-                ui.same_line();
-                item.draw_raw_ui(self, &i.to_string());
-            }
+            });
 
             // Remove
-            if let Some(i) = remove {
+            let id = format!("{}@{:p}", ident, list);
+            if let Some(i) = self.confirm(Confirmation::RemoveItem, &id, remove, |i| {
+                format!("Remove item {} from {} ?", i, ident)
+            }) {
                 list.remove(i);
             }
         } else {
@@ -204,6 +432,90 @@ impl<'ui> Gui<'ui> {
         }
     }
 
+    // Les lignes sont triées à l'affichage seulement, l'ordre de la save ne change pas
+    fn draw_vec_table<T>(&self, ident: &str, list: &mut Vec<T>, columns: &[&str], sort: TableSort)
+    where
+        T: RawUi + Default,
+    {
+        let ui = self.ui;
+
+        let _t = match self.begin_grid(&ImString::new(ident), columns.len() as i32 + 1) {
+            Some(t) => t,
+            None => return,
+        };
+
+        // En-têtes, un clic trie par la colonne
+        self.table_next_row();
+        for (column, name) in columns.iter().enumerate() {
+            self.table_next_column();
+            let arrow = match sort {
+                Some((sorted, true)) if sorted == column => " ^",
+                Some((sorted, false)) if sorted == column => " v",
+                _ => "",
+            };
+            let label = ImString::new(format!("{}{}##sort-{}", name, arrow, column));
+            if Selectable::new(&label).build(ui) {
+                let sort = match sort {
+                    Some((sorted, ascending)) if sorted == column => Some((column, !ascending)),
+                    _ => Some((column, true)),
+                };
+                self.set_table_sort(sort);
+            }
+        }
+
+        let mut order: Vec<usize> = (0..list.len()).collect();
+        if let Some((column, ascending)) = sort {
+            let values: Vec<_> = list.iter().map(|item| item.table_cell_value(column)).collect();
+            order.sort_by(|&a, &b| {
+                let ordering = compare_cells(values[a].as_deref(), values[b].as_deref());
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        let mut remove = None;
+        let mut clipper = ListClipper::new(order.len() as i32).begin(ui);
+        while clipper.step() {
+            for row in clipper.display_start()..clipper.display_end() {
+                let i = order[row as usize];
+                let _t = ui.push_id(i as i32);
+                self.table_next_row();
+                ui.align_text_to_frame_padding();
+                if ui.small_button(im_str!("remove")) {
+                    remove = Some(i);
+                }
+                ui.same_line();
+                ui.text(i.to_string());
+
+                self.push_raw_path(&i.to_string());
+                for (column, name) in columns.iter().enumerate() {
+                    self.table_next_column();
+                    self.push_raw_path(name);
+                    list[i].draw_table_cell(self, &format!("##{}", name), column);
+                    self.pop_raw_path();
+                }
+                self.pop_raw_path();
+            }
+        }
+
+        // Remove
+        let id = format!("{}@{:p}", ident, list);
+        if let Some(i) = self.confirm(Confirmation::RemoveItem, &id, remove, |i| {
+            format!("Remove item {} from {} ?", i, ident)
+        }) {
+            list.remove(i);
+        }
+
+        // Add
+        self.table_next_row();
+        if ui.button(im_str!("add")) {
+            list.push(T::default());
+        }
+    }
+
     pub fn draw_indexmap<K, V>(&self, ident: &str, list: &mut IndexMap<K, V>)
     where
         K: RawUi + Eq + Hash + Default + Display + 'static,
@@ -212,7 +524,9 @@ impl<'ui> Gui<'ui> {
         let ui = self.ui;
 
         // Tree node
-        let _t = match self.push_tree_node(ident) {
+        let tree_node = self.push_tree_node(ident);
+        self.draw_revert_context_menu();
+        let _t = match tree_node {
             Some(t) => t,
             None => return,
         };
@@ -224,7 +538,7 @@ impl<'ui> Gui<'ui> {
         };
 
         if !list.is_empty() {
-            // Item
+            // Item, des tree nodes de hauteur variable : pas de virtualisation
             let mut remove = None;
             for i in 0..list.len() {
                 self.table_next_row();
@@ -234,21 +548,32 @@ impl<'ui> Gui<'ui> {
                 }
                 ui.same_line();
 
-                if_chain! {
-                    if let Some((key, value)) = list.get_index_mut(i);
-                    if let Some(_t) = self.push_tree_node(&format!("{}##{}", key, i));
-                    if let Some(_t) = self.begin_table(&im_str!("table-{}", i), 1);
-                    then {
-                        self.table_next_row();
-                        key.draw_raw_ui(self, "id##key");
-                        self.table_next_row();
-                        value.draw_raw_ui(self, "value##value");
+                self.push_raw_path(&i.to_string());
+                if let Some((key, value)) = list.get_index_mut(i) {
+                    let tree_node = self.push_tree_node(&format!("{}##{}", key, i));
+                    self.draw_revert_context_menu();
+
+                    if_chain! {
+                        if let Some(_t) = tree_node;
+                        if let Some(_t) = self.begin_table(&im_str!("table-{}", i), 1);
+                        then {
+                            self.table_next_row();
+                            self.hide_original_value.set(true);
+                            key.draw_raw_ui(self, "id##key");
+                            self.hide_original_value.set(false);
+                            self.table_next_row();
+                            value.draw_raw_ui(self, "value##value");
+                        }
                     }
                 }
+                self.pop_raw_path();
             }
 
             // Remove
-            if let Some(i) = remove {
+            let id = format!("{}@{:p}", ident, list);
+            if let Some(i) = self.confirm(Confirmation::RemoveItem, &id, remove, |i| {
+                format!("Remove item {} from {} ?", i, ident)
+            }) {
                 list.shift_remove_index(i);
             }
         } else {
@@ -276,3 +601,187 @@ impl<'ui> Gui<'ui> {
         }
     }
 }
+
+// Revert
+impl<'ui> Gui<'ui> {
+    pub fn draw_revertable_raw_ui(&self, value: &mut dyn RawUi, ident: &str) {
+        self.draw_help("raw");
+        self.raw_ui_shown.set(true);
+        *self.raw_path.borrow_mut() = Some(Vec::new());
+        value.draw_raw_ui(self, ident);
+        *self.raw_path.borrow_mut() = None;
+    }
+
+    // Valeur d'origine grisée à côté d'un champ modifié
+    fn draw_original_value(&self, current: impl Display) {
+        let values = match &self.original_values {
+            Some(values) if !self.hide_original_value.get() => values,
+            _ => return,
+        };
+        let raw_path = self.raw_path.borrow();
+        if let Some(original) = raw_path.as_ref().and_then(|path| values.get(path)) {
+            if *original != current.to_string() {
+                let ui = self.ui;
+                ui.same_line();
+                ui.text_disabled(format!("(was {})", original));
+            }
+        }
+    }
+
+    fn push_raw_path(&self, ident: &str) {
+        if let Some(path) = self.raw_path.borrow_mut().as_mut() {
+            path.push(ident.to_owned());
+        }
+    }
+
+    fn pop_raw_path(&self) {
+        if let Some(path) = self.raw_path.borrow_mut().as_mut() {
+            path.pop();
+        }
+    }
+
+    fn draw_revert_context_menu(&self) {
+        let _t = self.begin_raw_context_menu();
+    }
+
+    fn begin_raw_context_menu(&self) -> Option<PopupToken> {
+        let ui = self.ui;
+        let raw_path = self.raw_path.borrow();
+        let path = raw_path.as_ref()?;
+
+        let token = self.begin_popup_context_item()?;
+        if MenuItem::new(im_str!("Revert to original")).build(ui) {
+            *self.revert_request.borrow_mut() = Some(path.clone());
+        }
+        if MenuItem::new(im_str!("Watch")).build(ui) {
+            self.watch.borrow_mut().add(Watch::Raw(path.clone()));
+        }
+        Some(token)
+    }
+}
+
+// Hexadécimal
+impl<'ui> Gui<'ui> {
+    // Réglage global inversé par champ, seulement dans les onglets raw du profil développeur
+    fn is_hex_field(&self) -> bool {
+        if !self.shows(Widgets::Developer) {
+            return false;
+        }
+        match self.raw_path.borrow().as_ref() {
+            Some(path) => self.hex_integers != self.hex_fields.borrow().contains(path),
+            None => false,
+        }
+    }
+
+    fn toggle_hex_field(&self) {
+        if let Some(path) = self.raw_path.borrow().as_ref() {
+            let mut hex_fields = self.hex_fields.borrow_mut();
+            if !hex_fields.remove(path) {
+                hex_fields.insert(path.clone());
+            }
+        }
+    }
+}
+
+// Vue tableau
+impl<'ui> Gui<'ui> {
+    fn table_view(&self) -> Option<TableSort> {
+        let raw_path = self.raw_path.borrow();
+        self.table_views.borrow().get(raw_path.as_ref()?).copied()
+    }
+
+    fn toggle_table_view(&self) {
+        if let Some(path) = self.raw_path.borrow().as_ref() {
+            let mut table_views = self.table_views.borrow_mut();
+            if table_views.remove(path).is_none() {
+                table_views.insert(path.clone(), None);
+            }
+        }
+    }
+
+    fn set_table_sort(&self, sort: TableSort) {
+        if let Some(path) = self.raw_path.borrow().as_ref() {
+            self.table_views.borrow_mut().insert(path.clone(), sort);
+        }
+    }
+}
+
+// Les nombres sont comparés en tant que nombres, les cellules sans valeur en dernier
+fn compare_cells(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => unicase::UniCase::new(a).cmp(&unicase::UniCase::new(b)),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn parse_int(text: &str) -> Option<i32> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok().map(|value| value as i32),
+        None => text.parse().ok(),
+    }
+}
+
+pub fn revert_save_game(save_game: &mut SaveGame, original: &SaveGame, path: &[String]) {
+    match (save_game, original) {
+        (
+            SaveGame::MassEffect1 { save_game, .. },
+            SaveGame::MassEffect1 { save_game: original, .. },
+        ) => save_game.state.revert(&original.state, path),
+        (
+            SaveGame::MassEffect1Leg { save_game, .. },
+            SaveGame::MassEffect1Leg { save_game: original, .. },
+        ) => save_game.save_data.revert(&original.save_data, path),
+        (
+            SaveGame::MassEffect2 { save_game, .. },
+            SaveGame::MassEffect2 { save_game: original, .. },
+        ) => save_game.as_mut().revert(original, path),
+        (
+            SaveGame::MassEffect2Leg { save_game, .. },
+            SaveGame::MassEffect2Leg { save_game: original, .. },
+        ) => save_game.as_mut().revert(original, path),
+        (
+            SaveGame::MassEffect3 { save_game, .. },
+            SaveGame::MassEffect3 { save_game: original, .. },
+        ) => save_game.as_mut().revert(original, path),
+        _ => (),
+    }
+}
+
+pub fn original_values(save_game: &SaveGame) -> OriginalValues {
+    let mut values = OriginalValues::new();
+    let mut path = Vec::new();
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            save_game.state.collect_values(&mut path, &mut values)
+        }
+        SaveGame::MassEffect1Leg { save_game, .. } => {
+            save_game.save_data.collect_values(&mut path, &mut values)
+        }
+        SaveGame::MassEffect2 { save_game, .. } => save_game.collect_values(&mut path, &mut values),
+        SaveGame::MassEffect2Leg { save_game, .. } => {
+            save_game.collect_values(&mut path, &mut values)
+        }
+        SaveGame::MassEffect3 { save_game, .. } => save_game.collect_values(&mut path, &mut values),
+    }
+    values
+}
+
+pub fn draw_watch_save_game(gui: &Gui, save_game: &mut SaveGame, ident: &str, path: &[String]) {
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => save_game.state.draw_watch(gui, ident, path),
+        SaveGame::MassEffect1Leg { save_game, .. } => {
+            save_game.save_data.draw_watch(gui, ident, path)
+        }
+        SaveGame::MassEffect2 { save_game, .. } => save_game.as_mut().draw_watch(gui, ident, path),
+        SaveGame::MassEffect2Leg { save_game, .. } => {
+            save_game.as_mut().draw_watch(gui, ident, path)
+        }
+        SaveGame::MassEffect3 { save_game, .. } => save_game.as_mut().draw_watch(gui, ident, path),
+    }
+}
@@ -0,0 +1,86 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::shared::{
+        plot::BoolVec,
+        recipe::Game,
+        romance::{RomanceDb, RomanceFix},
+    },
+};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    // Réévalué à chaque frame comme les problèmes connus de ME3
+    pub fn draw_romance_checker(
+        &self, opened: &mut bool, save_game: &mut Option<SaveGame>, romance_db: Option<&RomanceDb>,
+    ) {
+        let ui = self.ui;
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Romance checker"))
+            .size([450.0, 300.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let (game, booleans, is_female): (_, &mut BoolVec, _) = match save_game {
+                Some(SaveGame::MassEffect1Leg { save_game, .. }) => (
+                    Game::MassEffect1Leg,
+                    &mut save_game.save_data.plot.bool_variables,
+                    save_game.save_data.player.is_female,
+                ),
+                Some(SaveGame::MassEffect2 { save_game, .. }) => (
+                    Game::MassEffect2,
+                    &mut save_game.plot.bool_variables,
+                    save_game.player.is_female,
+                ),
+                Some(SaveGame::MassEffect2Leg { save_game, .. }) => (
+                    Game::MassEffect2Leg,
+                    &mut save_game.plot.bool_variables,
+                    save_game.player.is_female,
+                ),
+                Some(SaveGame::MassEffect3 { save_game, .. }) => (
+                    Game::MassEffect3,
+                    &mut save_game.plot.bool_variables,
+                    save_game.player.is_female,
+                ),
+                Some(SaveGame::MassEffect1 { .. }) | None => {
+                    ui.text_disabled(im_str!("Open a Legendary Edition, ME2 or ME3 save"));
+                    return;
+                }
+            };
+            let romance_db = match romance_db {
+                Some(romance_db) => romance_db,
+                None => return,
+            };
+
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                let issues = romance_db.check(game, booleans, is_female);
+                if issues.is_empty() {
+                    ui.text_disabled(im_str!("Romances are consistent"));
+                }
+
+                let mut fix: Option<&RomanceFix> = None;
+                for (i, issue) in issues.iter().enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    ui.text_colored([0.9, 0.8, 0.3, 1.0], &issue.message);
+                    for issue_fix in &issue.fixes {
+                        if ui.button(&ImString::new(&issue_fix.label)) {
+                            fix = Some(issue_fix);
+                        }
+                        ui.same_line();
+                    }
+                    ui.new_line();
+                    ui.separator();
+                }
+
+                if let Some(fix) = fix {
+                    fix.apply(booleans);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use imgui::{im_str, ComboBox, ImString, PopupModal, Selectable};
+
+use crate::{event_handler::MainEvent, save_data::shared::save_format::SaveFormat};
+
+use super::Gui;
+
+// Format d'une save non reconnu ou ambigu, en attente du choix de l'utilisateur
+pub struct SaveFormatChoice {
+    pub file_path: String,
+    // Vide si aucun format n'a été reconnu
+    pub detected: Vec<SaveFormat>,
+    pub selected: SaveFormat,
+}
+
+impl SaveFormatChoice {
+    pub fn new(file_path: String, detected: Vec<SaveFormat>) -> Self {
+        let selected = detected.first().copied().unwrap_or(SaveFormat::ALL[0]);
+        SaveFormatChoice { file_path, detected, selected }
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_save_format_prompt(&self, choice: &mut Option<SaveFormatChoice>) {
+        let ui = self.ui;
+
+        let SaveFormatChoice { file_path, detected, selected } = match choice {
+            Some(choice) => choice,
+            None => return,
+        };
+
+        ui.open_popup(im_str!("Save format###save_format"));
+        if let Some(_t) = PopupModal::new(im_str!("Save format###save_format"))
+            .always_auto_resize(true)
+            .begin_popup(ui)
+        {
+            ui.text(&*file_path);
+            if detected.is_empty() {
+                ui.text("The format of this file could not be detected from its content.");
+            } else {
+                ui.text("This file matches several formats.");
+            }
+            ui.text("Pick the game it comes from, it will be opened as such.");
+            ui.separator();
+
+            let preview = ImString::new(selected.to_string());
+            let width = ui.push_item_width(250.0);
+            if let Some(_t) = ComboBox::new(im_str!("Format")).preview_value(&preview).begin(ui) {
+                // Les formats reconnus d'abord
+                let others = SaveFormat::ALL.iter().filter(|format| !detected.contains(format));
+                for format in detected.iter().chain(others) {
+                    let mut label = format.to_string();
+                    if detected.contains(format) {
+                        label.push_str(" (detected)");
+                    }
+                    if Selectable::new(&ImString::new(label)).selected(format == selected).build(ui)
+                    {
+                        *selected = *format;
+                    }
+                }
+            }
+            width.pop(ui);
+            ui.separator();
+
+            let mut close = false;
+            if ui.button(im_str!("Open")) {
+                let _ = self.event_addr.send(MainEvent::OpenSaveAs(file_path.clone(), *selected));
+                close = true;
+            }
+            ui.same_line();
+            if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                close = true;
+            }
+
+            if close {
+                *choice = None;
+                ui.close_current_popup();
+            }
+        }
+    }
+}
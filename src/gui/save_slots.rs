@@ -0,0 +1,282 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, InputInt, PopupModal, Window};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    event_handler::MainEvent,
+    save_data::shared::{
+        launcher_config::LauncherConfig,
+        save_slot::{SaveSlot, SlotKind, SlotNaming},
+    },
+};
+
+use super::Gui;
+
+#[derive(Default)]
+pub struct SaveSlotsState {
+    pub opened: bool,
+    pub dir: Option<PathBuf>,
+    pub slots: Vec<SaveSlot>,
+    rename: Option<(usize, i32)>,
+    delete: Option<usize>,
+    error: Option<String>,
+    pub launcher_config: Option<(PathBuf, LauncherConfig)>,
+}
+
+impl SaveSlotsState {
+    pub fn open(&mut self, save_path: Option<&str>, event_addr: &flume::Sender<MainEvent>) {
+        self.opened = true;
+        if let Some(dir) = save_path.and_then(|path| Path::new(path).parent()) {
+            self.dir = Some(dir.to_owned());
+            let _ = event_addr.send(MainEvent::ListSaveSlots(dir.to_owned()));
+        }
+    }
+
+    pub fn set_slots(&mut self, dir: PathBuf, slots: Vec<SaveSlot>) {
+        // Les index en cours d'édition ne sont plus valides
+        if self.dir.as_ref() == Some(&dir) {
+            self.slots = slots;
+            self.rename = None;
+            self.delete = None;
+            self.error = None;
+        }
+    }
+
+    fn naming(&self, slot: &SaveSlot) -> SlotNaming {
+        let career_name = self
+            .dir
+            .as_ref()
+            .and_then(|dir| dir.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        SlotNaming::detect(&self.slots, &slot.extension(), &career_name)
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_save_slots(&self, state: &mut SaveSlotsState) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let mut opened = true;
+        let window = Window::new(im_str!("Save slots"))
+            .size([450.0, 400.0], Condition::FirstUseEver)
+            .opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            if ui.button(im_str!("Browse")) {
+                let default = state.dir.as_ref().map(|dir| dir.to_string_lossy().into_owned());
+                let dir = tinyfiledialogs::select_folder_dialog(
+                    "Career folder",
+                    &default.unwrap_or_else(|| Self::get_document_dir().to_string_lossy().into()),
+                );
+                if let Some(dir) = dir {
+                    state.dir = Some(PathBuf::from(&dir));
+                    let _ = self.event_addr.send(MainEvent::ListSaveSlots(PathBuf::from(dir)));
+                }
+            }
+
+            if let Some(dir) = state.dir.clone() {
+                ui.same_line();
+                if ui.button(im_str!("Refresh")) {
+                    let _ = self.event_addr.send(MainEvent::ListSaveSlots(dir.clone()));
+                }
+                ui.same_line();
+                if ui.button(im_str!("Launcher profile")) {
+                    let _ = self.event_addr.send(MainEvent::OpenLauncherConfig(dir.clone()));
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Legendary Edition launcher options shared by all careers");
+                }
+                ui.same_line();
+                ui.text(dir.to_string_lossy());
+                if let Some(error) = &state.error {
+                    ui.text_colored([0.9, 0.4, 0.3, 1.0], error);
+                }
+                ui.separator();
+
+                if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                    self.draw_save_slot_list(state, dir);
+                }
+            } else {
+                ui.text_disabled(im_str!("Open a save or browse to a career folder"));
+            }
+        }
+
+        if !opened {
+            state.opened = false;
+        }
+
+        self.draw_launcher_config(&mut state.launcher_config);
+    }
+
+    fn draw_launcher_config(&self, launcher_config: &mut Option<(PathBuf, LauncherConfig)>) {
+        let ui = self.ui;
+        let (path, config) = match launcher_config {
+            Some(launcher_config) => launcher_config,
+            None => return,
+        };
+
+        let mut opened = true;
+        let window = Window::new(im_str!("Launcher profile"))
+            .size([400.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            if ui.button(im_str!("Save")) {
+                let _ = self
+                    .event_addr
+                    .send(MainEvent::SaveLauncherConfig(path.clone(), config.clone()));
+            }
+            ui.same_line();
+            ui.text_disabled(path.to_string_lossy());
+            ui.separator();
+
+            if let Some(_t) = self.begin_table(im_str!("launcher_config"), 1) {
+                let mut section = None;
+                for (i, entry) in config.entries_mut().enumerate() {
+                    if entry.section != section {
+                        section = entry.section.clone();
+                        self.table_next_row();
+                        ui.text_disabled(section.as_deref().unwrap_or_default());
+                    }
+
+                    self.table_next_row();
+                    let ident = im_str!("{}##launcher-{}", entry.key, i);
+                    match entry.as_bool() {
+                        Some(mut value) => {
+                            if ui.checkbox(&ident, &mut value) {
+                                entry.set_bool(value);
+                            }
+                        }
+                        None => {
+                            let mut value = ImString::new(&entry.value);
+                            let width = ui.push_item_width(200.0);
+                            if ui.input_text(&ident, &mut value).resize_buffer(true).build() {
+                                entry.value = value.to_string();
+                            }
+                            width.pop(ui);
+                        }
+                    }
+                }
+            }
+        }
+
+        if !opened {
+            *launcher_config = None;
+        }
+    }
+
+    fn draw_save_slot_list(&self, state: &mut SaveSlotsState, dir: PathBuf) {
+        let ui = self.ui;
+
+        if let Some(_t) = self.begin_table(im_str!("save_slots"), 2) {
+            for (idx, slot) in state.slots.iter().enumerate() {
+                let _t = ui.push_id(idx as i32);
+                self.table_next_row();
+                ui.align_text_to_frame_padding();
+                ui.text(&slot.file_name);
+
+                self.table_next_column();
+                if ui.small_button(im_str!("Duplicate")) {
+                    match state.naming(slot).next_free(&state.slots) {
+                        Ok(file_name) => {
+                            let _ = self.event_addr.send(MainEvent::DuplicateSave(
+                                dir.join(&slot.file_name),
+                                dir.join(file_name),
+                            ));
+                        }
+                        Err(err) => state.error = Some(err.to_string()),
+                    }
+                }
+                ui.same_line();
+                if ui.small_button(im_str!("Rename")) {
+                    let number = match slot.kind {
+                        SlotKind::Manual(number) => number as i32,
+                        _ => 1,
+                    };
+                    state.rename = Some((idx, number));
+                }
+                ui.same_line();
+                if ui.small_button(im_str!("Delete")) {
+                    state.delete = Some(idx);
+                }
+            }
+        }
+
+        // Rename
+        if let Some((idx, mut number)) = state.rename {
+            ui.open_popup(im_str!("Rename save###rename_slot"));
+
+            if let Some(_t) = PopupModal::new(im_str!("Rename save###rename_slot"))
+                .always_auto_resize(true)
+                .begin_popup(ui)
+            {
+                let slot = &state.slots[idx];
+                ui.text(&slot.file_name);
+                InputInt::new(ui, im_str!("Slot"), &mut number).build();
+
+                let result = state.naming(slot).rename(&state.slots, number.max(0) as u32);
+                match &result {
+                    Ok(file_name) => ui.text(format!("=> {}", file_name)),
+                    Err(err) => ui.text_colored([0.9, 0.4, 0.3, 1.0], err.to_string()),
+                }
+                ui.separator();
+
+                let mut close = false;
+                if let Ok(file_name) = result {
+                    if ui.button_with_size(im_str!("OK"), [70.0, 0.0]) {
+                        let _ = self.event_addr.send(MainEvent::RenameSave(
+                            dir.join(&slot.file_name),
+                            dir.join(file_name),
+                        ));
+                        close = true;
+                    }
+                    ui.same_line();
+                }
+                if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                    close = true;
+                }
+
+                if close {
+                    state.rename = None;
+                    ui.close_current_popup();
+                } else {
+                    state.rename = Some((idx, number));
+                }
+            }
+        }
+
+        // Delete
+        if let Some(idx) = state.delete {
+            ui.open_popup(im_str!("Delete save###delete_slot"));
+
+            if let Some(_t) = PopupModal::new(im_str!("Delete save###delete_slot"))
+                .always_auto_resize(true)
+                .begin_popup(ui)
+            {
+                let slot = &state.slots[idx];
+                ui.text(format!("Delete {} ?", slot.file_name));
+                ui.text_disabled(im_str!("This cannot be undone."));
+                ui.separator();
+
+                let mut close = false;
+                if ui.button_with_size(im_str!("Delete"), [70.0, 0.0]) {
+                    let _ = self.event_addr.send(MainEvent::DeleteSave(dir.join(&slot.file_name)));
+                    close = true;
+                }
+                ui.same_line();
+                if ui.button_with_size(im_str!("Cancel"), [70.0, 0.0]) {
+                    close = true;
+                }
+
+                if close {
+                    state.delete = None;
+                    ui.close_current_popup();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use imgui::{im_str, ChildWindow, Condition, ImString, ProgressBar, Window};
+
+use crate::{event_handler::SaveGame, save_data::shared::save_stats::SaveStats};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_save_stats(&self, save_stats: &mut Option<SaveStats>) {
+        let ui = self.ui;
+
+        let mut opened = true;
+        if let Some(stats) = save_stats {
+            let window = Window::new(im_str!("Save statistics"))
+                .size([450.0, 400.0], Condition::FirstUseEver)
+                .opened(&mut opened);
+
+            if let Some(_t) = window.begin(ui) {
+                ui.text(format!("Total : {} bytes (uncompressed)", stats.total));
+                ui.text(format!(
+                    "Serialization : {:.1} ms",
+                    stats.serialize_time.as_secs_f64() * 1000.0
+                ));
+                ui.same_line();
+                self.draw_help_marker(
+                    "Size of each top-level section of the save, as it would be written.\nChecksums and compression are not included.",
+                );
+                ui.separator();
+
+                if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                    for section in &stats.sections {
+                        let fraction = stats.fraction(section);
+                        let overlay = ImString::new(format!("{:.1} %", fraction * 100.0));
+                        ProgressBar::new(fraction)
+                            .size([120.0, 0.0])
+                            .overlay_text(&overlay)
+                            .build(ui);
+                        ui.same_line();
+                        ui.text(format!("{} : {} bytes", section.name, section.size));
+                    }
+                }
+            }
+        }
+
+        if !opened {
+            *save_stats = None;
+        }
+    }
+}
+
+pub fn save_stats(save_game: &SaveGame) -> Result<SaveStats> {
+    let mut stats = SaveStats::default();
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            // Player et State sont dans des fichiers séparés de l'archive
+            stats.add(Some("player"), &save_game.player)?;
+            stats.add(Some("state"), &save_game.state)?;
+        }
+        SaveGame::MassEffect1Leg { save_game, .. } => stats.add(None, &save_game.save_data)?,
+        SaveGame::MassEffect2 { save_game, .. } => stats.add(None, save_game)?,
+        SaveGame::MassEffect2Leg { save_game, .. } => stats.add(None, save_game)?,
+        SaveGame::MassEffect3 { save_game, .. } => stats.add(None, save_game)?,
+    }
+    stats.sort();
+    Ok(stats)
+}
@@ -0,0 +1,162 @@
+use imgui::{im_str, ImString, InputInt, MouseButton, StyleColor};
+use indexmap::IndexMap;
+use std::collections::BTreeSet;
+
+use crate::save_data::shared::{
+    plot::{BoolSlice, PlotEntry},
+    plot_clipboard,
+};
+
+use super::Gui;
+
+// Une seule liste sélectionnée à la fois, identifiée par son widget
+#[derive(Default)]
+pub struct SelectionState {
+    list: String,
+    anchor: usize,
+    rows: BTreeSet<usize>,
+    value: i32,
+}
+
+impl<'ui> Gui<'ui> {
+    // Ctrl + clic ajoute ou retire une ligne, Maj + clic sélectionne depuis la dernière ligne cliquée.
+    // Tant que Ctrl ou Maj est enfoncé le widget édite une copie, la valeur n'est pas modifiée.
+    pub fn draw_selectable_row<T: Clone>(
+        &self, list: &str, row: usize, value: &mut T, draw: impl FnOnce(&mut T),
+    ) {
+        let ui = self.ui;
+        let io = ui.io();
+        let selecting = io.key_ctrl || io.key_shift;
+
+        {
+            let _color = self
+                .is_row_selected(list, row)
+                .then(|| ui.push_style_color(StyleColor::FrameBg, [0.26, 0.59, 0.98, 0.55]));
+            if selecting {
+                draw(&mut value.clone());
+            } else {
+                draw(value);
+            }
+        }
+
+        if selecting && ui.is_item_hovered() && ui.is_mouse_clicked(MouseButton::Left) {
+            let mut selection = self.selection.borrow_mut();
+            if selection.list != list {
+                *selection =
+                    SelectionState { list: list.to_owned(), anchor: row, ..Default::default() };
+            }
+
+            if io.key_shift {
+                let (start, end) = if selection.anchor < row {
+                    (selection.anchor, row)
+                } else {
+                    (row, selection.anchor)
+                };
+                selection.rows.extend(start..=end);
+            } else {
+                if !selection.rows.remove(&row) {
+                    selection.rows.insert(row);
+                }
+                selection.anchor = row;
+            }
+        }
+    }
+
+    fn is_row_selected(&self, list: &str, row: usize) -> bool {
+        let selection = self.selection.borrow();
+        selection.list == list && selection.rows.contains(&row)
+    }
+
+    // Set / Clear des lignes sélectionnées de `list`, à dessiner avant ses lignes
+    pub fn draw_bool_selection_actions(&self, list: &str) -> Option<(bool, Vec<usize>)> {
+        let ui = self.ui;
+        self.draw_selection_actions(list, |_| {
+            ui.same_line();
+            let set = ui.button(im_str!("Set##selection"));
+            ui.same_line();
+            let clear = ui.button(im_str!("Clear##selection"));
+            (set || clear).then_some(set)
+        })
+    }
+
+    // Même valeur pour toutes les lignes sélectionnées de `list`
+    pub fn draw_int_selection_actions(&self, list: &str) -> Option<(i32, Vec<usize>)> {
+        let ui = self.ui;
+        self.draw_selection_actions(list, |value| {
+            ui.same_line();
+            let width = ui.push_item_width(120.0);
+            InputInt::new(ui, im_str!("##selection-value"), value).build();
+            width.pop(ui);
+            ui.same_line();
+            ui.button(im_str!("Apply##selection")).then_some(*value)
+        })
+    }
+
+    fn draw_selection_actions<T>(
+        &self, list: &str, draw_actions: impl FnOnce(&mut i32) -> Option<T>,
+    ) -> Option<(T, Vec<usize>)> {
+        let ui = self.ui;
+        let mut selection = self.selection.borrow_mut();
+        if selection.list != list || selection.rows.is_empty() {
+            return None;
+        }
+
+        self.table_next_row();
+        ui.align_text_to_frame_padding();
+        ui.text(im_str!("{} selected :", selection.rows.len()));
+
+        let action = draw_actions(&mut selection.value);
+        ui.same_line();
+        if ui.button(im_str!("Deselect##selection")) {
+            selection.rows.clear();
+        }
+
+        action.map(|action| (action, selection.rows.iter().copied().collect()))
+    }
+
+    // Comme `draw_bool_selection_actions`, avec la copie des flags en texte
+    pub fn draw_plot_bool_selection(
+        &self, list: &str, booleans: &IndexMap<usize, PlotEntry>, variables: &mut BoolSlice,
+    ) {
+        let ui = self.ui;
+        let action = self.draw_selection_actions(list, |_| {
+            ui.same_line();
+            let set = ui.button(im_str!("Set##selection"));
+            ui.same_line();
+            let clear = ui.button(im_str!("Clear##selection"));
+            ui.same_line();
+            let copy = ui.button(im_str!("Copy##selection"));
+            if ui.is_item_hovered() {
+                ui.tooltip_text("Copy the selected flags as `ID=value` lines, to share them");
+            }
+
+            if copy {
+                Some(None)
+            } else {
+                (set || clear).then_some(Some(set))
+            }
+        });
+        let (action, rows) = match action {
+            Some(action) => action,
+            None => return,
+        };
+
+        let selected = rows.into_iter().filter_map(|row| booleans.get_index(row));
+        match action {
+            Some(value) => {
+                for (plot_id, _) in selected {
+                    if let Some(mut plot) = variables.get_mut(*plot_id) {
+                        *plot = value;
+                    }
+                }
+            }
+            None => {
+                let flags = selected.map(|(&plot_id, entry)| {
+                    let value = variables.get(plot_id).map(|b| *b).unwrap_or_default();
+                    (plot_id, value, entry.label.as_str())
+                });
+                ui.set_clipboard_text(&ImString::new(plot_clipboard::to_text(flags)));
+            }
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use imgui::{im_str, InputInt, StyleStackToken, StyleVar};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    batch::{DEFAULT_WORKER_THREADS, MAX_WORKER_THREADS},
+    event_handler::MainEvent,
+    save_data::mass_effect_1::zip_writer::ZipCompression,
+};
+
+use super::{raw_ui::TableSort, shortcuts::Shortcuts, Gui};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub ui_profile: UiProfile,
+    pub accessibility: Accessibility,
+    pub hex_integers: bool,
+    pub split_view: bool,
+    pub show_original_values: bool,
+    // En minutes, 0 pour désactiver
+    pub autosave_interval: u32,
+    pub float_rounding: FloatRounding,
+    pub me1_zip_compression: ZipCompression,
+    // Saves traitées en même temps par l'audit de carrière et le dossier surveillé, 0 par défaut
+    pub worker_threads: usize,
+    pub tour_completed: bool,
+    pub confirmations: Confirmations,
+    pub shortcuts: Shortcuts,
+    pub quick_actions: Vec<QuickAction>,
+    // Par type de save, voir `SaveGame::kind`
+    pub views: IndexMap<String, ViewPreferences>,
+    // Sections `[Table]` de l'ini d'imgui, les ids des tables sont déjà propres à chaque jeu
+    pub table_layouts: String,
+}
+
+// Vues tableau des onglets raw et derniers filtres des pickers
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ViewPreferences {
+    pub table_views: Vec<(Vec<String>, TableSort)>,
+    pub tlk_filter: String,
+    pub plot_lookup_query: String,
+    // Titres des onglets en lecture seule
+    pub locked_tabs: Vec<String>,
+}
+
+// Un bouton de la barre d'actions rapides, applique les presets dans l'ordre
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct QuickAction {
+    pub label: String,
+    pub recipes: Vec<String>,
+}
+
+// Chaque profil montre aussi tout ce que montrent les précédents
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+pub enum UiProfile {
+    Novice,
+    Advanced,
+    Developer,
+}
+
+impl Default for UiProfile {
+    fn default() -> Self {
+        UiProfile::Advanced
+    }
+}
+
+// Widgets masqués selon le profil, les onglets édités à la main sont toujours visibles
+#[derive(Clone, Copy)]
+pub enum Widgets {
+    // Onglets raw, vue côte à côte
+    Raw,
+    // Dummies, entiers en hexadécimal, table des noms ME1
+    Developer,
+}
+
+impl UiProfile {
+    pub fn shows(self, widgets: Widgets) -> bool {
+        match widgets {
+            Widgets::Raw => self >= UiProfile::Advanced,
+            Widgets::Developer => self == UiProfile::Developer,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct Accessibility {
+    pub palette: Palette,
+    pub high_contrast_checkboxes: bool,
+    pub large_hit_targets: bool,
+}
+
+// Évite les XP à 4999.9995 affichées bizarrement en jeu
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct FloatRounding {
+    pub enabled: bool,
+    pub decimals: u8,
+}
+
+impl FloatRounding {
+    // Appliqué seulement aux champs édités, la save n'est jamais arrondie
+    pub fn round(self, value: f32) -> f32 {
+        if !self.enabled || !value.is_finite() {
+            return value;
+        }
+        let factor = 10f64.powi(self.decimals as i32);
+        ((value as f64 * factor).round() / factor) as f32
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Palette {
+    Default,
+    ColorblindSafe,
+    HighContrast,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Confirmations {
+    pub remove_item: bool,
+    pub apply_preset: bool,
+    pub reset_talents: bool,
+}
+
+impl Default for Confirmations {
+    fn default() -> Self {
+        Self { remove_item: true, apply_preset: true, reset_talents: true }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::Default
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_settings(&self, settings: &mut Settings) {
+        let ui = self.ui;
+        let Accessibility { palette, high_contrast_checkboxes, large_hit_targets } =
+            &mut settings.accessibility;
+
+        let mut changed = false;
+
+        ui.text_disabled(im_str!("Profile"));
+        {
+            const ITEMS: [&imgui::ImStr; 3] =
+                [im_str!("Novice"), im_str!("Advanced"), im_str!("Developer")];
+
+            let mut edit_item = settings.ui_profile as usize;
+            if self.draw_edit_enum("Profile", &mut edit_item, &ITEMS) {
+                settings.ui_profile = match edit_item {
+                    0 => UiProfile::Novice,
+                    1 => UiProfile::Advanced,
+                    2 => UiProfile::Developer,
+                    _ => unreachable!(),
+                };
+                changed = true;
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Novice : edition tabs only.\nAdvanced : plus raw data.\nDeveloper : plus unknown bytes, hexadecimal integers and the ME1 name table.",
+            );
+        }
+
+        ui.separator();
+        ui.text_disabled(im_str!("Accessibility"));
+        {
+            const ITEMS: [&imgui::ImStr; 3] =
+                [im_str!("Default"), im_str!("Colorblind safe"), im_str!("High contrast")];
+
+            let mut edit_item = *palette as usize;
+            if self.draw_edit_enum("Palette", &mut edit_item, &ITEMS) {
+                *palette = match edit_item {
+                    0 => Palette::Default,
+                    1 => Palette::ColorblindSafe,
+                    2 => Palette::HighContrast,
+                    _ => unreachable!(),
+                };
+                changed = true;
+            }
+        }
+        changed |= ui.checkbox(im_str!("High contrast checkboxes"), high_contrast_checkboxes);
+        changed |= ui.checkbox(im_str!("Larger hit targets"), large_hit_targets);
+
+        if settings.ui_profile.shows(Widgets::Raw) {
+            ui.separator();
+            ui.text_disabled(im_str!("Raw data"));
+            if settings.ui_profile.shows(Widgets::Developer) {
+                changed |= ui.checkbox(im_str!("Hexadecimal integers"), &mut settings.hex_integers);
+                ui.same_line();
+                self.draw_help_marker("Can be changed per field with a right click.\nInput accepts both `0x` prefixed and decimal numbers.");
+            }
+            changed |=
+                ui.checkbox(im_str!("Show original values"), &mut settings.show_original_values);
+            ui.same_line();
+            self.draw_help_marker(
+                "Shows the value loaded from the save next to each edited raw field.",
+            );
+            changed |= ui.checkbox(im_str!("Side-by-side raw view"), &mut settings.split_view);
+            ui.same_line();
+            self.draw_help_marker(
+                "Shows the raw data next to the editor,\nuseful to see which fields a widget modifies.",
+            );
+        }
+
+        ui.separator();
+        ui.text_disabled(im_str!("Editing"));
+        {
+            let mut interval = settings.autosave_interval as i32;
+            ui.set_next_item_width(100.0);
+            if InputInt::new(ui, im_str!("Autosave interval (minutes)"), &mut interval).build() {
+                settings.autosave_interval = interval.max(0) as u32;
+                changed = true;
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Periodically writes the edited save next to the opened one (`MySave.pcsav.tse-autosave`).\nOpen it to roll back, it will be saved to the original file.\n0 to disable.",
+            );
+
+            let FloatRounding { enabled, decimals } = &mut settings.float_rounding;
+            changed |= ui.checkbox(im_str!("Round floats"), enabled);
+            ui.same_line();
+            self.draw_help_marker(
+                "Edited floats are rounded to this number of decimals.\nFloats you do not edit are saved unchanged.",
+            );
+            if *enabled {
+                let mut edit_decimals = *decimals as i32;
+                ui.set_next_item_width(100.0);
+                if InputInt::new(ui, im_str!("Decimals"), &mut edit_decimals).build() {
+                    *decimals = edit_decimals.clamp(0, 6) as u8;
+                    changed = true;
+                }
+            }
+
+            let compression = &mut settings.me1_zip_compression;
+            let mut stored = *compression == ZipCompression::Stored;
+            if ui.checkbox(im_str!("Store ME1 saves uncompressed"), &mut stored) {
+                *compression =
+                    if stored { ZipCompression::Stored } else { ZipCompression::default() };
+                changed = true;
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Compression of the zip inside Mass Effect 1 saves (not Legendary).\nSome third-party tools fail to read certain deflate streams.",
+            );
+            if let ZipCompression::Deflated { level } = compression {
+                let mut edit_level = *level as i32;
+                ui.set_next_item_width(100.0);
+                if InputInt::new(ui, im_str!("ME1 compression level"), &mut edit_level).build() {
+                    *level = edit_level.clamp(0, 9) as u32;
+                    changed = true;
+                }
+                ui.same_line();
+                self.draw_help_marker("0 (fastest) to 9 (smallest), 6 by default.");
+            }
+
+            let mut edit_threads = settings.worker_threads as i32;
+            ui.set_next_item_width(100.0);
+            if InputInt::new(ui, im_str!("Worker threads"), &mut edit_threads).build() {
+                settings.worker_threads = edit_threads.clamp(0, MAX_WORKER_THREADS as i32) as usize;
+                changed = true;
+            }
+            ui.same_line();
+            self.draw_help_marker(&format!(
+                "Saves read or patched at the same time by the career audit and the watched folder.\n0 for the default ({}), a save that fails does not stop the others.",
+                DEFAULT_WORKER_THREADS
+            ));
+        }
+
+        ui.separator();
+        ui.text_disabled(im_str!("Confirmations"));
+        {
+            let Confirmations { remove_item, apply_preset, reset_talents } =
+                &mut settings.confirmations;
+            changed |= ui.checkbox(im_str!("Removing list items"), remove_item);
+            changed |= ui.checkbox(im_str!("Applying presets"), apply_preset);
+            ui.same_line();
+            self.draw_help_marker(
+                "Presets and quick actions list every change they would make,\nto deselect some before applying them.",
+            );
+            changed |= ui.checkbox(im_str!("Resetting talents"), reset_talents);
+        }
+
+        ui.separator();
+        ui.text_disabled(im_str!("Shortcuts"));
+        ui.same_line();
+        self.draw_help_marker("Change them if they clash with the game or an overlay.");
+        changed |= self.draw_shortcut_settings(&mut settings.shortcuts);
+
+        #[cfg(target_os = "windows")]
+        {
+            ui.separator();
+            ui.text_disabled(im_str!("Explorer"));
+            ui.same_line();
+            self.draw_help_marker(
+                "Adds `Edit with Trilogy Save Editor` to the right click menu of the saves,
+for the current user and this copy of the editor.",
+            );
+            if ui.button(im_str!("Add to context menu")) {
+                let _ = self.event_addr.send(MainEvent::RegisterShellMenu);
+            }
+            ui.same_line();
+            if ui.button(im_str!("Remove from context menu")) {
+                let _ = self.event_addr.send(MainEvent::UnregisterShellMenu);
+            }
+        }
+
+        if changed {
+            let _ = self.event_addr.send(MainEvent::SaveSettings(settings.clone()));
+        }
+    }
+
+    pub fn style_vars(&self) -> Vec<StyleStackToken<'ui>> {
+        let ui = self.ui;
+        let accessibility = &self.accessibility;
+        let mut tokens = Vec::new();
+
+        if accessibility.high_contrast_checkboxes {
+            tokens.push(ui.push_style_var(StyleVar::FrameBorderSize(1.0)));
+        }
+
+        if accessibility.large_hit_targets {
+            tokens.push(ui.push_style_var(StyleVar::FramePadding([6.0, 6.0])));
+            tokens.push(ui.push_style_var(StyleVar::ItemSpacing([8.0, 6.0])));
+            tokens.push(ui.push_style_var(StyleVar::ScrollbarSize(18.0)));
+            tokens.push(ui.push_style_var(StyleVar::GrabMinSize(14.0)));
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn ui_profiles() {
+        // Les réglages d'avant les profils gardent tout l'affichage
+        let settings: Settings = ron::from_str("(hex_integers: true)").unwrap();
+        assert!(settings.ui_profile == UiProfile::Advanced);
+
+        let settings: Settings = ron::from_str("(ui_profile: Novice)").unwrap();
+        assert!(!settings.ui_profile.shows(Widgets::Raw));
+        assert!(UiProfile::Advanced.shows(Widgets::Raw));
+        assert!(!UiProfile::Advanced.shows(Widgets::Developer));
+        assert!(UiProfile::Developer.shows(Widgets::Raw));
+        assert!(UiProfile::Developer.shows(Widgets::Developer));
+    }
+
+    #[test]
+    fn float_rounding() -> Result<()> {
+        let rounding = FloatRounding { enabled: true, decimals: 0 };
+        assert_eq!(rounding.round(4999.9995), 5000.0);
+        assert_eq!(rounding.round(1234.5678), 1235.0);
+        assert_eq!(FloatRounding { enabled: true, decimals: 2 }.round(1234.5678), 1234.57);
+        assert_eq!(FloatRounding { enabled: true, decimals: 1 }.round(0.3 + f32::EPSILON), 0.3);
+        assert!(rounding.round(f32::NAN).is_nan());
+        assert_eq!(FloatRounding { enabled: false, decimals: 0 }.round(4999.9995), 4999.9995);
+
+        // Les floats non édités sont écrits tels quels
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let output = unreal::Serializer::to_byte_buf(&me2_save_game)?;
+        assert_eq!(input[..input.len() - 4], output[..]);
+
+        // Seul le champ édité est arrondi
+        me2_save_game.player.current_xp = rounding.round(1234567.9);
+        let output = unreal::Serializer::to_byte_buf(&me2_save_game)?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(me2_save_game.player.current_xp, 1234568.0);
+        Ok(())
+    }
+}
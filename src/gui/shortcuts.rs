@@ -0,0 +1,217 @@
+use imgui::{im_str, ImString};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::Gui;
+
+lazy_static! {
+    // `None`, A-Z, 0-9, F1-F12
+    static ref KEY_NAMES: Vec<ImString> = {
+        let letters = ('A'..='Z').map(String::from);
+        let digits = ('0'..='9').map(String::from);
+        let functions = (1..=12).map(|n| format!("F{}", n));
+        let keys = letters.chain(digits).chain(functions);
+        std::iter::once(String::from("None")).chain(keys).map(ImString::new).collect()
+    };
+}
+
+// Index dans `io.keys_down`, ce sont les `VirtualKeyCode` de winit
+fn key_index(name: &str) -> Option<usize> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c @ '1'..='9'), None) => Some(c as usize - '1' as usize),
+        (Some('0'), None) => Some(9),
+        (Some(c @ 'A'..='Z'), None) => Some(10 + c as usize - 'A' as usize),
+        _ => {
+            let n: usize = name.strip_prefix('F')?.parse().ok()?;
+            (1..=12).contains(&n).then(|| 36 + n)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct KeyBinding {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    // Vide pour aucun raccourci
+    pub key: String,
+}
+
+impl KeyBinding {
+    fn ctrl(key: &str) -> Self {
+        KeyBinding { ctrl: true, key: key.to_owned(), ..Default::default() }
+    }
+
+    fn ctrl_shift(key: &str) -> Self {
+        KeyBinding { shift: true, ..Self::ctrl(key) }
+    }
+
+    fn is_down(&self, io: &imgui::Io) -> bool {
+        let key_down = key_index(&self.key).map(|key| io.keys_down[key]).unwrap_or_default();
+        key_down && self.ctrl == io.key_ctrl && self.shift == io.key_shift && self.alt == io.key_alt
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.key.is_empty() {
+            return f.write_str("None");
+        }
+        for (modifier, name) in &[(self.ctrl, "Ctrl+"), (self.shift, "Shift+"), (self.alt, "Alt+")]
+        {
+            if *modifier {
+                f.write_str(name)?;
+            }
+        }
+        f.write_str(&self.key)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shortcut {
+    Open,
+    Save,
+    SaveNewSlot,
+    SplitView,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Shortcuts {
+    pub open: KeyBinding,
+    pub save: KeyBinding,
+    pub save_new_slot: KeyBinding,
+    pub split_view: KeyBinding,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            open: KeyBinding::ctrl("O"),
+            save: KeyBinding::ctrl("S"),
+            save_new_slot: KeyBinding::ctrl_shift("S"),
+            split_view: KeyBinding::ctrl("R"),
+        }
+    }
+}
+
+impl Shortcuts {
+    fn bindings(&self) -> [(Shortcut, &'static str, &KeyBinding); 4] {
+        [
+            (Shortcut::Open, "Open", &self.open),
+            (Shortcut::Save, "Save", &self.save),
+            (Shortcut::SaveNewSlot, "Save as new slot", &self.save_new_slot),
+            (Shortcut::SplitView, "Side-by-side raw view", &self.split_view),
+        ]
+    }
+
+    fn binding_mut(&mut self, shortcut: Shortcut) -> &mut KeyBinding {
+        match shortcut {
+            Shortcut::Open => &mut self.open,
+            Shortcut::Save => &mut self.save,
+            Shortcut::SaveNewSlot => &mut self.save_new_slot,
+            Shortcut::SplitView => &mut self.split_view,
+        }
+    }
+
+    // Autres raccourcis avec la même combinaison, aucun ne se déclenche
+    fn conflicts(&self, shortcut: Shortcut) -> Vec<&'static str> {
+        let bindings = self.bindings();
+        let binding = match bindings.iter().find(|(other, ..)| *other == shortcut) {
+            Some((_, _, binding)) if !binding.key.is_empty() => binding,
+            _ => return Vec::new(),
+        };
+        bindings
+            .iter()
+            .filter(|(other, _, other_binding)| *other != shortcut && other_binding == binding)
+            .map(|(_, name, _)| *name)
+            .collect()
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    // Déclenché une seule fois tant que la combinaison reste enfoncée
+    pub fn pressed_shortcut(
+        &self, shortcuts: &Shortcuts, held: &mut Option<Shortcut>,
+    ) -> Option<Shortcut> {
+        let io = self.ui.io();
+        let down = shortcuts
+            .bindings()
+            .iter()
+            .find(|(shortcut, _, binding)| {
+                binding.is_down(io) && shortcuts.conflicts(*shortcut).is_empty()
+            })
+            .map(|(shortcut, ..)| *shortcut);
+
+        let pressed = down.filter(|down| *held != Some(*down));
+        *held = down;
+        pressed
+    }
+
+    pub fn draw_shortcut_settings(&self, shortcuts: &mut Shortcuts) -> bool {
+        let ui = self.ui;
+        let key_names: Vec<_> = KEY_NAMES.iter().map(ImString::as_ref).collect();
+
+        let mut changed = false;
+        for (shortcut, name, _) in shortcuts.clone().bindings().iter() {
+            let _t = ui.push_id(*name);
+            let conflicts = shortcuts.conflicts(*shortcut);
+            let binding = shortcuts.binding_mut(*shortcut);
+
+            ui.text(name);
+            ui.same_line_with_pos(170.0);
+            changed |= ui.checkbox(im_str!("Ctrl"), &mut binding.ctrl);
+            ui.same_line();
+            changed |= ui.checkbox(im_str!("Shift"), &mut binding.shift);
+            ui.same_line();
+            changed |= ui.checkbox(im_str!("Alt"), &mut binding.alt);
+            ui.same_line();
+
+            let mut key = KEY_NAMES.iter().position(|key| key.to_str() == binding.key).unwrap_or(0);
+            ui.set_next_item_width(60.0);
+            if self.draw_edit_enum("##key", &mut key, &key_names) {
+                binding.key =
+                    if key == 0 { String::new() } else { KEY_NAMES[key].to_str().to_owned() };
+                changed = true;
+            }
+
+            if !conflicts.is_empty() {
+                ui.same_line();
+                ui.text_colored(
+                    [0.9, 0.3, 0.3, 1.0],
+                    format!("Same as {}, disabled", conflicts.join(", ")),
+                );
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shortcut_conflicts() {
+        assert_eq!(key_index("1"), Some(0));
+        assert_eq!(key_index("0"), Some(9));
+        assert_eq!(key_index("S"), Some(28));
+        assert_eq!(key_index("F5"), Some(41));
+        assert_eq!(key_index("F13"), None);
+
+        let mut shortcuts = Shortcuts::default();
+        assert!(shortcuts.conflicts(Shortcut::Save).is_empty());
+        assert_eq!(shortcuts.save_new_slot.to_string(), "Ctrl+Shift+S");
+
+        shortcuts.open = KeyBinding::ctrl("S");
+        assert_eq!(shortcuts.conflicts(Shortcut::Save), ["Open"]);
+        assert!(shortcuts.conflicts(Shortcut::SaveNewSlot).is_empty());
+
+        shortcuts.open.key.clear();
+        assert!(shortcuts.conflicts(Shortcut::Open).is_empty());
+        assert!(shortcuts.conflicts(Shortcut::Save).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use imgui::{im_str, Condition, ImString, Window};
+
+use crate::event_handler::SaveGame;
+
+use super::Gui;
+
+// Copies de la save en mémoire pour comparer des modifications sans multiplier les fichiers
+#[derive(Default)]
+pub struct SnapshotsState {
+    pub opened: bool,
+    name: ImString,
+    snapshots: Vec<Snapshot>,
+}
+
+struct Snapshot {
+    name: String,
+    save_game: SaveGame,
+}
+
+impl SnapshotsState {
+    // Les snapshots ne concernent que la save ouverte
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    fn take(&mut self, save_game: &SaveGame) {
+        let name = match self.name.to_str().trim() {
+            "" => format!("Snapshot {}", self.snapshots.len() + 1),
+            name => name.to_owned(),
+        };
+        self.snapshots.push(Snapshot { name, save_game: save_game.clone() });
+        self.name.clear();
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_snapshots(&self, state: &mut SnapshotsState, save_game: &mut Option<SaveGame>) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Snapshots"))
+            .size([400.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let current = match save_game {
+                Some(save_game) => save_game,
+                None => {
+                    ui.text_disabled(im_str!("Open a save first"));
+                    return;
+                }
+            };
+
+            ui.set_next_item_width(200.0);
+            ui.input_text(im_str!("##snapshot-name"), &mut state.name)
+                .hint(im_str!("Name"))
+                .resize_buffer(true)
+                .build();
+            ui.same_line();
+            if ui.button(im_str!("Take snapshot")) {
+                state.take(current);
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Keeps a copy of the current save in memory, to restore or export it later.\nSnapshots are lost when another save is opened.",
+            );
+            ui.separator();
+
+            if state.snapshots.is_empty() {
+                ui.text_disabled(im_str!("No snapshot"));
+            }
+
+            let mut remove = None;
+            for (i, snapshot) in state.snapshots.iter().enumerate() {
+                let _t = ui.push_id(i as i32);
+                ui.text(&snapshot.name);
+                ui.same_line_with_pos(200.0);
+                if ui.small_button(im_str!("Restore")) {
+                    *current = snapshot.save_game.clone();
+                }
+                ui.same_line();
+                if ui.small_button(im_str!("Export")) {
+                    self.save_dialog(&snapshot.save_game, None);
+                }
+                ui.same_line();
+                if ui.small_button(im_str!("Delete")) {
+                    remove = Some(i);
+                }
+            }
+
+            if let Some(i) = remove {
+                state.snapshots.remove(i);
+            }
+        }
+    }
+}
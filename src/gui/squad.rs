@@ -0,0 +1,93 @@
+use imgui::{im_str, ChildWindow, Condition, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::shared::{plot::BoolVec, recipe::Game, squad::SquadDb},
+};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_squad_selection(
+        &self, opened: &mut bool, save_game: &mut Option<SaveGame>, squad_db: Option<&SquadDb>,
+    ) {
+        let ui = self.ui;
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Squad selection"))
+            .size([450.0, 400.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let (game, booleans): (_, &mut BoolVec) = match save_game {
+                Some(SaveGame::MassEffect2 { save_game, .. }) => {
+                    (Game::MassEffect2, &mut save_game.plot.bool_variables)
+                }
+                Some(SaveGame::MassEffect2Leg { save_game, .. }) => {
+                    (Game::MassEffect2Leg, &mut save_game.plot.bool_variables)
+                }
+                Some(SaveGame::MassEffect3 { save_game, .. }) => {
+                    (Game::MassEffect3, &mut save_game.plot.bool_variables)
+                }
+                Some(SaveGame::MassEffect1 { .. })
+                | Some(SaveGame::MassEffect1Leg { .. })
+                | None => {
+                    ui.text_disabled(im_str!("Open a Mass Effect 2 or Mass Effect 3 save"));
+                    return;
+                }
+            };
+            let squad_db = match squad_db {
+                Some(squad_db) => squad_db,
+                None => return,
+            };
+
+            if ui.button(im_str!("Unlock all")) {
+                for squadmate in squad_db.squadmates(game) {
+                    squadmate.unlock(booleans);
+                }
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Recruited and selectable squadmates are offered on the squad selection screen.\nUnlocking also revives a squadmate marked as dead, for companions removed by a bug.\nUnlocking a squadmate before their recruitment mission may break that mission.",
+            );
+
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                let _t = match self.begin_table(im_str!("squad-table"), 4) {
+                    Some(t) => t,
+                    None => return,
+                };
+                for (i, squadmate) in squad_db.squadmates(game).enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    let status = squadmate.status(booleans);
+
+                    self.table_next_row();
+                    if status.dead {
+                        ui.text_colored([0.9, 0.4, 0.3, 1.0], format!("{} (dead)", squadmate.name));
+                    } else {
+                        ui.text(&squadmate.name);
+                    }
+
+                    self.table_next_column();
+                    let mut recruited = status.recruited;
+                    if ui.checkbox(im_str!("Recruited"), &mut recruited) {
+                        squadmate.set_recruited(booleans, recruited);
+                    }
+
+                    self.table_next_column();
+                    let mut selectable = status.selectable;
+                    if ui.checkbox(im_str!("Selectable"), &mut selectable) {
+                        squadmate.set_selectable(booleans, selectable);
+                    }
+
+                    self.table_next_column();
+                    let unlocked = status.recruited && status.selectable && !status.dead;
+                    if !unlocked && ui.small_button(im_str!("Unlock")) {
+                        squadmate.unlock(booleans);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{bail, Result};
+use imgui::{im_str, ChildWindow, Condition, Window};
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    save_data::shared::squad::{self, HenchmanBuild},
+};
+
+use super::Gui;
+
+// Build d'un coéquipier d'une autre save de ME2 / ME3, appliqué au même coéquipier de la save ouverte
+#[derive(Default)]
+pub struct SquadBuildState {
+    pub opened: bool,
+    source: Option<SaveGame>,
+    result: Option<Result<String, String>>,
+}
+
+impl SquadBuildState {
+    pub fn set_source(&mut self, save_game: SaveGame) {
+        self.source = Some(save_game);
+        self.result = None;
+    }
+}
+
+// Tag et niveau des coéquipiers
+fn squad_members(save_game: &SaveGame) -> Vec<(String, i32)> {
+    fn members<H: HenchmanBuild>(squad: &[H]) -> Vec<(String, i32)> {
+        squad.iter().map(|henchman| (henchman.tag().to_owned(), henchman.level())).collect()
+    }
+
+    match save_game {
+        SaveGame::MassEffect2 { save_game, .. } => members(&save_game.squad),
+        SaveGame::MassEffect2Leg { save_game, .. } => members(&save_game.squad),
+        SaveGame::MassEffect3 { save_game, .. } => members(&save_game.squad),
+        SaveGame::MassEffect1 { .. } | SaveGame::MassEffect1Leg { .. } => Vec::new(),
+    }
+}
+
+// ME2 et ME2LE partagent la même structure
+pub fn copy_squad_build(target: &mut SaveGame, source: &SaveGame, tag: &str) -> Result<()> {
+    fn copy<H: HenchmanBuild>(target: &mut [H], source: &[H], tag: &str) -> Result<()> {
+        match source.iter().find(|henchman| henchman.tag() == tag) {
+            Some(henchman) => squad::copy_build(target, henchman),
+            None => bail!("{} is not in the squad of the source save", tag),
+        }
+    }
+
+    let (kind, source_kind) = (target.kind(), source.kind());
+    match (target, source) {
+        (
+            SaveGame::MassEffect2 { save_game: target, .. },
+            SaveGame::MassEffect2 { save_game: source, .. },
+        ) => copy(&mut target.squad, &source.squad, tag),
+        (
+            SaveGame::MassEffect2 { save_game: target, .. },
+            SaveGame::MassEffect2Leg { save_game: source, .. },
+        ) => copy(&mut target.squad, &source.squad, tag),
+        (
+            SaveGame::MassEffect2Leg { save_game: target, .. },
+            SaveGame::MassEffect2 { save_game: source, .. },
+        ) => copy(&mut target.squad, &source.squad, tag),
+        (
+            SaveGame::MassEffect2Leg { save_game: target, .. },
+            SaveGame::MassEffect2Leg { save_game: source, .. },
+        ) => copy(&mut target.squad, &source.squad, tag),
+        (
+            SaveGame::MassEffect3 { save_game: target, .. },
+            SaveGame::MassEffect3 { save_game: source, .. },
+        ) => copy(&mut target.squad, &source.squad, tag),
+        _ => bail!("Can't copy a {} squadmate into a {} save", source_kind, kind),
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_squad_build(&self, state: &mut SquadBuildState, save_game: &mut Option<SaveGame>) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Copy squadmate build"))
+            .size([450.0, 350.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let target = match save_game {
+                Some(save_game) => save_game,
+                None => {
+                    ui.text_disabled(im_str!("Open a save first"));
+                    return;
+                }
+            };
+
+            if ui.button(im_str!("Source save")) {
+                let file = tinyfiledialogs::open_file_dialog(
+                    "",
+                    &Self::get_document_dir().to_string_lossy(),
+                    Some((&["*.pcsav", "*.sav"], "Mass Effect 2/3 Save (*.pcsav, *.sav)")),
+                );
+                if let Some(path) = file {
+                    let _ = self.event_addr.send(MainEvent::OpenSquadBuildSource(path));
+                }
+            }
+            ui.same_line();
+            match &state.source {
+                Some(source) => ui.text(source.file_path()),
+                None => ui.text_disabled(im_str!("No source save")),
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "Copies the powers with their ranks and evolutions, the level, the talent points and the gear of a squadmate.\nPowers are matched by class, the squadmate must already be in the squad of the opened save.",
+            );
+
+            match &state.result {
+                Some(Ok(message)) => ui.text(message),
+                Some(Err(err)) => ui.text_colored([0.9, 0.4, 0.3, 1.0], err),
+                None => (),
+            }
+
+            let source = match &state.source {
+                Some(source) => source,
+                None => return,
+            };
+            ui.separator();
+
+            let members = squad_members(source);
+            if members.is_empty() {
+                ui.text_disabled(im_str!("No squadmate in the source save"));
+                return;
+            }
+
+            let target_members = squad_members(target);
+            if let Some(_t) = ChildWindow::new("squad").begin(ui) {
+                let _t = match self.begin_table(im_str!("squad-build-table"), 3) {
+                    Some(t) => t,
+                    None => return,
+                };
+                for (i, (tag, level)) in members.iter().enumerate() {
+                    let _t = ui.push_id(i as i32);
+                    self.table_next_row();
+                    ui.text(tag);
+                    self.table_next_column();
+                    ui.text(format!("Level {}", level));
+                    self.table_next_column();
+                    if target_members.iter().any(|(target_tag, _)| target_tag == tag) {
+                        if ui.small_button(im_str!("Copy")) {
+                            state.result = Some(match copy_squad_build(target, source, tag) {
+                                Ok(()) => Ok(format!("Copied the build of {}", tag)),
+                                Err(err) => Err(format!("{:#}", err)),
+                            });
+                        }
+                    } else {
+                        ui.text_disabled(im_str!("Not in the squad"));
+                    }
+                }
+            }
+        }
+    }
+}
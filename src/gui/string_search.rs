@@ -0,0 +1,139 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::shared::string_search::{self, StringMatch},
+};
+
+use super::{watch::Watch, Gui};
+
+#[derive(Default)]
+pub struct StringSearchState {
+    pub opened: bool,
+    query: ImString,
+    replacement: ImString,
+    match_case: bool,
+    results: Option<Vec<StringMatch>>,
+    error: Option<String>,
+}
+
+impl StringSearchState {
+    pub fn clear(&mut self) {
+        self.results = None;
+        self.error = None;
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_string_search(
+        &self, state: &mut StringSearchState, save_game: &mut Option<SaveGame>,
+    ) {
+        let ui = self.ui;
+
+        if !state.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Search strings"))
+            .size([600.0, 400.0], Condition::FirstUseEver)
+            .opened(&mut state.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let save_game = match save_game {
+                Some(save_game) => save_game,
+                None => {
+                    ui.text_disabled(im_str!("Open a save to search its strings"));
+                    return;
+                }
+            };
+
+            let mut search = ui
+                .input_text(im_str!("Search"), &mut state.query)
+                .resize_buffer(true)
+                .enter_returns_true(true)
+                .build();
+            ui.input_text(im_str!("Replace with"), &mut state.replacement)
+                .resize_buffer(true)
+                .build();
+            ui.checkbox(im_str!("Match case"), &mut state.match_case);
+            ui.same_line();
+            self.draw_help_marker(
+                "Searches every string of the Raw tab, keys included (level names...).\nReplace all only changes the listed results.",
+            );
+
+            search |= ui.button(im_str!("Find"));
+            let mut replace = None;
+            if let Some(results) = &state.results {
+                ui.same_line();
+                if ui.button(im_str!("Replace all")) {
+                    replace = Some(results.iter().map(|result| result.path.clone()).collect());
+                }
+            }
+            ui.separator();
+
+            if let Some(err) = &state.error {
+                ui.text_colored([0.9, 0.4, 0.3, 1.0], err);
+            }
+
+            if let Some(_t) = ChildWindow::new("results").begin(ui) {
+                match &state.results {
+                    Some(results) if results.is_empty() => ui.text_disabled(im_str!("No match")),
+                    Some(results) => {
+                        ui.text(format!("{} matches", results.len()));
+                        for (i, result) in results.iter().enumerate() {
+                            let _t = ui.push_id(i as i32);
+                            if ui.small_button(im_str!("Watch")) {
+                                self.watch.borrow_mut().add(Watch::Raw(result.path.clone()));
+                            }
+                            if ui.is_item_hovered() {
+                                ui.tooltip_text("Shows the field in the watch panel");
+                            }
+                            ui.same_line();
+                            if ui.small_button(im_str!("Replace")) {
+                                replace = Some(vec![result.path.clone()]);
+                            }
+                            ui.same_line();
+                            ui.text(result.path.join(" > "));
+                            ui.same_line();
+                            ui.text_disabled(&result.value);
+                        }
+                    }
+                    None => (),
+                }
+            }
+
+            if search || replace.is_some() {
+                state.error = None;
+            }
+
+            if let Some(paths) = replace {
+                // Sur une copie, rien n'est remplacé si une clé est refusée
+                let mut replaced = save_game.clone();
+                let result = string_search::replace(
+                    replaced.raw_data_mut(),
+                    state.query.to_str(),
+                    state.replacement.to_str(),
+                    state.match_case,
+                    Some(&paths),
+                );
+                match result {
+                    Ok(_) => *save_game = replaced,
+                    Err(err) => state.error = Some(format!("{:#}", err)),
+                }
+                search = true;
+            }
+
+            if search {
+                let results = string_search::search(
+                    save_game.raw_data_mut(),
+                    state.query.to_str(),
+                    state.match_case,
+                );
+                match results {
+                    Ok(results) => state.results = Some(results),
+                    Err(err) => state.error = Some(format!("{:#}", err)),
+                }
+            }
+        }
+    }
+}
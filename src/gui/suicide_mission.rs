@@ -0,0 +1,141 @@
+use imgui::{im_str, ChildWindow, Condition, ImStr, ImString, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::mass_effect_2::{
+        plot::PlotTable,
+        suicide_mission::{self, Choices, CrewOutcome, Henchman, SquadState},
+    },
+};
+
+use super::Gui;
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_suicide_mission(
+        &self, choices: &mut Option<Choices>, save_game: &mut Option<SaveGame>,
+    ) {
+        let ui = self.ui;
+
+        let plot = match save_game {
+            Some(SaveGame::MassEffect2 { save_game, .. }) => &mut save_game.plot,
+            Some(SaveGame::MassEffect2Leg { save_game, .. }) => &mut save_game.plot,
+            _ => {
+                *choices = None;
+                return;
+            }
+        };
+
+        let mut opened = true;
+        if let Some(edit_choices) = choices {
+            let window = Window::new(im_str!("Suicide Mission"))
+                .size([450.0, 550.0], Condition::FirstUseEver)
+                .opened(&mut opened);
+
+            if let Some(_t) = window.begin(ui) {
+                if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                    self.draw_suicide_mission_content(edit_choices, plot);
+                }
+            }
+        }
+
+        if !opened {
+            *choices = None;
+        }
+    }
+
+    fn draw_suicide_mission_content(&self, choices: &mut Choices, plot: &mut PlotTable) {
+        let ui = self.ui;
+        let squad = SquadState::from_plot(plot);
+
+        // Équipe
+        ui.text_disabled(im_str!("Squad"));
+        for state in &squad.recruited {
+            let loyalty = if state.loyal { "loyal" } else { "not loyal" };
+            ui.bullet_text(&ImString::new(format!("{} ({})", state.henchman, loyalty)));
+        }
+        let upgrades = [
+            ("Heavy Ship Armor", squad.armor),
+            ("Multicore Shielding", squad.shields),
+            ("Thanix Cannon", squad.cannon),
+        ];
+        for (upgrade, purchased) in &upgrades {
+            let status = if *purchased { "purchased" } else { "missing" };
+            ui.bullet_text(&ImString::new(format!("{} : {}", upgrade, status)));
+        }
+        ui.bullet_text(&ImString::new(format!(
+            "Missions after crew abduction : {}",
+            squad.crew_delay
+        )));
+
+        // Choix
+        ui.separator();
+        ui.text_disabled(im_str!("Choices"));
+        ui.same_line();
+        self.draw_help_marker("Made during the mission, they are not stored in the save.");
+
+        let names: Vec<ImString> = std::iter::once(ImString::new("-"))
+            .chain(squad.recruited.iter().map(|state| ImString::new(state.henchman.to_string())))
+            .collect();
+        let items: Vec<&ImStr> = names.iter().map(ImString::as_ref).collect();
+        let draw_choice = |ident: &str, choice: &mut Option<Henchman>| {
+            let mut idx = choice
+                .and_then(|h| squad.recruited.iter().position(|state| state.henchman == h))
+                .map_or(0, |idx| idx + 1);
+            if self.draw_edit_enum(ident, &mut idx, &items) {
+                *choice = idx.checked_sub(1).map(|idx| squad.recruited[idx].henchman);
+            }
+        };
+
+        draw_choice("Squad during approach##ship-1", &mut choices.ship_squad[0]);
+        draw_choice("##ship-2", &mut choices.ship_squad[1]);
+        draw_choice("Tech specialist", &mut choices.tech_specialist);
+        draw_choice("First fireteam leader", &mut choices.first_leader);
+        draw_choice("Squad during long walk##walk-1", &mut choices.long_walk_squad[0]);
+        draw_choice("##walk-2", &mut choices.long_walk_squad[1]);
+        draw_choice("Biotic specialist", &mut choices.biotic_specialist);
+        draw_choice("Second fireteam leader", &mut choices.second_leader);
+        draw_choice("Crew escort", &mut choices.escort);
+        draw_choice("Final squad##final-1", &mut choices.final_squad[0]);
+        draw_choice("##final-2", &mut choices.final_squad[1]);
+
+        if ui.button(im_str!("Recommended choices")) {
+            *choices = Choices::recommended(&squad);
+        }
+        ui.same_line();
+        if ui.button(im_str!("Guarantee survival")) {
+            suicide_mission::guarantee_survival(plot, &squad);
+            *choices = Choices::recommended(&SquadState::from_plot(plot));
+        }
+        ui.same_line();
+        self.draw_help_marker(
+            "Makes every recruited squad member loyal, buys the Normandy upgrades and resets the crew abduction delay.",
+        );
+
+        // Résultat
+        let outcome = suicide_mission::simulate(&squad, choices);
+        ui.separator();
+        ui.text_disabled(im_str!("Outcome"));
+        for warning in &outcome.warnings {
+            ui.text_colored([0.9, 0.8, 0.3, 1.0], warning);
+        }
+        for (henchman, cause) in &outcome.deaths {
+            ui.text_colored([0.9, 0.4, 0.3, 1.0], format!("{} dies : {}", henchman, cause));
+        }
+        if outcome.deaths.is_empty() {
+            ui.text(im_str!("Everyone survives"));
+        } else {
+            let survivors: Vec<_> = outcome.survivors.iter().map(Henchman::to_string).collect();
+            ui.text(format!("Survivors : {}", survivors.join(", ")));
+        }
+        let crew = match outcome.crew {
+            CrewOutcome::AllSurvive => "Crew : everyone survives",
+            CrewOutcome::HalfSurvive => "Crew : half of the crew dies",
+            CrewOutcome::OnlyChakwas => "Crew : only Dr. Chakwas survives",
+            CrewOutcome::NoneSurvive => "Crew : no escort, the crew dies",
+        };
+        ui.text(crew);
+        if !outcome.shepard_survives {
+            ui.text_colored([0.9, 0.4, 0.3, 1.0], "Shepard dies : fewer than 2 survivors");
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use imgui::{im_str, ChildWindow, Condition, ImString, ListClipper, Selectable, Window};
+
+use crate::{event_handler::MainEvent, save_data::shared::tlk::TlkFile};
+
+use super::Gui;
+
+// Partagé avec le Gui pour afficher le texte des champs `Localized ...` des onglets raw
+#[derive(Default)]
+pub struct TlkState {
+    pub opened: bool,
+    pub tlk: Option<TlkFile>,
+    filter: ImString,
+    picker_filter: ImString,
+}
+
+impl TlkState {
+    pub fn set_tlk(&mut self, tlk: TlkFile) {
+        self.tlk = Some(tlk);
+        self.opened = true;
+    }
+
+    pub fn picker_filter(&self) -> &str {
+        self.picker_filter.to_str()
+    }
+
+    pub fn set_picker_filter(&mut self, filter: &str) {
+        self.picker_filter = ImString::new(filter);
+    }
+}
+
+fn matches_filter(id: i32, string: &str, filter: &str) -> bool {
+    filter.is_empty()
+        || id.to_string().starts_with(filter)
+        || string.to_lowercase().contains(&filter.to_lowercase())
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_tlk_viewer(&self) {
+        let ui = self.ui;
+        let mut state = self.tlk.borrow_mut();
+        let TlkState { opened, tlk, filter, .. } = &mut *state;
+
+        if !*opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("TLK"))
+            .size([500.0, 400.0], Condition::FirstUseEver)
+            .opened(opened);
+
+        if let Some(_t) = window.begin(ui) {
+            if ui.button(im_str!("Open TLK")) {
+                let file = tinyfiledialogs::open_file_dialog(
+                    "",
+                    &Self::get_document_dir().to_string_lossy(),
+                    Some((&["*.tlk"], "Mass Effect Talk File (*.tlk)")),
+                );
+                if let Some(path) = file {
+                    let _ = self.event_addr.send(MainEvent::OpenTlk(path));
+                }
+            }
+            ui.same_line();
+            self.draw_help_marker(
+                "ME2, ME3 and Legendary Edition TLK files, ME1 ones are stored inside packages.\nOnce loaded, the text of localized fields is shown in the raw tabs.",
+            );
+
+            match tlk {
+                Some(tlk) => {
+                    ui.input_text(im_str!("Search"), filter).resize_buffer(true).build();
+                    ui.separator();
+
+                    let strings: Vec<_> = tlk
+                        .iter()
+                        .filter(|(id, string)| matches_filter(*id, string, filter.to_str()))
+                        .collect();
+
+                    if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                        let mut clipper = ListClipper::new(strings.len() as i32).begin(ui);
+                        while clipper.step() {
+                            for i in clipper.display_start()..clipper.display_end() {
+                                let (id, string) = strings[i as usize];
+                                ui.text(format!("{:>9}  {}", id, string));
+                            }
+                        }
+                    }
+                }
+                None => ui.text_disabled(im_str!("No TLK loaded")),
+            }
+        }
+    }
+
+    // Texte + sélecteur à côté des ids de string localisée
+    pub fn draw_tlk_picker(&self, ident: &str, value: &mut i32) {
+        let ui = self.ui;
+        let mut state = self.tlk.borrow_mut();
+        let TlkState { tlk, picker_filter, .. } = &mut *state;
+
+        let tlk = match tlk {
+            Some(tlk) if ident.starts_with("Localized") => tlk,
+            _ => return,
+        };
+
+        let _t = ui.push_id(ident);
+        ui.same_line();
+        // Le dernier filtre est gardé
+        if ui.small_button(im_str!("...")) {
+            ui.open_popup(im_str!("tlk_picker"));
+        }
+        ui.same_line();
+        match tlk.get(*value) {
+            Some(string) => ui.text(string),
+            None => ui.text_disabled(im_str!("Unknown id")),
+        }
+
+        if let Some(_t) = ui.begin_popup(im_str!("tlk_picker")) {
+            ui.input_text(im_str!("Search"), picker_filter).resize_buffer(true).build();
+
+            let strings: Vec<_> = tlk
+                .iter()
+                .filter(|(id, string)| matches_filter(*id, string, picker_filter.to_str()))
+                .collect();
+
+            if let Some(_t) = ChildWindow::new("scroll").size([400.0, 300.0]).begin(ui) {
+                let mut clipper = ListClipper::new(strings.len() as i32).begin(ui);
+                while clipper.step() {
+                    for i in clipper.display_start()..clipper.display_end() {
+                        let (id, string) = strings[i as usize];
+                        let label = ImString::new(format!("{:>9}  {}", id, string));
+                        if Selectable::new(&label).selected(id == *value).build(ui) {
+                            *value = id;
+                            ui.close_current_popup();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
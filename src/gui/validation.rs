@@ -0,0 +1,181 @@
+use imgui::{im_str, Condition, ImString, StyleColor, Window};
+
+use crate::{
+    event_handler::SaveGame,
+    save_data::shared::validation::{Issues, Tab, Validate},
+};
+
+use super::Gui;
+
+// Le résumé reste plus longtemps qu'une notification, le temps de cliquer sur "View"
+const SUMMARY_TIME: f64 = 6.0; // secondes
+
+// Résumé des problèmes de la save à l'ouverture et fenêtre de détail
+#[derive(Default)]
+pub struct HealthState {
+    pub opened: bool,
+    summary: Option<(ImString, f64)>,
+}
+
+impl HealthState {
+    pub fn set_opened_save(&mut self, save_game: &SaveGame, time: f64) {
+        let issues = save_issues(save_game);
+        self.summary = match issues.len() {
+            0 => None,
+            1 => Some((ImString::new("1 warning"), time + SUMMARY_TIME)),
+            len => Some((ImString::new(format!("{} warnings", len)), time + SUMMARY_TIME)),
+        };
+    }
+}
+
+// ME1 n'a pas de vérifications
+pub fn save_issues(save_game: &SaveGame) -> Issues {
+    match save_game {
+        SaveGame::MassEffect1 { .. } => Issues::default(),
+        SaveGame::MassEffect1Leg { save_game, .. } => save_game.save_data.issues(),
+        SaveGame::MassEffect2 { save_game, .. } => save_game.issues(),
+        SaveGame::MassEffect2Leg { save_game, .. } => save_game.issues(),
+        SaveGame::MassEffect3 { save_game, .. } => save_game.issues(),
+    }
+}
+
+fn tab_title(tab: Tab) -> &'static str {
+    match tab {
+        Tab::General => "General",
+        Tab::Inventory => "Inventory",
+        Tab::HeadMorph => "Head Morph",
+        Tab::Raw => "Raw",
+    }
+}
+
+// Le `###` garde le même id d'onglet avec ou sans badge
+pub fn tab_label(name: &str, has_issues: bool, locked: bool) -> ImString {
+    let issues = if has_issues { " (!)" } else { "" };
+    let locked = if locked { " (locked)" } else { "" };
+    ImString::new(format!("{}{}{}###{}", name, issues, locked, name))
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_validation_issues(&self, issues: &Issues, tab: Tab) {
+        let ui = self.ui;
+        if !issues.has(tab) {
+            return;
+        }
+
+        for message in issues.tab(tab) {
+            ui.text_colored([0.9, 0.8, 0.3, 1.0], message);
+        }
+        ui.separator();
+    }
+
+    // Sous les notifications, en haut de la fenêtre
+    pub fn draw_health_summary(&self, health: &mut HealthState) {
+        let ui = self.ui;
+        let time = ui.time();
+
+        let summary = match &health.summary {
+            Some((summary, close_time)) if *close_time >= time => summary,
+            Some(_) => {
+                health.summary = None;
+                return;
+            }
+            None => return,
+        };
+
+        let _style = ui.push_style_color(StyleColor::WindowBg, [0.0, 0.0, 0.0, 0.3]);
+        let window = Window::new(im_str!("###health_summary"))
+            .position([ui.io().display_size[0] / 2.0, 50.0], Condition::Always)
+            .title_bar(false)
+            .resizable(false)
+            .movable(false)
+            .always_auto_resize(true);
+
+        let mut view = false;
+        let mut dismiss = false;
+        if let Some(_t) = window.begin(ui) {
+            ui.text_colored([0.9, 0.8, 0.3, 1.0], summary);
+            ui.same_line();
+            view = ui.small_button(im_str!("View"));
+            ui.same_line();
+            dismiss = ui.small_button(im_str!("x"));
+        }
+
+        if view {
+            health.opened = true;
+        }
+        if view || dismiss {
+            health.summary = None;
+        }
+    }
+
+    pub fn draw_health(
+        &self, health: &mut HealthState, save_game: &Option<SaveGame>, issues: &mut Option<Issues>,
+    ) {
+        let ui = self.ui;
+
+        if !health.opened {
+            return;
+        }
+
+        let window = Window::new(im_str!("Save health"))
+            .size([500.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut health.opened);
+
+        if let Some(_t) = window.begin(ui) {
+            let save_game = match save_game {
+                Some(save_game) => save_game,
+                None => {
+                    ui.text_disabled(im_str!("Open a save first"));
+                    return;
+                }
+            };
+
+            let issues = issues.get_or_insert_with(|| save_issues(save_game));
+            if issues.is_empty() {
+                ui.text(im_str!("No issues found"));
+                return;
+            }
+
+            ui.text_disabled(im_str!(
+                "The same warnings are shown at the top of their tab, marked with (!)"
+            ));
+            for &tab in &[Tab::General, Tab::Inventory, Tab::HeadMorph, Tab::Raw] {
+                if !issues.has(tab) {
+                    continue;
+                }
+                ui.separator();
+                ui.text(tab_title(tab));
+                for message in issues.tab(tab) {
+                    ui.bullet_text(&ImString::new(message));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn health_summary() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        save_game.player.credits = -1;
+        save_game.player.medigel = -1;
+        let save_game =
+            SaveGame::MassEffect2 { file_path: String::new(), save_game: Box::new(save_game) };
+
+        let mut health = HealthState::default();
+        health.set_opened_save(&save_game, 0.0);
+        assert_eq!(
+            health.summary.as_ref().map(|(summary, _)| summary.to_str()),
+            Some("2 warnings")
+        );
+        Ok(())
+    }
+}
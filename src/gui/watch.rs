@@ -0,0 +1,188 @@
+use imgui::{im_str, ChildWindow, Condition, InputInt, Window};
+
+use crate::{event_handler::SaveGame, save_data::RawUi};
+
+use super::{raw_ui, Gui};
+
+#[derive(Clone, PartialEq)]
+pub enum Watch {
+    Raw(Vec<String>),
+    Bool(usize),
+    Int(usize),
+    Float(usize),
+}
+
+impl Watch {
+    fn label(&self) -> String {
+        match self {
+            Watch::Raw(path) => path.join(" > "),
+            Watch::Bool(id) => format!("Bool {}", id),
+            Watch::Int(id) => format!("Int {}", id),
+            Watch::Float(id) => format!("Float {}", id),
+        }
+    }
+}
+
+// Partagé avec le Gui pour que les menus contextuels puissent ajouter des watches
+#[derive(Default)]
+pub struct WatchState {
+    pub opened: bool,
+    watches: Vec<Watch>,
+    new_kind: usize,
+    new_id: i32,
+}
+
+impl WatchState {
+    pub fn add(&mut self, watch: Watch) {
+        if !self.watches.contains(&watch) {
+            self.watches.push(watch);
+        }
+        self.opened = true;
+    }
+}
+
+impl<'ui> Gui<'ui> {
+    pub fn draw_watch_panel(&self, save_game: &mut Option<SaveGame>) {
+        const ITEMS: [&imgui::ImStr; 3] = [im_str!("Bool"), im_str!("Int"), im_str!("Float")];
+
+        let ui = self.ui;
+        let mut state = self.watch.borrow_mut();
+
+        if !state.opened {
+            return;
+        }
+
+        let mut opened = true;
+        let window = Window::new(im_str!("Watch"))
+            .size([400.0, 300.0], Condition::FirstUseEver)
+            .opened(&mut opened);
+
+        if let Some(_t) = window.begin(ui) {
+            // Ajout d'une variable de plot par id
+            let width = ui.push_item_width(80.0);
+            self.draw_edit_enum("##kind", &mut state.new_kind, &ITEMS);
+            ui.same_line();
+            InputInt::new(ui, im_str!("##id"), &mut state.new_id).build();
+            width.pop(ui);
+            ui.same_line();
+            if ui.button(im_str!("Add plot variable")) {
+                let id = state.new_id.max(0) as usize;
+                let watch = match state.new_kind {
+                    0 => Watch::Bool(id),
+                    1 => Watch::Int(id),
+                    _ => Watch::Float(id),
+                };
+                state.add(watch);
+            }
+            ui.same_line();
+            if ui.button(im_str!("Clear")) {
+                state.watches.clear();
+            }
+            ui.separator();
+
+            if let Some(_t) = ChildWindow::new("scroll").begin(ui) {
+                match save_game {
+                    Some(save_game) if !state.watches.is_empty() => {
+                        let mut remove = None;
+                        for (idx, watch) in state.watches.iter().enumerate() {
+                            let _t = ui.push_id(idx as i32);
+                            if ui.small_button(im_str!("x")) {
+                                remove = Some(idx);
+                            }
+                            ui.same_line();
+                            self.draw_watch(save_game, watch);
+                        }
+                        if let Some(idx) = remove {
+                            state.watches.remove(idx);
+                        }
+                    }
+                    Some(_) => ui.text_disabled(im_str!(
+                        "Right click on a raw field or a plot variable to watch it"
+                    )),
+                    None => ui.text_disabled(im_str!("Open a save to see watched values")),
+                }
+            }
+        }
+
+        if !opened {
+            state.opened = false;
+        }
+    }
+
+    fn draw_watch(&self, save_game: &mut SaveGame, watch: &Watch) {
+        let ident = watch.label();
+        let drawn = match watch {
+            Watch::Raw(path) => {
+                raw_ui::draw_watch_save_game(self, save_game, &ident, path);
+                true
+            }
+            Watch::Bool(id) => self.draw_watch_bool(save_game, &ident, *id),
+            Watch::Int(id) => self.draw_watch_int(save_game, &ident, *id),
+            Watch::Float(id) => self.draw_watch_float(save_game, &ident, *id),
+        };
+
+        if !drawn {
+            self.ui.text_disabled(format!("{} : not found", ident));
+        }
+    }
+
+    fn draw_watch_bool(&self, save_game: &mut SaveGame, ident: &str, id: usize) -> bool {
+        match save_game.plot_booleans_mut().get_mut(id) {
+            Some(mut value) => {
+                self.draw_edit_bool(ident, &mut value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn draw_watch_int(&self, save_game: &mut SaveGame, ident: &str, id: usize) -> bool {
+        let value = match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                save_game.state.plot.int_variables.get_mut(id)
+            }
+            SaveGame::MassEffect1Leg { save_game, .. } => {
+                save_game.save_data.plot.int_variables.get_mut(id)
+            }
+            SaveGame::MassEffect2 { save_game, .. } => save_game.plot.int_variables.get_mut(id),
+            SaveGame::MassEffect2Leg { save_game, .. } => save_game.plot.int_variables.get_mut(id),
+            SaveGame::MassEffect3 { save_game, .. } => {
+                save_game.plot.int_variables.get_mut(&(id as i32))
+            }
+        };
+
+        match value {
+            Some(value) => {
+                value.draw_raw_ui(self, ident);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn draw_watch_float(&self, save_game: &mut SaveGame, ident: &str, id: usize) -> bool {
+        let value = match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                save_game.state.plot.float_variables.get_mut(id)
+            }
+            SaveGame::MassEffect1Leg { save_game, .. } => {
+                save_game.save_data.plot.float_variables.get_mut(id)
+            }
+            SaveGame::MassEffect2 { save_game, .. } => save_game.plot.float_variables.get_mut(id),
+            SaveGame::MassEffect2Leg { save_game, .. } => {
+                save_game.plot.float_variables.get_mut(id)
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                save_game.plot.float_variables.get_mut(&(id as i32))
+            }
+        };
+
+        match value {
+            Some(value) => {
+                value.draw_raw_ui(self, ident);
+                true
+            }
+            None => false,
+        }
+    }
+}
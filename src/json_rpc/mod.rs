@@ -0,0 +1,493 @@
+use anyhow::{anyhow, bail, Context, Error, Result};
+use flume::{Receiver, Sender};
+use serde_json::{json, Value};
+use std::{
+    convert::TryFrom,
+    io::{BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    time::Duration,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    event_handler::{MainEvent, SaveGame},
+    gui::UiEvent,
+    save_data::shared::string_search,
+};
+
+// Mode sans interface : les outils externes pilotent l'éditeur en JSON-RPC 2.0 sur HTTP.
+// Mêmes événements que la GUI, une seule save ouverte à la fois.
+pub const DEFAULT_PORT: u16 = 47_219;
+const TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_BODY: usize = 1024 * 1024;
+// Au-delà des plus grands IDs des 3 jeux, évite de faire exploser le BoolVec
+const MAX_PLOT_ID: usize = 100_000;
+
+// Codes d'erreur JSON-RPC
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const SERVER_ERROR: i32 = -32000;
+
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl From<Error> for RpcError {
+    fn from(err: Error) -> Self {
+        RpcError { code: SERVER_ERROR, message: format!("{:#}", err) }
+    }
+}
+
+fn invalid_params(message: &str) -> RpcError {
+    RpcError { code: INVALID_PARAMS, message: message.to_owned() }
+}
+
+pub fn serve(port: u16, event_addr: Sender<MainEvent>, ui_rx: Receiver<UiEvent>) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("Failed to listen on port {}", port))?;
+    info!("JSON-RPC server listening on http://{}", listener.local_addr()?);
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    // Le token n'est jamais loggé, seulement affiché au lanceur du serveur
+    let token = session_token();
+    println!("Send `Authorization: Bearer {}` with every request", token);
+
+    // Compression ME1... (l'arrondi des floats ne concerne que les widgets)
+    let _ = event_addr.send(MainEvent::LoadSettings);
+    Server::new(event_addr, ui_rx, token).run(listener);
+    Ok(())
+}
+
+// UUID v4, 122 bits tirés du générateur aléatoire de l'OS
+fn session_token() -> String {
+    Uuid::new_v4().to_simple().to_string()
+}
+
+struct Server {
+    event_addr: Sender<MainEvent>,
+    ui_rx: Receiver<UiEvent>,
+    token: String,
+    save_game: Option<SaveGame>,
+}
+
+impl Server {
+    fn new(event_addr: Sender<MainEvent>, ui_rx: Receiver<UiEvent>, token: String) -> Self {
+        Server { event_addr, ui_rx, token, save_game: None }
+    }
+
+    // Une connexion à la fois, les requêtes passent dans l'ordre
+    fn run(mut self, listener: TcpListener) {
+        for stream in listener.incoming().flatten() {
+            if let Err(err) = self.handle_connection(stream) {
+                warn!("JSON-RPC : {:#}", err);
+            }
+        }
+    }
+
+    fn handle_connection(&mut self, mut stream: TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let port = stream.local_addr()?.port();
+        let request = read_http_request(&stream)?;
+        let (status, body) = match self.check_request(&request, port) {
+            Some(status) => (status, String::new()),
+            None => ("200 OK", self.handle_body(&request.body).to_string()),
+        };
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )?;
+        Ok(())
+    }
+
+    // Une page web peut joindre 127.0.0.1 (text/plain, DNS rebinding...), seuls les outils
+    // locaux qui ont le token passent
+    fn check_request(&self, request: &HttpRequest, port: u16) -> Option<&'static str> {
+        if !request.is_post {
+            return Some("405 Method Not Allowed");
+        }
+        let host_allowed = request.host.as_deref().map_or(false, |host| {
+            host == format!("127.0.0.1:{}", port) || host == format!("localhost:{}", port)
+        });
+        if request.has_origin || !host_allowed {
+            return Some("403 Forbidden");
+        }
+        let is_json = request.content_type.as_deref().map_or(false, |content_type| {
+            let mime = content_type.split(';').next().unwrap_or_default();
+            mime.trim().eq_ignore_ascii_case("application/json")
+        });
+        if !is_json {
+            return Some("415 Unsupported Media Type");
+        }
+        let token = request.authorization.as_deref().and_then(|auth| auth.strip_prefix("Bearer "));
+        if token.map(str::trim) != Some(self.token.as_str()) {
+            return Some("401 Unauthorized");
+        }
+        None
+    }
+
+    fn handle_body(&mut self, body: &str) -> Value {
+        let request: Value = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(err) => return error_response(Value::Null, PARSE_ERROR, &err.to_string()),
+        };
+
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let method = match request.get("method").and_then(Value::as_str) {
+            Some(method) => method,
+            None => return error_response(id, INVALID_REQUEST, "No method"),
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        match self.call(method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(RpcError { code, message }) => error_response(id, code, &message),
+        }
+    }
+
+    fn call(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        // Erreurs des événements qui n'attendaient pas de réponse
+        for event in self.ui_rx.try_iter() {
+            if let UiEvent::Error(err) = event {
+                warn!("{:#}", err);
+            }
+        }
+
+        let str_param = |name: &str| params.get(name).and_then(Value::as_str);
+        let match_case = params.get("match_case").and_then(Value::as_bool).unwrap_or(false);
+
+        let result = match method {
+            "open" => {
+                let path = str_param("path").ok_or_else(|| invalid_params("`path` expected"))?;
+                self.event_addr
+                    .send(MainEvent::OpenSave(path.to_owned()))
+                    .map_err(|_| anyhow!("The event handler stopped"))?;
+                match self.wait()? {
+                    UiEvent::OpenedSave(save_game, _) => self.save_game = Some(save_game),
+                    _ => return Err(anyhow!("The save was not opened").into()),
+                }
+                save_info(self.save_game()?)
+            }
+            "info" => save_info(self.save_game()?),
+            "get_plot_bool" => {
+                let id = params.get("id").and_then(as_usize);
+                let id = id.ok_or_else(|| invalid_params("`id` expected"))?;
+                let booleans = self.save_game()?.plot_booleans_mut();
+                booleans.get(id).map(|b| *b).unwrap_or_default().into()
+            }
+            "set_plot_bool" => {
+                let id = params.get("id").and_then(as_usize);
+                let value = params.get("value").and_then(Value::as_bool);
+                let (id, value) =
+                    id.zip(value).ok_or_else(|| invalid_params("`id` and `value` expected"))?;
+                if id > MAX_PLOT_ID {
+                    return Err(invalid_params(&format!("`id` must be at most {}", MAX_PLOT_ID)));
+                }
+
+                let booleans = self.save_game()?.plot_booleans_mut();
+                if id >= booleans.len() {
+                    booleans.resize(id + 1, false);
+                }
+                let previous = booleans[id];
+                booleans.set(id, value);
+                previous.into()
+            }
+            "search_strings" => {
+                let needle =
+                    str_param("needle").ok_or_else(|| invalid_params("`needle` expected"))?;
+                let raw_data = self.save_game()?.raw_data_mut();
+                let matches = string_search::search(raw_data, needle, match_case)?;
+                matches
+                    .into_iter()
+                    .map(|m| json!({ "path": m.path.join(" > "), "value": m.value }))
+                    .collect()
+            }
+            "replace_strings" => {
+                let needle = str_param("needle");
+                let replacement = str_param("replacement");
+                let (needle, replacement) = needle
+                    .zip(replacement)
+                    .ok_or_else(|| invalid_params("`needle` and `replacement` expected"))?;
+                // Sur une copie, rien n'est remplacé si une clé est refusée
+                let save_game = self.save_game()?;
+                let mut replaced = save_game.clone();
+                let count = string_search::replace(
+                    replaced.raw_data_mut(),
+                    needle,
+                    replacement,
+                    match_case,
+                    None,
+                )?;
+                *save_game = replaced;
+                count.into()
+            }
+            // Seulement par-dessus la save ouverte, pas d'écriture ailleurs sur le disque
+            "save" => {
+                let save_game = self.save_game()?.clone();
+                let path = save_game.file_path().to_owned();
+                if str_param("path").map_or(false, |param| param != path) {
+                    return Err(invalid_params("`path` must be the path of the opened save"));
+                }
+                self.event_addr
+                    .send(MainEvent::SaveSave(path.clone(), save_game))
+                    .map_err(|_| anyhow!("The event handler stopped"))?;
+                self.wait()?;
+                path.into()
+            }
+            _ => {
+                return Err(RpcError {
+                    code: METHOD_NOT_FOUND,
+                    message: format!("Unknown method `{}`", method),
+                })
+            }
+        };
+        Ok(result)
+    }
+
+    fn save_game(&mut self) -> Result<&mut SaveGame> {
+        self.save_game.as_mut().context("No save opened, call `open` first")
+    }
+
+    // Prochain événement qui termine l'opération, la progression est ignorée
+    fn wait(&self) -> Result<UiEvent> {
+        loop {
+            match self.ui_rx.recv_timeout(TIMEOUT).context("No response from the event handler")? {
+                UiEvent::Error(err) => return Err(err),
+                UiEvent::NewerSaveVersion(_, version, known_version) => bail!(
+                    "This save is version {}, newer than the supported version {}",
+                    version,
+                    known_version
+                ),
+                UiEvent::AmbiguousSaveFormat(..) => {
+                    bail!("The save format could not be detected from its content")
+                }
+                event @ (UiEvent::OpenedSave(..) | UiEvent::Notification(_)) => return Ok(event),
+                _ => (),
+            }
+        }
+    }
+}
+
+// Seulement les entiers positifs, les IDs de plot par exemple
+fn as_usize(value: &Value) -> Option<usize> {
+    value.as_u64().and_then(|n| usize::try_from(n).ok())
+}
+
+fn save_info(save_game: &SaveGame) -> Value {
+    json!({ "kind": save_game.kind(), "path": save_game.file_path() })
+}
+
+fn error_response(id: Value, code: i32, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "error": { "code": code, "message": message }, "id": id })
+}
+
+#[derive(Default)]
+struct HttpRequest {
+    is_post: bool,
+    host: Option<String>,
+    has_origin: bool,
+    content_type: Option<String>,
+    authorization: Option<String>,
+    body: String,
+}
+
+// Le corps n'est lu que pour un POST
+fn read_http_request(stream: &TcpStream) -> Result<HttpRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut request = HttpRequest { is_post: line.starts_with("POST "), ..Default::default() };
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => {
+                    content_length = value.parse().context("Invalid Content-Length")?
+                }
+                "host" => request.host = Some(value.to_owned()),
+                "origin" => request.has_origin = true,
+                "content-type" => request.content_type = Some(value.to_owned()),
+                "authorization" => request.authorization = Some(value.to_owned()),
+                _ => (),
+            }
+        }
+    }
+
+    if !request.is_post {
+        return Ok(request);
+    }
+    if content_length > MAX_BODY {
+        bail!("Request too large : {} bytes", content_length);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    request.body = String::from_utf8(body)?;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, thread};
+
+    use super::*;
+    use crate::event_handler;
+
+    const TOKEN: &str = "test-token";
+
+    fn send(port: u16, headers: &str, body: &str) -> Result<String> {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nHost: 127.0.0.1:{}\r\n{}Content-Length: {}\r\n\r\n{}",
+            port,
+            headers,
+            body.len(),
+            body
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    fn post(port: u16, body: &str) -> Result<Value> {
+        let headers =
+            format!("Content-Type: application/json\r\nAuthorization: Bearer {}\r\n", TOKEN);
+        let response = send(port, &headers, body)?;
+        let (_, body) = response.split_once("\r\n\r\n").context("No body")?;
+        Ok(serde_json::from_str(body)?)
+    }
+
+    fn start_server() -> Result<u16> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        let (event_addr, event_rx) = flume::unbounded();
+        let (ui_addr, ui_rx) = flume::unbounded();
+        thread::spawn(move || runtime.block_on(event_handler::event_loop(event_rx, ui_addr)));
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let port = listener.local_addr()?.port();
+        thread::spawn(move || Server::new(event_addr, ui_rx, TOKEN.to_owned()).run(listener));
+        Ok(port)
+    }
+
+    fn open_request(id: usize, path: &str) -> String {
+        json!({ "jsonrpc": "2.0", "id": id, "method": "open", "params": { "path": path } })
+            .to_string()
+    }
+
+    #[test]
+    fn open_edit_save() -> Result<()> {
+        let port = start_server()?;
+
+        // Pas encore de save
+        let response = post(port, r#"{"jsonrpc":"2.0","id":1,"method":"info"}"#)?;
+        assert!(response.get("error").is_some());
+
+        let path = std::env::temp_dir().join("tse_json_rpc_test.pcsav");
+        fs::copy("test/ME2Save.pcsav", &path)?;
+        let path = path.to_string_lossy().into_owned();
+
+        let response = post(port, &open_request(2, &path))?;
+        let result = response.get("result").context("open failed")?;
+        assert_eq!(result.get("kind").and_then(Value::as_str), Some("Mass Effect 2"));
+        assert_eq!(response.get("id"), Some(&json!(2)));
+
+        let response = post(
+            port,
+            r#"{"jsonrpc":"2.0","id":3,"method":"set_plot_bool","params":{"id":21,"value":false}}"#,
+        )?;
+        assert_eq!(response.get("result"), Some(&json!(true)));
+
+        // Pas d'ID démesuré
+        let response = post(
+            port,
+            r#"{"jsonrpc":"2.0","id":4,"method":"set_plot_bool","params":{"id":1e9,"value":true}}"#,
+        )?;
+        let code = response.get("error").and_then(|error| error.get("code"));
+        assert_eq!(code, Some(&json!(INVALID_PARAMS)));
+
+        // Pas d'écriture ailleurs que sur la save ouverte
+        let response = post(
+            port,
+            r#"{"jsonrpc":"2.0","id":5,"method":"save","params":{"path":"elsewhere.pcsav"}}"#,
+        )?;
+        let code = response.get("error").and_then(|error| error.get("code"));
+        assert_eq!(code, Some(&json!(INVALID_PARAMS)));
+
+        let response = post(port, r#"{"jsonrpc":"2.0","id":6,"method":"save"}"#)?;
+        assert!(response.get("result").is_some(), "{}", response);
+
+        // Relu depuis le disque
+        post(port, &open_request(7, &path))?;
+        let response =
+            post(port, r#"{"jsonrpc":"2.0","id":8,"method":"get_plot_bool","params":{"id":21}}"#)?;
+        assert_eq!(response.get("result"), Some(&json!(false)));
+        let _ = fs::remove_file(&path);
+
+        let response = post(port, r#"{"jsonrpc":"2.0","id":9,"method":"explode"}"#)?;
+        let code = response.get("error").and_then(|error| error.get("code"));
+        assert_eq!(code, Some(&json!(METHOD_NOT_FOUND)));
+
+        let response = post(port, "{not json")?;
+        let code = response.get("error").and_then(|error| error.get("code"));
+        assert_eq!(code, Some(&json!(PARSE_ERROR)));
+
+        // Trop imbriqué, refusé sans déborder la pile
+        let response = post(port, &"[".repeat(100_000))?;
+        let code = response.get("error").and_then(|error| error.get("code"));
+        assert_eq!(code, Some(&json!(PARSE_ERROR)));
+        Ok(())
+    }
+
+    #[test]
+    fn reject_foreign_requests() -> Result<()> {
+        let port = start_server()?;
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"info"}"#;
+        let auth = format!("Authorization: Bearer {}\r\n", TOKEN);
+        let json = "Content-Type: application/json\r\n";
+
+        // Sans token ou avec un mauvais
+        let response = send(port, json, body)?;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+        let response = send(port, &format!("{}Authorization: Bearer nope\r\n", json), body)?;
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+
+        // Requête d'une page web
+        let headers = format!("{}{}Origin: http://example.com\r\n", json, auth);
+        let response = send(port, &headers, body)?;
+        assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+        let response = send(port, &format!("Content-Type: text/plain\r\n{}", auth), body)?;
+        assert!(response.starts_with("HTTP/1.1 415"), "{}", response);
+
+        // DNS rebinding
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nHost: evil.example:{}\r\n{}{}Content-Length: {}\r\n\r\n{}",
+            port,
+            json,
+            auth,
+            body.len(),
+            body
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        assert!(response.starts_with("HTTP/1.1 403"), "{}", response);
+
+        let response = send(port, &format!("{}{}", json, auth), body)?;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        Ok(())
+    }
+}
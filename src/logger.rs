@@ -0,0 +1,113 @@
+use anyhow::Result;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    fs::File,
+    io::{self, Write},
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    fmt::writer::MakeWriter,
+    layer::{Context, SubscriberExt},
+    Layer, Registry,
+};
+
+const MAX_ENTRIES: usize = 2000;
+const LOG_FILE: &str = "trilogy_save_editor.log";
+
+pub struct LogEntry {
+    pub level: Level,
+    pub target: &'static str,
+    pub message: String,
+}
+
+// Partagé entre le subscriber et le log viewer
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl LogBuffer {
+    pub fn entries(&self) -> MutexGuard<'_, VecDeque<LogEntry>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries();
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+struct BufferLayer(LogBuffer);
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor(String::new());
+        event.record(&mut visitor);
+
+        self.0.push(LogEntry {
+            level: *metadata.level(),
+            target: metadata.target(),
+            message: visitor.0,
+        });
+    }
+}
+
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = if field.name() == "message" {
+            write!(self.0, "{:?}", value)
+        } else {
+            write!(self.0, " {}={:?}", field.name(), value)
+        };
+    }
+}
+
+#[derive(Clone)]
+struct LogFile(Arc<File>);
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.0).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.0).flush()
+    }
+}
+
+impl MakeWriter for LogFile {
+    type Writer = LogFile;
+
+    fn make_writer(&self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+pub fn init(verbose: bool) -> Result<LogBuffer> {
+    let buffer = LogBuffer::default();
+
+    let level = if verbose { LevelFilter::DEBUG } else { LevelFilter::INFO };
+    let subscriber = Registry::default().with(level).with(BufferLayer(LogBuffer::clone(&buffer)));
+
+    // Fichier à côté de l'exécutable
+    if verbose {
+        let path = std::env::current_exe()?.with_file_name(LOG_FILE);
+        let file = LogFile(Arc::new(File::create(path)?));
+        let file_layer = tracing_subscriber::fmt::layer().with_writer(file);
+        tracing::subscriber::set_global_default(subscriber.with(file_layer))?;
+    } else {
+        tracing::subscriber::set_global_default(subscriber)?;
+    }
+
+    Ok(buffer)
+}
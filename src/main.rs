@@ -4,6 +4,7 @@
 
 extern crate derive_more;
 
+use anyhow::{Context, Result};
 use clap::{Arg, ArgMatches};
 use std::panic::{self, PanicInfo};
 use tokio::task;
@@ -11,9 +12,14 @@ use tokio::task;
 #[macro_use]
 extern crate raw_ui_derive;
 
+mod batch;
 mod event_handler;
 mod gui;
+mod json_rpc;
+mod logger;
 mod save_data;
+mod shell_menu;
+mod single_instance;
 mod unreal;
 
 fn parse_args() -> ArgMatches<'static> {
@@ -21,7 +27,43 @@ fn parse_args() -> ArgMatches<'static> {
         .version(env!("CARGO_PKG_VERSION"))
         .author("by Karlitos")
         .about("A save editor for Mass Effect Trilogy (and Legendary)")
-        .arg(Arg::with_name("vulkan").long("vulkan").help("Use Vulkan backend"));
+        .arg(
+            Arg::with_name("renderer")
+                .long("renderer")
+                .value_name("RENDERER")
+                .possible_values(&gui::renderer_names())
+                .help("Choose the rendering backend, falls back to another one if it fails"),
+        )
+        .arg(Arg::with_name("vulkan").long("vulkan").help("Use Vulkan backend"))
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .help("Write a detailed log file next to the executable"),
+        )
+        .arg(
+            Arg::with_name("serve")
+                .long("serve")
+                .help("Run without window, open / edit / save over a local JSON-RPC API"),
+        )
+        .arg(
+            Arg::with_name("port")
+                .long("port")
+                .value_name("PORT")
+                .takes_value(true)
+                .requires("serve")
+                .help("Port of the JSON-RPC API"),
+        )
+        .arg(
+            Arg::with_name("export-schema")
+                .long("export-schema")
+                .value_name("FILE")
+                .help("Write a JSON Schema of the save structures, `-` to print it, and exit"),
+        )
+        .arg(
+            Arg::with_name("dev")
+                .long("dev")
+                .help("Enable the developer tools, like the generation of test saves"),
+        );
 
     #[cfg(target_os = "windows")]
     let app = app
@@ -31,7 +73,10 @@ fn parse_args() -> ArgMatches<'static> {
     #[cfg(target_os = "macos")]
     let app = app.arg(Arg::with_name("metal").long("metal").help("Use Metal backend"));
 
-    let app = app.arg(Arg::with_name("FILE").help("Mass Effect save file"));
+    let app =
+        app.arg(Arg::with_name("FILE").help(
+            "Mass Effect save file, `-` to read it from stdin or an http(s) URL to download it",
+        ));
 
     app.get_matches()
 }
@@ -48,16 +93,70 @@ async fn main() {
     }));
 
     let args = parse_args();
+    shell_menu::fix_working_dir();
+
+    let log_buffer = match logger::init(args.is_present("verbose")) {
+        Ok(log_buffer) => log_buffer,
+        Err(err) => {
+            eprintln!("Failed to initialize logging : {}", err);
+            logger::LogBuffer::default()
+        }
+    };
+    tracing::info!("Trilogy Save Editor v{}", env!("CARGO_PKG_VERSION"));
+
+    if let Some(path) = args.value_of("export-schema") {
+        let schema = save_data::schema::save_schema().to_string();
+        let result = match path {
+            "-" => {
+                println!("{}", schema);
+                Ok(())
+            }
+            path => std::fs::write(path, schema),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to export the schema : {}", err);
+        }
+        return;
+    }
+
+    // Pas de fenêtre ni d'instance unique, les requêtes passent par les mêmes événements
+    if args.is_present("serve") {
+        if let Err(err) = serve(&args) {
+            eprintln!("{:#}", err);
+        }
+        return;
+    }
+
+    // Le fichier est ouvert par l'instance déjà lancée
+    if let Some(file_path) = args.value_of("FILE") {
+        if single_instance::hand_over(file_path) {
+            return;
+        }
+    }
 
     let (event_addr, event_rx) = flume::unbounded();
     let (ui_addr, ui_rx) = flume::unbounded();
 
+    single_instance::listen(flume::Sender::clone(&event_addr));
+
     let event_loop = tokio::spawn(event_handler::event_loop(event_rx, ui_addr));
 
-    task::block_in_place(move || gui::run(event_addr, ui_rx, args));
+    task::block_in_place(move || gui::run(event_addr, ui_rx, args, log_buffer));
     event_loop.await.unwrap();
 }
 
+fn serve(args: &ArgMatches<'_>) -> Result<()> {
+    let port = match args.value_of("port") {
+        Some(port) => port.parse().with_context(|| format!("Invalid port `{}`", port))?,
+        None => json_rpc::DEFAULT_PORT,
+    };
+    let (event_addr, event_rx) = flume::unbounded();
+    let (ui_addr, ui_rx) = flume::unbounded();
+    tokio::spawn(event_handler::event_loop(event_rx, ui_addr));
+
+    task::block_in_place(|| json_rpc::serve(port, event_addr, ui_rx))
+}
+
 fn panic_hook(info: &PanicInfo<'_>) {
     let msg = match info.payload().downcast_ref::<&'static str>() {
         Some(s) => *s,
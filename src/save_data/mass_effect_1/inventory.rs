@@ -0,0 +1,229 @@
+use anyhow::{bail, Context, Result};
+use imgui::ImString;
+use std::cell::RefCell;
+
+use crate::unreal;
+
+use super::{
+    data::{ArrayType, Property},
+    item_db::{DbItem, Me1ItemDb},
+    player::Player,
+    property_copy::{property_name_of, resolve_object},
+};
+
+// Au-delà le jeu refuse de ramasser des objets
+pub const MAX_ITEMS: usize = 150;
+
+const INVENTORY: [&str; 3] = ["m_Player", "m_Squad", "m_Inventory"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InventoryItem {
+    pub object_id: i32,
+    pub item: DbItem,
+    pub sophistication: u8,
+}
+
+// Les objets de `m_aItem`, chacun est un objet de la save avec ses propres propriétés
+pub fn inventory_items(player: &Player) -> Result<Vec<InventoryItem>> {
+    let inventory = resolve_object(player, &INVENTORY)?;
+    let data = player.get_data(inventory as i32).borrow();
+    let array = data
+        .properties
+        .iter()
+        .find_map(|property| match property {
+            Property::Array { array, .. } if property_name_of(player, property) == "m_aItem" => {
+                Some(array)
+            }
+            _ => None,
+        })
+        .context("No `m_aItem` in the inventory")?;
+
+    Ok(array
+        .iter()
+        .filter_map(|item| match item {
+            ArrayType::Object(object_id) if *object_id > 0 => item_of(player, *object_id),
+            _ => None,
+        })
+        .collect())
+}
+
+fn item_of(player: &Player, object_id: i32) -> Option<InventoryItem> {
+    let data = player.get_data(object_id).borrow();
+    let (mut item_id, mut manufacturer_id, mut sophistication) = (None, None, 0);
+    for property in data.properties.iter() {
+        match (property_name_of(player, property).as_str(), property) {
+            ("m_nID", Property::Int { value, .. }) => item_id = Some(*value),
+            ("m_manufacturer", Property::Int { value, .. }) => manufacturer_id = Some(*value),
+            ("m_eSophistication", Property::Byte { value, .. }) => sophistication = *value,
+            _ => (),
+        }
+    }
+    Some(InventoryItem {
+        object_id,
+        item: DbItem { item_id: item_id?, manufacturer_id: manufacturer_id? },
+        sophistication,
+    })
+}
+
+// Les objets d'une même catégorie ont la même classe et les mêmes propriétés :
+// `[Weapon Mod]`, `[Grenade]`... pour les mods, `Pistol`, `Armor`... pour l'équipement
+fn category(name: &str) -> &str {
+    match name.find(']') {
+        Some(end) if name[..end].ends_with("Mod") || end + 1 == name.len() => &name[..=end],
+        _ => name.trim_end_matches(')').rsplit(' ').next().unwrap_or(name),
+    }
+}
+
+// Copie un objet de l'inventaire de la même catégorie, sa classe et ses noms existent donc déjà
+pub fn grant_item(
+    player: &mut Player, item_db: &Me1ItemDb, item: DbItem, sophistication: u8,
+) -> Result<i32> {
+    let items = inventory_items(player)?;
+    if items.len() >= MAX_ITEMS {
+        bail!("The inventory is full, {} items at most", MAX_ITEMS);
+    }
+
+    let name = item_db.get(&item).context("Unknown item")?;
+    let template = items
+        .iter()
+        .find(|other| other.item == item)
+        .or_else(|| {
+            items.iter().find(|other| {
+                item_db.get(&other.item).map(|other| category(other)) == Some(category(name))
+            })
+        })
+        .map(|other| other.object_id)
+        .with_context(|| {
+            format!(
+                "The inventory needs an item of the same kind ({}) to copy its structure",
+                category(name)
+            )
+        })?;
+
+    let mut object = player.get_object(template).clone();
+    object.value_id = player
+        .objects
+        .iter()
+        .filter(|other| other.object_name_id == object.object_name_id)
+        .map(|other| other.value_id + 1)
+        .max()
+        .unwrap_or(object.value_id);
+
+    let owner_name = unique_owner_name(player, &items, template);
+    let mut data = player.get_data(template).borrow().clone();
+    for property in data.properties.iter_mut() {
+        match (property_name_of(player, property).as_str(), property) {
+            ("m_nID", Property::Int { value, .. }) => *value = item.item_id,
+            ("m_manufacturer", Property::Int { value, .. }) => *value = item.manufacturer_id,
+            ("m_eSophistication", Property::Byte { value, .. }) => *value = sophistication,
+            ("OwnerName", Property::Str { size, string, .. }) => {
+                if let Some(owner_name) = &owner_name {
+                    *string = ImString::new(owner_name).into();
+                    *size = unreal::Serializer::to_byte_buf(string)?.len() as u32;
+                }
+            }
+            // Emplacements vides, les mods appartiennent à l'objet copié
+            ("m_aSlotSpec", Property::Array { array, .. }) => {
+                for slot in array.iter_mut() {
+                    if let ArrayType::Properties(properties) = slot {
+                        for property in properties.iter_mut() {
+                            if let Property::Array { array, .. } = property {
+                                for xmod in array.iter_mut() {
+                                    if let ArrayType::Object(object_id) = xmod {
+                                        *object_id = 0;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    player.objects.push(object);
+    player.datas.push(RefCell::new(data));
+    let object_id = player.objects.len() as i32;
+
+    let inventory = resolve_object(player, &INVENTORY)?;
+    let mut data = player.get_data(inventory as i32).borrow_mut();
+    for property in data.properties.iter_mut() {
+        if property_name_of(player, property) != "m_aItem" {
+            continue;
+        }
+        if let Property::Array { size, array, .. } = property {
+            array.push(ArrayType::Object(object_id));
+            *size += 4;
+        }
+    }
+    Ok(object_id)
+}
+
+// `Player.BioItemWeaponRanged_15` => `Player.BioItemWeaponRanged_N`, N au-delà de ceux de l'inventaire
+fn unique_owner_name(player: &Player, items: &[InventoryItem], template: i32) -> Option<String> {
+    let owner_name = |object_id: i32| {
+        player.get_data(object_id).borrow().properties.iter().find_map(|property| match property {
+            Property::Str { string, .. } if property_name_of(player, property) == "OwnerName" => {
+                Some(string.to_string())
+            }
+            _ => None,
+        })
+    };
+
+    let template_name = owner_name(template)?;
+    let base = &template_name[..template_name.rfind('_')?];
+    let last = items
+        .iter()
+        .filter_map(|item| owner_name(item.object_id))
+        .filter_map(|name| name.strip_prefix(base)?.strip_prefix('_')?.parse::<u32>().ok())
+        .max()?;
+    Some(format!("{}_{}", base, last + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn grant_items() -> Result<()> {
+        let input = fs::read("test/Clare00_AutoSave.MassEffectSave")?;
+        let mut save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let item_db: Me1ItemDb = ron::from_str(&fs::read_to_string("databases/me1_item_db.ron")?)?;
+        let len = inventory_items(&save_game.player)?.len();
+
+        assert_eq!(category("[Weapon Mod] Kinetic Coil"), "[Weapon Mod]");
+        assert_eq!(category("[Grenade]"), "[Grenade]");
+        assert_eq!(category("[Hahne-Kedar] Mantis (Human Light Armor)"), "Armor");
+        assert_eq!(category("[Spectre Master Gear] HMWP Pistol"), "Pistol");
+
+        // Un mod absent de l'inventaire et une armure
+        let improved_sighting = DbItem { item_id: 305, manufacturer_id: -1 };
+        grant_item(&mut save_game.player, &item_db, improved_sighting, 7)?;
+        let (&armor, _) = item_db
+            .iter()
+            .find(|(_, name)| name.contains("(Human Heavy Armor)"))
+            .context("No armor")?;
+        grant_item(&mut save_game.player, &item_db, armor, 10)?;
+
+        // Pas d'amplificateur à copier
+        let (&amp, _) =
+            item_db.iter().find(|(_, name)| name.ends_with(" Amp")).context("No amp")?;
+        assert!(grant_item(&mut save_game.player, &item_db, amp, 1).is_err());
+
+        // Les offsets et tailles sont recalculés à la sérialisation
+        let output = unreal::Serializer::to_byte_buf(&save_game)?;
+        let save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        let items = inventory_items(&save_game.player)?;
+        assert_eq!(items.len(), len + 2);
+        assert_eq!(items[len].item, improved_sighting);
+        assert_eq!(items[len].sophistication, 7);
+        assert_eq!((items[len + 1].item, items[len + 1].sophistication), (armor, 10));
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use derive_more::Deref;
 use indexmap::IndexMap;
 use serde::Deserialize;
 
-#[derive(Deserialize, PartialEq, Eq, Hash)]
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct DbItem {
     pub item_id: i32,
     pub manufacturer_id: i32,
@@ -1,10 +1,11 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{de, ser::SerializeStruct, Deserialize, Serialize};
 use std::{
     fmt,
-    io::{Cursor, Read, Write},
+    io::{Cursor, Read},
+    panic, thread,
 };
-use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+use zip::ZipArchive;
 
 use crate::unreal;
 
@@ -17,8 +18,13 @@ pub mod state;
 use self::state::*;
 
 pub mod data;
+pub mod inventory;
 pub mod item_db;
 pub mod plot_db;
+pub mod plot_labels;
+pub mod property_copy;
+pub mod property_text;
+pub mod zip_writer;
 
 #[derive(Clone)]
 pub struct Me1SaveGame {
@@ -60,32 +66,114 @@ impl Me1SaveGame {
         Ok((player, state, _world_save_package))
     }
 
+    // Chaque membre est sérialisé et compressé sur son propre thread, sauf player.sav
+    // qui contient des RefCell et reste sur celui-ci
     fn zip(&self) -> Result<List<u8>> {
-        let mut zip = Vec::new();
-        {
-            let mut zipper = ZipWriter::new(Cursor::new(&mut zip));
-            let options = FileOptions::default().compression_method(CompressionMethod::DEFLATE);
+        fn join<T>(handle: thread::ScopedJoinHandle<T>) -> T {
+            handle.join().unwrap_or_else(|err| panic::resume_unwind(err))
+        }
 
-            // Player
-            {
-                let player_data = unreal::Serializer::to_byte_buf(&self.player)?;
-                zipper.start_file("player.sav", options)?;
-                zipper.write_all(&player_data)?;
-            }
-            // State
-            {
-                let state_data = unreal::Serializer::to_byte_buf(&self.state)?;
-                zipper.start_file("state.sav", options)?;
-                zipper.write_all(&state_data)?;
+        let compression = zip_writer::zip_compression();
+        let compress = move |member: ZipMember, data: unreal::Result<Vec<u8>>| {
+            zip_writer::compress_file(member.file_name(), &data?, compression)
+        };
+
+        let (player, state, world_save_package) = thread::scope(|scope| {
+            let state = &self.state;
+            let state = scope
+                .spawn(move || compress(ZipMember::State, unreal::Serializer::to_byte_buf(state)));
+            let world_save_package = self._world_save_package.as_ref().map(|world_save_package| {
+                scope.spawn(move || {
+                    compress(
+                        ZipMember::WorldSavePackage,
+                        unreal::Serializer::to_byte_buf(world_save_package),
+                    )
+                })
+            });
+            let player = compress(ZipMember::Player, unreal::Serializer::to_byte_buf(&self.player));
+            (player, join(state), world_save_package.map(join))
+        });
+
+        let mut zip = zip_writer::ZipStream::new(Vec::new());
+        zip.write_file(&player?)?;
+        zip.write_file(&state?)?;
+        if let Some(world_save_package) = world_save_package {
+            zip.write_file(&world_save_package?)?;
+        }
+        Ok(zip.finish()?.into())
+    }
+
+    // WorldSavePackage.sav n'est pas dans toutes les saves
+    pub fn members(&self) -> Vec<ZipMember> {
+        let mut members = vec![ZipMember::Player, ZipMember::State];
+        if self._world_save_package.is_some() {
+            members.push(ZipMember::WorldSavePackage);
+        }
+        members
+    }
+
+    pub fn export_member(&self, member: ZipMember) -> Result<Vec<u8>> {
+        let data = match member {
+            ZipMember::Player => unreal::Serializer::to_byte_buf(&self.player)?,
+            ZipMember::State => unreal::Serializer::to_byte_buf(&self.state)?,
+            ZipMember::WorldSavePackage => match &self._world_save_package {
+                Some(world_save_package) => unreal::Serializer::to_byte_buf(world_save_package)?,
+                None => bail!("This save has no WorldSavePackage.sav"),
+            },
+        };
+        Ok(data)
+    }
+
+    // Le membre n'est remplacé que s'il est lu en entier et réécrit à l'identique en taille,
+    // sinon la save reste intacte
+    pub fn import_member(&mut self, member: ZipMember, input: &[u8]) -> Result<()> {
+        fn read_all<'a, T>(member: ZipMember, input: &'a [u8]) -> Result<T>
+        where
+            T: Deserialize<'a> + Serialize,
+        {
+            let (value, read) = unreal::Deserializer::from_bytes_partial(input)
+                .with_context(|| format!("Failed to read {}", member.file_name()))?;
+            let written = unreal::Serializer::to_byte_buf(&value)?.len();
+            if read != input.len() || written != input.len() {
+                bail!(
+                    "{} is not a valid member : {} bytes read and {} written back out of {}",
+                    member.file_name(),
+                    read,
+                    written,
+                    input.len()
+                );
             }
-            // WorldSavePackage
-            if let Some(_world_save_package) = &self._world_save_package {
-                let world_save_package_data = unreal::Serializer::to_byte_buf(_world_save_package)?;
-                zipper.start_file("WorldSavePackage.sav", options)?;
-                zipper.write_all(&world_save_package_data)?;
+            Ok(value)
+        }
+
+        match member {
+            ZipMember::Player => self.player = read_all(member, input)?,
+            ZipMember::State => self.state = read_all(member, input)?,
+            ZipMember::WorldSavePackage => {
+                if self._world_save_package.is_none() {
+                    bail!("This save has no WorldSavePackage.sav");
+                }
+                self._world_save_package = Some(read_all(member, input)?);
             }
         }
-        Ok(zip.into())
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ZipMember {
+    Player,
+    State,
+    WorldSavePackage,
+}
+
+impl ZipMember {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ZipMember::Player => "player.sav",
+            ZipMember::State => "state.sav",
+            ZipMember::WorldSavePackage => "WorldSavePackage.sav",
+        }
     }
 }
 
@@ -226,4 +314,34 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn export_import_member() -> Result<()> {
+        let input = std::fs::read("test/Clare00_AutoSave.MassEffectSave")?;
+        let mut me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let output = unreal::Serializer::to_byte_buf(&me1_save_game)?;
+
+        assert_eq!(
+            me1_save_game.members(),
+            [ZipMember::Player, ZipMember::State, ZipMember::WorldSavePackage]
+        );
+        for member in me1_save_game.members() {
+            let data = me1_save_game.export_member(member)?;
+            me1_save_game.import_member(member, &data)?;
+        }
+
+        // Un membre illisible est refusé et la save reste intacte
+        let player = me1_save_game.export_member(ZipMember::Player)?;
+        assert!(me1_save_game
+            .import_member(ZipMember::Player, &player[..player.len() / 2])
+            .is_err());
+        let mut longer = player.clone();
+        longer.push(0);
+        assert!(me1_save_game.import_member(ZipMember::Player, &longer).is_err());
+        assert!(me1_save_game.import_member(ZipMember::State, &[0; 4]).is_err());
+
+        let output_2 = unreal::Serializer::to_byte_buf(&me1_save_game)?;
+        assert_eq!(output, output_2);
+        Ok(())
+    }
 }
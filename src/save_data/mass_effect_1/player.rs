@@ -17,10 +17,10 @@ pub struct Player {
     _no_mans_land1: List<u8>,
     header: Header,
     pub names: List<RefCell<Name>>,
-    classes: List<Class>,
+    pub(super) classes: List<Class>,
     pub objects: List<Object>,
     _no_mans_land2: List<u8>,
-    datas: List<RefCell<Data>>,
+    pub(super) datas: List<RefCell<Data>>,
     pub duplicate: RefCell<Option<Name>>, // Spécial n'est pas (dé)sérialisé
 }
 
@@ -142,6 +142,7 @@ impl serde::Serialize for Player {
         let mut header = header.clone();
 
         header.name_len = names.len() as u32;
+        header.objects_len = objects.len() as u32;
         header.classes_offset = header.name_offset;
         for name in names.iter() {
             header.classes_offset += name.borrow().size().map_err(Error::custom)? as u32;
@@ -215,9 +216,9 @@ impl Name {
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Class {
-    package_id: u32,
+    pub(super) package_id: u32,
     _osef1: Dummy<4>,
-    base_name_id: u32,
+    pub(super) base_name_id: u32,
     _osef2: Dummy<4>,
     link_id: u32,
     pub class_name_id: u32,
@@ -228,9 +229,9 @@ pub struct Class {
 pub struct Object {
     pub class_id: i32,
     class_parent_id: u32,
-    link_id: u32,
+    pub(super) link_id: u32,
     pub object_name_id: u32,
-    value_id: u32,
+    pub(super) value_id: u32,
     archtype_id: u32,
     flag: u64,
     pub data_size: u32,
@@ -1,7 +1,7 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::save_data::shared::plot::PlotCategory;
+use crate::save_data::shared::plot::{named_categories, PlotCategory};
 
 #[derive(Deserialize)]
 pub struct Me1PlotDb {
@@ -9,6 +9,17 @@ pub struct Me1PlotDb {
     pub missions: IndexMap<String, PlotCategory>,
 }
 
+impl Me1PlotDb {
+    pub fn categories(&self) -> impl Iterator<Item = &PlotCategory> {
+        self.named_categories().map(|(_, category)| category)
+    }
+
+    pub fn named_categories(&self) -> impl Iterator<Item = (String, &PlotCategory)> {
+        named_categories("Player / Crew", &self.player_crew)
+            .chain(named_categories("Missions", &self.missions))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use anyhow::Result;
@@ -0,0 +1,53 @@
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+// Labels de booléens de plot venant d'un fichier texte, là où le plot DB n'en a pas.
+// Une ligne `ID=label` par booléen, les lignes qui commencent par `#` sont des commentaires.
+#[derive(Default)]
+pub struct Me1PlotLabels {
+    pub file_path: String,
+    pub booleans: IndexMap<usize, String>,
+}
+
+impl Me1PlotLabels {
+    pub fn parse(file_path: String, text: &str) -> Result<Self> {
+        let mut booleans = IndexMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let context = || format!("Line {} : `{}`", i + 1, line);
+            let (id, label) =
+                line.split_once('=').with_context(|| format!("{}, no `=`", context()))?;
+            let id: usize = id.trim().parse().with_context(context)?;
+            booleans.insert(id, label.trim().to_owned());
+        }
+
+        if booleans.is_empty() {
+            bail!("No label found, expected `ID=label` lines");
+        }
+        Ok(Me1PlotLabels { file_path, booleans })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_labels() -> Result<()> {
+        let text = "# Noveria\n\n 4536 = Lorik Qui'in # Alive\r\n4537=Gianna Parasini\n";
+        let labels = Me1PlotLabels::parse("labels.txt".to_owned(), text)?;
+        assert_eq!(
+            labels.booleans.into_iter().collect::<Vec<_>>(),
+            [(4536, "Lorik Qui'in # Alive".to_owned()), (4537, "Gianna Parasini".to_owned())]
+        );
+
+        assert!(Me1PlotLabels::parse(String::new(), "4536 Lorik").is_err());
+        assert!(Me1PlotLabels::parse(String::new(), "Lorik=4536").is_err());
+        assert!(Me1PlotLabels::parse(String::new(), "# nothing").is_err());
+        Ok(())
+    }
+}
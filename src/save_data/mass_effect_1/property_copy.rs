@@ -0,0 +1,284 @@
+use anyhow::{bail, Context, Result};
+use std::{cell::RefCell, collections::HashMap};
+
+use super::{
+    data::{ArrayType, Property, StructType},
+    player::Player,
+};
+
+// Copie une propriété d'une save ME1 à une autre.
+// `path` part de l'objet `CurrentGame` et suit les propriétés objet : `m_Player.m_Squad.m_Inventory.m_aItem`
+pub fn copy_property(source: &Player, target: &mut Player, path: &str) -> Result<()> {
+    let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+    let (property_name, object_path) = match segments.split_last() {
+        Some((property_name, object_path)) if !property_name.is_empty() => {
+            (*property_name, object_path)
+        }
+        _ => bail!("Empty property path"),
+    };
+
+    let source_owner = resolve_object(source, object_path)?;
+    let target_owner = resolve_object(target, object_path)?;
+
+    let property = source.datas[source_owner - 1]
+        .borrow()
+        .properties
+        .iter()
+        .find(|property| property_name_of(source, property) == property_name)
+        .cloned()
+        .with_context(|| format!("`{}` not found in the source save", path))?;
+
+    // Les objets du chemin existent déjà dans les deux saves
+    let mut copier = Copier { source, target, objects: HashMap::new() };
+    copier.objects.insert(source_owner as i32, target_owner as i32);
+    let property = copier.property(&property)?;
+
+    let mut data = target.datas[target_owner - 1].borrow_mut();
+    let existing = data
+        .properties
+        .iter()
+        .position(|property| property_name_of(target, property) == property_name);
+    match existing {
+        Some(i) => data.properties[i] = property,
+        None => {
+            // Avant le `None` final
+            let end = data.properties.len().saturating_sub(1);
+            data.properties.insert(end, property);
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn property_name_of(player: &Player, property: &Property) -> String {
+    let name_id = match property {
+        Property::Array { name_id, .. }
+        | Property::Bool { name_id, .. }
+        | Property::Byte { name_id, .. }
+        | Property::Float { name_id, .. }
+        | Property::Int { name_id, .. }
+        | Property::Name { name_id, .. }
+        | Property::Object { name_id, .. }
+        | Property::Str { name_id, .. }
+        | Property::StringRef { name_id, .. }
+        | Property::Struct { name_id, .. }
+        | Property::None { name_id, .. } => *name_id,
+    };
+    player.get_name(name_id).borrow().to_string()
+}
+
+// Renvoie l'id (à partir de 1) de l'objet au bout du chemin
+pub(super) fn resolve_object(player: &Player, object_path: &[&str]) -> Result<usize> {
+    let mut current = player
+        .objects
+        .iter()
+        .position(|object| {
+            player.get_name(object.object_name_id).borrow().to_str() == "CurrentGame"
+        })
+        .context("No `CurrentGame` object in save")?
+        + 1;
+
+    for &segment in object_path {
+        let data = player.datas[current - 1].borrow();
+        current = data
+            .properties
+            .iter()
+            .find_map(|property| match property {
+                Property::Object { object_id, .. }
+                    if *object_id > 0 && property_name_of(player, property) == segment =>
+                {
+                    Some(*object_id as usize)
+                }
+                _ => None,
+            })
+            .with_context(|| format!("No object property `{}`", segment))?;
+    }
+    Ok(current)
+}
+
+struct Copier<'a> {
+    source: &'a Player,
+    target: &'a mut Player,
+    // id source => id cible
+    objects: HashMap<i32, i32>,
+}
+
+impl Copier<'_> {
+    fn name(&mut self, id: u32) -> u32 {
+        let name = self.source.get_name(id).borrow();
+        let existing =
+            self.target.names.iter().position(|other| other.borrow().to_str() == name.to_str());
+        match existing {
+            Some(id) => id as u32,
+            None => {
+                let mut name = name.clone();
+                name.is_duplicate = false;
+                self.target.names.push(RefCell::new(name));
+                self.target.names.len() as u32 - 1
+            }
+        }
+    }
+
+    // Les imports (classes, textures, etc.) doivent déjà exister dans la save cible
+    fn import(&self, id: i32) -> Result<i32> {
+        let key = |player: &Player, id: i32| {
+            let class = player.get_class(id);
+            [class.package_id, class.base_name_id, class.class_name_id]
+                .iter()
+                .map(|&name_id| player.get_name(name_id).borrow().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let source_key = key(self.source, id);
+        (1..=self.target.classes.len() as i32)
+            .map(|i| -i)
+            .find(|&target_id| key(self.target, target_id) == source_key)
+            .with_context(|| format!("`{}` is missing from the target save", source_key.join(".")))
+    }
+
+    fn object(&mut self, id: i32) -> Result<i32> {
+        if id == 0 {
+            return Ok(0);
+        }
+        if id < 0 {
+            return self.import(id);
+        }
+        if let Some(&target_id) = self.objects.get(&id) {
+            return Ok(target_id);
+        }
+
+        // L'emplacement est réservé avant la copie des références, qui peut ajouter d'autres objets
+        let mut object = self.source.get_object(id).clone();
+        let data = self.source.get_data(id).borrow().clone();
+        let target_id = self.target.objects.len() as i32 + 1;
+        self.objects.insert(id, target_id);
+        self.target.objects.push(object.clone());
+        self.target.datas.push(RefCell::new(data.clone()));
+
+        object.class_id = self.object(object.class_id)?;
+        object.object_name_id = self.name(object.object_name_id);
+        object.link_id = self.object(object.link_id as i32)? as u32;
+        // Nouvelle instance : `Nom_N` doit rester unique
+        let object_name_id = object.object_name_id;
+        object.value_id = self
+            .target
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|&(i, other)| {
+                i as i32 + 1 != target_id && other.object_name_id == object_name_id
+            })
+            .map(|(_, other)| other.value_id + 1)
+            .max()
+            .unwrap_or(object.value_id);
+        self.target.objects[target_id as usize - 1] = object;
+
+        let properties = self.properties(&data.properties)?;
+        self.target.datas[target_id as usize - 1].borrow_mut().properties = properties.into();
+
+        Ok(target_id)
+    }
+
+    fn properties(&mut self, properties: &[Property]) -> Result<Vec<Property>> {
+        properties.iter().map(|property| self.property(property)).collect()
+    }
+
+    fn property(&mut self, property: &Property) -> Result<Property> {
+        let mut property = property.clone();
+        match &mut property {
+            Property::Array { name_id, type_id, array, .. } => {
+                *name_id = self.name(*name_id);
+                *type_id = self.name(*type_id);
+                for item in array {
+                    match item {
+                        ArrayType::Object(object_id) => *object_id = self.object(*object_id)?,
+                        ArrayType::Properties(properties) => {
+                            *properties = self.properties(properties)?.into()
+                        }
+                        ArrayType::Int(_) | ArrayType::Vector(_) | ArrayType::String(_) => (),
+                    }
+                }
+            }
+            Property::Bool { name_id, type_id, .. }
+            | Property::Byte { name_id, type_id, .. }
+            | Property::Float { name_id, type_id, .. }
+            | Property::Int { name_id, type_id, .. }
+            | Property::Str { name_id, type_id, .. }
+            | Property::StringRef { name_id, type_id, .. } => {
+                *name_id = self.name(*name_id);
+                *type_id = self.name(*type_id);
+            }
+            Property::Name { name_id, type_id, value_name_id, .. } => {
+                *name_id = self.name(*name_id);
+                *type_id = self.name(*type_id);
+                *value_name_id = self.name(*value_name_id);
+            }
+            Property::Object { name_id, type_id, object_id, .. } => {
+                *name_id = self.name(*name_id);
+                *type_id = self.name(*type_id);
+                *object_id = self.object(*object_id)?;
+            }
+            Property::Struct { name_id, type_id, struct_name_id, properties, .. } => {
+                *name_id = self.name(*name_id);
+                *type_id = self.name(*type_id);
+                *struct_name_id = self.name(*struct_name_id);
+                if let StructType::Properties(properties) = properties {
+                    *properties = self.properties(properties)?.into();
+                }
+            }
+            Property::None { name_id, .. } => *name_id = self.name(*name_id),
+        }
+        Ok(property)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    use super::*;
+
+    fn open(path: &str) -> Result<Me1SaveGame> {
+        let mut input = Vec::new();
+        File::open(path)?.read_to_end(&mut input)?;
+        Ok(unreal::Deserializer::from_bytes(&input)?)
+    }
+
+    fn array_len(player: &Player, path: &[&str], property_name: &str) -> Result<usize> {
+        let owner = resolve_object(player, path)?;
+        let data = player.datas[owner - 1].borrow();
+        data.properties
+            .iter()
+            .find_map(|property| match property {
+                Property::Array { array, .. }
+                    if property_name_of(player, property) == property_name =>
+                {
+                    Some(array.len())
+                }
+                _ => None,
+            })
+            .context("Array not found")
+    }
+
+    #[test]
+    fn copy_inventory() -> Result<()> {
+        let source = open("test/Char_01-60-3-2-2-26-6-2018-57-26.MassEffectSave")?;
+        let mut target = open("test/Clare00_AutoSave.MassEffectSave")?;
+
+        let inventory = ["m_Player", "m_Squad", "m_Inventory"];
+        let source_len = array_len(&source.player, &inventory, "m_aItem")?;
+
+        copy_property(&source.player, &mut target.player, "m_Player.m_Squad.m_Inventory.m_aItem")?;
+        assert!(
+            copy_property(&source.player, &mut target.player, "m_Player.m_Nope.m_aItem").is_err()
+        );
+
+        // Les offsets et tailles sont recalculés à la sérialisation
+        let output = unreal::Serializer::to_byte_buf(&target)?;
+        let target: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(array_len(&target.player, &inventory, "m_aItem")?, source_len);
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+use anyhow::{bail, Context, Result};
+use imgui::ImString;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, convert::TryInto};
+
+use crate::save_data::{
+    shared::{appearance::LinearColor, Rotator, Vector},
+    Dummy, ImguiString,
+};
+
+use super::{
+    data::{ArrayType, Property, StructType},
+    player::Player,
+};
+
+// Propriétés d'un objet avec les noms résolus, pour diff et édition en dehors de l'éditeur
+#[derive(Deserialize, Serialize)]
+struct TextProperty {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    // Index dans un tableau statique
+    #[serde(default, skip_serializing_if = "is_zero")]
+    index: u32,
+    value: TextValue,
+}
+
+#[derive(Deserialize, Serialize)]
+enum TextValue {
+    Array(Vec<TextArrayItem>),
+    Bool(bool),
+    Byte(u8),
+    Float(f32),
+    Int(i32),
+    // Nom et numéro d'instance
+    Name(String, u32),
+    Object(i32),
+    Str(ImguiString),
+    StringRef(i32),
+    Struct(String, TextStruct),
+}
+
+#[derive(Deserialize, Serialize)]
+enum TextArrayItem {
+    Int(i32),
+    Object(i32),
+    Vector(Vector),
+    String(ImguiString),
+    Properties(Vec<TextProperty>),
+}
+
+#[derive(Deserialize, Serialize)]
+enum TextStruct {
+    LinearColor(LinearColor),
+    Vector(Vector),
+    Rotator(Rotator),
+    Properties(Vec<TextProperty>),
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+// Exporte les propriétés de l'objet `object_id` (à partir de 1)
+pub fn export_properties(player: &Player, object_id: i32) -> Result<String> {
+    if object_id < 1 || object_id as usize > player.objects.len() {
+        bail!("Object {} not found", object_id);
+    }
+
+    let data = player.get_data(object_id).borrow();
+    let properties = export_list(player, &data.properties);
+
+    let pretty_config = PrettyConfig::new().with_new_line(String::from('\n'));
+    Ok(ron::ser::to_string_pretty(&properties, pretty_config)?)
+}
+
+fn export_list(player: &Player, properties: &[Property]) -> Vec<TextProperty> {
+    properties.iter().filter_map(|property| export_property(player, property)).collect()
+}
+
+fn export_property(player: &Player, property: &Property) -> Option<TextProperty> {
+    let name = |id: u32| player.get_name(id).borrow().to_string();
+    let number = |dummy: &Dummy<4>| u32::from_le_bytes(dummy.0);
+
+    let (name_id, type_id, index, value) = match property {
+        Property::Array { name_id, type_id, _osef3, array, .. } => {
+            let array = array
+                .iter()
+                .map(|item| match item {
+                    ArrayType::Int(value) => TextArrayItem::Int(*value),
+                    ArrayType::Object(object_id) => TextArrayItem::Object(*object_id),
+                    ArrayType::Vector(vector) => TextArrayItem::Vector(vector.clone()),
+                    ArrayType::String(string) => TextArrayItem::String(string.clone()),
+                    ArrayType::Properties(properties) => {
+                        TextArrayItem::Properties(export_list(player, properties))
+                    }
+                })
+                .collect();
+            (name_id, type_id, _osef3, TextValue::Array(array))
+        }
+        Property::Bool { name_id, type_id, _osef3, value, .. } => {
+            (name_id, type_id, _osef3, TextValue::Bool(*value))
+        }
+        Property::Byte { name_id, type_id, _osef3, value, .. } => {
+            (name_id, type_id, _osef3, TextValue::Byte(*value))
+        }
+        Property::Float { name_id, type_id, _osef3, value, .. } => {
+            (name_id, type_id, _osef3, TextValue::Float(*value))
+        }
+        Property::Int { name_id, type_id, _osef3, value, .. } => {
+            (name_id, type_id, _osef3, TextValue::Int(*value))
+        }
+        Property::Name { name_id, type_id, _osef3, value_name_id, _osef4, .. } => {
+            (name_id, type_id, _osef3, TextValue::Name(name(*value_name_id), number(_osef4)))
+        }
+        Property::Object { name_id, type_id, _osef3, object_id, .. } => {
+            (name_id, type_id, _osef3, TextValue::Object(*object_id))
+        }
+        Property::Str { name_id, type_id, _osef3, string, .. } => {
+            (name_id, type_id, _osef3, TextValue::Str(string.clone()))
+        }
+        Property::StringRef { name_id, type_id, _osef3, value, .. } => {
+            (name_id, type_id, _osef3, TextValue::StringRef(*value))
+        }
+        Property::Struct { name_id, type_id, _osef3, struct_name_id, properties, .. } => {
+            let properties = match properties {
+                StructType::LinearColor(color) => TextStruct::LinearColor(color.clone()),
+                StructType::Vector(vector) => TextStruct::Vector(vector.clone()),
+                StructType::Rotator(rotator) => TextStruct::Rotator(rotator.clone()),
+                StructType::Properties(properties) => {
+                    TextStruct::Properties(export_list(player, properties))
+                }
+            };
+            (name_id, type_id, _osef3, TextValue::Struct(name(*struct_name_id), properties))
+        }
+        // Implicite, ajouté à l'import
+        Property::None { .. } => return None,
+    };
+
+    Some(TextProperty {
+        name: name(*name_id),
+        type_name: name(*type_id),
+        index: number(index),
+        value,
+    })
+}
+
+// Remplace les propriétés de l'objet `object_id`, les noms manquants sont ajoutés à la save
+pub fn import_properties(player: &mut Player, object_id: i32, text: &str) -> Result<()> {
+    if object_id < 1 || object_id as usize > player.objects.len() {
+        bail!("Object {} not found", object_id);
+    }
+
+    let properties: Vec<TextProperty> = ron::from_str(text)?;
+    let properties = import_list(player, properties)?;
+    player.get_data(object_id).borrow_mut().properties = properties.into();
+    Ok(())
+}
+
+fn import_list(player: &mut Player, properties: Vec<TextProperty>) -> Result<Vec<Property>> {
+    let mut list = properties
+        .into_iter()
+        .map(|property| import_property(player, property))
+        .collect::<Result<Vec<_>>>()?;
+
+    list.push(Property::None { name_id: add_name(player, "None"), _osef: Dummy::default() });
+    Ok(list)
+}
+
+fn import_property(player: &mut Player, property: TextProperty) -> Result<Property> {
+    let TextProperty { name, type_name, index, value } = property;
+    let name_id = add_name(player, &name);
+    let type_id = add_name(player, &type_name);
+    let (_osef1, _osef2, _osef3) = (Dummy::default(), Dummy::default(), Dummy(index.to_le_bytes()));
+
+    let expected_type = match value {
+        TextValue::Array(_) => "ArrayProperty",
+        TextValue::Bool(_) => "BoolProperty",
+        TextValue::Byte(_) => "ByteProperty",
+        TextValue::Float(_) => "FloatProperty",
+        TextValue::Int(_) => "IntProperty",
+        // Les enums sont des `ByteProperty` avec un nom comme valeur
+        TextValue::Name(..) if type_name == "ByteProperty" => "ByteProperty",
+        TextValue::Name(..) => "NameProperty",
+        TextValue::Object(_) => "ObjectProperty",
+        TextValue::Str(_) => "StrProperty",
+        TextValue::StringRef(_) => "StringRefProperty",
+        TextValue::Struct(..) => "StructProperty",
+    };
+    if type_name != expected_type {
+        bail!("`{}` : expected type `{}`, found `{}`", name, expected_type, type_name);
+    }
+
+    // La taille est recalculée une fois la propriété construite
+    let size = 0;
+    let mut property = match value {
+        TextValue::Array(array) => {
+            let array = array
+                .into_iter()
+                .map(|item| {
+                    Ok(match item {
+                        TextArrayItem::Int(value) => ArrayType::Int(value),
+                        TextArrayItem::Object(object_id) => ArrayType::Object(object_id),
+                        TextArrayItem::Vector(vector) => ArrayType::Vector(vector),
+                        TextArrayItem::String(string) => ArrayType::String(string),
+                        TextArrayItem::Properties(properties) => {
+                            ArrayType::Properties(import_list(player, properties)?.into())
+                        }
+                    })
+                })
+                .collect::<Result<_>>()
+                .with_context(|| format!("In `{}`", name))?;
+            Property::Array { name_id, _osef1, type_id, _osef2, size, _osef3, array }
+        }
+        TextValue::Bool(value) => {
+            Property::Bool { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+        }
+        TextValue::Byte(value) => {
+            Property::Byte { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+        }
+        TextValue::Float(value) => {
+            Property::Float { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+        }
+        TextValue::Int(value) => {
+            Property::Int { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+        }
+        TextValue::Name(value, number) => Property::Name {
+            name_id,
+            _osef1,
+            type_id,
+            _osef2,
+            size,
+            _osef3,
+            value_name_id: add_name(player, &value),
+            _osef4: Dummy(number.to_le_bytes()),
+        },
+        TextValue::Object(object_id) => {
+            Property::Object { name_id, _osef1, type_id, _osef2, size, _osef3, object_id }
+        }
+        TextValue::Str(string) => {
+            Property::Str { name_id, _osef1, type_id, _osef2, size, _osef3, string }
+        }
+        TextValue::StringRef(value) => {
+            Property::StringRef { name_id, _osef1, type_id, _osef2, size, _osef3, value }
+        }
+        TextValue::Struct(struct_name, properties) => {
+            let properties = match properties {
+                TextStruct::LinearColor(color) => StructType::LinearColor(color),
+                TextStruct::Vector(vector) => StructType::Vector(vector),
+                TextStruct::Rotator(rotator) => StructType::Rotator(rotator),
+                TextStruct::Properties(properties) => StructType::Properties(
+                    import_list(player, properties)
+                        .with_context(|| format!("In `{}`", name))?
+                        .into(),
+                ),
+            };
+            Property::Struct {
+                name_id,
+                _osef1,
+                type_id,
+                _osef2,
+                size,
+                _osef3,
+                struct_name_id: add_name(player, &struct_name),
+                _osef4: Dummy::default(),
+                properties,
+            }
+        }
+    };
+
+    // Taille des données, sans l'en-tête de 24 octets ni le nom de la struct
+    let data_size = match property {
+        Property::Bool { .. } => 0,
+        Property::Struct { .. } => property.size()? - 32,
+        _ => property.size()? - 24,
+    };
+    match &mut property {
+        Property::Array { size, .. }
+        | Property::Bool { size, .. }
+        | Property::Byte { size, .. }
+        | Property::Float { size, .. }
+        | Property::Int { size, .. }
+        | Property::Name { size, .. }
+        | Property::Object { size, .. }
+        | Property::Str { size, .. }
+        | Property::StringRef { size, .. }
+        | Property::Struct { size, .. } => *size = data_size.try_into()?,
+        Property::None { .. } => unreachable!(),
+    }
+    Ok(property)
+}
+
+fn add_name(player: &mut Player, name: &str) -> u32 {
+    let existing = player.names.iter().position(|other| other.borrow().to_str() == name);
+    match existing {
+        Some(id) => id as u32,
+        None => {
+            // Les flags sont repris d'un nom existant
+            let mut new_name = player.names[0].borrow().clone();
+            *new_name = ImguiString::from(ImString::new(name));
+            new_name.is_duplicate = false;
+            player.names.push(RefCell::new(new_name));
+            player.names.len() as u32 - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn export_import_properties() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/Clare00_AutoSave.MassEffectSave")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let player = &mut me1_save_game.player;
+        let original = unreal::Serializer::to_byte_buf(player)?;
+
+        // Aller-retour sans modification
+        for object_id in 1..=player.objects.len() as i32 {
+            let text = export_properties(player, object_id)?;
+            import_properties(player, object_id, &text)?;
+        }
+        assert_eq!(unreal::Serializer::to_byte_buf(player)?, original);
+
+        // Édition en texte
+        let current_game = player
+            .objects
+            .iter()
+            .position(|object| {
+                player.get_name(object.object_name_id).borrow().to_str() == "CurrentGame"
+            })
+            .unwrap() as i32
+            + 1;
+        let text = export_properties(player, current_game)?;
+        assert!(text.contains("name: \"m_Player\""));
+
+        let text = text.replacen(
+            "[",
+            "[\n(name: \"m_NewInt\", type: \"IntProperty\", value: Int(42)),",
+            1,
+        );
+        import_properties(player, current_game, &text)?;
+        assert!(export_properties(player, current_game)?.contains("m_NewInt"));
+
+        let output = unreal::Serializer::to_byte_buf(&me1_save_game)?;
+        let _: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let bad_type = "[(name: \"m_Foo\", type: \"IntProperty\", value: Bool(true))]";
+        assert!(import_properties(&mut me1_save_game.player, current_game, bad_type).is_err());
+        Ok(())
+    }
+}
@@ -4,7 +4,11 @@ use std::fmt;
 
 use crate::{
     gui::Gui,
-    save_data::{shared::plot::Me1PlotTable, Dummy, ImguiString, List, RawUi},
+    save_data::{
+        schema::{Definitions, FieldDesc, TypeDesc},
+        shared::plot::Me1PlotTable,
+        Dummy, ImguiString, List, OriginalValues, RawUi,
+    },
 };
 
 #[derive(Serialize, Clone)]
@@ -20,6 +24,37 @@ impl RawUi for State {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         self.plot.draw_raw_ui(gui, ident);
     }
+
+    fn revert(&mut self, original: &Self, path: &[String]) {
+        self.plot.revert(&original.plot, path);
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        self.plot.draw_watch(gui, ident, path);
+    }
+
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        self.plot.collect_values(path, values);
+    }
+
+    fn describe(definitions: &mut Definitions) -> TypeDesc {
+        definitions.define_struct::<Self>(|definitions| {
+            let hidden =
+                |name, type_desc| FieldDesc { name, title: None, readonly: false, type_desc };
+            vec![
+                hidden("_begin", Dummy::<12>::describe(definitions)),
+                hidden("base_level_name", TypeDesc::String),
+                hidden("_osef1", Dummy::<24>::describe(definitions)),
+                FieldDesc {
+                    name: "plot",
+                    title: Some("Plot"),
+                    readonly: false,
+                    type_desc: Me1PlotTable::describe(definitions),
+                },
+                hidden("_osef2", TypeDesc::Opaque("remaining bytes")),
+            ]
+        })
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for State {
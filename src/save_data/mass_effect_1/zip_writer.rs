@@ -0,0 +1,204 @@
+use anyhow::Result;
+use crc::{Crc, CRC_32_ISO_HDLC};
+use flate2::{write::DeflateEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ZipCompression {
+    Stored,
+    // 0 - 9
+    Deflated { level: u32 },
+}
+
+impl Default for ZipCompression {
+    fn default() -> Self {
+        ZipCompression::Deflated { level: DEFAULT_LEVEL }
+    }
+}
+
+// Comme la précision des floats, global car le zip est écrit pendant la sérialisation
+const DEFAULT_LEVEL: u32 = 6;
+const STORED: u32 = u32::MAX;
+static COMPRESSION: AtomicU32 = AtomicU32::new(DEFAULT_LEVEL);
+
+pub fn set_zip_compression(compression: ZipCompression) {
+    let value = match compression {
+        ZipCompression::Stored => STORED,
+        ZipCompression::Deflated { level } => level.min(9),
+    };
+    COMPRESSION.store(value, Ordering::Relaxed);
+}
+
+pub fn zip_compression() -> ZipCompression {
+    match COMPRESSION.load(Ordering::Relaxed) {
+        STORED => ZipCompression::Stored,
+        level => ZipCompression::Deflated { level },
+    }
+}
+
+// Un membre prêt à être écrit, compressé à part pour pouvoir l'être sur un autre thread
+pub struct CompressedFile {
+    name: String,
+    method: u16,
+    crc: u32,
+    uncompressed_len: u32,
+    data: Vec<u8>,
+}
+
+pub fn compress_file(
+    name: &str, data: &[u8], compression: ZipCompression,
+) -> Result<CompressedFile> {
+    let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+    let (method, compressed) = match compression {
+        ZipCompression::Stored => (0u16, data.to_vec()),
+        ZipCompression::Deflated { level } => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(level.min(9)));
+            encoder.write_all(data)?;
+            (8, encoder.finish()?)
+        }
+    };
+    Ok(CompressedFile {
+        name: name.to_owned(),
+        method,
+        crc: crc.checksum(data),
+        uncompressed_len: data.len() as u32,
+        data: compressed,
+    })
+}
+
+// Le ZipWriter de la crate zip ne permet pas de choisir le niveau de compression.
+// Mêmes en-têtes que lui : pas de date, permissions 644, pas de zip64.
+// Les membres sont écrits au fur et à mesure, seul le répertoire central est gardé pour la fin.
+pub struct ZipStream<W: Write> {
+    output: W,
+    offset: u32,
+    central_directory: Vec<u8>,
+    file_count: u16,
+}
+
+impl<W: Write> ZipStream<W> {
+    const VERSION_MADE_BY: u16 = 3 << 8 | 46; // Unix, 4.6
+    const VERSION_NEEDED: u16 = 20;
+    const DOS_TIME: u16 = 0;
+    const DOS_DATE: u16 = 1 | 1 << 5; // 1980-01-01
+    const EXTERNAL_ATTRIBUTES: u32 = 0o100644 << 16; // Fichier normal, rw-r--r--
+
+    pub fn new(output: W) -> Self {
+        ZipStream { output, offset: 0, central_directory: Vec::new(), file_count: 0 }
+    }
+
+    pub fn write_file(&mut self, file: &CompressedFile) -> Result<()> {
+        let CompressedFile { name, method, crc, uncompressed_len, data } = file;
+        let header_start = self.offset;
+
+        // Commun aux 2 en-têtes
+        let mut common = Vec::new();
+        common.extend(&Self::VERSION_NEEDED.to_le_bytes());
+        common.extend(&0u16.to_le_bytes()); // flags
+        common.extend(&method.to_le_bytes());
+        common.extend(&Self::DOS_TIME.to_le_bytes());
+        common.extend(&Self::DOS_DATE.to_le_bytes());
+        common.extend(&crc.to_le_bytes());
+        common.extend(&(data.len() as u32).to_le_bytes());
+        common.extend(&uncompressed_len.to_le_bytes());
+        common.extend(&(name.len() as u16).to_le_bytes());
+        common.extend(&0u16.to_le_bytes()); // extra field
+
+        // Local header
+        let mut local_header = 0x04034b50u32.to_le_bytes().to_vec();
+        local_header.extend(&common);
+        local_header.extend(name.as_bytes());
+        self.output.write_all(&local_header)?;
+        self.output.write_all(data)?;
+        self.offset += (local_header.len() + data.len()) as u32;
+
+        // Central directory
+        let central_directory = &mut self.central_directory;
+        central_directory.extend(&0x02014b50u32.to_le_bytes());
+        central_directory.extend(&Self::VERSION_MADE_BY.to_le_bytes());
+        central_directory.extend(&common);
+        central_directory.extend(&0u16.to_le_bytes()); // comment
+        central_directory.extend(&0u16.to_le_bytes()); // disk
+        central_directory.extend(&0u16.to_le_bytes()); // internal attributes
+        central_directory.extend(&Self::EXTERNAL_ATTRIBUTES.to_le_bytes());
+        central_directory.extend(&header_start.to_le_bytes());
+        central_directory.extend(name.as_bytes());
+        self.file_count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        // End of central directory
+        let mut end = self.central_directory.clone();
+        end.extend(&0x06054b50u32.to_le_bytes());
+        end.extend(&0u16.to_le_bytes()); // disk
+        end.extend(&0u16.to_le_bytes()); // disk with central directory
+        end.extend(&self.file_count.to_le_bytes());
+        end.extend(&self.file_count.to_le_bytes());
+        end.extend(&(self.central_directory.len() as u32).to_le_bytes());
+        end.extend(&self.offset.to_le_bytes());
+        end.extend(&0u16.to_le_bytes()); // comment
+        self.output.write_all(&end)?;
+        Ok(self.output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Read};
+    use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+    use super::*;
+
+    fn write_zip(files: &[(&str, Vec<u8>)], compression: ZipCompression) -> Result<Vec<u8>> {
+        let mut zip = ZipStream::new(Vec::new());
+        for (name, data) in files {
+            zip.write_file(&compress_file(name, data, compression)?)?;
+        }
+        zip.finish()
+    }
+
+    #[test]
+    fn zip_compression() -> Result<()> {
+        let files = [
+            ("player.sav", b"player ".repeat(500)),
+            ("state.sav", (0..=255).cycle().take(3000).collect::<Vec<u8>>()),
+        ];
+
+        // Niveau par défaut, identique à la crate zip
+        let mut expected = Vec::new();
+        {
+            let mut zipper = ZipWriter::new(Cursor::new(&mut expected));
+            let options = FileOptions::default().compression_method(CompressionMethod::DEFLATE);
+            for (name, data) in &files {
+                zipper.start_file(*name, options)?;
+                zipper.write_all(data)?;
+            }
+        }
+        assert_eq!(write_zip(&files, ZipCompression::default())?, expected);
+
+        let compressions = [
+            ZipCompression::Stored,
+            ZipCompression::Deflated { level: 0 },
+            ZipCompression::Deflated { level: 9 },
+        ];
+        for compression in &compressions {
+            let output = write_zip(&files, *compression)?;
+            let mut zip = ZipArchive::new(Cursor::new(output))?;
+            for (name, data) in &files {
+                let mut file = zip.by_name(name)?;
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                assert_eq!(&bytes, data);
+
+                let stored = file.compression() == CompressionMethod::STORE;
+                assert_eq!(stored, *compression == ZipCompression::Stored);
+            }
+        }
+        Ok(())
+    }
+}
@@ -13,7 +13,11 @@ use std::{fmt, io::Read};
 use crate::unreal;
 
 use super::{
-    shared::{plot::Me1PlotTable, Rotator, SaveTimeStamp, Vector},
+    shared::{
+        plot::Me1PlotTable,
+        validation::{Issues, Validate},
+        Rotator, SaveTimeStamp, Vector,
+    },
     Dummy, ImguiString, List,
 };
 
@@ -38,6 +42,35 @@ pub struct Me1LegSaveGame {
     checksum: u32,
     _unknown: Dummy<4>,
     uncompressed_size: u32,
+    standalone: bool,
+}
+
+impl Me1LegSaveGame {
+    const MAGIC_NUMBER: u32 = 0x9E2A83C1;
+    const BLOCK_SIZE: u32 = 0x40000;
+
+    // Les en-têtes et la taille sont calculés à la sérialisation
+    pub fn new(save_data: Me1LegSaveData) -> Self {
+        Me1LegSaveGame {
+            magic_number: Self::MAGIC_NUMBER,
+            block_size: Self::BLOCK_SIZE,
+            headers: Vec::new().into(),
+            save_data,
+            checksum: 0,
+            _unknown: Default::default(),
+            uncompressed_size: 0,
+            standalone: false,
+        }
+    }
+
+    // Chunk exporté seul par d'autres outils (sans en-têtes ni compression)
+    pub fn from_standalone_chunk(save_data: Me1LegSaveData) -> Self {
+        Me1LegSaveGame { standalone: true, ..Self::new(save_data) }
+    }
+
+    pub fn is_standalone(&self) -> bool {
+        self.standalone
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for Me1LegSaveGame {
@@ -111,6 +144,7 @@ impl<'de> serde::Deserialize<'de> for Me1LegSaveGame {
                     checksum,
                     _unknown,
                     uncompressed_size,
+                    standalone: false,
                 })
             }
         }
@@ -131,8 +165,13 @@ impl serde::Serialize for Me1LegSaveGame {
             checksum,
             _unknown,
             uncompressed_size: _,
+            standalone,
         } = self;
 
+        if *standalone {
+            return save_data.serialize(serializer);
+        }
+
         let mut headers = Vec::new();
 
         let uncompressed =
@@ -185,7 +224,7 @@ pub struct Me1LegSaveData {
     _unknown4: Vec<Dummy<4>>,
     _unknown5: Vec<Vec<Dummy<8>>>,
     _unknown6: Vec<Dummy<4>>,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     seconds_played: i32,
     pub player: Player,
     _unknown7: Dummy<16>,
@@ -202,9 +241,19 @@ pub struct Me1LegSaveData {
     _remaining_bytes: List<u8>,
 }
 
+impl Validate for Me1LegSaveData {
+    fn validate(&self, issues: &mut Issues) {
+        self.player.validate(issues);
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me1LegVersion(i32);
 
+impl Me1LegVersion {
+    pub const LATEST: i32 = 50;
+}
+
 impl<'de> serde::Deserialize<'de> for Me1LegVersion {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -212,7 +261,7 @@ impl<'de> serde::Deserialize<'de> for Me1LegVersion {
     {
         let version: i32 = serde::Deserialize::deserialize(deserializer)?;
 
-        if version != 50 {
+        if version != Self::LATEST {
             return Err(de::Error::custom(
                 "Wrong save version, please use a save from the latest version of the game",
             ));
@@ -359,6 +408,46 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn standalone_chunk() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME1Leg00_QuickSave.uncompressed")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        let save_data: Me1LegSaveData = unreal::Deserializer::from_bytes(&input)?;
+        let me1_save_game = Me1LegSaveGame::from_standalone_chunk(save_data);
+
+        let output = unreal::Serializer::to_byte_buf(&me1_save_game)?;
+        assert_eq!(input.len(), output.len());
+
+        // Pas de compression ni de checksum
+        let save_data: Me1LegSaveData = unreal::Deserializer::from_bytes(&output)?;
+        let output_2 = unreal::Serializer::to_byte_buf(&save_data)?;
+        assert_eq!(output, output_2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn auto_levelup_templates() -> Result<()> {
+        let input = std::fs::read("test/ME1Leg00_QuickSave.uncompressed")?;
+        let save_data: Me1LegSaveData = unreal::Deserializer::from_bytes(&input)?;
+
+        // Chaque équipier a son propre template
+        for henchman in &save_data.squad {
+            let options = player::auto_levelup_options("");
+            let template = options
+                .options
+                .iter()
+                .find(|(id, _)| *id == henchman.auto_levelup_template_id)
+                .map(|(_, name)| *name);
+            assert_eq!(template, henchman.character_name());
+        }
+        Ok(())
+    }
+
     // #[test]
     // fn uncompress() -> Result<()> {
     //     let mut input = Vec::new();
@@ -4,6 +4,8 @@ use crate::save_data::{
     shared::{
         appearance::HasHeadMorph,
         player::{Notoriety, Origin},
+        plot::PlotOptions,
+        validation::{Issues, Tab, Validate},
     },
     Dummy, ImguiString,
 };
@@ -19,7 +21,7 @@ pub struct Player {
     localized_last_name: i32,
     pub origin: Origin,
     pub notoriety: Notoriety,
-    specialization_bonus_id: i32,
+    pub specialization_bonus_id: i32,
     _unknown2: Dummy<1>,
     pub talent_points: i32,
     _unknown3: Dummy<4>,
@@ -34,7 +36,7 @@ pub struct Player {
     pub omnigel: f32,
     pub face_code: ImguiString,
     _unknown4: Dummy<4>,
-    auto_levelup_template_id: i32,
+    pub auto_levelup_template_id: i32,
     health_per_level: f32,
     _unknown5: Dummy<9>,
     stamina: i32,
@@ -45,6 +47,14 @@ pub struct Player {
     health_current: f32,
 }
 
+// Templates de montée de niveau auto relevés sur une save, ceux de Shepard restent affichés par leur ID
+pub const AUTO_LEVELUP_TEMPLATES: [(i32, &str); 6] =
+    [(1, "Ashley"), (2, "Kaidan"), (3, "Liara"), (4, "Wrex"), (5, "Garrus"), (6, "Tali'Zorah")];
+
+pub fn auto_levelup_options(label: &str) -> PlotOptions<'_> {
+    PlotOptions { label, options: AUTO_LEVELUP_TEMPLATES.to_vec() }
+}
+
 #[derive(Deserialize, Serialize, RawUi, Clone, Default)]
 pub struct SimpleTalent {
     talent_id: i32,
@@ -53,9 +63,9 @@ pub struct SimpleTalent {
 
 #[derive(Deserialize, Serialize, RawUi, Clone, Default)]
 pub struct ComplexTalent {
-    talent_id: i32,
+    pub talent_id: i32,
     pub ranks: i32,
-    max_rank: i32,
+    pub max_rank: i32,
     level_offset: i32,
     levels_per_rank: i32,
     visual_order: i32,
@@ -63,6 +73,66 @@ pub struct ComplexTalent {
     prereq_talent_rank_array: Vec<i32>,
 }
 
+impl ComplexTalent {
+    // (talent, rang minimum)
+    pub fn prerequisites(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.prereq_talent_id_array
+            .iter()
+            .copied()
+            .zip(self.prereq_talent_rank_array.iter().copied())
+    }
+}
+
+// Un talent absent de la liste compte comme rang 0
+fn rank_of(talents: &[ComplexTalent], talent_id: i32) -> i32 {
+    talents
+        .iter()
+        .find(|talent| talent.talent_id == talent_id)
+        .map(|talent| talent.ranks)
+        .unwrap_or(0)
+}
+
+pub fn missing_prerequisites(talents: &[ComplexTalent], talent: &ComplexTalent) -> Vec<(i32, i32)> {
+    talent.prerequisites().filter(|&(id, rank)| rank_of(talents, id) < rank).collect()
+}
+
+// Nouveaux rangs `(index, rang)` pour monter un talent d'un rang en dépensant le minimum de points dans ses prérequis
+pub fn unlock_plan(talents: &[ComplexTalent], index: usize) -> Option<Vec<(usize, i32)>> {
+    fn raise(
+        talents: &[ComplexTalent], ranks: &mut [i32], index: usize, rank: i32,
+        visiting: &mut Vec<usize>,
+    ) -> Option<()> {
+        let talent = &talents[index];
+        if ranks[index] >= rank {
+            return Some(());
+        }
+        if rank > talent.max_rank || visiting.contains(&index) {
+            return None;
+        }
+
+        visiting.push(index);
+        for (id, required) in talent.prerequisites() {
+            let prerequisite = talents.iter().position(|talent| talent.talent_id == id)?;
+            raise(talents, ranks, prerequisite, required, visiting)?;
+        }
+        visiting.pop();
+
+        ranks[index] = rank;
+        Some(())
+    }
+
+    let mut ranks: Vec<_> = talents.iter().map(|talent| talent.ranks).collect();
+    raise(talents, &mut ranks, index, talents[index].ranks + 1, &mut Vec::new())?;
+
+    let plan =
+        ranks.into_iter().enumerate().filter(|&(i, rank)| rank != talents[i].ranks).collect();
+    Some(plan)
+}
+
+pub fn plan_cost(talents: &[ComplexTalent], plan: &[(usize, i32)]) -> i32 {
+    plan.iter().map(|&(i, rank)| rank - talents[i].ranks).sum()
+}
+
 #[derive(Deserialize, Serialize, RawUi, Clone, Default)]
 pub struct Inventory {
     pub equipped: Vec<Item>,
@@ -111,3 +181,69 @@ struct ItemMod {
     manufacturer_id: i32,
     _osef: Dummy<4>,
 }
+
+impl Validate for Player {
+    fn validate(&self, issues: &mut Issues) {
+        issues
+            .check(Tab::General, self.level >= 1, || format!("Level is below 1 ({})", self.level));
+        issues.non_negative(Tab::General, "Talent points", self.talent_points);
+        issues.non_negative(Tab::General, "Credits", self.credits);
+        issues.non_negative(Tab::General, "Medigel", self.medigel);
+        issues.non_negative(Tab::General, "Grenades", self.grenades);
+        issues.non_negative(Tab::General, "Omnigel", self.omnigel);
+        for talent in &self.complex_talents {
+            issues.check(Tab::General, talent.ranks <= talent.max_rank, || {
+                format!(
+                    "Talent {} has more ranks than its maximum ({} / {})",
+                    talent.talent_id, talent.ranks, talent.max_rank
+                )
+            });
+            let name = format!("Talent {} prerequisite ids", talent.talent_id);
+            let (ids, ranks) =
+                (talent.prereq_talent_id_array.len(), talent.prereq_talent_rank_array.len());
+            issues.same_len(Tab::Raw, &name, ids, "ranks", ranks);
+        }
+        // Le jeu n'en affiche pas plus
+        let items = self.inventory.inventory.len();
+        issues.check(Tab::Inventory, items <= 150, || {
+            format!("Inventory holds more than 150 items ({})", items)
+        });
+        self.head_morph.validate(issues);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn talent(talent_id: i32, ranks: i32, prerequisites: &[(i32, i32)]) -> ComplexTalent {
+        ComplexTalent {
+            talent_id,
+            ranks,
+            max_rank: 12,
+            prereq_talent_id_array: prerequisites.iter().map(|&(id, _)| id).collect(),
+            prereq_talent_rank_array: prerequisites.iter().map(|&(_, rank)| rank).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unlock_talent() {
+        // Lift <- Throw 2 <- Warp 1
+        let talents =
+            vec![talent(56, 0, &[]), talent(49, 0, &[(56, 1)]), talent(50, 0, &[(49, 2)])];
+
+        assert!(missing_prerequisites(&talents, &talents[0]).is_empty());
+        assert_eq!(missing_prerequisites(&talents, &talents[1]), [(56, 1)]);
+        assert_eq!(missing_prerequisites(&talents, &talents[2]), [(49, 2)]);
+
+        // Les prérequis des prérequis sont aussi montés
+        let plan = unlock_plan(&talents, 2).unwrap();
+        assert_eq!(plan, [(0, 1), (1, 2), (2, 1)]);
+        assert_eq!(plan_cost(&talents, &plan), 4);
+
+        // Prérequis absent de la liste
+        let talents = vec![talent(50, 0, &[(49, 2)])];
+        assert!(unlock_plan(&talents, 0).is_none());
+    }
+}
@@ -13,7 +13,7 @@ pub struct Henchman {
     pub quick_slots: Vec<Item>,
     pub talent_points: i32,
     _unknown1: Dummy<4>,
-    auto_levelup_template_id: i32,
+    pub auto_levelup_template_id: i32,
     localized_last_name: i32,
     localized_class_name: i32,
     _unknown2: Dummy<1>,
@@ -29,6 +29,21 @@ pub struct Henchman {
     _unknown5: Dummy<4>,
     level: i32,
     _unknown6: Dummy<4>,
-    helmet_visible: bool,
+    pub helmet_visible: bool,
     _unknown7: Dummy<1>,
 }
+
+impl Henchman {
+    pub fn character_name(&self) -> Option<&'static str> {
+        let name = match self.tag.to_str() {
+            "hench_asari" => "Liara",
+            "hench_humanfemale" => "Ashley",
+            "hench_humanmale" => "Kaidan",
+            "hench_krogan" => "Wrex",
+            "hench_quarian" => "Tali'Zorah",
+            "hench_turian" => "Garrus",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
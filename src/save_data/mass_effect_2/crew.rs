@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::save_data::shared::recipe::PlotEdit;
+
+use super::{plot::PlotTable, suicide_mission::CrewOutcome};
+
+// Équipage du Normandy enlevé par les Récolteurs, voir `suicide_mission` pour le compteur
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrewMember {
+    Kelly,
+    Chakwas,
+    Gardner,
+    Donnelly,
+    Daniels,
+}
+
+impl CrewMember {
+    pub const ALL: [CrewMember; 5] = [
+        CrewMember::Kelly,
+        CrewMember::Chakwas,
+        CrewMember::Gardner,
+        CrewMember::Donnelly,
+        CrewMember::Daniels,
+    ];
+
+    fn died_id(self) -> usize {
+        match self {
+            CrewMember::Kelly => 3629,
+            CrewMember::Chakwas => 3630,
+            CrewMember::Gardner => 3515,
+            CrewMember::Donnelly => 3631,
+            CrewMember::Daniels => 3632,
+        }
+    }
+
+    // Kelly meurt dès la première mission de retard, le reste de l'équipage après la quatrième
+    pub fn survives(self, outcome: CrewOutcome) -> bool {
+        match outcome {
+            CrewOutcome::AllSurvive => true,
+            CrewOutcome::HalfSurvive => self != CrewMember::Kelly,
+            CrewOutcome::OnlyChakwas => self == CrewMember::Chakwas,
+            CrewOutcome::NoneSurvive => false,
+        }
+    }
+}
+
+impl fmt::Display for CrewMember {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            CrewMember::Kelly => "Kelly Chambers",
+            CrewMember::Chakwas => "Dr. Karin Chakwas",
+            CrewMember::Gardner => "Rupert Gardner",
+            CrewMember::Donnelly => "Kenneth Donnelly",
+            CrewMember::Daniels => "Gabriella Daniels",
+        })
+    }
+}
+
+pub const CREW_DELAY: usize = 166;
+const ALL_CREW_SURVIVED: usize = 3351;
+const HAD_CASUALTIES: usize = 3349;
+
+// Résultat attendu pour un nombre de missions faites après l'enlèvement, avec une escorte loyale
+pub fn expected_outcome(delay: i32) -> CrewOutcome {
+    match delay {
+        i32::MIN..=0 => CrewOutcome::AllSurvive,
+        1..=3 => CrewOutcome::HalfSurvive,
+        _ => CrewOutcome::OnlyChakwas,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CrewState {
+    pub delay: i32,
+    pub died: Vec<(CrewMember, bool)>,
+    pub all_survived: bool,
+    pub had_casualties: bool,
+}
+
+impl CrewState {
+    pub fn from_plot(plot: &PlotTable) -> Self {
+        let get_bool = |id: usize| plot.bool_variables.get(id).map(|b| *b) == Some(true);
+
+        CrewState {
+            delay: plot.int_variables.get(CREW_DELAY).copied().unwrap_or_default(),
+            died: CrewMember::ALL
+                .iter()
+                .map(|&member| (member, get_bool(member.died_id())))
+                .collect(),
+            all_survived: get_bool(ALL_CREW_SURVIVED),
+            had_casualties: get_bool(HAD_CASUALTIES),
+        }
+    }
+
+    // Le jeu n'écrit les morts qu'à la fin de la mission suicide
+    pub fn is_resolved(&self) -> bool {
+        self.all_survived || self.had_casualties
+    }
+}
+
+// Avant la mission suicide, seul le compteur compte
+pub fn set_delay(plot: &mut PlotTable, delay: i32) {
+    plot.set_int(CREW_DELAY, delay.max(0));
+}
+
+pub fn set_died(plot: &mut PlotTable, member: CrewMember, died: bool) {
+    plot.set_bool(member.died_id(), died);
+    update_summary(plot);
+}
+
+// Après la mission suicide, les drapeaux lus par ME3, avec un compteur cohérent
+pub fn set_outcome(plot: &mut PlotTable, outcome: CrewOutcome) {
+    for &member in &CrewMember::ALL {
+        plot.set_bool(member.died_id(), !member.survives(outcome));
+    }
+    update_summary(plot);
+
+    let delay = plot.int_variables.get(CREW_DELAY).copied().unwrap_or_default();
+    let consistent_delay = match outcome {
+        CrewOutcome::AllSurvive => Some(0),
+        CrewOutcome::HalfSurvive => Some(1),
+        CrewOutcome::OnlyChakwas => Some(4),
+        // Sans escorte le compteur n'y est pour rien
+        CrewOutcome::NoneSurvive => None,
+    };
+    if let Some(consistent_delay) = consistent_delay {
+        if expected_outcome(delay) != outcome {
+            set_delay(plot, consistent_delay);
+        }
+    }
+}
+
+fn update_summary(plot: &mut PlotTable) {
+    let casualties = CrewMember::ALL
+        .iter()
+        .any(|member| plot.bool_variables.get(member.died_id()).map(|b| *b) == Some(true));
+    plot.set_bool(ALL_CREW_SURVIVED, !casualties);
+    plot.set_bool(HAD_CASUALTIES, casualties);
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn crew_survival() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = &mut me2_save_game.plot;
+
+        set_outcome(plot, CrewOutcome::OnlyChakwas);
+        let crew = CrewState::from_plot(plot);
+        assert!(crew.is_resolved());
+        assert!(crew.had_casualties && !crew.all_survived);
+        assert_eq!(expected_outcome(crew.delay), CrewOutcome::OnlyChakwas);
+        for (member, died) in crew.died {
+            assert_eq!(died, member != CrewMember::Chakwas, "{}", member);
+        }
+
+        // Kelly sauvée
+        set_died(plot, CrewMember::Kelly, false);
+        let crew = CrewState::from_plot(plot);
+        assert!(crew.died.contains(&(CrewMember::Kelly, false)));
+        assert!(crew.had_casualties);
+
+        set_outcome(plot, CrewOutcome::AllSurvive);
+        let crew = CrewState::from_plot(plot);
+        assert_eq!(crew.delay, 0);
+        assert!(crew.all_survived && !crew.had_casualties);
+        assert!(crew.died.iter().all(|(_, died)| !died));
+        Ok(())
+    }
+}
@@ -4,8 +4,9 @@ use serde::{de, Deserialize, Serialize};
 
 use super::{
     shared::{
-        plot::Me1PlotTable, Door, EndGameState, Guid, KismetRecord, Level, Rotator, SaveTimeStamp,
-        Vector,
+        plot::Me1PlotTable,
+        validation::{Issues, Validate},
+        Door, EndGameState, Guid, KismetRecord, Level, Rotator, SaveTimeStamp, Vector,
     },
     Dummy, ImguiString,
 };
@@ -21,6 +22,9 @@ use plot::*;
 
 pub mod plot_db;
 
+pub mod crew;
+pub mod suicide_mission;
+
 mod galaxy_map;
 use galaxy_map::*;
 
@@ -30,29 +34,40 @@ pub struct Me2SaveGame {
     debug_name: ImguiString,
     seconds_played: f32,
     disc: i32,
-    base_level_name: ImguiString,
+    pub base_level_name: ImguiString,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
-    location: Vector,
-    rotation: Rotator,
+    pub timestamp: SaveTimeStamp,
+    pub location: Vector,
+    pub rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
+    pub levels: Vec<Level>,
     streaming_records: IndexMap<ImguiString, bool>,
     kismet_records: Vec<KismetRecord>,
     doors: Vec<Door>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     pub me1_plot: Me1PlotTable,
     galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
 }
 
+impl Validate for Me2SaveGame {
+    fn validate(&self, issues: &mut Issues) {
+        self.player.validate(issues);
+        self.plot.validate(issues);
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me2Version(i32);
 
+impl Me2Version {
+    pub const LATEST: i32 = 29;
+}
+
 impl<'de> serde::Deserialize<'de> for Me2Version {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -60,7 +75,7 @@ impl<'de> serde::Deserialize<'de> for Me2Version {
     {
         let version: i32 = serde::Deserialize::deserialize(deserializer)?;
 
-        if version != 29 {
+        if version != Self::LATEST {
             return Err(de::Error::custom(
                 "Wrong save version, please use a save from the latest version of the game",
             ));
@@ -76,30 +91,41 @@ pub struct Me2LegSaveGame {
     debug_name: ImguiString,
     seconds_played: f32,
     disc: i32,
-    base_level_name: ImguiString,
+    pub base_level_name: ImguiString,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
-    location: Vector,
-    rotation: Rotator,
+    pub timestamp: SaveTimeStamp,
+    pub location: Vector,
+    pub rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
+    pub levels: Vec<Level>,
     streaming_records: IndexMap<ImguiString, bool>,
     kismet_records: Vec<KismetRecord>,
     doors: Vec<Door>,
     pawns: Vec<Guid>,
     pub player: Player,
     _unknown: Dummy<28>,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     pub me1_plot: Me1PlotTable,
     galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
 }
 
+impl Validate for Me2LegSaveGame {
+    fn validate(&self, issues: &mut Issues) {
+        self.player.validate(issues);
+        self.plot.validate(issues);
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me2LegVersion(i32);
 
+impl Me2LegVersion {
+    pub const LATEST: i32 = 30;
+}
+
 impl<'de> serde::Deserialize<'de> for Me2LegVersion {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -107,7 +133,7 @@ impl<'de> serde::Deserialize<'de> for Me2LegVersion {
     {
         let version: i32 = serde::Deserialize::deserialize(deserializer)?;
 
-        if version != 30 {
+        if version != Self::LATEST {
             return Err(de::Error::custom(
                 "Wrong save version, please use a save from the latest version of the game",
             ));
@@ -180,7 +206,10 @@ mod test {
         {fs::File, io::Read},
     };
 
-    use crate::unreal;
+    use crate::{
+        save_data::{OriginalValues, RawUi},
+        unreal,
+    };
 
     use super::*;
 
@@ -263,4 +292,27 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn collect_original_values() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME2Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let mut values = OriginalValues::new();
+        me2_save_game.collect_values(&mut Vec::new(), &mut values);
+
+        // Mêmes chemins que le raw UI
+        let path = |path: &[&str]| path.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            values.get(&path(&["Player", "Credits"])),
+            Some(&me2_save_game.player.credits.to_string())
+        );
+        let first_bool = me2_save_game.plot.bool_variables[0].to_string();
+        assert_eq!(values.get(&path(&["Plot", "Bool Variables", "0"])), Some(&first_bool));
+        Ok(())
+    }
 }
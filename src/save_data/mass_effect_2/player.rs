@@ -4,6 +4,7 @@ use crate::save_data::{
     shared::{
         appearance::Appearance,
         player::{Notoriety, Origin, WeaponLoadout},
+        validation::{Issues, Tab, Validate},
     },
     ImguiString,
 };
@@ -24,8 +25,8 @@ pub struct Player {
     mapped_power_3: ImguiString,
     pub appearance: Appearance,
     pub powers: Vec<Power>,
-    weapons: Vec<Weapon>,
-    weapons_loadout: WeaponLoadout,
+    pub weapons: Vec<Weapon>,
+    pub weapons_loadout: WeaponLoadout,
     hotkeys: Vec<Hotkey>,
     pub credits: i32,
     pub medigel: i32,
@@ -41,20 +42,20 @@ pub struct Player {
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Power {
-    name: ImguiString,
-    rank: f32,
+    pub name: ImguiString,
+    pub rank: f32,
     pub power_class_name: ImguiString,
     wheel_display_index: i32,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
-struct Weapon {
-    class_name: ImguiString,
+pub struct Weapon {
+    pub class_name: ImguiString,
     ammo_used_count: i32,
     ammo_total: i32,
     current_weapon: bool,
     was_last_weapon: bool,
-    ammo_power_name: ImguiString,
+    pub ammo_power_name: ImguiString,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
@@ -62,3 +63,20 @@ struct Hotkey {
     pawn_name: ImguiString,
     power_id: i32,
 }
+
+impl Validate for Player {
+    fn validate(&self, issues: &mut Issues) {
+        issues
+            .check(Tab::General, self.level >= 1, || format!("Level is below 1 ({})", self.level));
+        issues.non_negative(Tab::General, "Talent points", self.talent_points);
+        issues.non_negative(Tab::General, "Credits", self.credits);
+        issues.non_negative(Tab::General, "Medigel", self.medigel);
+        issues.non_negative(Tab::General, "Eezo", self.eezo);
+        issues.non_negative(Tab::General, "Iridium", self.iridium);
+        issues.non_negative(Tab::General, "Palladium", self.palladium);
+        issues.non_negative(Tab::General, "Platinum", self.platinum);
+        issues.non_negative(Tab::General, "Probes", self.probes);
+        issues.non_negative(Tab::General, "Fuel", self.current_fuel);
+        self.appearance.head_morph.validate(issues);
+    }
+}
@@ -1,7 +1,8 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
+use std::iter;
 
-use crate::save_data::shared::plot::PlotCategory;
+use crate::save_data::shared::plot::{named_categories, PlotCategory};
 
 #[derive(Deserialize)]
 pub struct Me2PlotDb {
@@ -16,6 +17,24 @@ pub struct Me2PlotDb {
     pub imported_me1: IndexMap<String, PlotCategory>,
 }
 
+impl Me2PlotDb {
+    pub fn categories(&self) -> impl Iterator<Item = &PlotCategory> {
+        self.named_categories().map(|(_, category)| category)
+    }
+
+    pub fn named_categories(&self) -> impl Iterator<Item = (String, &PlotCategory)> {
+        iter::once(("Player".to_owned(), &self.player))
+            .chain(named_categories("Crew", &self.crew))
+            .chain(named_categories("Romance", &self.romance))
+            .chain(named_categories("Missions", &self.missions))
+            .chain(named_categories("Loyalty missions", &self.loyalty_missions))
+            .chain(named_categories("Research / Upgrades", &self.research_upgrades))
+            .chain(iter::once(("Rewards".to_owned(), &self.rewards)))
+            .chain(iter::once(("Captain's cabin".to_owned(), &self.captains_cabin)))
+            .chain(named_categories("Imported ME1", &self.imported_me1))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use anyhow::Result;
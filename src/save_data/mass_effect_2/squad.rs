@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::{shared::player::WeaponLoadout, ImguiString};
+use crate::save_data::{
+    shared::{player::WeaponLoadout, squad::HenchmanBuild},
+    ImguiString,
+};
 
 use super::player::Power;
 
@@ -13,3 +16,32 @@ pub struct Henchman {
     weapon_loadout: WeaponLoadout,
     mapped_power: ImguiString,
 }
+
+impl HenchmanBuild for Henchman {
+    fn tag(&self) -> &str {
+        self.tag.to_str()
+    }
+
+    fn level(&self) -> i32 {
+        self.character_level
+    }
+
+    // Les pouvoirs sont associés par classe, leur ordre peut changer d'une save à l'autre
+    fn copy_build(&mut self, source: &Self) {
+        for power in &source.powers {
+            let class_name = power.power_class_name.to_str();
+            match self
+                .powers
+                .iter_mut()
+                .find(|p| unicase::eq(p.power_class_name.to_str(), class_name))
+            {
+                Some(target) => *target = power.clone(),
+                None => self.powers.push(power.clone()),
+            }
+        }
+        self.character_level = source.character_level;
+        self.talent_points = source.talent_points;
+        self.weapon_loadout = source.weapon_loadout.clone();
+        self.mapped_power = source.mapped_power.clone();
+    }
+}
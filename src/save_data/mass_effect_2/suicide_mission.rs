@@ -0,0 +1,464 @@
+use std::fmt;
+
+use crate::save_data::shared::recipe::PlotEdit;
+
+use super::{
+    crew::{self, CREW_DELAY},
+    plot::PlotTable,
+};
+
+// Règles issues du wiki, le jeu tire parfois au sort entre plusieurs candidats équivalents
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Henchman {
+    Garrus,
+    Grunt,
+    Jack,
+    Jacob,
+    Kasumi,
+    Legion,
+    Miranda,
+    Mordin,
+    Samara,
+    Tali,
+    Thane,
+    Zaeed,
+}
+
+impl Henchman {
+    pub const ALL: [Henchman; 12] = [
+        Henchman::Garrus,
+        Henchman::Grunt,
+        Henchman::Jack,
+        Henchman::Jacob,
+        Henchman::Kasumi,
+        Henchman::Legion,
+        Henchman::Miranda,
+        Henchman::Mordin,
+        Henchman::Samara,
+        Henchman::Tali,
+        Henchman::Thane,
+        Henchman::Zaeed,
+    ];
+
+    // (recruited, loyal, died)
+    fn plot_ids(self) -> (usize, usize, usize) {
+        match self {
+            Henchman::Garrus => (38, 183, 200),
+            Henchman::Grunt => (42, 189, 204),
+            Henchman::Jack => (35, 179, 197),
+            Henchman::Jacob => (34, 178, 196),
+            Henchman::Kasumi => (37, 182, 199),
+            Henchman::Legion => (36, 180, 198),
+            Henchman::Miranda => (33, 177, 195),
+            Henchman::Mordin => (41, 188, 203),
+            Henchman::Samara => (43, 191, 205),
+            Henchman::Tali => (40, 186, 202),
+            Henchman::Thane => (39, 185, 201),
+            Henchman::Zaeed => (44, 192, 206),
+        }
+    }
+
+    fn defense(self) -> u32 {
+        match self {
+            Henchman::Garrus | Henchman::Grunt | Henchman::Zaeed => 3,
+            Henchman::Jacob
+            | Henchman::Legion
+            | Henchman::Miranda
+            | Henchman::Samara
+            | Henchman::Thane => 1,
+            Henchman::Jack | Henchman::Kasumi | Henchman::Mordin | Henchman::Tali => 0,
+        }
+    }
+
+    pub fn is_tech_specialist(self) -> bool {
+        matches!(self, Henchman::Tali | Henchman::Legion | Henchman::Kasumi)
+    }
+
+    pub fn is_biotic_specialist(self) -> bool {
+        matches!(self, Henchman::Jack | Henchman::Samara)
+    }
+
+    pub fn is_leader(self) -> bool {
+        matches!(self, Henchman::Garrus | Henchman::Jacob | Henchman::Miranda)
+    }
+}
+
+impl fmt::Display for Henchman {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Henchman::Samara => f.write_str("Samara / Morinth"),
+            _ => fmt::Debug::fmt(self, f),
+        }
+    }
+}
+
+const HEAVY_SHIP_ARMOR: usize = 114;
+const MULTICORE_SHIELDING: usize = 110;
+const THANIX_CANNON: usize = 117;
+
+// (discovered, unlocked) des améliorations du Normandy
+const SHIP_UPGRADE_FLAGS: [(usize, usize); 3] = [(1183, 5642), (1192, 5648), (1182, 5645)];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HenchmanState {
+    pub henchman: Henchman,
+    pub loyal: bool,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct SquadState {
+    pub recruited: Vec<HenchmanState>,
+    pub armor: bool,
+    pub shields: bool,
+    pub cannon: bool,
+    pub crew_delay: i32,
+}
+
+impl SquadState {
+    pub fn from_plot(plot: &PlotTable) -> Self {
+        let get_bool = |id: usize| plot.bool_variables.get(id).map(|b| *b) == Some(true);
+        let get_int = |id: usize| plot.int_variables.get(id).copied().unwrap_or_default();
+
+        let recruited = Henchman::ALL
+            .iter()
+            .filter_map(|&henchman| {
+                let (recruited, loyal, _) = henchman.plot_ids();
+                if get_bool(recruited) {
+                    Some(HenchmanState { henchman, loyal: get_bool(loyal) })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        SquadState {
+            recruited,
+            armor: get_int(HEAVY_SHIP_ARMOR) > 0,
+            shields: get_int(MULTICORE_SHIELDING) > 0,
+            cannon: get_int(THANIX_CANNON) > 0,
+            crew_delay: get_int(CREW_DELAY),
+        }
+    }
+
+    fn is_loyal(&self, henchman: Henchman) -> bool {
+        self.recruited.iter().any(|state| state.henchman == henchman && state.loyal)
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct Choices {
+    pub ship_squad: [Option<Henchman>; 2],
+    pub tech_specialist: Option<Henchman>,
+    pub first_leader: Option<Henchman>,
+    pub long_walk_squad: [Option<Henchman>; 2],
+    pub biotic_specialist: Option<Henchman>,
+    pub second_leader: Option<Henchman>,
+    pub escort: Option<Henchman>,
+    pub final_squad: [Option<Henchman>; 2],
+}
+
+impl Choices {
+    // Les meilleurs choix possibles avec l'équipe actuelle
+    pub fn recommended(squad: &SquadState) -> Self {
+        let mut used = Vec::new();
+        let mut pick = |filter: &dyn Fn(Henchman) -> bool| {
+            let henchman = squad
+                .recruited
+                .iter()
+                .filter(|state| state.loyal && !used.contains(&state.henchman))
+                .map(|state| state.henchman)
+                .find(|&henchman| filter(henchman));
+            used.extend(henchman);
+            henchman
+        };
+
+        let tech_specialist = pick(&Henchman::is_tech_specialist);
+        let first_leader = pick(&Henchman::is_leader);
+        let biotic_specialist = pick(&Henchman::is_biotic_specialist);
+        let second_leader = pick(&Henchman::is_leader);
+        // Les meilleurs défenseurs restent en arrière
+        let escort = pick(&|henchman| henchman.defense() == 0);
+        let final_squad = [pick(&|_| true), pick(&|_| true)];
+
+        Choices {
+            ship_squad: final_squad,
+            tech_specialist,
+            first_leader,
+            long_walk_squad: final_squad,
+            biotic_specialist,
+            second_leader,
+            escort,
+            final_squad,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Cause {
+    NoArmor,
+    NoShields,
+    NoCannon,
+    Vents,
+    LongWalk,
+    SecondLeader,
+    Escort,
+    HoldTheLine,
+    FinalSquad,
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Cause::NoArmor => "No Heavy Ship Armor",
+            Cause::NoShields => "No Multicore Shielding",
+            Cause::NoCannon => "No Thanix Cannon",
+            Cause::Vents => "Shot in the vents",
+            Cause::LongWalk => "Taken by the swarms",
+            Cause::SecondLeader => "Bad second fireteam leader",
+            Cause::Escort => "Disloyal escort",
+            Cause::HoldTheLine => "Hold the line",
+            Cause::FinalSquad => "Disloyal in final squad",
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CrewOutcome {
+    AllSurvive,
+    HalfSurvive,
+    OnlyChakwas,
+    NoneSurvive,
+}
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub deaths: Vec<(Henchman, Cause)>,
+    pub survivors: Vec<Henchman>,
+    pub crew: CrewOutcome,
+    pub shepard_survives: bool,
+    pub warnings: Vec<String>,
+}
+
+pub fn simulate(squad: &SquadState, choices: &Choices) -> Outcome {
+    let mut alive: Vec<Henchman> = squad.recruited.iter().map(|state| state.henchman).collect();
+    let mut deaths = Vec::new();
+    let mut warnings = Vec::new();
+
+    let mut kill = |alive: &mut Vec<Henchman>, henchman: Henchman, cause: Cause| {
+        if let Some(idx) = alive.iter().position(|&h| h == henchman) {
+            alive.remove(idx);
+            deaths.push((henchman, cause));
+        }
+    };
+    let first_alive = |alive: &[Henchman], priority: &[Henchman], excluded: &[Option<Henchman>]| {
+        priority.iter().copied().find(|h| alive.contains(h) && !excluded.contains(&Some(*h)))
+    };
+
+    // Bataille du Normandy
+    use Henchman::*;
+    if !squad.armor {
+        if let Some(henchman) = first_alive(&alive, &[Jack], &choices.ship_squad) {
+            kill(&mut alive, henchman, Cause::NoArmor);
+        }
+    }
+    if !squad.shields {
+        let priority = [Kasumi, Legion, Tali, Thane, Garrus, Zaeed, Grunt, Samara];
+        if let Some(henchman) = first_alive(&alive, &priority, &choices.ship_squad) {
+            kill(&mut alive, henchman, Cause::NoShields);
+        }
+    }
+    if !squad.cannon {
+        let priority = [Thane, Garrus, Zaeed, Grunt, Jacob, Samara];
+        if let Some(henchman) = first_alive(&alive, &priority, &choices.ship_squad) {
+            kill(&mut alive, henchman, Cause::NoCannon);
+        }
+    }
+
+    let mut check = |role: &str, choice: Option<Henchman>, alive: &[Henchman]| match choice {
+        Some(henchman) if alive.contains(&henchman) => Some(henchman),
+        Some(henchman) => {
+            warnings.push(format!("{} ({}) is not available", role, henchman));
+            None
+        }
+        None => {
+            warnings.push(format!("No {} selected", role));
+            None
+        }
+    };
+
+    // Les conduits
+    let tech = check("tech specialist", choices.tech_specialist, &alive);
+    let first_leader = check("first fireteam leader", choices.first_leader, &alive);
+    if let Some(tech) = tech {
+        let good_tech = tech.is_tech_specialist() && squad.is_loyal(tech);
+        let good_leader = matches!(first_leader, Some(h) if h.is_leader() && squad.is_loyal(h));
+        if !good_tech || !good_leader {
+            kill(&mut alive, tech, Cause::Vents);
+        }
+    }
+
+    // La longue marche
+    let biotic = check("biotic specialist", choices.biotic_specialist, &alive);
+    let second_leader = check("second fireteam leader", choices.second_leader, &alive);
+    if !matches!(biotic, Some(h) if h.is_biotic_specialist() && squad.is_loyal(h)) {
+        let priority = [
+            Thane, Jack, Garrus, Legion, Grunt, Samara, Jacob, Mordin, Tali, Kasumi, Zaeed, Miranda,
+        ];
+        let in_squad: Vec<_> = choices
+            .long_walk_squad
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|h| Some(*h) != biotic)
+            .collect();
+        if let Some(&henchman) = priority.iter().find(|h| in_squad.contains(h) && alive.contains(h))
+        {
+            kill(&mut alive, henchman, Cause::LongWalk);
+        }
+    }
+    if let Some(leader) = second_leader {
+        if !(leader.is_leader() && squad.is_loyal(leader)) {
+            kill(&mut alive, leader, Cause::SecondLeader);
+        }
+    }
+
+    // Escorte de l'équipage
+    let escort = choices.escort.filter(|h| alive.contains(h));
+    if let Some(escort) = escort {
+        if !squad.is_loyal(escort) {
+            kill(&mut alive, escort, Cause::Escort);
+        }
+    }
+    let crew = match escort {
+        Some(_) => crew::expected_outcome(squad.crew_delay),
+        None => CrewOutcome::NoneSurvive,
+    };
+
+    // Combat final
+    let final_squad: Vec<_> = choices
+        .final_squad
+        .iter()
+        .filter_map(|&choice| check("final squad member", choice, &alive))
+        .collect();
+    for &henchman in &final_squad {
+        if !squad.is_loyal(henchman) {
+            kill(&mut alive, henchman, Cause::FinalSquad);
+        }
+    }
+
+    // Tenir la ligne
+    let defenders: Vec<_> =
+        alive.iter().copied().filter(|h| !final_squad.contains(h) && Some(*h) != escort).collect();
+    for henchman in hold_the_line_deaths(squad, &defenders) {
+        kill(&mut alive, henchman, Cause::HoldTheLine);
+    }
+
+    let shepard_survives = alive.len() >= 2;
+    Outcome { deaths, survivors: alive, crew, shepard_survives, warnings }
+}
+
+fn hold_the_line_deaths(squad: &SquadState, defenders: &[Henchman]) -> Vec<Henchman> {
+    use Henchman::*;
+
+    if defenders.is_empty() {
+        return Vec::new();
+    }
+
+    let total: u32 =
+        defenders.iter().map(|&h| h.defense() + if squad.is_loyal(h) { 1 } else { 0 }).sum();
+    let score = total as f32 / defenders.len() as f32;
+    let many = defenders.len() >= 5;
+
+    let count = if score >= 2.0 {
+        0
+    } else if score >= 1.5 {
+        if many {
+            0
+        } else {
+            1
+        }
+    } else if score >= 1.0 {
+        if many {
+            1
+        } else {
+            2
+        }
+    } else if score >= 0.5 {
+        if many {
+            2
+        } else {
+            3
+        }
+    } else {
+        defenders.len()
+    };
+
+    // Les moins loyaux et les plus faibles tombent en premier
+    let priority =
+        [Mordin, Tali, Kasumi, Jack, Miranda, Jacob, Garrus, Samara, Legion, Thane, Zaeed, Grunt];
+    let mut candidates: Vec<_> =
+        priority.iter().copied().filter(|h| defenders.contains(h)).collect();
+    candidates.sort_by_key(|&h| squad.is_loyal(h));
+    candidates.truncate(count);
+    candidates
+}
+
+// Tout le monde loyal, le Normandy amélioré et l'équipage secouru à temps
+pub fn guarantee_survival(plot: &mut PlotTable, squad: &SquadState) {
+    for state in &squad.recruited {
+        let (_, loyal, died) = state.henchman.plot_ids();
+        plot.set_bool(loyal, true);
+        plot.set_bool(died, false);
+    }
+    for &(discovered, unlocked) in &SHIP_UPGRADE_FLAGS {
+        plot.set_bool(discovered, true);
+        plot.set_bool(unlocked, true);
+    }
+    for &upgrade in &[HEAVY_SHIP_ARMOR, MULTICORE_SHIELDING, THANIX_CANNON] {
+        plot.set_int(upgrade, 1);
+    }
+    plot.set_int(CREW_DELAY, 0);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn squad(loyal: bool, upgrades: bool) -> SquadState {
+        SquadState {
+            recruited: Henchman::ALL
+                .iter()
+                .map(|&henchman| HenchmanState { henchman, loyal })
+                .collect(),
+            armor: upgrades,
+            shields: upgrades,
+            cannon: upgrades,
+            crew_delay: 0,
+        }
+    }
+
+    #[test]
+    fn suicide_mission() {
+        // Meilleur scénario
+        let best = squad(true, true);
+        let outcome = simulate(&best, &Choices::recommended(&best));
+        assert!(outcome.deaths.is_empty(), "{:?}", outcome.deaths);
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+        assert_eq!(outcome.crew, CrewOutcome::AllSurvive);
+        assert!(outcome.shepard_survives);
+
+        // Sans amélioration ni loyauté
+        let worst = squad(false, false);
+        let choices = Choices {
+            tech_specialist: Some(Henchman::Mordin),
+            first_leader: Some(Henchman::Jack),
+            ..Default::default()
+        };
+        let outcome = simulate(&worst, &choices);
+        assert!(outcome.deaths.contains(&(Henchman::Jack, Cause::NoArmor)));
+        assert!(outcome.deaths.contains(&(Henchman::Kasumi, Cause::NoShields)));
+        assert!(outcome.deaths.contains(&(Henchman::Thane, Cause::NoCannon)));
+        assert!(outcome.deaths.contains(&(Henchman::Mordin, Cause::Vents)));
+        assert!(!outcome.warnings.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+
+use super::{plot::PlotTable, plot_db::Me3PlotDb, Me3SaveGame};
+
+pub const EMS_PLOT_ID: i32 = 10430;
+
+// Une save de la carrière, seul le plot est gardé pour comparer les saves entre elles
+pub struct CareerSave {
+    pub file_name: String,
+    pub seconds_played: f32,
+    pub ems: i32,
+    plot: PlotTable,
+}
+
+impl CareerSave {
+    pub fn new(file_name: String, save_game: Me3SaveGame) -> Self {
+        let ems = save_game.plot.int_variables.get(&EMS_PLOT_ID).copied().unwrap_or_default();
+        CareerSave {
+            file_name,
+            seconds_played: save_game.seconds_played,
+            ems,
+            plot: save_game.plot,
+        }
+    }
+}
+
+// Saves triées par temps de jeu, l'ordre des slots ne suit pas forcément la partie
+pub struct CareerAudit {
+    pub saves: Vec<CareerSave>,
+}
+
+impl CareerAudit {
+    pub fn new(mut saves: Vec<CareerSave>) -> Self {
+        saves.sort_by(|a, b| {
+            a.seconds_played.partial_cmp(&b.seconds_played).unwrap_or(Ordering::Equal)
+        });
+        CareerAudit { saves }
+    }
+
+    // Variables connues du plot DB qui ont baissé depuis la save précédente
+    pub fn losses(&self, index: usize, plot_db: &Me3PlotDb) -> Vec<String> {
+        let (previous, next) = match (index.checked_sub(1), self.saves.get(index)) {
+            (Some(previous), Some(next)) => (&self.saves[previous].plot, &next.plot),
+            _ => return Vec::new(),
+        };
+        let is_set = |plot: &PlotTable, id: usize| {
+            plot.bool_variables.get(id).map(|b| *b).unwrap_or_default()
+        };
+        let int = |plot: &PlotTable, id: usize| {
+            plot.int_variables.get(&(id as i32)).copied().unwrap_or_default()
+        };
+
+        let mut losses = Vec::new();
+        for (name, category) in plot_db.named_categories() {
+            for (&id, entry) in &category.booleans {
+                if is_set(previous, id) && !is_set(next, id) {
+                    losses.push(format!("{} > {} : lost", name, entry));
+                }
+            }
+            for (&id, entry) in &category.ints {
+                let (before, after) = (int(previous, id), int(next, id));
+                if after < before {
+                    losses.push(format!("{} > {} : {} -> {}", name, entry, before, after));
+                }
+            }
+        }
+        losses
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::shared::recipe::PlotEdit, unreal};
+
+    use super::*;
+
+    #[test]
+    fn audit_career() -> Result<()> {
+        let mut input = String::new();
+        {
+            let mut file = File::open("databases/me3_plot_db.ron")?;
+            file.read_to_string(&mut input)?;
+        }
+        let plot_db: Me3PlotDb = ron::from_str(&input)?;
+
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Une save plus tardive où l'EMS a baissé et un flag connu a disparu
+        let mut later = me3_save_game.clone();
+        later.seconds_played += 60.0;
+        later.plot.set_int(EMS_PLOT_ID as usize, 100);
+        later.plot.set_bool(22590, true);
+        let mut latest = later.clone();
+        latest.seconds_played += 60.0;
+        latest.plot.set_int(EMS_PLOT_ID as usize, 50);
+        latest.plot.set_bool(22590, false);
+
+        let audit = CareerAudit::new(vec![
+            CareerSave::new(String::from("Save_0003.pcsav"), latest),
+            CareerSave::new(String::from("Save_0001.pcsav"), me3_save_game),
+            CareerSave::new(String::from("Save_0002.pcsav"), later),
+        ]);
+        let file_names: Vec<_> = audit.saves.iter().map(|save| save.file_name.as_str()).collect();
+        assert_eq!(file_names, ["Save_0001.pcsav", "Save_0002.pcsav", "Save_0003.pcsav"]);
+        assert_eq!(audit.saves[2].ems, 50);
+
+        assert!(audit.losses(0, &plot_db).is_empty());
+        assert_eq!(
+            audit.losses(2, &plot_db),
+            [
+                "General > Effective Military Strength : 100 -> 50",
+                "Appearances > Casual > N7 Hoodie : lost"
+            ]
+        );
+        Ok(())
+    }
+}
@@ -4,21 +4,38 @@ use crate::save_data::shared::Vector2d;
 
 #[derive(Deserialize, Serialize, RawUi, Clone)]
 pub struct GalaxyMap {
-    planets: Vec<Planet>,
-    systems: Vec<System>,
+    pub planets: Vec<Planet>,
+    pub systems: Vec<System>,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Planet {
-    id: i32,
-    visited: bool,
-    probes: Vec<Vector2d>,
-    show_as_scanned: bool,
+    pub id: i32,
+    pub visited: bool,
+    pub probes: Vec<Vector2d>,
+    pub show_as_scanned: bool,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct System {
-    id: i32,
-    reaper_alert_level: f32,
-    reaper_detected: bool,
+    pub id: i32,
+    pub reaper_alert_level: f32,
+    pub reaper_detected: bool,
+}
+
+impl GalaxyMap {
+    // Les Moissonneurs arrivent quand on scanne trop dans un système
+    pub fn calm_reapers(&mut self) {
+        for system in &mut self.systems {
+            system.reaper_alert_level = 0.0;
+            system.reaper_detected = false;
+        }
+    }
+}
+
+impl Planet {
+    // 360103 est dans le système 360100
+    pub fn system_id(&self) -> i32 {
+        self.id / 100 * 100
+    }
 }
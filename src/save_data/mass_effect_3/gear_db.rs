@@ -0,0 +1,164 @@
+use imgui::ImString;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::{shared::player::WeaponLoadout, ImguiString};
+
+// Munitions et bonus d'intel, par nom de classe / de variable
+#[derive(Deserialize)]
+pub struct Me3GearDb {
+    pub ammo_powers: IndexMap<String, String>,
+    pub intel_bonuses: IndexMap<String, String>,
+    #[serde(default)]
+    pub weapons: IndexMap<String, Me3WeaponEntry>,
+    // Par classe, sans le suffixe `NonCombat`
+    #[serde(default)]
+    pub weight_capacities: IndexMap<String, f32>,
+}
+
+#[derive(Deserialize)]
+pub struct Me3WeaponEntry {
+    pub name: String,
+    // Au niveau I, sans mods
+    pub weight: f32,
+}
+
+pub struct WeightStats {
+    pub weight: f32,
+    pub capacity: f32,
+    // En %, de +200 à -200
+    pub cooldown_bonus: f32,
+    // Armes équipées absentes de la DB, comptées comme sans poids
+    pub unknown: Vec<String>,
+}
+
+impl Me3GearDb {
+    pub fn weapon(&self, class_name: &str) -> Option<&Me3WeaponEntry> {
+        self.weapons
+            .iter()
+            .find(|(class, _)| unicase::eq(class.as_str(), class_name))
+            .map(|(_, w)| w)
+    }
+
+    // L'arme lourde ne compte pas
+    pub fn weight_stats(&self, class_name: &str, loadout: &WeaponLoadout) -> Option<WeightStats> {
+        let class_name = class_name.trim_end_matches("NonCombat");
+        let capacity = self
+            .weight_capacities
+            .iter()
+            .find(|(class, _)| unicase::eq(class.as_str(), class_name))
+            .map(|(_, capacity)| *capacity)?;
+
+        let WeaponLoadout { assault_rifle, shotgun, sniper_rifle, submachine_gun, pistol, .. } =
+            loadout;
+        let equipped = [assault_rifle, shotgun, sniper_rifle, submachine_gun, pistol];
+
+        let mut weight = 0.0;
+        let mut unknown = Vec::new();
+        for class_name in equipped.iter().map(|weapon| weapon.to_str()) {
+            if is_empty_slot(class_name) {
+                continue;
+            }
+            match self.weapon(class_name) {
+                Some(weapon) => weight += weapon.weight,
+                None => unknown.push(class_name.to_owned()),
+            }
+        }
+
+        let cooldown_bonus = (200.0 * (1.0 - weight / capacity)).clamp(-200.0, 200.0);
+        Some(WeightStats { weight, capacity, cooldown_bonus, unknown })
+    }
+}
+
+// Les emplacements vides contiennent `None`
+pub fn is_empty_slot(class_name: &str) -> bool {
+    class_name.is_empty() || unicase::eq(class_name, "None")
+}
+
+// Un bonus est une paire de variables : `Intel_*` et l'effet permanent qui l'applique
+const PERMANENT_EFFECT_PREFIX: &str = "PermanentPlayerGameEffect_";
+
+fn variable_keys(bonus: &str) -> [String; 2] {
+    [format!("{}{}", PERMANENT_EFFECT_PREFIX, bonus), bonus.to_owned()]
+}
+
+pub fn has_bonus(player_variables: &IndexMap<ImguiString, i32>, bonus: &str) -> bool {
+    let [effect, _] = variable_keys(bonus);
+    player_variables.iter().any(|(key, value)| unicase::eq(key.to_str(), &effect) && *value != 0)
+}
+
+pub fn set_bonus(player_variables: &mut IndexMap<ImguiString, i32>, bonus: &str, value: bool) {
+    for key in &variable_keys(bonus) {
+        let found = player_variables.keys().position(|k| unicase::eq(k.to_str(), key));
+        match (found, value) {
+            (Some(i), true) => *player_variables.get_index_mut(i).unwrap().1 = 1,
+            (None, true) => {
+                player_variables.insert(ImString::new(key).into(), 1);
+            }
+            (Some(i), false) => {
+                player_variables.shift_remove_index(i);
+            }
+            (None, false) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn gear_bonuses() -> Result<()> {
+        let gear_db: Me3GearDb = {
+            let mut input = String::new();
+            let mut file = File::open("databases/me3_gear_db.ron")?;
+            file.read_to_string(&mut input)?;
+            ron::from_str(&input)?
+        };
+
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let variables = &mut me3_save_game.player_variables;
+
+        // Tous les bonus de la save de test sont dans la DB
+        for bonus in gear_db.intel_bonuses.keys() {
+            assert!(has_bonus(variables, bonus));
+        }
+
+        let len = variables.len();
+        set_bonus(variables, "Intel_Feron_Shields", false);
+        assert!(!has_bonus(variables, "Intel_Feron_Shields"));
+        assert_eq!(variables.len(), len - 2);
+
+        set_bonus(variables, "Intel_Feron_Shields", true);
+        assert!(has_bonus(variables, "Intel_Feron_Shields"));
+        assert_eq!(variables.len(), len);
+
+        // Poids des armes équipées
+        let player = &mut me3_save_game.player;
+        let stats = gear_db.weight_stats(player.class_name.to_str(), &player.weapons_loadout);
+        assert!(matches!(stats, Some(stats) if stats.unknown.is_empty()));
+
+        let loadout = &mut player.weapons_loadout;
+        *loadout = WeaponLoadout::default();
+        loadout.pistol = ImString::new("SFXGameContent.SFXWeapon_Pistol_Predator").into();
+        let stats = gear_db.weight_stats("SFXGame.SFXPawn_PlayerAdeptNonCombat", loadout).unwrap();
+        assert_eq!(stats.weight, 0.5);
+        assert!((stats.cooldown_bonus - 120.0).abs() < 0.01);
+
+        loadout.shotgun = ImString::new("SFXGameContent.SFXWeapon_Shotgun_Claymore").into();
+        loadout.sniper_rifle = ImString::new("SFXGameContent.SFXWeapon_SniperRifle_Widow").into();
+        let stats = gear_db.weight_stats("SFXGame.SFXPawn_PlayerAdept", loadout).unwrap();
+        assert_eq!(stats.cooldown_bonus, -200.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::shared::recipe::PlotEdit;
+
+use super::plot::PlotTable;
+
+// Bugs d'import connus de ME3, reconnus par une combinaison de plots
+#[derive(Deserialize)]
+pub struct KnownIssuesDb(pub Vec<KnownIssue>);
+
+#[derive(Deserialize)]
+pub struct KnownIssue {
+    pub title: String,
+    pub description: String,
+    pub when: PlotPattern,
+    pub fixes: Vec<KnownIssueFix>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct PlotPattern {
+    pub booleans: IndexMap<usize, bool>,
+    pub ints: IndexMap<usize, i32>,
+}
+
+#[derive(Deserialize)]
+pub struct KnownIssueFix {
+    pub label: String,
+    #[serde(default)]
+    pub booleans: IndexMap<usize, bool>,
+    #[serde(default)]
+    pub ints: IndexMap<usize, i32>,
+}
+
+impl KnownIssuesDb {
+    pub fn found<'a, 'p>(&'a self, plot: &'p PlotTable) -> impl Iterator<Item = &'a KnownIssue> + 'p
+    where
+        'a: 'p,
+    {
+        self.0.iter().filter(move |issue| issue.when.matches(plot))
+    }
+}
+
+impl PlotPattern {
+    // Un plot absent vaut `false` / 0, comme en jeu
+    pub fn matches(&self, plot: &PlotTable) -> bool {
+        let booleans = self.booleans.iter().all(|(&id, &value)| {
+            plot.bool_variables.get(id).map(|bit| *bit).unwrap_or_default() == value
+        });
+        let ints = self.ints.iter().all(|(&id, &value)| {
+            plot.int_variables.get(&(id as i32)).copied().unwrap_or_default() == value
+        });
+        booleans && ints
+    }
+}
+
+impl KnownIssueFix {
+    pub fn apply(&self, plot: &mut PlotTable) {
+        for (&id, &value) in &self.booleans {
+            plot.set_bool(id, value);
+        }
+        for (&id, &value) in &self.ints {
+            plot.set_int(id, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn fix_known_issues() -> Result<()> {
+        let mut input = String::new();
+        {
+            let mut file = File::open("databases/me3_known_issues.ron")?;
+            file.read_to_string(&mut input)?;
+        }
+        let known_issues: KnownIssuesDb = ron::from_str(&input)?;
+
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = &mut me3_save_game.plot;
+
+        // Chaque correctif fait disparaître son problème
+        for issue in &known_issues.0 {
+            for fix in &issue.fixes {
+                let mut plot = plot.clone();
+                for (&id, &value) in &issue.when.booleans {
+                    plot.set_bool(id, value);
+                }
+                for (&id, &value) in &issue.when.ints {
+                    plot.set_int(id, value);
+                }
+                assert!(issue.when.matches(&plot), "{}", issue.title);
+
+                fix.apply(&mut plot);
+                assert!(!issue.when.matches(&plot), "{} : {}", issue.title, fix.label);
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,10 @@ use indexmap::IndexMap;
 use serde::{de, Deserialize, Serialize};
 
 use super::{
-    shared::{Door, EndGameState, Guid, KismetRecord, Level, Rotator, SaveTimeStamp, Vector},
+    shared::{
+        validation::{Issues, Validate},
+        Door, EndGameState, Guid, KismetRecord, Level, Rotator, SaveTimeStamp, Vector,
+    },
     ImguiString,
 };
 
@@ -18,7 +21,13 @@ use plot::*;
 
 pub mod plot_db;
 
-mod galaxy_map;
+pub mod known_issues;
+
+pub mod career_audit;
+
+pub mod gear_db;
+
+pub mod galaxy_map;
 use galaxy_map::*;
 
 #[derive(Deserialize, Serialize, RawUi, Clone)]
@@ -27,26 +36,26 @@ pub struct Me3SaveGame {
     debug_name: ImguiString,
     seconds_played: f32,
     disc: i32,
-    base_level_name: ImguiString,
+    pub base_level_name: ImguiString,
     base_level_name_display_override_as_read: ImguiString,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
-    location: Vector,
-    rotation: Rotator,
+    pub timestamp: SaveTimeStamp,
+    pub location: Vector,
+    pub rotation: Rotator,
     current_loading_tip: i32,
-    levels: Vec<Level>,
+    pub levels: Vec<Level>,
     streaming_records: IndexMap<ImguiString, bool>,
     kismet_records: Vec<KismetRecord>,
     doors: Vec<Door>,
     placeables: Vec<Placeable>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     _me1_plot: Me1PlotTable,
     pub player_variables: IndexMap<ImguiString, i32>,
-    galaxy_map: GalaxyMap,
+    pub galaxy_map: GalaxyMap,
     dependant_dlcs: Vec<DependentDlc>,
     treasures: Vec<LevelTreasure>,
     use_modules: Vec<Guid>,
@@ -55,9 +64,20 @@ pub struct Me3SaveGame {
     saved_objective_text: i32,
 }
 
+impl Validate for Me3SaveGame {
+    fn validate(&self, issues: &mut Issues) {
+        self.player.validate(issues);
+        self.plot.validate(issues);
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct Me3Version(i32);
 
+impl Me3Version {
+    pub const LATEST: i32 = 59;
+}
+
 impl<'de> serde::Deserialize<'de> for Me3Version {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -65,7 +85,7 @@ impl<'de> serde::Deserialize<'de> for Me3Version {
     {
         let version: i32 = serde::Deserialize::deserialize(deserializer)?;
 
-        if version != 59 {
+        if version != Self::LATEST {
             return Err(de::Error::custom(
                 "Wrong save version, please use a save from the latest version of the game",
             ));
@@ -86,6 +106,7 @@ pub enum Difficulty {
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Placeable {
+    #[rawui(readonly)]
     guid: Guid,
     is_destroyed: PlaceableState,
     is_deactivated: PlaceableState,
@@ -158,7 +179,7 @@ mod test {
         {fs::File, io::Read},
     };
 
-    use crate::unreal;
+    use crate::{save_data::RawUi, unreal};
 
     use super::*;
 
@@ -201,4 +222,59 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn revert() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        let original: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut me3_save_game = original.clone();
+
+        me3_save_game.disc += 1;
+        me3_save_game.seconds_played += 1.0;
+        *me3_save_game.player_variables.get_index_mut(0).unwrap().1 += 1;
+
+        // Field
+        me3_save_game.revert(&original, &[String::from("Seconds Played")]);
+        assert_eq!(me3_save_game.seconds_played, original.seconds_played);
+        assert_ne!(me3_save_game.disc, original.disc);
+
+        // Sub-tree
+        me3_save_game.revert(&original, &[String::from("Player Variables"), String::from("0")]);
+        assert_eq!(me3_save_game.player_variables[0], original.player_variables[0]);
+
+        // Everything
+        me3_save_game.revert(&original, &[]);
+        let output = unreal::Serializer::to_byte_buf(&me3_save_game)?;
+        assert_eq!(&input[..input.len() - 4], &output[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_context() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        let truncated = &input[..input.len() / 2];
+        let err = match unreal::Deserializer::from_bytes::<Me3SaveGame>(truncated) {
+            Ok(_) => panic!("a truncated save should not deserialize"),
+            Err(err) => err,
+        };
+        let context = err.context().unwrap();
+
+        assert_eq!(context.error, unreal::Error::Eof);
+        assert!(context.offset <= truncated.len());
+        assert!(!context.path.is_empty());
+        assert!(context.report().contains(&context.path));
+
+        Ok(())
+    }
 }
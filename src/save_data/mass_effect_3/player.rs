@@ -5,6 +5,7 @@ use crate::save_data::{
     shared::{
         appearance::Appearance,
         player::{Notoriety, Origin, WeaponLoadout},
+        validation::{Issues, Tab, Validate},
     },
     Dummy, ImguiString,
 };
@@ -29,13 +30,13 @@ pub struct Player {
     pub appearance: Appearance,
     emissive_id: i32,
     pub powers: Vec<Power>,
-    war_assets: IndexMap<i32, i32>,
-    weapons: Vec<Weapon>,
+    pub war_assets: IndexMap<i32, i32>,
+    pub weapons: Vec<Weapon>,
     weapons_mods: Vec<WeaponMod>,
-    weapons_loadout: WeaponLoadout,
-    primary_weapon: ImguiString,
-    secondary_weapon: ImguiString,
-    loadout_weapon_group: Vec<i32>,
+    pub weapons_loadout: WeaponLoadout,
+    pub primary_weapon: ImguiString,
+    pub secondary_weapon: ImguiString,
+    pub loadout_weapon_group: Vec<i32>,
     hotkeys: Vec<Hotkey>,
     current_health: f32,
     pub credits: i32,
@@ -44,7 +45,7 @@ pub struct Player {
     iridium: i32,
     palladium: i32,
     platinum: i32,
-    probes: i32,
+    pub probes: i32,
     pub current_fuel: f32,
     pub grenades: i32,
     pub face_code: ImguiString,
@@ -54,8 +55,8 @@ pub struct Player {
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Power {
-    name: ImguiString,
-    rank: f32,
+    pub name: ImguiString,
+    pub rank: f32,
     evolved_choice_0: i32,
     evolved_choice_1: i32,
     evolved_choice_2: i32,
@@ -68,12 +69,12 @@ pub struct Power {
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Weapon {
-    class_name: ImguiString,
+    pub class_name: ImguiString,
     ammo_used_count: i32,
     ammo_total: i32,
     current_weapon: bool,
     was_last_weapon: bool,
-    ammo_power_name: ImguiString,
+    pub ammo_power_name: ImguiString,
     ammo_power_source_tag: ImguiString,
 }
 
@@ -88,3 +89,16 @@ struct Hotkey {
     pawn_name: ImguiString,
     power_name: ImguiString,
 }
+
+impl Validate for Player {
+    fn validate(&self, issues: &mut Issues) {
+        issues
+            .check(Tab::General, self.level >= 1, || format!("Level is below 1 ({})", self.level));
+        issues.non_negative(Tab::General, "Talent points", self.talent_points);
+        issues.non_negative(Tab::General, "Credits", self.credits);
+        issues.non_negative(Tab::General, "Medigel", self.medigel);
+        issues.non_negative(Tab::General, "Grenades", self.grenades);
+        issues.non_negative(Tab::General, "Fuel", self.current_fuel);
+        self.appearance.head_morph.validate(issues);
+    }
+}
@@ -1,7 +1,10 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::shared::plot::{BoolVec, PlotCodex};
+use crate::save_data::shared::{
+    plot::{BoolVec, PlotCodex},
+    validation::{Issues, Tab, Validate},
+};
 
 #[derive(Deserialize, Serialize, RawUi, Clone)]
 pub struct PlotTable {
@@ -29,3 +32,12 @@ pub struct PlotQuest {
     active_goal: i32,
     history: Vec<i32>,
 }
+
+impl Validate for PlotTable {
+    fn validate(&self, issues: &mut Issues) {
+        let (quests, ids) = (self.quest_progress.len(), self.quest_ids.len());
+        issues.same_len(Tab::Raw, "Quest Progress", quests, "Quest Ids", ids);
+        let (entries, ids) = (self.codex_entries.len(), self.codex_ids.len());
+        issues.same_len(Tab::Raw, "Codex Entries", entries, "Codex Ids", ids);
+    }
+}
@@ -1,7 +1,11 @@
 use indexmap::IndexMap;
 use serde::Deserialize;
+use std::iter;
 
-use crate::save_data::{mass_effect_1::plot_db::Me1PlotDb, shared::plot::PlotCategory};
+use crate::save_data::{
+    mass_effect_1::plot_db::Me1PlotDb,
+    shared::plot::{named_categories, PlotCategory},
+};
 
 #[derive(Deserialize)]
 pub struct Me3PlotDb {
@@ -10,13 +14,42 @@ pub struct Me3PlotDb {
     pub crew: IndexMap<String, PlotCategory>,
     pub romance: IndexMap<String, PlotCategory>,
     pub missions: IndexMap<String, PlotCategory>,
-    pub citadel_dlc: IndexMap<String, PlotCategory>,
+    pub citadel_dlc: IndexMap<String, IndexMap<String, PlotCategory>>,
     pub normandy: IndexMap<String, PlotCategory>,
     pub intel: PlotCategory,
     pub weapons_powers: IndexMap<String, PlotVariable>,
     pub me1_imported: Me1PlotDb,
 }
 
+impl Me3PlotDb {
+    // Sans `weapons_powers` qui n'est pas une catégorie
+    pub fn categories(&self) -> impl Iterator<Item = &PlotCategory> {
+        self.named_categories().map(|(_, category)| category)
+    }
+
+    pub fn named_categories(&self) -> impl Iterator<Item = (String, &PlotCategory)> {
+        let citadel_dlc = self.citadel_dlc.iter().flat_map(|(section, categories)| {
+            categories.iter().map(move |(name, category)| {
+                (format!("Citadel DLC > {} > {}", section, name), category)
+            })
+        });
+        let me1_imported = self
+            .me1_imported
+            .named_categories()
+            .map(|(name, category)| (format!("Mass Effect 1 > {}", name), category));
+
+        iter::once(("General".to_owned(), &self.general))
+            .chain(named_categories("Appearances", &self.appearances))
+            .chain(named_categories("Crew", &self.crew))
+            .chain(named_categories("Romance", &self.romance))
+            .chain(named_categories("Missions", &self.missions))
+            .chain(citadel_dlc)
+            .chain(named_categories("Normandy", &self.normandy))
+            .chain(iter::once(("Intel".to_owned(), &self.intel)))
+            .chain(me1_imported)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct PlotVariable {
     pub booleans: IndexMap<usize, String>,
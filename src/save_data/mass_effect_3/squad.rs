@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::{shared::player::WeaponLoadout, ImguiString};
+use crate::save_data::{
+    shared::{player::WeaponLoadout, squad::HenchmanBuild},
+    ImguiString,
+};
 
 use super::player::{Power, Weapon, WeaponMod};
 
@@ -16,3 +19,76 @@ pub struct Henchman {
     grenades: i32,
     weapons: Vec<Weapon>,
 }
+
+impl HenchmanBuild for Henchman {
+    fn tag(&self) -> &str {
+        self.tag.to_str()
+    }
+
+    fn level(&self) -> i32 {
+        self.character_level
+    }
+
+    // Les pouvoirs sont associés par classe, leur ordre peut changer d'une save à l'autre
+    fn copy_build(&mut self, source: &Self) {
+        for power in &source.powers {
+            let class_name = power.power_class_name.to_str();
+            match self
+                .powers
+                .iter_mut()
+                .find(|p| unicase::eq(p.power_class_name.to_str(), class_name))
+            {
+                Some(target) => *target = power.clone(),
+                None => self.powers.push(power.clone()),
+            }
+        }
+        self.character_level = source.character_level;
+        self.talent_points = source.talent_points;
+        self.weapon_loadout = source.weapon_loadout.clone();
+        self.mapped_power = source.mapped_power.clone();
+        self.weapon_mods = source.weapon_mods.clone();
+        self.grenades = source.grenades;
+        self.weapons = source.weapons.clone();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use imgui::ImString;
+    use std::fs;
+
+    use crate::{
+        save_data::{mass_effect_3::Me3SaveGame, shared::squad},
+        unreal,
+    };
+
+    use super::*;
+
+    #[test]
+    fn copy_henchman_build() -> Result<()> {
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let mut save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        // Build d'une autre save, pouvoirs dans un autre ordre
+        let mut source = save_game.squad[0].clone();
+        source.character_level = 30;
+        source.powers.reverse();
+        let power = &mut source.powers[0];
+        power.rank = 6.0;
+        let class_name = power.power_class_name.clone();
+        let powers_len = source.powers.len();
+
+        squad::copy_build(&mut save_game.squad, &source)?;
+        let henchman = &save_game.squad[0];
+        assert_eq!(henchman.level(), 30);
+        assert_eq!(henchman.powers.len(), powers_len);
+        let power = henchman.powers.iter().find(|p| p.power_class_name == class_name).unwrap();
+        assert_eq!(power.rank, 6.0);
+
+        // Seulement le même coéquipier
+        source.tag = ImString::new("hench_unknown").into();
+        assert!(squad::copy_build(&mut save_game.squad, &source).is_err());
+        Ok(())
+    }
+}
@@ -1,24 +1,113 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use derive_more::{Deref, DerefMut, Display, From};
+use if_chain::if_chain;
 use imgui::ImString;
 use indexmap::IndexMap;
 use serde::{de, ser::SerializeSeq, Serialize};
 use std::{
+    any,
+    collections::{HashMap, HashSet},
     fmt::{self, Display},
     hash::Hash,
 };
 
 use crate::gui::Gui;
 
+use self::schema::{Definitions, TypeDesc};
+
 pub mod mass_effect_1;
 pub mod mass_effect_1_leg;
 pub mod mass_effect_2;
 pub mod mass_effect_3;
+pub mod schema;
 pub mod shared;
 
 // Raw Ui
+// Valeurs d'origine affichées par champ, par chemin comme pour le revert
+pub type OriginalValues = HashMap<Vec<String>, String>;
+
 pub trait RawUi {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str);
+
+    // Revert le champ (ou le sous-arbre) désigné par `path` à sa valeur d'origine
+    fn revert(&mut self, original: &Self, _path: &[String])
+    where
+        Self: Sized + Clone,
+    {
+        *self = original.clone();
+    }
+
+    // Dessine le champ désigné par `path`, pour le panneau de watch
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        if path.is_empty() {
+            self.draw_raw_ui(gui, ident);
+        }
+    }
+
+    // Relève la valeur de chaque champ simple, avec le même chemin que `draw_raw_ui`
+    fn collect_values(&self, _path: &mut Vec<String>, _values: &mut OriginalValues) {}
+
+    // Vue tableau d'une liste de structures : un champ par colonne
+    fn table_columns() -> Option<&'static [&'static str]>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    fn draw_table_cell(&mut self, _gui: &Gui, _ident: &str, _column: usize) {}
+
+    // Dessiné sur une seule ligne de hauteur fixe, les listes peuvent être virtualisées
+    fn is_flat() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    fn table_cell_value(&self, _column: usize) -> Option<String> {
+        None
+    }
+
+    // Chaque string modifiable, avec le même chemin que `draw_raw_ui`
+    fn visit_strings(
+        &mut self, _path: &mut Vec<String>, _visit: &mut dyn FnMut(&[String], &mut ImString),
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    // Type pour l'export du schéma, voir `schema::save_schema`
+    fn describe(_definitions: &mut Definitions) -> TypeDesc
+    where
+        Self: Sized,
+    {
+        TypeDesc::Opaque(any::type_name::<Self>())
+    }
+}
+
+fn collect_value(value: impl Display, path: &[String], values: &mut OriginalValues) {
+    values.insert(path.to_vec(), value.to_string());
+}
+
+// Champ en lecture seule, voir `#[rawui(readonly)]`
+pub struct ReadOnly<T>(pub T);
+
+impl<T: RawUi> RawUi for ReadOnly<T> {
+    fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
+        gui.draw_read_only(|| self.0.draw_raw_ui(gui, ident));
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        gui.draw_read_only(|| self.0.draw_watch(gui, ident, path));
+    }
+
+    fn is_flat() -> bool {
+        T::is_flat()
+    }
+
+    fn describe(definitions: &mut Definitions) -> TypeDesc {
+        T::describe(definitions)
+    }
 }
 
 // Nouveau string type pour pouvoir implémenter serde...
@@ -29,6 +118,25 @@ impl RawUi for ImguiString {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_string(ident, &mut self.0);
     }
+
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        collect_value(self, path, values);
+    }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn visit_strings(
+        &mut self, path: &mut Vec<String>, visit: &mut dyn FnMut(&[String], &mut ImString),
+    ) -> Result<()> {
+        visit(path, &mut self.0);
+        Ok(())
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::String
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for ImguiString {
@@ -60,6 +168,17 @@ impl<const LEN: usize> Default for Dummy<LEN> {
     }
 }
 
+// Seulement avec le profil développeur, voir `RawUi` derive
+impl<const LEN: usize> RawUi for Dummy<LEN> {
+    fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
+        gui.draw_hex_bytes(ident, &self.0);
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::FixedArray(Box::new(TypeDesc::Byte), LEN)
+    }
+}
+
 impl<'de, const LEN: usize> serde::Deserialize<'de> for Dummy<LEN> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -159,35 +278,246 @@ impl RawUi for i32 {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_i32(ident, self);
     }
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        collect_value(self, path, values);
+    }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::Int
+    }
 }
 
 impl RawUi for f32 {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_f32(ident, self);
     }
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        collect_value(self, path, values);
+    }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::Float
+    }
 }
 
 impl RawUi for bool {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_bool(ident, self);
     }
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        collect_value(self, path, values);
+    }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::Bool
+    }
 }
 
 impl<T> RawUi for Vec<T>
 where
-    T: RawUi + Default,
+    T: RawUi + Default + Clone,
 {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_vec(ident, self);
     }
+
+    fn revert(&mut self, original: &Self, path: &[String]) {
+        match path.split_first() {
+            Some((i, path)) => {
+                if_chain! {
+                    if let Ok(i) = i.parse::<usize>();
+                    if let Some(item) = self.get_mut(i);
+                    if let Some(original) = original.get(i);
+                    then {
+                        item.revert(original, path);
+                    }
+                }
+            }
+            None => *self = original.clone(),
+        }
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        match path.split_first() {
+            Some((i, path)) => {
+                if let Some(item) = i.parse::<usize>().ok().and_then(|i| self.get_mut(i)) {
+                    item.draw_watch(gui, ident, path);
+                }
+            }
+            None => self.draw_raw_ui(gui, ident),
+        }
+    }
+
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        for (i, item) in self.iter().enumerate() {
+            path.push(i.to_string());
+            item.collect_values(path, values);
+            path.pop();
+        }
+    }
+
+    fn visit_strings(
+        &mut self, path: &mut Vec<String>, visit: &mut dyn FnMut(&[String], &mut ImString),
+    ) -> Result<()> {
+        for (i, item) in self.iter_mut().enumerate() {
+            path.push(i.to_string());
+            item.visit_strings(path, visit)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    fn describe(definitions: &mut Definitions) -> TypeDesc {
+        TypeDesc::Array(Box::new(T::describe(definitions)))
+    }
 }
 
 impl<K, V> RawUi for IndexMap<K, V>
 where
-    K: RawUi + Eq + Hash + Default + Display + 'static,
-    V: RawUi + Default,
+    K: RawUi + Eq + Hash + Default + Display + Clone + 'static,
+    V: RawUi + Default + Clone,
 {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_indexmap(ident, self);
     }
+
+    fn revert(&mut self, original: &Self, path: &[String]) {
+        match path.split_first() {
+            Some((i, path)) => {
+                // Par clé, l'index a pu changer
+                if_chain! {
+                    if let Ok(i) = i.parse::<usize>();
+                    if let Some((key, value)) = self.get_index_mut(i);
+                    if let Some(original) = original.get(key);
+                    then {
+                        value.revert(original, path);
+                    }
+                }
+            }
+            None => *self = original.clone(),
+        }
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        match path.split_first() {
+            Some((i, path)) => {
+                if let Some((_, value)) =
+                    i.parse::<usize>().ok().and_then(|i| self.get_index_mut(i))
+                {
+                    value.draw_watch(gui, ident, path);
+                }
+            }
+            None => self.draw_raw_ui(gui, ident),
+        }
+    }
+    // La clé est dessinée au même chemin que la valeur, seule la valeur est relevée
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        for (i, value) in self.values().enumerate() {
+            path.push(i.to_string());
+            value.collect_values(path, values);
+            path.pop();
+        }
+    }
+
+    // Les clés aussi (noms de niveaux...), la map est reconstruite si l'une d'elles change
+    fn visit_strings(
+        &mut self, path: &mut Vec<String>, visit: &mut dyn FnMut(&[String], &mut ImString),
+    ) -> Result<()> {
+        let mut renamed = Vec::new();
+        for (i, (key, value)) in self.iter_mut().enumerate() {
+            path.push(i.to_string());
+            let mut new_key = key.clone();
+            new_key.visit_strings(path, visit)?;
+            if new_key != *key {
+                renamed.push((i, new_key));
+            }
+            value.visit_strings(path, visit)?;
+            path.pop();
+        }
+
+        if !renamed.is_empty() {
+            let mut keys: Vec<_> = self.keys().cloned().collect();
+            for (i, key) in renamed {
+                keys[i] = key;
+            }
+
+            // Deux entrées fusionnées perdraient l'une des valeurs
+            let mut seen = HashSet::new();
+            if let Some(key) = keys.iter().find(|key| !seen.insert(*key)) {
+                bail!("Two keys of `{}` would both become `{}`", path.join(" > "), key);
+            }
+
+            let values: Vec<_> = self.drain(..).map(|(_, value)| value).collect();
+            *self = keys.into_iter().zip(values).collect();
+        }
+        Ok(())
+    }
+
+    fn describe(definitions: &mut Definitions) -> TypeDesc {
+        TypeDesc::Map(Box::new(K::describe(definitions)), Box::new(V::describe(definitions)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(RawUi, Clone, Default)]
+    struct Tuned {
+        level: i32,
+        #[rawui(rename = "Shepard's Name")]
+        first_name: ImguiString,
+        #[rawui(skip)]
+        cache: i32,
+        #[rawui(readonly)]
+        checksum: i32,
+        _unknown: i32,
+    }
+
+    #[test]
+    fn raw_ui_attributes() {
+        let tuned = Tuned { checksum: 0x1234, ..Default::default() };
+        let mut values = OriginalValues::new();
+        tuned.collect_values(&mut Vec::new(), &mut values);
+
+        let mut paths: Vec<_> = values.keys().map(|path| path.join("/")).collect();
+        paths.sort();
+        assert_eq!(paths, ["Checksum", "Level", "Shepard's Name"]);
+
+        // Pas de revert par le chemin d'un champ en lecture seule
+        let mut reverted = tuned.clone();
+        reverted.checksum = 0;
+        reverted.revert(&tuned, &[String::from("Checksum")]);
+        assert_eq!(reverted.checksum, 0);
+        assert_eq!(tuned.cache + tuned._unknown, 0);
+    }
+
+    #[test]
+    fn table_columns() {
+        assert_eq!(Tuned::table_columns(), Some(&["Level", "Shepard's Name", "Checksum"][..]));
+
+        let tuned = Tuned { level: 60, checksum: 0x1234, ..Default::default() };
+        assert_eq!(tuned.table_cell_value(0).as_deref(), Some("60"));
+        assert_eq!(tuned.table_cell_value(2).as_deref(), Some("4660"));
+        assert_eq!(tuned.table_cell_value(3), None);
+
+        // Pas de colonnes pour une liste de valeurs simples
+        assert!(i32::table_columns().is_none());
+
+        // Seules les listes de valeurs simples sont virtualisées
+        assert!(i32::is_flat() && ReadOnly::<ImguiString>::is_flat());
+        assert!(!Tuned::is_flat() && !Vec::<i32>::is_flat());
+    }
 }
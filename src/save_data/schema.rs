@@ -0,0 +1,260 @@
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+use std::any;
+
+use super::{
+    mass_effect_1::state::State,
+    mass_effect_1_leg::Me1LegSaveData,
+    mass_effect_2::{Me2LegSaveGame, Me2SaveGame},
+    mass_effect_3::Me3SaveGame,
+    RawUi,
+};
+
+// Description des structures de save pour les outils externes, voir `--export-schema`.
+// Les champs sont dans l'ordre du fichier, y compris ceux que l'éditeur ne dessine pas.
+pub enum TypeDesc {
+    Bool,
+    Byte,
+    Int,
+    Float,
+    String,
+    Array(Box<TypeDesc>),
+    FixedArray(Box<TypeDesc>, usize),
+    Map(Box<TypeDesc>, Box<TypeDesc>),
+    Optional(Box<TypeDesc>),
+    Ref(String),
+    // Lu mais pas interprété par l'éditeur
+    Opaque(&'static str),
+}
+
+pub struct FieldDesc {
+    pub name: &'static str,
+    // Label du raw UI, `None` pour les champs cachés
+    pub title: Option<&'static str>,
+    pub readonly: bool,
+    pub type_desc: TypeDesc,
+}
+
+pub enum Definition {
+    Struct(Vec<FieldDesc>),
+    Enum(&'static [&'static str]),
+}
+
+#[derive(Default)]
+pub struct Definitions(IndexMap<String, Definition>);
+
+impl Definitions {
+    pub fn define_struct<T: ?Sized>(
+        &mut self, fields: impl FnOnce(&mut Self) -> Vec<FieldDesc>,
+    ) -> TypeDesc {
+        let key = type_key::<T>();
+        if !self.0.contains_key(&key) {
+            // Réservé avant les champs, pour les types récursifs
+            self.0.insert(key.clone(), Definition::Struct(Vec::new()));
+            let fields = fields(self);
+            self.0.insert(key.clone(), Definition::Struct(fields));
+        }
+        TypeDesc::Ref(key)
+    }
+
+    pub fn define_enum<T: ?Sized>(&mut self, variants: &'static [&'static str]) -> TypeDesc {
+        let key = type_key::<T>();
+        self.0.entry(key.clone()).or_insert(Definition::Enum(variants));
+        TypeDesc::Ref(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Definition> {
+        self.0.get(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.0.keys()
+    }
+}
+
+// Type racine de chaque format, les définitions de tous les types rencontrés
+pub fn save_definitions() -> (Vec<(&'static str, TypeDesc)>, Definitions) {
+    let mut definitions = Definitions::default();
+    let roots = vec![
+        ("Mass Effect 1 (State)", State::describe(&mut definitions)),
+        ("Mass Effect 1 Legendary", Me1LegSaveData::describe(&mut definitions)),
+        ("Mass Effect 2", Me2SaveGame::describe(&mut definitions)),
+        ("Mass Effect 2 Legendary", Me2LegSaveGame::describe(&mut definitions)),
+        ("Mass Effect 3", Me3SaveGame::describe(&mut definitions)),
+    ];
+    (roots, definitions)
+}
+
+// Notation proche de Rust pour la documentation du format
+pub fn type_name(type_desc: &TypeDesc) -> String {
+    match type_desc {
+        TypeDesc::Bool => "bool".to_owned(),
+        TypeDesc::Byte => "u8".to_owned(),
+        TypeDesc::Int => "i32".to_owned(),
+        TypeDesc::Float => "f32".to_owned(),
+        TypeDesc::String => "String".to_owned(),
+        TypeDesc::Array(items) => format!("Vec<{}>", type_name(items)),
+        TypeDesc::FixedArray(items, len) => format!("[{}; {}]", type_name(items), len),
+        TypeDesc::Map(key, value) => format!("Map<{}, {}>", type_name(key), type_name(value)),
+        TypeDesc::Optional(type_desc) => format!("Option<{}>", type_name(type_desc)),
+        TypeDesc::Ref(key) => key.rsplit("::").next().unwrap_or(key).to_owned(),
+        TypeDesc::Opaque(type_name) => format!("opaque ({})", type_name),
+    }
+}
+
+// `trilogy_save_editor::save_data::mass_effect_2::player::Player` => `mass_effect_2::player::Player`
+fn type_key<T: ?Sized>() -> String {
+    let name = any::type_name::<T>();
+    let name = name.split_once("::").map_or(name, |(_, path)| path);
+    name.trim_start_matches("save_data::").to_owned()
+}
+
+pub fn save_schema() -> Value {
+    let (roots, definitions) = save_definitions();
+
+    let one_of: Vec<_> = roots
+        .into_iter()
+        .map(|(title, type_desc)| {
+            let mut schema = type_schema(&type_desc);
+            if let Value::Object(object) = &mut schema {
+                object.insert("title".to_owned(), title.into());
+            }
+            schema
+        })
+        .collect();
+
+    let defs: Map<_, _> = definitions
+        .0
+        .iter()
+        .map(|(key, definition)| (key.clone(), definition_schema(definition)))
+        .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Trilogy Save Editor save structures",
+        "description": format!(
+            "Save structures understood by Trilogy Save Editor v{}, fields in file order. Enums are stored as their variant index, maps as a list of [key, value] pairs.",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "oneOf": one_of,
+        "$defs": defs,
+    })
+}
+
+fn definition_schema(definition: &Definition) -> Value {
+    match definition {
+        Definition::Struct(fields) => {
+            let properties: Map<_, _> = fields
+                .iter()
+                .map(|field| {
+                    let mut schema = type_schema(&field.type_desc);
+                    if let Value::Object(object) = &mut schema {
+                        if let Some(title) = field.title {
+                            object.insert("title".to_owned(), title.into());
+                        }
+                        if field.readonly {
+                            object.insert("readOnly".to_owned(), true.into());
+                        }
+                    }
+                    (field.name.to_owned(), schema)
+                })
+                .collect();
+            let required: Vec<_> = fields.iter().map(|field| field.name).collect();
+
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            })
+        }
+        // Stockés par leur index, le nom du variant sert de titre
+        Definition::Enum(variants) => {
+            let one_of: Vec<_> = variants
+                .iter()
+                .enumerate()
+                .map(|(i, variant)| json!({ "const": i, "title": variant }))
+                .collect();
+            json!({ "type": "integer", "oneOf": one_of })
+        }
+    }
+}
+
+fn type_schema(type_desc: &TypeDesc) -> Value {
+    match type_desc {
+        TypeDesc::Bool => json!({ "type": "boolean" }),
+        TypeDesc::Byte => json!({ "type": "integer", "minimum": 0, "maximum": 255 }),
+        TypeDesc::Int => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        TypeDesc::Float => json!({ "type": "number" }),
+        TypeDesc::String => json!({ "type": "string" }),
+        TypeDesc::Array(items) => json!({ "type": "array", "items": type_schema(items) }),
+        TypeDesc::FixedArray(items, len) => json!({
+            "type": "array",
+            "items": type_schema(items),
+            "minItems": len,
+            "maxItems": len,
+        }),
+        TypeDesc::Map(key, value) => {
+            let pair = json!({
+                "type": "array",
+                "prefixItems": [type_schema(key), type_schema(value)],
+                "items": false,
+                "minItems": 2,
+            });
+            json!({ "type": "array", "items": pair })
+        }
+        TypeDesc::Optional(type_desc) => {
+            json!({ "anyOf": [{ "type": "null" }, type_schema(type_desc)] })
+        }
+        TypeDesc::Ref(key) => json!({ "$ref": format!("#/$defs/{}", key) }),
+        TypeDesc::Opaque(type_name) => {
+            json!({ "description": format!("Not interpreted ({})", type_name) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn export_schema() -> Result<()> {
+        let schema = save_schema();
+        assert_eq!(serde_json::from_str::<Value>(&schema.to_string())?, schema);
+
+        let defs = schema.get("$defs").unwrap();
+        let me2 = defs.get("mass_effect_2::Me2SaveGame").unwrap();
+        let properties = me2.get("properties").unwrap();
+        assert_eq!(
+            properties.get("player").and_then(|player| player.get("$ref")),
+            Some(&"#/$defs/mass_effect_2::player::Player".into())
+        );
+        // Les champs cachés sont décrits aussi, pour garder l'ordre du fichier
+        assert!(properties.get("_version").is_some());
+
+        let credits = defs.get("mass_effect_2::player::Player").and_then(|player| {
+            player.get("properties").and_then(|properties| properties.get("credits"))
+        });
+        assert_eq!(credits.and_then(|credits| credits.get("type")), Some(&"integer".into()));
+        assert_eq!(credits.and_then(|credits| credits.get("title")), Some(&"Credits".into()));
+
+        let difficulty = defs.get("mass_effect_3::Difficulty").unwrap();
+        assert_eq!(difficulty.get("type"), Some(&"integer".into()));
+        let variants = difficulty.get("oneOf").and_then(Value::as_array).unwrap();
+        assert_eq!(variants[1].get("const"), Some(&1.into()));
+        assert_eq!(variants[1].get("title"), Some(&"Casual".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn type_names() {
+        let map = TypeDesc::Map(Box::new(TypeDesc::String), Box::new(TypeDesc::Int));
+        assert_eq!(type_name(&TypeDesc::Array(Box::new(map))), "Vec<Map<String, i32>>");
+        assert_eq!(type_name(&TypeDesc::FixedArray(Box::new(TypeDesc::Byte), 16)), "[u8; 16]");
+
+        let player = TypeDesc::Ref("mass_effect_2::player::Player".to_owned());
+        assert_eq!(type_name(&TypeDesc::Optional(Box::new(player))), "Option<Player>");
+    }
+}
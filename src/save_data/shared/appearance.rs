@@ -1,18 +1,30 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use flate2::{
+    read::{ZlibDecoder, ZlibEncoder},
+    Compression,
+};
+use imgui::ImString;
 use indexmap::IndexMap;
 use serde::{
     de,
     ser::{Error, SerializeStruct, SerializeTupleStruct},
     Deserialize, Serialize,
 };
-use std::fmt;
+use std::{fmt, io::Read};
 
 use crate::{
     gui::Gui,
-    save_data::{ImguiString, RawUi},
+    save_data::{
+        schema::{Definitions, FieldDesc, TypeDesc},
+        ImguiString, OriginalValues, RawUi,
+    },
+    unreal,
 };
 
-use super::Vector;
+use super::{
+    validation::{Issues, Tab, Validate},
+    Vector,
+};
 
 #[derive(Deserialize, Serialize, RawUi, Clone)]
 pub struct Appearance {
@@ -52,6 +64,66 @@ impl RawUi for HasHeadMorph {
             head_morph.draw_raw_ui(gui, "Head Morph");
         }
     }
+
+    fn revert(&mut self, original: &Self, path: &[String]) {
+        match (&mut self.head_morph, &original.head_morph) {
+            (Some(head_morph), Some(original)) if !path.is_empty() => {
+                head_morph.revert(original, path)
+            }
+            _ => *self = original.clone(),
+        }
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        match &mut self.head_morph {
+            Some(head_morph) if !path.is_empty() => head_morph.draw_watch(gui, ident, path),
+            _ => self.draw_raw_ui(gui, ident),
+        }
+    }
+
+    fn visit_strings(
+        &mut self, path: &mut Vec<String>, visit: &mut dyn FnMut(&[String], &mut ImString),
+    ) -> Result<()> {
+        match &mut self.head_morph {
+            Some(head_morph) => head_morph.visit_strings(path, visit),
+            None => Ok(()),
+        }
+    }
+
+    // Comme pour le dessin, les deux partagent le chemin de `HasHeadMorph`
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        self.has_head_morph.collect_values(path, values);
+        if let Some(head_morph) = &self.head_morph {
+            head_morph.collect_values(path, values);
+        }
+    }
+
+    fn describe(definitions: &mut Definitions) -> TypeDesc {
+        definitions.define_struct::<Self>(|definitions| {
+            vec![
+                FieldDesc {
+                    name: "has_head_morph",
+                    title: Some("Has Head Morph"),
+                    readonly: false,
+                    type_desc: TypeDesc::Bool,
+                },
+                FieldDesc {
+                    name: "head_morph",
+                    title: Some("Head Morph"),
+                    readonly: false,
+                    type_desc: TypeDesc::Optional(Box::new(HeadMorph::describe(definitions))),
+                },
+            ]
+        })
+    }
+}
+
+impl Validate for HasHeadMorph {
+    fn validate(&self, issues: &mut Issues) {
+        issues.check(Tab::HeadMorph, !self.has_head_morph || self.head_morph.is_some(), || {
+            String::from("Head morph is enabled without head morph data, import one first")
+        });
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for HasHeadMorph {
@@ -113,6 +185,77 @@ pub struct HeadMorph {
     pub texture_parameters: IndexMap<ImguiString, ImguiString>,
 }
 
+// Code d'apparence : `TSE<version>:<base64(zlib(head morph))>`
+const APPEARANCE_CODE_PREFIX: &str = "TSE";
+const APPEARANCE_CODE_VERSION: u32 = 1;
+
+impl HeadMorph {
+    pub fn to_appearance_code(&self) -> Result<String> {
+        let uncompressed = unreal::Serializer::to_byte_buf(self)?;
+
+        let mut compressed = Vec::new();
+        {
+            let mut z = ZlibEncoder::new(&uncompressed[..], Compression::best());
+            z.read_to_end(&mut compressed)?;
+        }
+
+        Ok(format!(
+            "{}{}:{}",
+            APPEARANCE_CODE_PREFIX,
+            APPEARANCE_CODE_VERSION,
+            base64::encode_config(compressed, base64::URL_SAFE_NO_PAD)
+        ))
+    }
+
+    pub fn from_appearance_code(code: &str) -> Result<Self> {
+        let (version, payload) = code
+            .trim()
+            .strip_prefix(APPEARANCE_CODE_PREFIX)
+            .and_then(|code| code.split_once(':'))
+            .context("Not an appearance code")?;
+
+        let version: u32 = version.parse().context("Invalid appearance code version")?;
+        if version > APPEARANCE_CODE_VERSION {
+            bail!("This appearance code was made with a newer version of Trilogy Save Editor");
+        }
+
+        let compressed = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+            .context("Corrupted appearance code")?;
+
+        let mut uncompressed = Vec::new();
+        {
+            let mut z = ZlibDecoder::new(&compressed[..]);
+            z.read_to_end(&mut uncompressed).context("Corrupted appearance code")?;
+        }
+
+        let head_morph = unreal::Deserializer::from_bytes(&uncompressed)?;
+        Ok(head_morph)
+    }
+}
+
+// Code du créateur de personnage : `743.8GD.I13.D2G...`
+const FACE_CODE_ALPHABET: &str = "123456789ABCDEFGHIJKLMNOPQRSTUVW";
+const FACE_CODE_MIN_LEN: usize = 30;
+
+pub fn normalize_face_code(code: &str) -> Result<String> {
+    let chars: Vec<char> = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '.')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    if let Some((i, c)) = chars.iter().enumerate().find(|(_, c)| !FACE_CODE_ALPHABET.contains(**c))
+    {
+        bail!("Invalid character `{}` at position {}", c, i + 1);
+    }
+    if chars.len() < FACE_CODE_MIN_LEN {
+        bail!("Too short, {} characters instead of at least {}", chars.len(), FACE_CODE_MIN_LEN);
+    }
+
+    let groups: Vec<String> = chars.chunks(3).map(|group| group.iter().collect()).collect();
+    Ok(groups.join("."))
+}
+
 #[derive(Default, Clone)]
 pub struct LinearColor([f32; 4]);
 
@@ -120,6 +263,14 @@ impl RawUi for LinearColor {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_color(ident, &mut self.0);
     }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::FixedArray(Box::new(TypeDesc::Float), 4)
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for LinearColor {
@@ -147,3 +298,60 @@ impl serde::Serialize for LinearColor {
         linear_color.end()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_3::Me3SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn appearance_code() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let head_morph = me3_save_game.player.appearance.head_morph.head_morph.unwrap();
+
+        let code = head_morph.to_appearance_code()?;
+        let decoded = HeadMorph::from_appearance_code(&code)?;
+
+        assert_eq!(
+            unreal::Serializer::to_byte_buf(&head_morph)?,
+            unreal::Serializer::to_byte_buf(&decoded)?
+        );
+
+        // Version future
+        assert!(HeadMorph::from_appearance_code(&code.replacen("TSE1", "TSE2", 1)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_code() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        let me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let face_code = me3_save_game.player.face_code.to_str();
+        assert_eq!(normalize_face_code(face_code)?, face_code);
+
+        // Collé sans séparateurs
+        let pasted = face_code.replace('.', " ").to_lowercase();
+        assert_eq!(normalize_face_code(&pasted)?, face_code);
+
+        assert!(normalize_face_code("743.8GD.I13").is_err());
+        assert!(normalize_face_code(&face_code.replacen('7', "0", 1)).is_err());
+
+        Ok(())
+    }
+}
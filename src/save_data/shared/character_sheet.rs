@@ -0,0 +1,105 @@
+use indexmap::IndexMap;
+use std::fmt::Write;
+
+use crate::save_data::shared::plot::{BoolSlice, PlotCategory};
+
+#[derive(Default)]
+pub struct CharacterSheet {
+    pub title: String,
+    pub details: Vec<(&'static str, String)>,
+    pub sections: Vec<(String, Vec<String>)>,
+}
+
+impl CharacterSheet {
+    pub fn new(title: impl Into<String>) -> Self {
+        CharacterSheet { title: title.into(), ..Default::default() }
+    }
+
+    pub fn detail(&mut self, name: &'static str, value: impl ToString) {
+        self.details.push((name, value.to_string()));
+    }
+
+    pub fn section(&mut self, title: impl Into<String>, lines: Vec<String>) {
+        if !lines.is_empty() {
+            self.sections.push((title.into(), lines));
+        }
+    }
+
+    // Les booléens à vrai des catégories du plot DB, une section par catégorie
+    pub fn decisions<'a>(
+        &mut self, booleans: &BoolSlice,
+        categories: impl IntoIterator<Item = &'a IndexMap<String, PlotCategory>>,
+    ) {
+        for (title, category) in categories.into_iter().flatten() {
+            let lines = category
+                .booleans
+                .iter()
+                .filter(|(&id, _)| booleans.get(id).map(|b| *b).unwrap_or(false))
+                .map(|(_, entry)| entry.label.clone())
+                .collect();
+            self.section(format!("Decisions - {}", title), lines);
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title);
+        for (name, value) in &self.details {
+            let _ = writeln!(markdown, "- **{}** : {}", name, value);
+        }
+        for (title, lines) in &self.sections {
+            let _ = writeln!(markdown, "\n## {}\n", title);
+            for line in lines {
+                let _ = writeln!(markdown, "- {}", line);
+            }
+        }
+        markdown
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+            <style>body {{ font-family: sans-serif; max-width: 50em; margin: auto; }}</style>\n\
+            </head>\n<body>\n<h1>{0}</h1>\n<table>\n",
+            escape_html(&self.title)
+        );
+        for (name, value) in &self.details {
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", name, escape_html(value));
+        }
+        html.push_str("</table>\n");
+        for (title, lines) in &self.sections {
+            let _ = writeln!(html, "<h2>{}</h2>\n<ul>", escape_html(title));
+            for line in lines {
+                let _ = writeln!(html, "<li>{}</li>", escape_html(line));
+            }
+            html.push_str("</ul>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn character_sheet() {
+        let mut sheet = CharacterSheet::new("Clare");
+        sheet.detail("Level", 60);
+        sheet.section("Powers", vec![String::from("Warp <4>")]);
+        sheet.section("Empty", Vec::new());
+
+        let markdown = sheet.to_markdown();
+        assert!(markdown.starts_with("# Clare\n"));
+        assert!(markdown.contains("- **Level** : 60\n"));
+        assert!(markdown.contains("## Powers\n\n- Warp <4>\n"));
+        assert!(!markdown.contains("Empty"));
+
+        let html = sheet.to_html();
+        assert!(html.contains("<li>Warp &lt;4&gt;</li>"));
+    }
+}
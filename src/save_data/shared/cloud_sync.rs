@@ -0,0 +1,74 @@
+use std::path::{Component, Path, PathBuf};
+
+// Dossier des copies de travail, à côté de l'exécutable comme `settings.ron`
+const WORKING_COPIES_DIR: &str = "working_copies";
+
+// Service qui synchronise le dossier de la save, le client peut écraser les modifications en cours de session
+pub fn cloud_provider(path: &Path) -> Option<&'static str> {
+    let components: Vec<String> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect();
+
+    // Steam Cloud : `Steam/userdata/<id>/<app id>/remote/...`
+    let is_steam_cloud = components.windows(4).any(|window| {
+        window[0] == "userdata"
+            && window[1].chars().all(|c| c.is_ascii_digit())
+            && window[3] == "remote"
+    });
+    if is_steam_cloud {
+        return Some("Steam Cloud");
+    }
+
+    components.iter().find_map(|name| {
+        if name.starts_with("onedrive") {
+            Some("OneDrive")
+        } else if name == "dropbox" {
+            Some("Dropbox")
+        } else if name == "google drive" || name == "my drive" {
+            Some("Google Drive")
+        } else if name == "iclouddrive" || name == "icloud drive" {
+            Some("iCloud Drive")
+        } else {
+            None
+        }
+    })
+}
+
+pub fn working_copy_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default();
+    Path::new(WORKING_COPIES_DIR).join(file_name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_cloud_provider() {
+        let provider = |path: &str| cloud_provider(Path::new(path));
+
+        assert_eq!(
+            provider("C:/Users/Shepard/OneDrive - Alliance/Documents/BioWare/Mass Effect 2/Save/ME2Save.pcsav"),
+            Some("OneDrive")
+        );
+        assert_eq!(
+            provider(
+                "C:/Program Files (x86)/Steam/userdata/12345678/1328670/remote/Save/Char_01.pcsav"
+            ),
+            Some("Steam Cloud")
+        );
+        assert_eq!(provider("/home/shepard/Dropbox/ME3Save.pcsav"), Some("Dropbox"));
+        assert_eq!(
+            provider("C:/Users/Shepard/Documents/BioWare/Mass Effect 3/Save/ME3Save.pcsav"),
+            None
+        );
+        assert_eq!(
+            working_copy_path(Path::new("/home/shepard/Dropbox/ME3Save.pcsav")),
+            Path::new("working_copies/ME3Save.pcsav")
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, path::PathBuf, time::SystemTime};
+
+// Saves d'un dossier de carrière, par date de modification.
+// Une save n'est prête qu'une fois inchangée entre deux scans, le jeu peut être en train de l'écrire.
+#[derive(Default)]
+pub struct FolderWatch {
+    known: HashMap<PathBuf, SystemTime>,
+    pending: HashMap<PathBuf, SystemTime>,
+}
+
+impl FolderWatch {
+    // Les saves déjà présentes ne sont pas concernées
+    pub fn new(files: HashMap<PathBuf, SystemTime>) -> Self {
+        FolderWatch { known: files, pending: HashMap::new() }
+    }
+
+    pub fn update(&mut self, files: HashMap<PathBuf, SystemTime>) -> Vec<PathBuf> {
+        let mut ready = Vec::new();
+        let mut pending = HashMap::new();
+        for (path, modified) in files {
+            if self.known.get(&path) == Some(&modified) {
+                continue;
+            }
+            if self.pending.get(&path) == Some(&modified) {
+                self.known.insert(path.clone(), modified);
+                ready.push(path);
+            } else {
+                pending.insert(path, modified);
+            }
+        }
+        self.pending = pending;
+        ready.sort();
+        ready
+    }
+
+    // Date après notre propre écriture, pour ne pas patcher la save en boucle
+    pub fn set_modified(&mut self, path: PathBuf, modified: SystemTime) {
+        self.known.insert(path, modified);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn ready_saves() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let files = |list: &[(&str, u64)]| {
+            list.iter().map(|&(name, secs)| (PathBuf::from(name), time(secs))).collect()
+        };
+
+        let mut watch = FolderWatch::new(files(&[("Save_0001.pcsav", 1)]));
+        assert!(watch.update(files(&[("Save_0001.pcsav", 1)])).is_empty());
+
+        // En cours d'écriture, puis stable
+        let saves = [("Save_0001.pcsav", 1), ("AutoSave.pcsav", 10)];
+        assert!(watch.update(files(&saves[..1])).is_empty());
+        assert!(watch.update(files(&[saves[0], ("AutoSave.pcsav", 9)])).is_empty());
+        assert!(watch.update(files(&saves)).is_empty());
+        assert_eq!(watch.update(files(&saves)), [PathBuf::from("AutoSave.pcsav")]);
+        assert!(watch.update(files(&saves)).is_empty());
+
+        // Notre propre écriture
+        watch.set_modified(PathBuf::from("AutoSave.pcsav"), time(11));
+        let saves = [("Save_0001.pcsav", 1), ("AutoSave.pcsav", 11)];
+        assert!(watch.update(files(&saves)).is_empty());
+        assert!(watch.update(files(&saves)).is_empty());
+
+        // Écrasée par le jeu
+        let saves = [("Save_0001.pcsav", 1), ("AutoSave.pcsav", 20)];
+        assert!(watch.update(files(&saves)).is_empty());
+        assert_eq!(watch.update(files(&saves)), [PathBuf::from("AutoSave.pcsav")]);
+    }
+}
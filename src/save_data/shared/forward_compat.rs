@@ -0,0 +1,132 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use std::convert::TryInto;
+
+use crate::{
+    save_data::{
+        mass_effect_1_leg::Me1LegVersion, mass_effect_2::Me2LegVersion, mass_effect_3::Me3Version,
+    },
+    unreal,
+};
+
+// Mode de compatibilité pour une save plus récente que l'éditeur, ou d'une version d'essai / démo.
+// La save est lue comme la dernière version connue, les données en trop à la fin sont gardées telles quelles.
+#[derive(Clone)]
+pub struct ForwardCompat {
+    pub version: i32,
+    pub known_version: i32,
+    pub trailing: Vec<u8>,
+}
+
+fn read_version(input: &[u8]) -> Option<i32> {
+    Some(i32::from_le_bytes(input.get(0..4)?.try_into().ok()?))
+}
+
+// Version plus récente que celle de ME2LE ou ME3, renvoie `(version, dernière version connue)`
+pub fn newer_version(input: &[u8]) -> Option<(i32, i32)> {
+    let version = read_version(input)?;
+
+    // Les versions de ME1LE sont entre celles de ME2LE et ME3
+    if version > Me3Version::LATEST {
+        Some((version, Me3Version::LATEST))
+    } else if version > Me2LegVersion::LATEST && version < Me1LegVersion::LATEST {
+        Some((version, Me2LegVersion::LATEST))
+    } else {
+        None
+    }
+}
+
+// Versions d'essai et démos de ME3, plus anciennes que le jeu complet mais après celle de ME1LE
+pub fn variant_version(input: &[u8]) -> Option<(i32, i32)> {
+    let version = read_version(input)?;
+    (version > Me1LegVersion::LATEST && version < Me3Version::LATEST)
+        .then_some((version, Me3Version::LATEST))
+}
+
+impl ForwardCompat {
+    pub fn is_variant(&self) -> bool {
+        self.version < self.known_version
+    }
+    pub fn deserialize<T: DeserializeOwned>(input: &[u8], known_version: i32) -> Result<(T, Self)> {
+        let mut input = input.to_vec();
+        let version = i32::from_le_bytes(input[0..4].try_into()?);
+        input[0..4].copy_from_slice(&known_version.to_le_bytes());
+
+        // Sans le checksum, recalculé à la sauvegarde
+        let end = input.len().saturating_sub(4);
+        let (save_game, len) = unreal::Deserializer::from_bytes_partial(&input[..end])?;
+        let trailing = input[len..end].to_vec();
+
+        Ok((save_game, ForwardCompat { version, known_version, trailing }))
+    }
+
+    // Avant le calcul du checksum
+    pub fn restore(&self, output: &mut Vec<u8>) {
+        output[0..4].copy_from_slice(&self.version.to_le_bytes());
+        output.extend(&self.trailing);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::save_data::mass_effect_3::Me3SaveGame;
+
+    use super::*;
+
+    #[test]
+    fn open_newer_version() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        // Une version inconnue avec des données en plus avant le checksum
+        let checksum_offset = input.len() - 4;
+        let mut newer = input[..checksum_offset].to_vec();
+        newer[0..4].copy_from_slice(&(Me3Version::LATEST + 1).to_le_bytes());
+        newer.extend(&[1, 2, 3, 4, 5]);
+        newer.extend(&input[checksum_offset..]);
+
+        assert!(unreal::Deserializer::from_bytes::<Me3SaveGame>(&newer).is_err());
+        assert_eq!(newer_version(&newer), Some((Me3Version::LATEST + 1, Me3Version::LATEST)));
+        assert_eq!(newer_version(&input), None);
+
+        let (me3_save_game, forward_compat) =
+            ForwardCompat::deserialize::<Me3SaveGame>(&newer, Me3Version::LATEST)?;
+        assert_eq!(forward_compat.trailing, [1, 2, 3, 4, 5]);
+
+        let mut output = unreal::Serializer::to_byte_buf(&me3_save_game)?;
+        forward_compat.restore(&mut output);
+        assert_eq!(output, newer[..newer.len() - 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn rescue_variant() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+
+        // Une version de démo, plus ancienne que le jeu complet
+        let mut variant = input.clone();
+        variant[0..4].copy_from_slice(&(Me3Version::LATEST - 1).to_le_bytes());
+        assert_eq!(newer_version(&variant), None);
+        assert_eq!(variant_version(&variant), Some((Me3Version::LATEST - 1, Me3Version::LATEST)));
+        assert_eq!(variant_version(&input), None);
+
+        let (me3_save_game, forward_compat) =
+            ForwardCompat::deserialize::<Me3SaveGame>(&variant, Me3Version::LATEST)?;
+        assert!(forward_compat.is_variant());
+
+        // Convertie, elle est écrite comme une save du jeu complet
+        let output = unreal::Serializer::to_byte_buf(&me3_save_game)?;
+        assert_eq!(output, input[..input.len() - 4]);
+        Ok(())
+    }
+}
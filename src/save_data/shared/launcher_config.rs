@@ -0,0 +1,125 @@
+use std::fmt;
+
+// Options partagées du launcher de la Legendary Edition : `LauncherConfig.cfg`, dans le dossier
+// `Mass Effect Legendary Edition` au-dessus des dossiers de carrières.
+// Fichier texte `Clé=Valeur` avec d'éventuelles sections, les autres lignes sont conservées telles quelles.
+pub const LAUNCHER_CONFIG_FILE: &str = "LauncherConfig.cfg";
+
+#[derive(Clone)]
+enum Line {
+    Section(String),
+    Entry(Entry),
+    Other(String),
+}
+
+#[derive(Clone)]
+pub struct Entry {
+    pub section: Option<String>,
+    pub key: String,
+    pub value: String,
+}
+
+impl Entry {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.value.to_lowercase().as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    // Garde la casse d'origine (`True` / `true`)
+    pub fn set_bool(&mut self, value: bool) {
+        let is_capitalized = self.value.starts_with(|c: char| c.is_uppercase());
+        self.value = match (value, is_capitalized) {
+            (true, true) => "True",
+            (true, false) => "true",
+            (false, true) => "False",
+            (false, false) => "false",
+        }
+        .to_owned();
+    }
+}
+
+#[derive(Clone)]
+pub struct LauncherConfig {
+    lines: Vec<Line>,
+    line_ending: &'static str,
+}
+
+impl LauncherConfig {
+    pub fn parse(input: &str) -> Self {
+        let line_ending = if input.contains("\r\n") { "\r\n" } else { "\n" };
+
+        let mut section = None;
+        let lines = input
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                    let name = trimmed[1..trimmed.len() - 1].to_owned();
+                    section = Some(name.clone());
+                    Line::Section(name)
+                } else if trimmed.starts_with(';') || trimmed.starts_with('#') {
+                    Line::Other(line.to_owned())
+                } else if let Some((key, value)) = line.split_once('=') {
+                    Line::Entry(Entry {
+                        section: section.clone(),
+                        key: key.trim().to_owned(),
+                        value: value.trim().to_owned(),
+                    })
+                } else {
+                    Line::Other(line.to_owned())
+                }
+            })
+            .collect();
+
+        LauncherConfig { lines, line_ending }
+    }
+
+    pub fn entries_mut(&mut self) -> impl Iterator<Item = &mut Entry> {
+        self.lines.iter_mut().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for LauncherConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Section(name) => write!(f, "[{}]", name)?,
+                Line::Entry(Entry { key, value, .. }) => write!(f, "{}={}", key, value)?,
+                Line::Other(line) => f.write_str(line)?,
+            }
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edit_launcher_config() {
+        let input = "; Launcher\r\n[Launcher]\r\nLanguage=INT\r\nAutoLaunch=False\r\n\r\n[ME1]\r\nCharacterCreatorUnlocked=true\r\n";
+
+        let mut config = LauncherConfig::parse(input);
+        assert_eq!(config.to_string(), input);
+
+        for entry in config.entries_mut() {
+            if let Some(value) = entry.as_bool() {
+                entry.set_bool(!value);
+            }
+        }
+        let sections: Vec<_> = config.entries_mut().map(|entry| entry.section.clone()).collect();
+        assert_eq!(sections[2], Some("ME1".to_owned()));
+        assert_eq!(
+            config.to_string(),
+            "; Launcher\r\n[Launcher]\r\nLanguage=INT\r\nAutoLaunch=True\r\n\r\n[ME1]\r\nCharacterCreatorUnlocked=false\r\n"
+        );
+    }
+}
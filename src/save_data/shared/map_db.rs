@@ -0,0 +1,143 @@
+use imgui::ImString;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use super::{Level, Rotator, Vector};
+use crate::save_data::ImguiString;
+
+// Cartes connues de ME2 / ME3 par nom de niveau, pour sortir Shepard d'un endroit où il est coincé
+#[derive(Deserialize, Default)]
+pub struct MapDb {
+    pub me2: IndexMap<String, KnownMap>,
+    pub me3: IndexMap<String, KnownMap>,
+}
+
+#[derive(Deserialize)]
+pub struct KnownMap {
+    pub label: String,
+    // Une position n'a de sens que dans sa carte
+    #[serde(default)]
+    pub safe_location: Option<SafeLocation>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct SafeLocation {
+    pub location: Vector,
+    pub rotation: Rotator,
+}
+
+// Les noms de niveaux n'ont pas toujours la même casse (`Biop_Nor`)
+pub fn find_map<'a>(
+    maps: &'a IndexMap<String, KnownMap>, level_name: &str,
+) -> Option<(&'a String, &'a KnownMap)> {
+    maps.iter().find(|(name, _)| name.eq_ignore_ascii_case(level_name))
+}
+
+pub struct MapLocation<'a> {
+    pub base_level_name: &'a mut ImguiString,
+    pub location: &'a mut Vector,
+    pub rotation: &'a mut Rotator,
+    pub levels: &'a mut Vec<Level>,
+}
+
+impl MapLocation<'_> {
+    // Les sous-niveaux chargés de l'ancienne carte sont oubliés,
+    // le jeu charge ceux de la nouvelle par défaut
+    pub fn teleport(&mut self, level_name: &str, safe_location: Option<&SafeLocation>) {
+        if !self.base_level_name.to_str().eq_ignore_ascii_case(level_name) {
+            **self.base_level_name = ImString::new(level_name);
+            self.levels.clear();
+        }
+        if let Some(SafeLocation { location, rotation }) = safe_location {
+            *self.location = location.clone();
+            *self.rotation = rotation.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_2::{Me2LegSaveGame, Me2SaveGame},
+            mass_effect_3::Me3SaveGame,
+        },
+        unreal,
+    };
+
+    fn assert_safe_location(
+        maps: &IndexMap<String, KnownMap>, level_name: &ImguiString, location: &Vector,
+        rotation: &Rotator,
+    ) {
+        let (_, map) = find_map(maps, level_name.to_str()).expect("Unknown map");
+        let safe_location = map.safe_location.as_ref().expect("No safe location");
+        let (expected, actual) = (&safe_location.location, location);
+        assert_eq!((expected.x, expected.y, expected.z), (actual.x, actual.y, actual.z));
+        let (expected, actual) = (&safe_location.rotation, rotation);
+        assert_eq!(
+            (expected.pitch, expected.yaw, expected.roll),
+            (actual.pitch, actual.yaw, actual.roll)
+        );
+    }
+
+    #[test]
+    fn safe_locations_from_saves() -> Result<()> {
+        let input = fs::read_to_string("databases/map_db.ron")?;
+        let map_db: MapDb = ron::from_str(&input)?;
+
+        // Chaque position de la base vient d'une de ces saves
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        assert_safe_location(&map_db.me2, &save.base_level_name, &save.location, &save.rotation);
+
+        let input = fs::read("test/ME2LegSave.pcsav")?;
+        let save: Me2LegSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        assert_safe_location(&map_db.me2, &save.base_level_name, &save.location, &save.rotation);
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let save: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        assert_safe_location(&map_db.me3, &save.base_level_name, &save.location, &save.rotation);
+
+        let safe_locations = map_db.me2.values().chain(map_db.me3.values());
+        assert_eq!(safe_locations.filter(|map| map.safe_location.is_some()).count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn teleport() -> Result<()> {
+        let input = fs::read_to_string("databases/map_db.ron")?;
+        let map_db: MapDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut map_location = MapLocation {
+            base_level_name: &mut me2_save_game.base_level_name,
+            location: &mut me2_save_game.location,
+            rotation: &mut me2_save_game.rotation,
+            levels: &mut me2_save_game.levels,
+        };
+        assert_eq!(map_location.base_level_name.to_str(), "BioP_OmgHub");
+        assert!(!map_location.levels.is_empty());
+
+        // Même carte, seule la position change
+        let location = SafeLocation {
+            location: Vector { x: 1.0, y: 2.0, z: 3.0 },
+            rotation: Rotator { pitch: 0, yaw: 16384, roll: 0 },
+        };
+        map_location.teleport("BIOP_OMGHUB", Some(&location));
+        assert_eq!(map_location.base_level_name.to_str(), "BioP_OmgHub");
+        assert!(!map_location.levels.is_empty());
+        assert_eq!(map_location.location.y, 2.0);
+
+        let (name, normandy) = find_map(&map_db.me2, "biop_nor").expect("Normandy is known");
+        map_location.teleport(name, normandy.safe_location.as_ref());
+        assert_eq!(map_location.base_level_name.to_str(), "BioP_Nor");
+        assert!(map_location.levels.is_empty());
+        assert_ne!(map_location.location.y, 2.0);
+        Ok(())
+    }
+}
@@ -1,15 +1,41 @@
 use imgui::ImString;
 use serde::{de, ser, Deserialize, Serialize};
-use std::fmt;
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use uuid::Uuid;
 
 use crate::gui::Gui;
 
-use super::{ImguiString, List, RawUi};
+use super::{
+    schema::{Definitions, TypeDesc},
+    ImguiString, List, RawUi,
+};
 
 pub mod appearance;
+pub mod character_sheet;
+pub mod cloud_sync;
+pub mod folder_watch;
+pub mod forward_compat;
+pub mod launcher_config;
+pub mod map_db;
+pub mod patch;
 pub mod player;
 pub mod plot;
+pub mod plot_clipboard;
+pub mod plot_coverage;
+pub mod plot_references;
+pub mod recipe;
+pub mod romance;
+pub mod save_format;
+pub mod save_slot;
+pub mod save_stats;
+pub mod squad;
+pub mod string_search;
+pub mod synthetic;
+pub mod tlk;
+pub mod validation;
 
 #[derive(RawUi, Clone)]
 #[repr(u32)]
@@ -53,11 +79,40 @@ pub struct SaveTimeStamp {
     year: i32,
 }
 
+impl SaveTimeStamp {
+    // En UTC, l'éditeur ne connaît pas le fuseau horaire du jeu
+    pub fn now() -> Self {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+        Self::from_unix(secs)
+    }
+
+    // Jours depuis 1970 => date du calendrier grégorien, le mois commence à 1
+    pub fn from_unix(secs: u64) -> Self {
+        let days = (secs / 86_400) as i64 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+        SaveTimeStamp {
+            seconds_since_midnight: (secs % 86_400) as i32,
+            day: day as i32,
+            month: month as i32,
+            year: year as i32,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Vector {
-    x: f32,
-    y: f32,
-    z: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
@@ -68,9 +123,9 @@ pub struct Vector2d {
 
 #[derive(Deserialize, Serialize, RawUi, Clone)]
 pub struct Rotator {
-    pitch: i32,
-    yaw: i32,
-    roll: i32,
+    pub pitch: i32,
+    pub yaw: i32,
+    pub roll: i32,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
@@ -175,16 +230,27 @@ impl RawUi for Guid {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_edit_guid(ident, self);
     }
+
+    fn is_flat() -> bool {
+        true
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::FixedArray(Box::new(TypeDesc::Byte), 16)
+    }
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct KismetRecord {
+    // Relie l'enregistrement à l'objet du niveau
+    #[rawui(readonly)]
     guid: Guid,
     value: bool,
 }
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct Door {
+    #[rawui(readonly)]
     guid: Guid,
     current_state: DoorState,
     old_state: DoorState,
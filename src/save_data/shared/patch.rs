@@ -0,0 +1,258 @@
+use anyhow::{bail, ensure, Context, Result};
+use crc::{Crc, CRC_32_ISO_HDLC};
+use std::{collections::HashMap, convert::TryFrom};
+
+// Patch binaire au format BPS entre le fichier d'origine et la save modifiée,
+// pour partager des modifications exactes sans partager toute la save.
+// https://www.romhacking.net/documents/746/
+const MAGIC: &[u8; 4] = b"BPS1";
+const CRC: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+// En dessous, copier les octets dans le patch coûte moins cher qu'une référence
+const MIN_MATCH: usize = 8;
+// La taille annoncée vient du patch, une save modifiée ne grossit jamais autant
+const MAX_GROWTH: usize = 4;
+
+const SOURCE_READ: u64 = 0;
+const TARGET_READ: u64 = 1;
+const SOURCE_COPY: u64 = 2;
+const TARGET_COPY: u64 = 3;
+
+pub fn create(source: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut patch = MAGIC.to_vec();
+    write_number(&mut patch, source.len() as u64);
+    write_number(&mut patch, target.len() as u64);
+    write_number(&mut patch, 0);
+
+    let mut blocks = HashMap::new();
+    for (i, block) in source.windows(MIN_MATCH).enumerate() {
+        blocks.entry(block).or_insert(i);
+    }
+
+    let mut literal_start = 0;
+    let mut source_offset = 0;
+    let mut i = 0;
+    while i < target.len() {
+        // Octets inchangés à la même position
+        let unchanged = source.get(i..).map_or(0, |source| common_len(source, &target[i..]));
+        if unchanged >= MIN_MATCH {
+            write_literal(&mut patch, &target[literal_start..i]);
+            write_action(&mut patch, SOURCE_READ, unchanged);
+            i += unchanged;
+            literal_start = i;
+            continue;
+        }
+
+        // Octets déplacés, après une chaîne qui a changé de longueur par exemple
+        if let Some(&start) = target.get(i..i + MIN_MATCH).and_then(|block| blocks.get(block)) {
+            let len = common_len(&source[start..], &target[i..]);
+            write_literal(&mut patch, &target[literal_start..i]);
+            write_action(&mut patch, SOURCE_COPY, len);
+            write_offset(&mut patch, start as i64 - source_offset as i64);
+            source_offset = start + len;
+            i += len;
+            literal_start = i;
+            continue;
+        }
+
+        i += 1;
+    }
+    write_literal(&mut patch, &target[literal_start..]);
+
+    patch.extend(&CRC.checksum(source).to_le_bytes());
+    patch.extend(&CRC.checksum(target).to_le_bytes());
+    let checksum = CRC.checksum(&patch);
+    patch.extend(&checksum.to_le_bytes());
+    patch
+}
+
+pub fn apply(source: &[u8], patch: &[u8]) -> Result<Vec<u8>> {
+    ensure!(patch.len() >= MAGIC.len() + 12 && patch.starts_with(MAGIC), "Not a BPS patch");
+
+    let (body, footer) = patch.split_at(patch.len() - 12);
+    let footer_crc = |i: usize| {
+        u32::from_le_bytes([footer[i * 4], footer[i * 4 + 1], footer[i * 4 + 2], footer[i * 4 + 3]])
+    };
+    ensure!(CRC.checksum(&patch[..patch.len() - 4]) == footer_crc(2), "The patch is corrupted");
+    ensure!(
+        CRC.checksum(source) == footer_crc(0),
+        "The patch was made for another save, or this save was modified since"
+    );
+
+    let mut reader = Reader { input: body, pos: MAGIC.len() };
+    let source_len = reader.number()? as usize;
+    let target_len = reader.number()? as usize;
+    ensure!(source_len == source.len(), "The patch was made for another save");
+    ensure!(target_len <= source.len().saturating_mul(MAX_GROWTH), "Invalid patch");
+    let metadata_len = reader.number()? as usize;
+    reader.bytes(metadata_len)?;
+
+    let mut target = Vec::with_capacity(target_len);
+    let (mut source_offset, mut target_offset) = (0_i64, 0_i64);
+    while reader.pos < body.len() {
+        let action = reader.number()?;
+        let len = usize::try_from(action >> 2).ok().and_then(|len| len.checked_add(1));
+        let len = len
+            .filter(|&len| len <= target_len - target.len())
+            .context("The patched save is not the expected one")?;
+        match action & 3 {
+            SOURCE_READ => {
+                let start = target.len();
+                target.extend(source.get(start..start + len).context("Invalid source read")?);
+            }
+            TARGET_READ => target.extend(reader.bytes(len)?),
+            SOURCE_COPY => {
+                source_offset += reader.offset()?;
+                let start = usize::try_from(source_offset).context("Invalid source copy")?;
+                let end = start.checked_add(len).context("Invalid source copy")?;
+                target.extend(source.get(start..end).context("Invalid source copy")?);
+                source_offset += len as i64;
+            }
+            TARGET_COPY => {
+                // Peut recouvrir ce qui est en train d'être écrit, octet par octet
+                target_offset += reader.offset()?;
+                for _ in 0..len {
+                    let i = usize::try_from(target_offset).context("Invalid target copy")?;
+                    let byte = *target.get(i).context("Invalid target copy")?;
+                    target.push(byte);
+                    target_offset += 1;
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    if target.len() != target_len || CRC.checksum(&target) != footer_crc(1) {
+        bail!("The patched save is not the expected one");
+    }
+    Ok(target)
+}
+
+fn common_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn write_number(patch: &mut Vec<u8>, mut n: u64) {
+    loop {
+        let byte = (n & 0x7F) as u8;
+        n >>= 7;
+        if n == 0 {
+            patch.push(0x80 | byte);
+            return;
+        }
+        patch.push(byte);
+        n -= 1;
+    }
+}
+
+fn write_action(patch: &mut Vec<u8>, action: u64, len: usize) {
+    write_number(patch, ((len as u64 - 1) << 2) | action);
+}
+
+fn write_literal(patch: &mut Vec<u8>, bytes: &[u8]) {
+    if !bytes.is_empty() {
+        write_action(patch, TARGET_READ, bytes.len());
+        patch.extend(bytes);
+    }
+}
+
+fn write_offset(patch: &mut Vec<u8>, offset: i64) {
+    write_number(patch, (offset.unsigned_abs() << 1) | (offset < 0) as u64);
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).context("Truncated patch")?;
+        let bytes = self.input.get(self.pos..end).context("Truncated patch")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn number(&mut self) -> Result<u64> {
+        let (mut n, mut shift) = (0_u64, 1_u64);
+        loop {
+            let byte = self.bytes(1)?[0] as u64;
+            let value = (byte & 0x7F).checked_mul(shift);
+            n = value.and_then(|value| n.checked_add(value)).context("Invalid patch")?;
+            if byte & 0x80 != 0 {
+                return Ok(n);
+            }
+            shift = shift.checked_mul(0x80).context("Invalid patch")?;
+            n = n.checked_add(shift).context("Invalid patch")?;
+        }
+    }
+
+    fn offset(&mut self) -> Result<i64> {
+        let n = self.number()?;
+        let offset = (n >> 1) as i64;
+        Ok(if n & 1 == 1 { -offset } else { offset })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn create_and_apply() -> Result<()> {
+        let source = fs::read("test/ME2Save.pcsav")?;
+
+        // Une valeur modifiée et quelques octets insérés
+        let mut target = source.clone();
+        target[0x100] ^= 0xFF;
+        target.splice(0x2000..0x2000, b"Shepard".iter().copied());
+        target.truncate(target.len() - 3);
+
+        let patch = create(&source, &target);
+        assert!(patch.len() < 200, "{} bytes", patch.len());
+        assert_eq!(apply(&source, &patch)?, target);
+
+        // Seulement sur le fichier d'origine
+        let mut other = source.clone();
+        other[0x200] ^= 0xFF;
+        assert!(apply(&other, &patch).is_err());
+
+        let mut corrupted = patch.clone();
+        corrupted[10] ^= 0xFF;
+        assert!(apply(&source, &corrupted).is_err());
+
+        // Rien à patcher
+        assert_eq!(apply(&source, &create(&source, &source))?, source);
+        Ok(())
+    }
+
+    #[test]
+    fn reject_oversized_patch() {
+        let source = b"Commander Shepard".to_vec();
+        let forge = |target_len: u64, actions: &[(u64, usize)]| {
+            let mut patch = MAGIC.to_vec();
+            write_number(&mut patch, source.len() as u64);
+            write_number(&mut patch, target_len);
+            write_number(&mut patch, 0);
+            for &(action, len) in actions {
+                write_action(&mut patch, action, len);
+                if action >= SOURCE_COPY {
+                    write_offset(&mut patch, 0);
+                }
+            }
+            patch.extend(&CRC.checksum(&source).to_le_bytes());
+            patch.extend(&0_u32.to_le_bytes());
+            let checksum = CRC.checksum(&patch);
+            patch.extend(&checksum.to_le_bytes());
+            patch
+        };
+
+        // Taille annoncée démesurée, rien n'est alloué
+        assert!(apply(&source, &forge(u64::MAX >> 1, &[])).is_err());
+        // Longueurs qui débordent
+        assert!(apply(&source, &forge(8, &[(SOURCE_COPY, usize::MAX >> 2)])).is_err());
+        assert!(apply(&source, &forge(8, &[(TARGET_READ, usize::MAX >> 2)])).is_err());
+        assert!(apply(&source, &forge(8, &[(SOURCE_READ, 4), (TARGET_COPY, 1 << 40)])).is_err());
+    }
+}
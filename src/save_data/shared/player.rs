@@ -1,8 +1,9 @@
+use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
 use crate::save_data::ImguiString;
 
-#[derive(Deserialize, Serialize, RawUi, Clone)]
+#[derive(Deserialize, Serialize, RawUi, Clone, Display)]
 pub enum Origin {
     None,
     Spacer,
@@ -10,7 +11,7 @@ pub enum Origin {
     Earthborn,
 }
 
-#[derive(Deserialize, Serialize, RawUi, Clone)]
+#[derive(Deserialize, Serialize, RawUi, Clone, Display)]
 pub enum Notoriety {
     None,
     Survivor,
@@ -20,10 +21,10 @@ pub enum Notoriety {
 
 #[derive(Deserialize, Serialize, RawUi, Default, Clone)]
 pub struct WeaponLoadout {
-    assault_rifle: ImguiString,
-    shotgun: ImguiString,
-    sniper_rifle: ImguiString,
-    submachine_gun: ImguiString,
-    pistol: ImguiString,
-    heavy_weapon: ImguiString,
+    pub assault_rifle: ImguiString,
+    pub shotgun: ImguiString,
+    pub sniper_rifle: ImguiString,
+    pub submachine_gun: ImguiString,
+    pub pistol: ImguiString,
+    pub heavy_weapon: ImguiString,
 }
@@ -1,21 +1,76 @@
 use anyhow::Result;
 use bitvec::prelude::*;
 use derive_more::{Deref, DerefMut};
+use if_chain::if_chain;
 use indexmap::IndexMap;
 use serde::{de, Deserialize, Serialize};
 use std::fmt;
 
-use crate::{gui::Gui, save_data::RawUi};
+use crate::{
+    gui::Gui,
+    save_data::{
+        schema::{Definitions, TypeDesc},
+        OriginalValues, RawUi,
+    },
+};
 
 pub type BoolSlice = BitSlice<Lsb0, u32>;
 
 #[derive(Deref, DerefMut, Clone)]
 pub struct BoolVec(BitVec<Lsb0, u32>);
 
+// Les booléens sont rangés par 32 dans des entiers, bit de poids faible en premier :
+// l'ID du plot `n` est le bit `n % 32` de l'entier `n / 32`
+pub fn packed_position(plot_id: usize) -> (usize, usize) {
+    (plot_id / 32, plot_id % 32)
+}
+
+pub fn packed_plot_id(index: usize, bit: usize) -> usize {
+    index * 32 + bit
+}
+
 impl RawUi for BoolVec {
     fn draw_raw_ui(&mut self, gui: &Gui, ident: &str) {
         gui.draw_boolvec(ident, &mut self.0);
     }
+
+    fn revert(&mut self, original: &Self, path: &[String]) {
+        match path.first() {
+            Some(i) => {
+                if_chain! {
+                    if let Ok(i) = i.parse::<usize>();
+                    if i < self.len();
+                    if let Some(original) = original.get(i);
+                    then {
+                        self.set(i, *original);
+                    }
+                }
+            }
+            None => *self = original.clone(),
+        }
+    }
+
+    fn draw_watch(&mut self, gui: &Gui, ident: &str, path: &[String]) {
+        match path.first() {
+            Some(i) => {
+                if let Some(mut value) = i.parse::<usize>().ok().and_then(|i| self.get_mut(i)) {
+                    gui.draw_edit_bool(ident, &mut value);
+                }
+            }
+            None => self.draw_raw_ui(gui, ident),
+        }
+    }
+    fn collect_values(&self, path: &mut Vec<String>, values: &mut OriginalValues) {
+        for (i, value) in self.iter().enumerate() {
+            path.push(i.to_string());
+            values.insert(path.clone(), value.to_string());
+            path.pop();
+        }
+    }
+
+    fn describe(_: &mut Definitions) -> TypeDesc {
+        TypeDesc::Array(Box::new(TypeDesc::Bool))
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for BoolVec {
@@ -72,6 +127,269 @@ pub struct PlotCodex {
 
 #[derive(Deserialize, Serialize)]
 pub struct PlotCategory {
-    pub booleans: IndexMap<usize, String>,
-    pub ints: IndexMap<usize, String>,
+    pub booleans: IndexMap<usize, PlotEntry>,
+    pub ints: IndexMap<usize, PlotEntry>,
+    #[serde(default)]
+    pub bitfields: IndexMap<usize, PlotBitfield>,
+    // Booléen => booléens qui doivent déjà être à vrai pour que les scripts du jeu suivent
+    #[serde(default)]
+    pub requires: IndexMap<usize, Vec<usize>>,
+}
+
+impl PlotCategory {
+    // Prérequis manquants d'un booléen à vrai
+    pub fn missing_requirements(&self, plot_id: usize, booleans: &BoolSlice) -> Vec<usize> {
+        let is_set = |id: usize| booleans.get(id).map(|b| *b).unwrap_or(false);
+        match self.requires.get(&plot_id) {
+            Some(requires) if is_set(plot_id) => {
+                requires.iter().copied().filter(|&id| !is_set(id)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn label(&self, plot_id: usize) -> String {
+        match self.booleans.get(&plot_id) {
+            Some(entry) => entry.label.clone(),
+            None => format!("Plot {}", plot_id),
+        }
+    }
+}
+
+// Catégories d'un onglet, nommées `Onglet > Catégorie`
+pub fn named_categories<'a>(
+    tab: &'a str, categories: &'a IndexMap<String, PlotCategory>,
+) -> impl Iterator<Item = (String, &'a PlotCategory)> + 'a {
+    categories.iter().map(move |(name, category)| (format!("{} > {}", tab, name), category))
+}
+
+// Entrée du plot DB, soit un simple label soit `PlotEntry(label: "...", description: "...")`
+#[derive(Serialize, Clone, Default)]
+pub struct PlotEntry {
+    pub label: String,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub act: Option<String>,
+    // Valeurs possibles d'un int
+    pub values: IndexMap<i32, String>,
+}
+
+impl PlotEntry {
+    pub fn has_details(&self) -> bool {
+        self.description.is_some() || self.link.is_some() || self.act.is_some()
+    }
+
+    // Les valeurs du DB en priorité, sinon celles listées dans le label
+    pub fn options(&self) -> Option<PlotOptions<'_>> {
+        if self.values.is_empty() {
+            return PlotOptions::parse(&self.label);
+        }
+        let options = self.values.iter().map(|(value, name)| (*value, name.as_str())).collect();
+        Some(PlotOptions { label: &self.label, options })
+    }
+}
+
+impl fmt::Display for PlotEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PlotEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct PlotEntryVisitor;
+        impl<'de> de::Visitor<'de> for PlotEntryVisitor {
+            type Value = PlotEntry;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string or a PlotEntry")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(PlotEntry { label: value.to_owned(), ..Default::default() })
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(field_identifier, rename_all = "lowercase")]
+                enum Field {
+                    Label,
+                    Description,
+                    Link,
+                    Act,
+                    Values,
+                }
+
+                let mut label = None;
+                let mut entry = PlotEntry::default();
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Label => label = Some(map.next_value()?),
+                        Field::Description => entry.description = Some(map.next_value()?),
+                        Field::Link => entry.link = Some(map.next_value()?),
+                        Field::Act => entry.act = Some(map.next_value()?),
+                        Field::Values => entry.values = map.next_value()?,
+                    }
+                }
+                entry.label = label.ok_or_else(|| de::Error::missing_field("label"))?;
+                Ok(entry)
+            }
+        }
+        deserializer.deserialize_any(PlotEntryVisitor)
+    }
+}
+
+// Int dont chaque bit est un flag
+#[derive(Deserialize, Serialize)]
+pub struct PlotBitfield {
+    pub label: String,
+    pub bits: IndexMap<u8, String>,
+}
+
+// Int dont les valeurs sont listées dans le label : `Garrus appearance (0 = Default, 1 = Alternate)`
+pub struct PlotOptions<'a> {
+    pub label: &'a str,
+    pub options: Vec<(i32, &'a str)>,
+}
+
+impl<'a> PlotOptions<'a> {
+    pub fn parse(desc: &'a str) -> Option<Self> {
+        let (label, options) = desc.strip_suffix(')')?.rsplit_once(" (")?;
+        let options = options
+            .split(',')
+            .map(|option| {
+                let (value, name) = option.split_once('=')?;
+                Some((value.trim().parse().ok()?, name.trim()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(PlotOptions { label, options })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_plot_bitfield() -> Result<()> {
+        let input = r#"PlotCategory(
+            booleans: {},
+            ints: {},
+            bitfields: {
+                42: PlotBitfield(
+                    label: "Flags",
+                    bits: { 0: "First", 31: "Last" },
+                ),
+            },
+        )"#;
+
+        let plot_category: PlotCategory = ron::from_str(input)?;
+        let bitfield = &plot_category.bitfields[&42];
+        assert_eq!(bitfield.bits[&31], "Last");
+
+        // Champ optionnel
+        let plot_category: PlotCategory = ron::from_str("PlotCategory(booleans: {}, ints: {})")?;
+        assert!(plot_category.bitfields.is_empty());
+        assert!(plot_category.requires.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn packed_plot_position() {
+        assert_eq!(packed_position(0), (0, 0));
+        assert_eq!(packed_position(1234), (38, 18));
+        assert_eq!(packed_plot_id(38, 18), 1234);
+
+        // Même position que dans les entiers de la save
+        let mut booleans = BoolVec(bitvec![Lsb0, u32; 0; 1280]);
+        booleans.set(1234, true);
+        let bitfields = booleans.0.into_vec();
+        assert_eq!(bitfields[38], 1 << 18);
+        assert_eq!(bitfields.iter().filter(|&&bitfield| bitfield != 0).count(), 1);
+    }
+
+    #[test]
+    fn missing_requirements() -> Result<()> {
+        let input = r#"PlotCategory(
+            booleans: { 1: "Recruited", 2: "Loyal" },
+            ints: {},
+            requires: { 2: [1, 40] },
+        )"#;
+        let plot_category: PlotCategory = ron::from_str(input)?;
+
+        let mut booleans = bitvec![Lsb0, u32; 0; 64];
+        assert!(plot_category.missing_requirements(2, &booleans).is_empty());
+
+        booleans.set(2, true);
+        booleans.set(40, true);
+        assert_eq!(plot_category.missing_requirements(2, &booleans), vec![1]);
+        assert_eq!(plot_category.label(1), "Recruited");
+        assert_eq!(plot_category.label(40), "Plot 40");
+
+        booleans.set(1, true);
+        assert!(plot_category.missing_requirements(2, &booleans).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_plot_entry() -> Result<()> {
+        let input = r#"PlotCategory(
+            booleans: {
+                1: "Simple",
+                2: PlotEntry(
+                    label: "Detailed",
+                    description: "Set after the briefing",
+                    link: "https://masseffect.fandom.com/wiki/Eden_Prime",
+                    act: "Prologue",
+                ),
+            },
+            ints: {
+                3: PlotEntry(label: "Outcome", values: { 0: "None", 2: "Saved" }),
+                4: "Appearance (0 = Default, 1 = Alternate)",
+            },
+        )"#;
+
+        let plot_category: PlotCategory = ron::from_str(input)?;
+        let simple = &plot_category.booleans[&1];
+        assert_eq!(simple.label, "Simple");
+        assert!(!simple.has_details());
+
+        let detailed = &plot_category.booleans[&2];
+        assert_eq!(detailed.to_string(), "Detailed");
+        assert_eq!(detailed.act.as_deref(), Some("Prologue"));
+        assert!(detailed.has_details());
+
+        let options = plot_category.ints[&3].options().expect("options");
+        assert_eq!(options.options, vec![(0, "None"), (2, "Saved")]);
+        let options = plot_category.ints[&4].options().expect("options");
+        assert_eq!(options.label, "Appearance");
+
+        assert!(ron::from_str::<PlotEntry>("PlotEntry(description: \"No label\")").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_plot_options() {
+        let options = PlotOptions::parse("Tali appearance (0 = Default, 1 = Alternate, 2 = DLC)")
+            .expect("options");
+        assert_eq!(options.label, "Tali appearance");
+        assert_eq!(options.options, vec![(0, "Default"), (1, "Alternate"), (2, "DLC")]);
+
+        assert!(PlotOptions::parse("Paragon points").is_none());
+        assert!(PlotOptions::parse("Squad size (max 3)").is_none());
+        // Les plages de valeurs restent des ints
+        assert!(PlotOptions::parse("Jack progress (1-4 = friends, 5 = romance)").is_none());
+    }
 }
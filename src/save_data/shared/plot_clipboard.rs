@@ -0,0 +1,55 @@
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+// Quelques flags de plot en texte, pour les partager sans échanger de save.
+// Une ligne `ID=valeur` par flag, ce qui suit `#` est un commentaire.
+pub fn to_text<'a>(flags: impl Iterator<Item = (usize, bool, &'a str)>) -> String {
+    flags.map(|(id, value, label)| format!("{}={} # {}\n", id, value as u8, label)).collect()
+}
+
+pub fn parse(text: &str) -> Result<IndexMap<usize, bool>> {
+    let mut flags = IndexMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let context = || format!("Line {} : `{}`", i + 1, line);
+        let (id, value) = line.split_once('=').with_context(|| format!("{}, no `=`", context()))?;
+        let id: usize = id.trim().parse().with_context(context)?;
+        let value = match value.trim() {
+            "1" | "true" => true,
+            "0" | "false" => false,
+            _ => bail!("{}, the value must be 1 or 0", context()),
+        };
+        flags.insert(id, value);
+    }
+
+    if flags.is_empty() {
+        bail!("No plot flag found, expected `ID=1` or `ID=0` lines");
+    }
+    Ok(flags)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plot_flags_text() {
+        let text =
+            to_text(vec![(14914, true, "Charmed him"), (22226, false, "Played ME1")].into_iter());
+        assert_eq!(text, "14914=1 # Charmed him\n22226=0 # Played ME1\n");
+
+        let flags = parse(&text).unwrap();
+        assert_eq!(flags.into_iter().collect::<Vec<_>>(), [(14914, true), (22226, false)]);
+
+        let flags = parse("# From a forum post\n\n 17662 = true\r\n").unwrap();
+        assert_eq!(flags.get(&17662), Some(&true));
+
+        assert!(parse("17662=2").is_err());
+        assert!(parse("plot=1").is_err());
+        assert!(parse("# nothing").is_err());
+    }
+}
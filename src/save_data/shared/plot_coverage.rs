@@ -0,0 +1,146 @@
+use std::{collections::HashSet, fmt::Write, iter::FromIterator, ops::RangeInclusive};
+
+use crate::save_data::shared::plot::{BoolSlice, PlotCategory};
+
+#[derive(Default)]
+pub struct PlotLabels {
+    pub booleans: HashSet<usize>,
+    pub ints: HashSet<usize>,
+}
+
+impl PlotLabels {
+    pub fn add_category(&mut self, category: &PlotCategory) {
+        self.booleans.extend(category.booleans.keys());
+        self.ints.extend(category.ints.keys());
+        self.ints.extend(category.bitfields.keys());
+    }
+}
+
+impl<'a> FromIterator<&'a PlotCategory> for PlotLabels {
+    fn from_iter<I: IntoIterator<Item = &'a PlotCategory>>(iter: I) -> Self {
+        let mut labels = PlotLabels::default();
+        for category in iter {
+            labels.add_category(category);
+        }
+        labels
+    }
+}
+
+pub struct PlotCoverage {
+    pub bool_len: usize,
+    pub int_len: usize,
+    pub labeled_booleans: usize,
+    pub labeled_ints: usize,
+    pub unlabeled_booleans: Vec<RangeInclusive<usize>>,
+    pub unlabeled_ints: Vec<RangeInclusive<usize>>,
+}
+
+impl PlotCoverage {
+    pub fn new(
+        booleans: &BoolSlice, ints: impl IntoIterator<Item = (usize, i32)>, labels: &PlotLabels,
+    ) -> Self {
+        let labeled_booleans = labels.booleans.iter().filter(|&&id| id < booleans.len()).count();
+        let unlabeled_booleans =
+            to_ranges(booleans.iter_ones().filter(|id| !labels.booleans.contains(id)).collect());
+
+        let mut int_len = 0;
+        let mut set_ints = Vec::new();
+        for (id, value) in ints {
+            int_len = int_len.max(id + 1);
+            if value != 0 && !labels.ints.contains(&id) {
+                set_ints.push(id);
+            }
+        }
+        let labeled_ints = labels.ints.iter().filter(|&&id| id < int_len).count();
+        let unlabeled_ints = to_ranges(set_ints);
+
+        PlotCoverage {
+            bool_len: booleans.len(),
+            int_len,
+            labeled_booleans,
+            labeled_ints,
+            unlabeled_booleans,
+            unlabeled_ints,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("type,start,end,count\n");
+        let kinds = [("bool", &self.unlabeled_booleans), ("int", &self.unlabeled_ints)];
+        for (kind, ranges) in &kinds {
+            for range in ranges.iter() {
+                let count = range.end() - range.start() + 1;
+                let _ = writeln!(csv, "{},{},{},{}", kind, range.start(), range.end(), count);
+            }
+        }
+        csv
+    }
+}
+
+fn to_ranges(mut ids: Vec<usize>) -> Vec<RangeInclusive<usize>> {
+    ids.sort_unstable();
+
+    let mut ranges: Vec<RangeInclusive<usize>> = Vec::new();
+    for id in ids {
+        match ranges.last_mut() {
+            Some(range) if *range.end() + 1 == id => *range = *range.start()..=id,
+            _ => ranges.push(id..=id),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{
+        save_data::mass_effect_2::{plot_db::Me2PlotDb, Me2SaveGame},
+        unreal,
+    };
+
+    use super::*;
+
+    #[test]
+    fn me2_plot_coverage() -> Result<()> {
+        let me2_plot_db: Me2PlotDb = {
+            let mut input = String::new();
+            let mut file = File::open("databases/me2_plot_db.ron")?;
+            file.read_to_string(&mut input)?;
+            ron::from_str(&input)?
+        };
+
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME2Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = &me2_save_game.plot;
+
+        let labels: PlotLabels = me2_plot_db.categories().collect();
+        let coverage = PlotCoverage::new(
+            &plot.bool_variables,
+            plot.int_variables.iter().copied().enumerate(),
+            &labels,
+        );
+
+        assert_eq!(coverage.bool_len, plot.bool_variables.len());
+        assert!(coverage.labeled_booleans > 0);
+
+        // Aucune variable étiquetée dans les plages
+        for range in &coverage.unlabeled_booleans {
+            assert!(range.clone().all(|id| plot.bool_variables[id]));
+            assert!(range.clone().all(|id| !labels.booleans.contains(&id)));
+        }
+
+        let csv = coverage.to_csv();
+        assert_eq!(
+            csv.lines().count(),
+            1 + coverage.unlabeled_booleans.len() + coverage.unlabeled_ints.len()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::save_data::{
+    mass_effect_3::{known_issues::KnownIssuesDb, plot_db::Me3PlotDb},
+    shared::{
+        plot::{PlotCategory, PlotEntry},
+        recipe::{Game, RecipeDb},
+    },
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PlotId {
+    Bool(usize),
+    Int(usize),
+}
+
+// Tous les endroits des databases où apparaît une variable de plot, pour voir ce qu'implique sa modification
+pub fn category_references<'a>(
+    plot_id: PlotId, categories: impl Iterator<Item = (String, &'a PlotCategory)>,
+) -> Vec<String> {
+    let mut references = Vec::new();
+    for (name, category) in categories {
+        match plot_id {
+            PlotId::Bool(id) => {
+                if let Some(entry) = category.booleans.get(&id) {
+                    references.push(format!("{} : {}", name, entry));
+                }
+                for (&flag, requires) in &category.requires {
+                    if requires.contains(&id) {
+                        references.push(format!("{} : required by {}", name, category.label(flag)));
+                    }
+                }
+            }
+            PlotId::Int(id) => {
+                if let Some(entry) = category.ints.get(&id) {
+                    references.push(format!("{} : {}", name, entry));
+                }
+                if let Some(bitfield) = category.bitfields.get(&id) {
+                    references.push(format!("{} : {} (bitfield)", name, bitfield.label));
+                }
+            }
+        }
+    }
+    references
+}
+
+// `Weapons / Powers` n'est pas une catégorie
+pub fn me3_weapons_powers_references(plot_id: PlotId, plot_db: &Me3PlotDb) -> Vec<String> {
+    let id = match plot_id {
+        PlotId::Bool(id) => id,
+        PlotId::Int(_) => return Vec::new(),
+    };
+    plot_db
+        .weapons_powers
+        .iter()
+        .filter_map(|(name, plot_variable)| {
+            let label = plot_variable.booleans.get(&id)?;
+            Some(format!("Weapons / Powers > {} : {}", name, label))
+        })
+        .collect()
+}
+
+// Les recettes de ME1 et ME1LE modifient le même plot, idem pour ME2 et ME2LE
+pub fn recipe_references(plot_id: PlotId, recipes: &RecipeDb, game: Game) -> Vec<String> {
+    let plot_table = |game| match game {
+        Game::MassEffect1 | Game::MassEffect1Leg => Game::MassEffect1,
+        Game::MassEffect2 | Game::MassEffect2Leg => Game::MassEffect2,
+        Game::MassEffect3 => Game::MassEffect3,
+    };
+
+    recipes
+        .iter()
+        .filter(|recipe| recipe.games.iter().any(|&other| plot_table(other) == plot_table(game)))
+        .filter(|recipe| match plot_id {
+            PlotId::Bool(id) => recipe.booleans.contains_key(&id),
+            PlotId::Int(id) => recipe.ints.contains_key(&id),
+        })
+        .map(|recipe| format!("Preset : {}", recipe.name))
+        .collect()
+}
+
+pub fn known_issue_references(plot_id: PlotId, known_issues: &KnownIssuesDb) -> Vec<String> {
+    let mut references = Vec::new();
+    for issue in &known_issues.0 {
+        let (when, fixes) = match plot_id {
+            PlotId::Bool(id) => (
+                issue.when.booleans.contains_key(&id),
+                issue.fixes.iter().filter(|fix| fix.booleans.contains_key(&id)).collect::<Vec<_>>(),
+            ),
+            PlotId::Int(id) => (
+                issue.when.ints.contains_key(&id),
+                issue.fixes.iter().filter(|fix| fix.ints.contains_key(&id)).collect(),
+            ),
+        };
+
+        if when {
+            references.push(format!("Known issue : {}", issue.title));
+        }
+        for fix in fixes {
+            references.push(format!("Known issue fix : {} > {}", issue.title, fix.label));
+        }
+    }
+    references
+}
+
+// Entrées du plot DB dont l'ID est `query`, ou dont le label le contient
+pub fn lookup<'a>(
+    categories: impl Iterator<Item = (String, &'a PlotCategory)>, query: &str,
+) -> Vec<(String, PlotId, &'a PlotEntry)> {
+    let query = query.trim();
+    let id: Option<usize> = query.parse().ok();
+    let query = query.to_lowercase();
+    let matches = |plot_id: usize, entry: &PlotEntry| match id {
+        Some(id) => plot_id == id,
+        None => entry.label.to_lowercase().contains(&query),
+    };
+
+    let mut entries = Vec::new();
+    for (name, category) in categories {
+        for (&plot_id, entry) in &category.booleans {
+            if matches(plot_id, entry) {
+                entries.push((name.clone(), PlotId::Bool(plot_id), entry));
+            }
+        }
+        for (&plot_id, entry) in &category.ints {
+            if matches(plot_id, entry) {
+                entries.push((name.clone(), PlotId::Int(plot_id), entry));
+            }
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use super::*;
+
+    #[test]
+    fn find_plot_references() -> Result<()> {
+        let read = |path: &str| -> Result<String> {
+            let mut input = String::new();
+            File::open(path)?.read_to_string(&mut input)?;
+            Ok(input)
+        };
+        let me3_plot_db: Me3PlotDb = ron::from_str(&read("databases/me3_plot_db.ron")?)?;
+        let known_issues: KnownIssuesDb = ron::from_str(&read("databases/me3_known_issues.ron")?)?;
+
+        // [The Fan] Charmed him
+        let plot_id = PlotId::Bool(14914);
+        let references = category_references(plot_id, me3_plot_db.named_categories());
+        assert!(references.iter().any(|reference| reference.starts_with("Mass Effect 1 > ")));
+
+        let references = known_issue_references(plot_id, &known_issues);
+        assert_eq!(
+            references,
+            [
+                "Known issue : Conrad Verner paragon bug",
+                "Known issue fix : Conrad Verner paragon bug > Charmed him (paragon)"
+            ]
+        );
+
+        assert!(category_references(PlotId::Int(14914), me3_plot_db.named_categories()).is_empty());
+
+        let entries = lookup(me3_plot_db.named_categories(), " 14914 ");
+        assert!(entries.iter().all(|(_, plot_id, _)| *plot_id == PlotId::Bool(14914)));
+        assert!(!entries.is_empty());
+
+        let entries = lookup(me3_plot_db.named_categories(), "CHARMED HIM");
+        assert!(entries.iter().any(|(_, plot_id, _)| *plot_id == PlotId::Bool(14914)));
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::{bail, Result};
+use derive_more::Deref;
+use imgui::ImString;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::save_data::{
+    mass_effect_1_leg::player::{Item, ItemLevel},
+    mass_effect_2, mass_effect_3,
+    shared::{
+        player::WeaponLoadout,
+        plot::{BoolVec, Me1PlotTable},
+    },
+    ImguiString,
+};
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum Game {
+    MassEffect1,
+    MassEffect1Leg,
+    MassEffect2,
+    MassEffect2Leg,
+    MassEffect3,
+}
+
+#[derive(Deserialize, Deref)]
+pub struct RecipeDb(Vec<Recipe>);
+
+#[derive(Deserialize, Clone)]
+pub struct Recipe {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub games: Vec<Game>,
+    #[serde(default)]
+    pub booleans: IndexMap<usize, bool>,
+    #[serde(default)]
+    pub ints: IndexMap<usize, i32>,
+    #[serde(default)]
+    pub floats: IndexMap<usize, f32>,
+    #[serde(default)]
+    pub items: Vec<RecipeItem>,
+    #[serde(default)]
+    pub loadout: Option<RecipeLoadout>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RecipeItem {
+    pub item_id: i32,
+    pub manufacturer_id: i32,
+    #[serde(default)]
+    pub item_level: ItemLevel,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct RecipeLoadout {
+    pub assault_rifle: Option<String>,
+    pub shotgun: Option<String>,
+    pub sniper_rifle: Option<String>,
+    pub submachine_gun: Option<String>,
+    pub pistol: Option<String>,
+    pub heavy_weapon: Option<String>,
+    pub ammo_power: Option<String>,
+}
+
+// Même ordre que `WeaponLoadout`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WeaponSlot {
+    AssaultRifle,
+    Shotgun,
+    SniperRifle,
+    SubmachineGun,
+    Pistol,
+    HeavyWeapon,
+}
+
+impl Recipe {
+    pub fn is_applicable(&self, game: Game) -> bool {
+        // Seul l'inventaire de ME1LE est éditable
+        self.games.contains(&game)
+            && (self.items.is_empty() || game == Game::MassEffect1Leg)
+            && (self.loadout.is_none()
+                || matches!(game, Game::MassEffect2 | Game::MassEffect2Leg | Game::MassEffect3))
+    }
+
+    pub fn apply_plot<P: PlotEdit>(&self, plot: &mut P) {
+        for (&id, &value) in &self.booleans {
+            plot.set_bool(id, value);
+        }
+        for (&id, &value) in &self.ints {
+            plot.set_int(id, value);
+        }
+        for (&id, &value) in &self.floats {
+            plot.set_float(id, value);
+        }
+    }
+
+    pub fn grant_items(&self, inventory: &mut Vec<Item>) {
+        for RecipeItem { item_id, manufacturer_id, item_level } in &self.items {
+            let mut item = Item::default();
+            item.item_id = *item_id;
+            item.manufacturer_id = *manufacturer_id;
+            item.item_level = item_level.clone();
+            inventory.push(item);
+        }
+    }
+}
+
+impl RecipeLoadout {
+    pub fn weapons(&self) -> impl Iterator<Item = (WeaponSlot, &str)> {
+        let slots = vec![
+            (WeaponSlot::AssaultRifle, &self.assault_rifle),
+            (WeaponSlot::Shotgun, &self.shotgun),
+            (WeaponSlot::SniperRifle, &self.sniper_rifle),
+            (WeaponSlot::SubmachineGun, &self.submachine_gun),
+            (WeaponSlot::Pistol, &self.pistol),
+            (WeaponSlot::HeavyWeapon, &self.heavy_weapon),
+        ];
+        slots
+            .into_iter()
+            .filter_map(|(slot, weapon)| weapon.as_deref().map(|weapon| (slot, weapon)))
+    }
+
+    pub fn check_class(&self, game: Game, class_name: &str) -> Result<()> {
+        let allowed = allowed_weapon_slots(game, class_name);
+        for (slot, _) in self.weapons() {
+            if !allowed.contains(&slot) {
+                let class = class_name.rsplit("SFXPawn_Player").next().unwrap_or(class_name);
+                bail!("{} cannot carry {:?}", class, slot);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply<W: LoadoutWeapon>(&self, loadout: &mut WeaponLoadout, weapons: &mut [W]) {
+        for (slot, weapon) in self.weapons() {
+            let field = match slot {
+                WeaponSlot::AssaultRifle => &mut loadout.assault_rifle,
+                WeaponSlot::Shotgun => &mut loadout.shotgun,
+                WeaponSlot::SniperRifle => &mut loadout.sniper_rifle,
+                WeaponSlot::SubmachineGun => &mut loadout.submachine_gun,
+                WeaponSlot::Pistol => &mut loadout.pistol,
+                WeaponSlot::HeavyWeapon => &mut loadout.heavy_weapon,
+            };
+            *field = ImString::new(weapon).into();
+        }
+
+        // Seulement les armes déjà possédées
+        if let Some(ammo_power) = &self.ammo_power {
+            for weapon in weapons {
+                let is_equipped = self.weapons().any(|(_, name)| {
+                    let short_name = name.rsplit('.').next().unwrap_or(name);
+                    unicase::eq(weapon.class_name(), short_name)
+                        || unicase::eq(weapon.class_name(), name)
+                });
+                if is_equipped {
+                    weapon.set_ammo_power(ammo_power);
+                }
+            }
+        }
+    }
+}
+
+fn allowed_weapon_slots(game: Game, class_name: &str) -> &'static [WeaponSlot] {
+    use WeaponSlot::*;
+    const ALL: &[WeaponSlot] =
+        &[AssaultRifle, Shotgun, SniperRifle, SubmachineGun, Pistol, HeavyWeapon];
+
+    match game {
+        Game::MassEffect2 | Game::MassEffect2Leg => {
+            let class = class_name.rsplit("SFXPawn_Player").next().unwrap_or_default();
+            match class {
+                "Soldier" => &[AssaultRifle, Shotgun, SniperRifle, Pistol, HeavyWeapon],
+                "Infiltrator" => &[SniperRifle, SubmachineGun, Pistol, HeavyWeapon],
+                "Vanguard" => &[Shotgun, SubmachineGun, Pistol, HeavyWeapon],
+                "Adept" | "Engineer" | "Sentinel" => &[SubmachineGun, Pistol, HeavyWeapon],
+                _ => ALL,
+            }
+        }
+        // Toutes les classes peuvent tout porter dans ME3, seul le poids compte
+        _ => ALL,
+    }
+}
+
+pub trait LoadoutWeapon {
+    fn class_name(&self) -> &str;
+    fn set_ammo_power(&mut self, ammo_power: &str);
+}
+
+impl LoadoutWeapon for mass_effect_2::player::Weapon {
+    fn class_name(&self) -> &str {
+        self.class_name.to_str()
+    }
+
+    fn set_ammo_power(&mut self, ammo_power: &str) {
+        self.ammo_power_name = ImguiString::from(ImString::new(ammo_power));
+    }
+}
+
+impl LoadoutWeapon for mass_effect_3::player::Weapon {
+    fn class_name(&self) -> &str {
+        self.class_name.to_str()
+    }
+
+    fn set_ammo_power(&mut self, ammo_power: &str) {
+        self.ammo_power_name = ImguiString::from(ImString::new(ammo_power));
+    }
+}
+
+pub trait PlotEdit {
+    fn set_bool(&mut self, id: usize, value: bool);
+    fn set_int(&mut self, id: usize, value: i32);
+    fn set_float(&mut self, id: usize, value: f32);
+}
+
+fn set_bool(bools: &mut BoolVec, id: usize, value: bool) {
+    if id >= bools.len() {
+        bools.resize(id + 1, false);
+    }
+    bools.set(id, value);
+}
+
+fn set_in_vec<T: Default + Clone>(vec: &mut Vec<T>, id: usize, value: T) {
+    if id >= vec.len() {
+        vec.resize(id + 1, T::default());
+    }
+    vec[id] = value;
+}
+
+impl PlotEdit for Me1PlotTable {
+    fn set_bool(&mut self, id: usize, value: bool) {
+        set_bool(&mut self.bool_variables, id, value);
+    }
+
+    fn set_int(&mut self, id: usize, value: i32) {
+        set_in_vec(&mut self.int_variables, id, value);
+    }
+
+    fn set_float(&mut self, id: usize, value: f32) {
+        set_in_vec(&mut self.float_variables, id, value);
+    }
+}
+
+impl PlotEdit for mass_effect_2::plot::PlotTable {
+    fn set_bool(&mut self, id: usize, value: bool) {
+        set_bool(&mut self.bool_variables, id, value);
+    }
+
+    fn set_int(&mut self, id: usize, value: i32) {
+        set_in_vec(&mut self.int_variables, id, value);
+    }
+
+    fn set_float(&mut self, id: usize, value: f32) {
+        set_in_vec(&mut self.float_variables, id, value);
+    }
+}
+
+impl PlotEdit for mass_effect_3::plot::PlotTable {
+    fn set_bool(&mut self, id: usize, value: bool) {
+        set_bool(&mut self.bool_variables, id, value);
+    }
+
+    fn set_int(&mut self, id: usize, value: i32) {
+        self.int_variables.insert(id as i32, value);
+    }
+
+    fn set_float(&mut self, id: usize, value: f32) {
+        self.float_variables.insert(id as i32, value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_recipe_db() -> Result<()> {
+        let mut input = String::new();
+        {
+            let mut file = File::open("databases/recipes.ron")?;
+            file.read_to_string(&mut input)?;
+        }
+
+        let _recipe_db: RecipeDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn loadout_class_check() -> Result<()> {
+        let loadout = RecipeLoadout {
+            sniper_rifle: Some(String::from("SFXGameContent_Inventory.SFXWeapon_SniperRifle")),
+            pistol: Some(String::from("SFXGameContent_Inventory.SFXWeapon_HandCannon")),
+            ..Default::default()
+        };
+
+        loadout.check_class(Game::MassEffect2, "SFXGame.SFXPawn_PlayerInfiltrator")?;
+        assert!(loadout.check_class(Game::MassEffect2, "SFXGame.SFXPawn_PlayerAdept").is_err());
+        loadout.check_class(Game::MassEffect3, "SFXGame.SFXPawn_PlayerAdept")?;
+
+        let mut weapon_loadout = WeaponLoadout::default();
+        let mut weapons: Vec<mass_effect_2::player::Weapon> = Vec::new();
+        loadout.apply(&mut weapon_loadout, &mut weapons);
+        assert_eq!(
+            weapon_loadout.sniper_rifle.to_str(),
+            "SFXGameContent_Inventory.SFXWeapon_SniperRifle"
+        );
+        assert_eq!(weapon_loadout.assault_rifle.to_str(), "");
+
+        Ok(())
+    }
+}
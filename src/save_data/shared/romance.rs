@@ -0,0 +1,157 @@
+use serde::Deserialize;
+
+use crate::save_data::shared::{plot::BoolSlice, recipe::Game};
+
+// Romances d'un même plot qui ne peuvent pas être actives en même temps
+#[derive(Deserialize)]
+pub struct RomanceDb(pub Vec<RomanceGroup>);
+
+#[derive(Deserialize)]
+pub struct RomanceGroup {
+    pub name: String,
+    // Plot de ME1 / ME2 / ME3, les versions Legendary partagent celui du jeu d'origine
+    pub game: Game,
+    pub romances: Vec<Romance>,
+}
+
+#[derive(Deserialize)]
+pub struct Romance {
+    pub name: String,
+    pub flag: usize,
+    // Romance possible seulement avec ce genre de Shepard
+    #[serde(default)]
+    pub only: Option<Gender>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+pub struct RomanceIssue {
+    pub message: String,
+    pub fixes: Vec<RomanceFix>,
+}
+
+pub struct RomanceFix {
+    pub label: String,
+    pub unset: Vec<usize>,
+}
+
+impl RomanceDb {
+    pub fn check(&self, game: Game, booleans: &BoolSlice, is_female: bool) -> Vec<RomanceIssue> {
+        let game = match game {
+            Game::MassEffect1 | Game::MassEffect1Leg => Game::MassEffect1,
+            Game::MassEffect2 | Game::MassEffect2Leg => Game::MassEffect2,
+            Game::MassEffect3 => Game::MassEffect3,
+        };
+        let gender = if is_female { Gender::Female } else { Gender::Male };
+
+        let mut issues = Vec::new();
+        for group in self.0.iter().filter(|group| group.game == game) {
+            let active: Vec<_> = group
+                .romances
+                .iter()
+                .filter(|romance| booleans.get(romance.flag).map(|b| *b).unwrap_or_default())
+                .collect();
+
+            for romance in &active {
+                if romance.only.map(|only| only != gender).unwrap_or_default() {
+                    issues.push(RomanceIssue {
+                        message: format!(
+                            "{} : {} cannot be romanced by a {} Shepard",
+                            group.name,
+                            romance.name,
+                            if is_female { "female" } else { "male" }
+                        ),
+                        fixes: vec![RomanceFix {
+                            label: format!("Remove {} romance", romance.name),
+                            unset: vec![romance.flag],
+                        }],
+                    });
+                }
+            }
+
+            if active.len() > 1 {
+                let names: Vec<_> = active.iter().map(|romance| romance.name.as_str()).collect();
+                let fixes = active
+                    .iter()
+                    .map(|kept| RomanceFix {
+                        label: format!("Keep {}", kept.name),
+                        unset: active
+                            .iter()
+                            .filter(|romance| romance.flag != kept.flag)
+                            .map(|romance| romance.flag)
+                            .collect(),
+                    })
+                    .collect();
+                issues.push(RomanceIssue {
+                    message: format!(
+                        "{} : only one romance is expected, found {}",
+                        group.name,
+                        names.join(", ")
+                    ),
+                    fixes,
+                });
+            }
+        }
+        issues
+    }
+}
+
+impl RomanceFix {
+    pub fn apply(&self, booleans: &mut BoolSlice) {
+        for &flag in &self.unset {
+            if flag < booleans.len() {
+                booleans.set(flag, false);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn check_romances() -> Result<()> {
+        let mut input = String::new();
+        {
+            let mut file = File::open("databases/romance_rules.ron")?;
+            file.read_to_string(&mut input)?;
+        }
+        let romance_db: RomanceDb = ron::from_str(&input)?;
+
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME2Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let booleans = &mut me2_save_game.plot.bool_variables;
+        for romance in &romance_db.0[1].romances {
+            booleans.set(romance.flag, false);
+        }
+        assert!(romance_db.check(Game::MassEffect2Leg, booleans, false).is_empty());
+
+        // Jack et Garrus
+        booleans.set(5208, true);
+        booleans.set(5207, true);
+        let issues = romance_db.check(Game::MassEffect2, booleans, false);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].message,
+            "Mass Effect 2 : Garrus cannot be romanced by a male Shepard"
+        );
+
+        issues[0].fixes[0].apply(booleans);
+        assert!(romance_db.check(Game::MassEffect2, booleans, false).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use std::{fmt, path::Path};
+
+use crate::save_data::{
+    mass_effect_1_leg::Me1LegVersion,
+    mass_effect_2::{Me2LegVersion, Me2Version},
+    mass_effect_3::Me3Version,
+};
+
+// Format d'une save, déduit de son contenu plutôt que de son extension
+// pour ouvrir les copies renommées (.bak, .sav...)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SaveFormat {
+    MassEffect1,
+    MassEffect1Leg,
+    MassEffect1LegChunk,
+    MassEffect2,
+    MassEffect2Leg,
+    MassEffect3,
+}
+
+impl SaveFormat {
+    pub const ALL: [SaveFormat; 6] = [
+        SaveFormat::MassEffect1,
+        SaveFormat::MassEffect1Leg,
+        SaveFormat::MassEffect1LegChunk,
+        SaveFormat::MassEffect2,
+        SaveFormat::MassEffect2Leg,
+        SaveFormat::MassEffect3,
+    ];
+
+    const ME1_LEG_MAGIC: [u8; 4] = [0xC1, 0x83, 0x2A, 0x9E];
+
+    // Chaque format dont l'en-tête correspond, en général un seul
+    pub fn detect(input: &[u8]) -> Vec<SaveFormat> {
+        let version = input
+            .get(0..4)
+            .map(|bytes| i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|format| match format {
+                SaveFormat::MassEffect1 => is_me1_save(input),
+                SaveFormat::MassEffect1Leg => input.get(0..4) == Some(&Self::ME1_LEG_MAGIC[..]),
+                SaveFormat::MassEffect1LegChunk => version == Some(Me1LegVersion::LATEST),
+                SaveFormat::MassEffect2 => version == Some(Me2Version::LATEST),
+                SaveFormat::MassEffect2Leg => version == Some(Me2LegVersion::LATEST),
+                SaveFormat::MassEffect3 => version == Some(Me3Version::LATEST),
+            })
+            .collect()
+    }
+
+    // Seul ME1 a sa propre extension
+    pub fn from_extension(path: &Path) -> Option<SaveFormat> {
+        match path.extension() {
+            Some(ext) if unicase::eq(ext.to_string_lossy().as_ref(), "MassEffectSave") => {
+                Some(SaveFormat::MassEffect1)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SaveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SaveFormat::MassEffect1 => "Mass Effect 1",
+            SaveFormat::MassEffect1Leg => "Mass Effect 1 Legendary",
+            SaveFormat::MassEffect1LegChunk => "Mass Effect 1 Legendary chunk",
+            SaveFormat::MassEffect2 => "Mass Effect 2",
+            SaveFormat::MassEffect2Leg => "Mass Effect 2 Legendary",
+            SaveFormat::MassEffect3 => "Mass Effect 3",
+        };
+        f.write_str(name)
+    }
+}
+
+// ME1 n'a pas de numéro de version, c'est une archive zip après un petit en-tête
+pub fn is_me1_save(input: &[u8]) -> bool {
+    let zip_offset = match input.get(8..12) {
+        Some(bytes) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+        None => return false,
+    };
+    input.get(zip_offset..zip_offset + 4) == Some(&b"PK\x03\x04"[..])
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn detect_save_format() -> Result<()> {
+        let files = [
+            ("test/Char_01-60-3-2-2-26-6-2018-57-26.MassEffectSave", SaveFormat::MassEffect1),
+            ("test/ME1Leg00_QuickSave.pcsav", SaveFormat::MassEffect1Leg),
+            ("test/ME1Leg00_QuickSave.uncompressed", SaveFormat::MassEffect1LegChunk),
+            ("test/ME2Save.pcsav", SaveFormat::MassEffect2),
+            ("test/ME2LegSave.pcsav", SaveFormat::MassEffect2Leg),
+            ("test/ME3Save.pcsav", SaveFormat::MassEffect3),
+        ];
+
+        for (file, format) in &files {
+            let input = fs::read(file)?;
+            assert_eq!(SaveFormat::detect(&input), [*format], "{}", file);
+        }
+
+        assert!(SaveFormat::detect(&[]).is_empty());
+        assert!(SaveFormat::detect(&[0; 64]).is_empty());
+        Ok(())
+    }
+}
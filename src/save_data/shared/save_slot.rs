@@ -0,0 +1,170 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+// Le numéro de slot n'est stocké que dans le nom du fichier, seule la date de la save est renouvelée
+// pour un nouveau slot, voir `SaveGame::renew_timestamp`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SlotKind {
+    Manual(u32),
+    AutoSave,
+    QuickSave,
+    ChapterSave,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+pub struct SaveSlot {
+    pub file_name: String,
+    pub kind: SlotKind,
+}
+
+impl SaveSlot {
+    pub fn new(file_name: String) -> Self {
+        let stem = Path::new(&file_name).file_stem().unwrap_or_default().to_string_lossy();
+
+        let kind = if stem.ends_with("AutoSave") {
+            SlotKind::AutoSave
+        } else if stem.ends_with("QuickSave") {
+            SlotKind::QuickSave
+        } else if stem.ends_with("ChapterSave") {
+            SlotKind::ChapterSave
+        } else {
+            match split_slot(&stem) {
+                Some((_, digits)) => SlotKind::Manual(digits.parse().unwrap_or_default()),
+                None => SlotKind::Other,
+            }
+        };
+
+        SaveSlot { file_name, kind }
+    }
+
+    pub fn extension(&self) -> String {
+        Path::new(&self.file_name).extension().unwrap_or_default().to_string_lossy().into_owned()
+    }
+}
+
+// `Save_0001` => ("Save", "0001")
+fn split_slot(stem: &str) -> Option<(&str, &str)> {
+    let (prefix, digits) = stem.rsplit_once('_')?;
+    if prefix.is_empty() || digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((prefix, digits))
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SlotNaming {
+    prefix: String,
+    width: usize,
+    extension: String,
+}
+
+impl SlotNaming {
+    // Reprend la convention des saves déjà présentes dans le dossier de carrière
+    pub fn detect(slots: &[SaveSlot], extension: &str, career_name: &str) -> Self {
+        let existing = slots
+            .iter()
+            .filter(|slot| unicase::eq(slot.extension().as_str(), extension))
+            .filter_map(|slot| {
+                let stem = Path::new(&slot.file_name).file_stem()?.to_str()?;
+                split_slot(stem).map(|(prefix, digits)| (prefix.to_owned(), digits.len()))
+            })
+            .next();
+
+        let (prefix, width) = existing.unwrap_or_else(|| {
+            if unicase::eq(extension, "MassEffectSave") {
+                // ME1 : `<Carrière>_01.MassEffectSave`
+                (career_name.to_owned(), 2)
+            } else {
+                // ME1LE, ME2, ME3 : `Save_0001.pcsav`
+                (String::from("Save"), 4)
+            }
+        });
+
+        SlotNaming { prefix, width, extension: extension.to_owned() }
+    }
+
+    // Le jeu n'affiche pas les numéros plus longs que le format
+    pub fn max_slot(&self) -> u32 {
+        10u32.pow(self.width as u32) - 1
+    }
+
+    pub fn file_name(&self, number: u32) -> String {
+        format!("{}_{:0width$}.{}", self.prefix, number, self.extension, width = self.width)
+    }
+
+    pub fn next_free(&self, slots: &[SaveSlot]) -> Result<String> {
+        let is_free = |number| {
+            let file_name = self.file_name(number);
+            !slots.iter().any(|slot| unicase::eq(slot.file_name.as_str(), &file_name))
+        };
+        match (1..=self.max_slot()).find(|&number| is_free(number)) {
+            Some(number) => Ok(self.file_name(number)),
+            None => bail!("No free slot left ({} max)", self.max_slot()),
+        }
+    }
+
+    pub fn rename(&self, slots: &[SaveSlot], number: u32) -> Result<String> {
+        let file_name = self.file_name(number);
+        if number == 0 {
+            bail!("Slot numbers start at 1");
+        }
+        if number > self.max_slot() {
+            bail!("Slot numbers end at {}", self.max_slot());
+        }
+        if slots.iter().any(|slot| unicase::eq(slot.file_name.as_str(), &file_name)) {
+            bail!("{} already exists", file_name);
+        }
+        Ok(file_name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::save_data::shared::SaveTimeStamp;
+
+    #[test]
+    fn slot_naming() -> Result<()> {
+        let slots: Vec<_> = vec!["Save_0001.pcsav", "Save_0002.pcsav", "AutoSave.pcsav"]
+            .into_iter()
+            .map(|file_name| SaveSlot::new(file_name.to_owned()))
+            .collect();
+
+        assert_eq!(slots[1].kind, SlotKind::Manual(2));
+        assert_eq!(slots[2].kind, SlotKind::AutoSave);
+
+        let naming = SlotNaming::detect(&slots, "pcsav", "Shepard_00");
+        assert_eq!(naming.next_free(&slots)?, "Save_0003.pcsav");
+        assert!(naming.rename(&slots, 2).is_err());
+        assert!(naming.rename(&slots, 10000).is_err());
+        assert_eq!(naming.rename(&slots, 12)?, "Save_0012.pcsav");
+
+        let naming = SlotNaming::detect(&[], "MassEffectSave", "Clare00");
+        assert_eq!(naming.next_free(&[])?, "Clare00_01.MassEffectSave");
+
+        // Slots pleins
+        let slots: Vec<_> =
+            (1..=99).map(|number| SaveSlot::new(naming.file_name(number))).collect();
+        assert!(naming.next_free(&slots).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn slot_timestamp() {
+        let timestamp = SaveTimeStamp::from_unix(0);
+        assert_eq!((timestamp.day, timestamp.month, timestamp.year), (1, 1, 1970));
+
+        // 2021-05-14 13:46:40
+        let timestamp = SaveTimeStamp::from_unix(1_621_000_000);
+        assert_eq!((timestamp.day, timestamp.month, timestamp.year), (14, 5, 2021));
+        assert_eq!(timestamp.seconds_since_midnight, 13 * 3600 + 46 * 60 + 40);
+
+        // Fin d'une année bissextile
+        let timestamp = SaveTimeStamp::from_unix(1_609_372_800);
+        assert_eq!((timestamp.day, timestamp.month, timestamp.year), (31, 12, 2020));
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::unreal;
+
+pub struct Section {
+    pub name: String,
+    pub size: usize,
+}
+
+#[derive(Default)]
+pub struct SaveStats {
+    pub total: usize,
+    pub sections: Vec<Section>,
+    pub serialize_time: Duration,
+}
+
+impl SaveStats {
+    // Ajoute les champs de `value`, préfixés si la save est en plusieurs parties (ME1)
+    pub fn add<T: Serialize>(&mut self, prefix: Option<&str>, value: &T) -> Result<()> {
+        let start = Instant::now();
+        let sizes = unreal::Serializer::section_sizes(value)?;
+        self.serialize_time += start.elapsed();
+
+        for (name, size) in sizes {
+            let name = match prefix {
+                Some(prefix) => format!("{}.{}", prefix, name),
+                None => name.to_owned(),
+            };
+            self.total += size;
+            self.sections.push(Section { name, size });
+        }
+        Ok(())
+    }
+
+    // Les plus grosses sections d'abord
+    pub fn sort(&mut self) {
+        self.sections.sort_by_key(|section| std::cmp::Reverse(section.size));
+    }
+
+    pub fn fraction(&self, section: &Section) -> f32 {
+        section.size as f32 / self.total.max(1) as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::save_data::mass_effect_2::Me2SaveGame;
+
+    use super::*;
+
+    #[test]
+    fn save_stats() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME2Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let mut stats = SaveStats::default();
+        stats.add(None, &me2_save_game)?;
+        stats.sort();
+
+        // Tout sauf le checksum
+        assert_eq!(stats.total, input.len() - 4);
+        assert!(stats.sections.iter().any(|section| section.name == "plot"));
+        assert!(stats.sections.windows(2).all(|w| w[0].size >= w[1].size));
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::save_data::shared::{plot::BoolSlice, recipe::Game};
+
+// Recrutement et disponibilité des coéquipiers, pour les débloquer en avance
+// ou récupérer ceux qu'un bug a retirés
+#[derive(Deserialize)]
+pub struct SquadDb(pub Vec<Squadmate>);
+
+#[derive(Deserialize)]
+pub struct Squadmate {
+    pub name: String,
+    // ME2 / ME3, les versions Legendary partagent le plot du jeu d'origine
+    pub game: Game,
+    pub recruited: usize,
+    // Proposé à l'écran de sélection de l'escouade
+    pub selectable: usize,
+    #[serde(default)]
+    pub died: Option<usize>,
+}
+
+pub struct SquadmateStatus {
+    pub recruited: bool,
+    pub selectable: bool,
+    pub dead: bool,
+}
+
+impl SquadDb {
+    pub fn squadmates(&self, game: Game) -> impl Iterator<Item = &Squadmate> {
+        let game = match game {
+            Game::MassEffect2Leg => Game::MassEffect2,
+            game => game,
+        };
+        self.0.iter().filter(move |squadmate| squadmate.game == game)
+    }
+}
+
+impl Squadmate {
+    pub fn status(&self, booleans: &BoolSlice) -> SquadmateStatus {
+        let get = |flag: usize| booleans.get(flag).map(|b| *b).unwrap_or_default();
+        SquadmateStatus {
+            recruited: get(self.recruited),
+            selectable: get(self.selectable),
+            dead: self.died.map(get).unwrap_or_default(),
+        }
+    }
+
+    pub fn set_recruited(&self, booleans: &mut BoolSlice, recruited: bool) {
+        set(booleans, self.recruited, recruited);
+    }
+
+    pub fn set_selectable(&self, booleans: &mut BoolSlice, selectable: bool) {
+        set(booleans, self.selectable, selectable);
+    }
+
+    // Recruté, sélectionnable et en vie
+    pub fn unlock(&self, booleans: &mut BoolSlice) {
+        set(booleans, self.recruited, true);
+        set(booleans, self.selectable, true);
+        if let Some(died) = self.died {
+            set(booleans, died, false);
+        }
+    }
+}
+
+fn set(booleans: &mut BoolSlice, flag: usize, value: bool) {
+    if flag < booleans.len() {
+        booleans.set(flag, value);
+    }
+}
+
+// Coéquipier de la save (ME2 / ME3), retrouvé par tag d'une save à l'autre
+pub trait HenchmanBuild {
+    fn tag(&self) -> &str;
+    fn level(&self) -> i32;
+    // Pouvoirs et rangs, évolutions, niveau, équipement
+    fn copy_build(&mut self, source: &Self);
+}
+
+pub fn copy_build<H: HenchmanBuild>(squad: &mut [H], source: &H) -> Result<()> {
+    let henchman = squad
+        .iter_mut()
+        .find(|henchman| unicase::eq(henchman.tag(), source.tag()))
+        .with_context(|| format!("{} is not in the squad of the opened save", source.tag()))?;
+    henchman.copy_build(source);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::fs;
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn unlock_squadmates() -> Result<()> {
+        let squad_db: SquadDb = ron::from_str(&fs::read_to_string("databases/squad_db.ron")?)?;
+        assert_eq!(squad_db.squadmates(Game::MassEffect2Leg).count(), 12);
+        assert_eq!(squad_db.squadmates(Game::MassEffect3).count(), 8);
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let booleans = &mut me2_save_game.plot.bool_variables;
+
+        let zaeed = squad_db.squadmates(Game::MassEffect2).find(|s| s.name == "Zaeed").unwrap();
+        zaeed.set_recruited(booleans, false);
+        zaeed.set_selectable(booleans, false);
+        booleans.set(206, true);
+        let status = zaeed.status(booleans);
+        assert!(!status.recruited && !status.selectable && status.dead);
+
+        zaeed.unlock(booleans);
+        let status = zaeed.status(booleans);
+        assert!(status.recruited && status.selectable && !status.dead);
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use imgui::ImString;
+
+use crate::save_data::RawUi;
+
+pub struct StringMatch {
+    pub path: Vec<String>,
+    pub value: String,
+}
+
+// Dans toutes les strings de l'onglet Raw, la casse n'est ignorée que pour l'ASCII
+pub fn search(root: &mut dyn RawUi, needle: &str, match_case: bool) -> Result<Vec<StringMatch>> {
+    let mut matches = Vec::new();
+    if needle.is_empty() {
+        return Ok(matches);
+    }
+
+    root.visit_strings(&mut Vec::new(), &mut |path, string| {
+        if find(string.to_str(), needle, match_case).is_some() {
+            matches.push(StringMatch { path: path.to_vec(), value: string.to_string() });
+        }
+    })?;
+    Ok(matches)
+}
+
+// Seulement dans les champs de `paths` si précisés, renvoie le nombre de strings modifiées
+// En cas d'erreur une partie a pu être remplacée, à faire sur une copie
+pub fn replace(
+    root: &mut dyn RawUi, needle: &str, replacement: &str, match_case: bool,
+    paths: Option<&[Vec<String>]>,
+) -> Result<usize> {
+    let mut count = 0;
+    root.visit_strings(&mut Vec::new(), &mut |path, string| {
+        if let Some(paths) = paths {
+            if !paths.iter().any(|p| p == path) {
+                return;
+            }
+        }
+        if let Some(replaced) = replace_in(string.to_str(), needle, replacement, match_case) {
+            *string = ImString::new(replaced);
+            count += 1;
+        }
+    })?;
+    Ok(count)
+}
+
+fn find(haystack: &str, needle: &str, match_case: bool) -> Option<usize> {
+    if match_case {
+        haystack.find(needle)
+    } else {
+        // Même longueur en octets, les positions restent valides
+        haystack.to_ascii_lowercase().find(&needle.to_ascii_lowercase())
+    }
+}
+
+fn replace_in(haystack: &str, needle: &str, replacement: &str, match_case: bool) -> Option<String> {
+    if needle.is_empty() {
+        return None;
+    }
+
+    let mut result = String::new();
+    let mut rest = haystack;
+    while let Some(i) = find(rest, needle, match_case) {
+        result.push_str(&rest[..i]);
+        result.push_str(replacement);
+        rest = &rest[i + needle.len()..];
+    }
+
+    if rest.len() == haystack.len() {
+        return None;
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use indexmap::IndexMap;
+    use std::{fs::File, io::Read};
+
+    use crate::{
+        save_data::{mass_effect_3::Me3SaveGame, ImguiString},
+        unreal,
+    };
+
+    use super::*;
+
+    #[test]
+    fn search_and_replace() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME3Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me3_save_game: Me3SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let matches = search(&mut me3_save_game, "biod_nor_002wrex", false)?;
+        assert!(!matches.is_empty());
+        assert!(search(&mut me3_save_game, "biod_nor_002wrex", true)?.is_empty());
+
+        // Une seule occurrence
+        let first = &matches[0].path;
+        let count = replace(
+            &mut me3_save_game,
+            "BioD_Nor_002Wrex",
+            "BioD_Nor_002Urdnot",
+            true,
+            Some(std::slice::from_ref(first)),
+        )?;
+        assert_eq!(count, 1);
+        let remaining = search(&mut me3_save_game, "BioD_Nor_002Wrex", true)?;
+        assert_eq!(remaining.len(), matches.len() - 1);
+
+        // Partout, y compris les clés des maps (streaming records)
+        replace(&mut me3_save_game, "BioD_Nor_002Wrex", "BioD_Nor_002Urdnot", true, None)?;
+        assert!(search(&mut me3_save_game, "002Wrex", true)?.is_empty());
+
+        let output = unreal::Serializer::to_byte_buf(&me3_save_game)?;
+        let mut reread: Me3SaveGame = unreal::Deserializer::from_bytes(&output)?;
+        assert_eq!(search(&mut reread, "002Urdnot", true)?.len(), matches.len());
+
+        // Deux clés qui deviendraient identiques
+        let mut map: IndexMap<ImguiString, i32> = IndexMap::new();
+        map.insert(ImguiString::from(ImString::new("BioD_Nor_002a")), 1);
+        map.insert(ImguiString::from(ImString::new("BioD_Nor_002b")), 2);
+        assert!(replace(&mut map, "002a", "002b", true, None).is_err());
+        assert!(map.contains_key(&ImguiString::from(ImString::new("BioD_Nor_002a"))));
+
+        assert_eq!(replace_in("a.B.b", "b", "c", false).as_deref(), Some("a.c.c"));
+        assert_eq!(replace_in("abc", "x", "y", false), None);
+        Ok(())
+    }
+}
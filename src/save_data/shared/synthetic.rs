@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use std::any;
+
+use crate::{
+    save_data::{
+        mass_effect_1::{
+            player::Player,
+            state::State,
+            zip_writer::{self, ZipCompression},
+            Me1SaveGame,
+        },
+        mass_effect_1_leg::{Me1LegSaveData, Me1LegSaveGame, Me1LegVersion},
+        mass_effect_2::{Me2LegSaveGame, Me2LegVersion, Me2SaveGame, Me2Version},
+        mass_effect_3::{Me3SaveGame, Me3Version},
+    },
+    unreal,
+};
+
+// Saves minimales de chaque format pour tester les parsers et les presets :
+// en-têtes valides, tables vides, tout le reste à zéro.
+// Chaque structure est lue par les mêmes deserializers que les vraies saves.
+
+const MAX_SIZE: usize = 0x10000;
+
+// "RGMH", version 1
+const ME1_BEGIN: [u8; 8] = [0x52, 0x47, 0x4D, 0x48, 0x01, 0x00, 0x00, 0x00];
+const UNREAL_PACKAGE_MAGIC: u32 = 0x9E2A83C1;
+
+// Plus petite entrée remplie de zéros après `header` qui se lit,
+// les octets restants d'une structure (`List<u8>`) sont donc vides
+fn zeroed_bytes<T: DeserializeOwned>(header: &[u8]) -> Result<Vec<u8>> {
+    let read = |len: usize| {
+        let mut input = vec![0; len];
+        input[..header.len()].copy_from_slice(header);
+        unreal::Deserializer::from_bytes_partial::<T>(&input).ok().map(|(_, read)| {
+            input.truncate(read);
+            input
+        })
+    };
+
+    let mut input = read(MAX_SIZE)
+        .with_context(|| format!("Failed to generate a {}", any::type_name::<T>()))?;
+    let (mut low, mut high) = (header.len(), input.len());
+    while low < high {
+        let len = (low + high) / 2;
+        match read(len) {
+            Some(shorter) => {
+                high = shorter.len();
+                input = shorter;
+            }
+            None => low = len + 1,
+        }
+    }
+    Ok(input)
+}
+
+fn zeroed<T: DeserializeOwned>(header: &[u8]) -> Result<T> {
+    let input = zeroed_bytes::<T>(header)?;
+    Ok(unreal::Deserializer::from_bytes(&input)?)
+}
+
+// player.sav sans noms, classes ni objets et state.sav, sans WorldSavePackage.sav
+pub fn me1() -> Result<Me1SaveGame> {
+    let mut header = ME1_BEGIN.to_vec();
+    header.extend(&12u32.to_le_bytes()); // header_offset
+    header.extend(&UNREAL_PACKAGE_MAGIC.to_le_bytes());
+    let player = zeroed_bytes::<Player>(&header)?;
+    let state = zeroed_bytes::<State>(&[])?;
+
+    let compression = ZipCompression::default();
+    let mut zip = zip_writer::ZipStream::new(Vec::new());
+    zip.write_file(&zip_writer::compress_file("player.sav", &player, compression)?)?;
+    zip.write_file(&zip_writer::compress_file("state.sav", &state, compression)?)?;
+
+    let mut input = ME1_BEGIN.to_vec();
+    input.extend(&12u32.to_le_bytes()); // zip_offset
+    input.extend(zip.finish()?);
+    Ok(unreal::Deserializer::from_bytes(&input)?)
+}
+
+pub fn me1_leg() -> Result<Me1LegSaveGame> {
+    let save_data: Me1LegSaveData = zeroed(&Me1LegVersion::LATEST.to_le_bytes())?;
+    Ok(Me1LegSaveGame::new(save_data))
+}
+
+pub fn me2() -> Result<Me2SaveGame> {
+    zeroed(&Me2Version::LATEST.to_le_bytes())
+}
+
+pub fn me2_leg() -> Result<Me2LegSaveGame> {
+    zeroed(&Me2LegVersion::LATEST.to_le_bytes())
+}
+
+pub fn me3() -> Result<Me3SaveGame> {
+    zeroed(&Me3Version::LATEST.to_le_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use crate::unreal;
+
+    use super::*;
+
+    #[test]
+    fn synthetic_saves() -> Result<()> {
+        // Relues à l'identique
+        let me1 = unreal::Serializer::to_byte_buf(&me1()?)?;
+        let me1_2: Me1SaveGame = unreal::Deserializer::from_bytes(&me1)?;
+        assert_eq!(me1, unreal::Serializer::to_byte_buf(&me1_2)?);
+
+        let me1_leg = unreal::Serializer::to_byte_buf(&me1_leg()?)?;
+        let me1_leg_2: Me1LegSaveGame = unreal::Deserializer::from_bytes(&me1_leg)?;
+        assert!(!me1_leg_2.is_standalone());
+        assert_eq!(me1_leg, unreal::Serializer::to_byte_buf(&me1_leg_2)?);
+
+        let me2 = unreal::Serializer::to_byte_buf(&me2()?)?;
+        let (_, read) = unreal::Deserializer::from_bytes_partial::<Me2SaveGame>(&me2)?;
+        assert_eq!(read, me2.len());
+
+        let me2_leg = unreal::Serializer::to_byte_buf(&me2_leg()?)?;
+        let (_, read) = unreal::Deserializer::from_bytes_partial::<Me2LegSaveGame>(&me2_leg)?;
+        assert_eq!(read, me2_leg.len());
+
+        let me3 = unreal::Serializer::to_byte_buf(&me3()?)?;
+        let (_, read) = unreal::Deserializer::from_bytes_partial::<Me3SaveGame>(&me3)?;
+        assert_eq!(read, me3.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use std::convert::TryInto;
+
+const TLK_MAGIC: u32 = 0x006B_6C54;
+const HEADER_LEN: usize = 28;
+
+// Fichier TLK autonome (ME2, ME3, LE2, LE3), ceux de ME1 sont dans les packages
+#[derive(Clone, Default)]
+pub struct TlkFile {
+    strings: IndexMap<i32, String>,
+}
+
+impl TlkFile {
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        let read_i32 = |offset: usize| -> Result<i32> {
+            let bytes = input.get(offset..offset + 4).context("Unexpected end of file")?;
+            Ok(i32::from_le_bytes(bytes.try_into()?))
+        };
+
+        if read_i32(0)? as u32 != TLK_MAGIC {
+            bail!("Not a TLK file");
+        }
+        let male_count = read_i32(12)?.max(0) as usize;
+        let female_count = read_i32(16)?.max(0) as usize;
+        let node_count = read_i32(20)?.max(0) as usize;
+
+        let entries_offset = HEADER_LEN;
+        let nodes_offset = entries_offset + (male_count + female_count) * 8;
+        let data_offset = nodes_offset + node_count * 8;
+
+        let nodes = (0..node_count)
+            .map(|i| Ok((read_i32(nodes_offset + i * 8)?, read_i32(nodes_offset + i * 8 + 4)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let data = input.get(data_offset..).context("Unexpected end of file")?;
+
+        // Les entrées masculines d'abord, les féminines ne remplacent pas un id existant
+        let mut strings = IndexMap::new();
+        for i in 0..male_count + female_count {
+            let id = read_i32(entries_offset + i * 8)?;
+            let bit_offset = read_i32(entries_offset + i * 8 + 4)?;
+            if bit_offset < 0 || strings.contains_key(&id) {
+                continue;
+            }
+            strings.insert(id, decode_string(&nodes, data, bit_offset as usize)?);
+        }
+
+        Ok(TlkFile { strings })
+    }
+
+    pub fn get(&self, id: i32) -> Option<&str> {
+        self.strings.get(&id).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &str)> {
+        self.strings.iter().map(|(id, string)| (*id, string.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+// Arbre de Huffman : un id de noeud négatif est une feuille, `-1 - id` est le caractère
+fn decode_string(nodes: &[(i32, i32)], data: &[u8], mut bit_offset: usize) -> Result<String> {
+    let mut string = String::new();
+    let mut node = 0;
+
+    while bit_offset < data.len() * 8 {
+        let bit = (data[bit_offset / 8] >> (bit_offset % 8)) & 1;
+        let (left, right) = *nodes.get(node).context("Invalid Huffman tree")?;
+        let next = if bit == 1 { right } else { left };
+
+        if next >= 0 {
+            node = next as usize;
+        } else {
+            let c = (-1 - next) as u16;
+            if c == 0 {
+                return Ok(string);
+            }
+            string.push(std::char::from_u32(c as u32).unwrap_or(std::char::REPLACEMENT_CHARACTER));
+            node = 0;
+        }
+        bit_offset += 1;
+    }
+    bail!("Unterminated string")
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn parse_tlk() -> Result<()> {
+        let header = [TLK_MAGIC as i32, 3, 2, 1, 1, 2, 1];
+        let entries = [100, 0, 100, 5];
+        // 'a' = 0, 'b' = 10, '\0' = 11
+        let nodes = [-1 - 'a' as i32, 1, -1 - 'b' as i32, -1];
+
+        let mut input = Vec::new();
+        for value in header.iter().chain(&entries).chain(&nodes) {
+            input.extend(&value.to_le_bytes());
+        }
+        // "ab" puis "b" (féminin, ignoré) : 0 10 11 | 10 11
+        input.extend(&[0b1011_1010, 0b0000_0001]);
+
+        let tlk = TlkFile::parse(&input)?;
+        assert_eq!(tlk.len(), 1);
+        assert_eq!(tlk.get(100), Some("ab"));
+        assert_eq!(tlk.get(101), None);
+
+        assert!(TlkFile::parse(&[0; 4]).is_err());
+        Ok(())
+    }
+}
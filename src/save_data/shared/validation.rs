@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+// Onglet où le problème peut être corrigé
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tab {
+    General,
+    Inventory,
+    HeadMorph,
+    Raw,
+}
+
+pub struct Issue {
+    pub tab: Tab,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct Issues(Vec<Issue>);
+
+impl Issues {
+    pub fn check(&mut self, tab: Tab, ok: bool, message: impl FnOnce() -> String) {
+        if !ok {
+            self.0.push(Issue { tab, message: message() });
+        }
+    }
+
+    pub fn non_negative<T: PartialOrd + Default + Display>(
+        &mut self, tab: Tab, name: &str, value: T,
+    ) {
+        self.check(tab, value >= T::default(), || format!("{} is negative ({})", name, value));
+    }
+
+    pub fn same_len(&mut self, tab: Tab, name: &str, len: usize, other_name: &str, other: usize) {
+        self.check(tab, len == other, || {
+            format!("{} ({}) and {} ({}) have different sizes", name, len, other_name, other)
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn has(&self, tab: Tab) -> bool {
+        self.0.iter().any(|issue| issue.tab == tab)
+    }
+
+    pub fn tab(&self, tab: Tab) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(move |issue| issue.tab == tab).map(|issue| issue.message.as_str())
+    }
+}
+
+// Vérifications rapides, faites à chaque frame
+pub trait Validate {
+    fn validate(&self, issues: &mut Issues);
+
+    fn issues(&self) -> Issues {
+        let mut issues = Issues::default();
+        self.validate(&mut issues);
+        issues
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::{fs::File, io::Read};
+
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    use super::*;
+
+    #[test]
+    fn validate_me2() -> Result<()> {
+        let mut input = Vec::new();
+        {
+            let mut file = File::open("test/ME2Save.pcsav")?;
+            file.read_to_end(&mut input)?;
+        }
+        let mut me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let issues = me2_save_game.issues();
+        for &tab in &[Tab::General, Tab::Inventory, Tab::HeadMorph, Tab::Raw] {
+            assert!(!issues.has(tab));
+        }
+
+        me2_save_game.player.credits = -1;
+        me2_save_game.player.appearance.head_morph.has_head_morph = true;
+        me2_save_game.player.appearance.head_morph.head_morph = None;
+
+        let issues = me2_save_game.issues();
+        assert!(issues.has(Tab::General));
+        assert!(issues.has(Tab::HeadMorph));
+        assert!(!issues.has(Tab::Raw));
+        assert_eq!(issues.tab(Tab::General).next(), Some("Credits is negative (-1)"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+// Seul `fix_working_dir` sert hors de Windows
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use std::path::Path;
+
+// Entrée "Edit with Trilogy Save Editor" du menu contextuel de l'Explorateur.
+// Enregistrée pour l'utilisateur courant (HKCU), sans droits administrateur, avec `reg.exe`.
+const EXTENSIONS: [&str; 2] = [".pcsav", ".MassEffectSave"];
+const VERB: &str = "TrilogySaveEditor";
+const LABEL: &str = "Edit with Trilogy Save Editor";
+
+fn verb_key(extension: &str) -> String {
+    format!(r"HKCU\Software\Classes\SystemFileAssociations\{}\shell\{}", extension, VERB)
+}
+
+// Arguments de chaque appel à `reg.exe`
+fn register_commands(exe: &Path) -> Vec<Vec<String>> {
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let mut commands = Vec::new();
+    for extension in &EXTENSIONS {
+        let key = verb_key(extension);
+        commands.push(vec![
+            "add".into(),
+            key.clone(),
+            "/ve".into(),
+            "/d".into(),
+            LABEL.into(),
+            "/f".into(),
+        ]);
+        commands.push(vec![
+            "add".into(),
+            key.clone(),
+            "/v".into(),
+            "Icon".into(),
+            "/d".into(),
+            exe.display().to_string(),
+            "/f".into(),
+        ]);
+        commands.push(vec![
+            "add".into(),
+            format!(r"{}\command", key),
+            "/ve".into(),
+            "/d".into(),
+            command.clone(),
+            "/f".into(),
+        ]);
+    }
+    commands
+}
+
+fn unregister_commands() -> Vec<Vec<String>> {
+    EXTENSIONS
+        .iter()
+        .map(|extension| vec!["delete".into(), verb_key(extension), "/f".into()])
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn run_reg(args: &[String]) -> anyhow::Result<bool> {
+    use std::{os::windows::process::CommandExt, process::Command};
+
+    // Pas de console qui s'ouvre
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let status = Command::new("reg").args(args).creation_flags(CREATE_NO_WINDOW).status()?;
+    Ok(status.success())
+}
+
+#[cfg(target_os = "windows")]
+pub fn register() -> anyhow::Result<()> {
+    let exe = std::env::current_exe()?;
+    for args in register_commands(&exe) {
+        if !run_reg(&args)? {
+            anyhow::bail!("Failed to write the registry key {}", args[1]);
+        }
+    }
+    Ok(())
+}
+
+// Les clés déjà absentes ne sont pas une erreur
+#[cfg(target_os = "windows")]
+pub fn unregister() -> anyhow::Result<()> {
+    for args in unregister_commands() {
+        run_reg(&args)?;
+    }
+    Ok(())
+}
+
+// Lancé depuis l'Explorateur, le dossier courant n'est pas celui de l'éditeur
+// alors que les databases sont cherchées à côté de lui
+pub fn fix_working_dir() {
+    if Path::new("databases").is_dir() {
+        return;
+    }
+    let exe_dir = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_owned));
+    if let Some(exe_dir) = exe_dir {
+        if exe_dir.join("databases").is_dir() {
+            let _ = std::env::set_current_dir(exe_dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shell_menu_commands() {
+        let exe = Path::new(r"C:\Tools\trilogy_save_editor.exe");
+        let commands = register_commands(exe);
+        assert_eq!(commands.len(), 6);
+        assert_eq!(
+            commands[2],
+            [
+                "add",
+                r"HKCU\Software\Classes\SystemFileAssociations\.pcsav\shell\TrilogySaveEditor\command",
+                "/ve",
+                "/d",
+                r#""C:\Tools\trilogy_save_editor.exe" "%1""#,
+                "/f"
+            ]
+        );
+
+        // La désinstallation supprime tout ce qui a été ajouté
+        let unregister = unregister_commands();
+        for args in &commands {
+            assert!(unregister.iter().any(|delete| args[1].starts_with(&delete[1])));
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use flume::Sender;
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::event_handler::MainEvent;
+
+// Une seule instance à la fois : la 2ème passe le fichier à ouvrir à la 1ère par une socket locale
+const PORT: u16 = 47_218;
+const MAGIC: &str = "trilogy-save-editor-open";
+
+fn local_addr() -> SocketAddr {
+    SocketAddr::from((Ipv4Addr::LOCALHOST, PORT))
+}
+
+// Le port est ouvert à toute la machine, seul l'utilisateur qui peut lire le token est écouté
+fn token_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("trilogy-save-editor").join("instance.token"))
+}
+
+fn write_token(path: &Path, token: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Recréé pour que les droits s'appliquent
+    let _ = fs::remove_file(path);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(token.as_bytes())
+}
+
+// `true` si une instance déjà lancée ouvre le fichier
+pub fn hand_over(file_path: &str) -> bool {
+    // Le dossier courant de l'autre instance n'est pas le même
+    let file_path = match Path::new(file_path).canonicalize() {
+        Ok(file_path) => file_path,
+        Err(_) => return false,
+    };
+    let token = match token_path().and_then(|path| fs::read_to_string(path).ok()) {
+        Some(token) => token,
+        None => return false,
+    };
+    match send(local_addr(), token.trim(), &file_path.to_string_lossy()) {
+        Ok(()) => {
+            info!("{} handed over to the running instance", file_path.display());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn listen(event_addr: Sender<MainEvent>) {
+    let listener = match TcpListener::bind(local_addr()) {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!("Single instance disabled : {}", err);
+            return;
+        }
+    };
+
+    let token = Uuid::new_v4().to_simple().to_string();
+    let written = match token_path() {
+        Some(path) => write_token(&path, &token),
+        None => Err(io::Error::new(io::ErrorKind::NotFound, "No config directory")),
+    };
+    match written {
+        Ok(()) => {
+            thread::spawn(move || serve(listener, token, event_addr));
+        }
+        Err(err) => warn!("Single instance disabled : {}", err),
+    }
+}
+
+fn send(addr: SocketAddr, token: &str, file_path: &str) -> io::Result<()> {
+    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_millis(500))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    writeln!(stream, "{} {} {}", MAGIC, token, file_path)?;
+
+    // Une autre application sur le même port ne répond pas `ok`
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    if response.trim_end() != "ok" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Unexpected response"));
+    }
+    Ok(())
+}
+
+fn serve(listener: TcpListener, token: String, event_addr: Sender<MainEvent>) {
+    for stream in listener.incoming().flatten() {
+        let mut request = String::new();
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+        if BufReader::new(&stream).read_line(&mut request).is_err() {
+            continue;
+        }
+
+        let request = request.trim_end().strip_prefix(MAGIC).map(str::trim_start);
+        let file_path = request
+            .and_then(|request| request.split_once(' '))
+            .and_then(|(request_token, file_path)| (request_token == token).then_some(file_path));
+
+        match file_path {
+            Some(file_path) => {
+                let _ = writeln!(&stream, "ok");
+                if event_addr.send(MainEvent::OpenSave(file_path.to_owned())).is_err() {
+                    break;
+                }
+            }
+            None if request.is_some() => warn!("Handover refused, wrong token"),
+            None => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TOKEN: &str = "test-token";
+
+    #[test]
+    fn hand_over_file() -> io::Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let addr = listener.local_addr()?;
+        let (event_addr, event_rx) = flume::unbounded();
+        thread::spawn(move || serve(listener, TOKEN.to_owned(), event_addr));
+
+        send(addr, TOKEN, "C:/Saves/Save_0001.pcsav")?;
+        match event_rx.recv_timeout(Duration::from_secs(2)) {
+            Ok(MainEvent::OpenSave(file_path)) => {
+                assert_eq!(file_path, "C:/Saves/Save_0001.pcsav")
+            }
+            _ => panic!("The file was not handed over"),
+        }
+
+        // Sans le préfixe ou avec un autre token, rien n'est ouvert
+        let mut stream = TcpStream::connect(addr)?;
+        writeln!(stream, "C:/Saves/Save_0002.pcsav")?;
+        drop(stream);
+        assert!(send(addr, "nope", "C:/Saves/Save_0003.pcsav").is_err());
+        assert!(event_rx.recv_timeout(Duration::from_millis(200)).is_err());
+        Ok(())
+    }
+}
@@ -6,18 +6,73 @@ use serde::{
     },
     Deserialize,
 };
-use std::{convert::TryInto, mem::size_of};
+use std::{any, convert::TryInto, fmt::Write, mem::size_of};
 
-use super::Result;
+use super::{ErrorContext, Result};
+
+enum PathSegment {
+    Field(&'static str),
+    Index(usize),
+}
 
 pub struct Deserializer<'de> {
+    start: &'de [u8],
     input: &'de [u8],
+    path: Vec<PathSegment>,
 }
 
 impl<'de> Deserializer<'de> {
     pub fn from_bytes<T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
-        let mut deserializer = Deserializer { input };
-        T::deserialize(&mut deserializer)
+        Self::from_bytes_partial(input).map(|(value, _)| value)
+    }
+
+    // Renvoie aussi le nombre d'octets lus, la suite de l'entrée n'est pas interprétée
+    pub fn from_bytes_partial<T: Deserialize<'de>>(input: &'de [u8]) -> Result<(T, usize)> {
+        let mut deserializer = Deserializer { start: input, input, path: Vec::new() };
+        match T::deserialize(&mut deserializer) {
+            Ok(value) => {
+                let offset = deserializer.offset();
+                tracing::debug!(
+                    "Deserialized {} ({} / {} bytes)",
+                    any::type_name::<T>(),
+                    offset,
+                    input.len()
+                );
+                Ok((value, offset))
+            }
+            Err(err) => {
+                let offset = deserializer.offset();
+                let path = deserializer.breadcrumb();
+                // Aussi utilisé pour détecter le format, pas forcément une vraie erreur
+                tracing::debug!(
+                    "Failed to deserialize {} at offset 0x{:X}, in `{}` : {}",
+                    any::type_name::<T>(),
+                    offset,
+                    path,
+                    err
+                );
+                Err(super::Error::Context(Box::new(ErrorContext::new(err, input, offset, path))))
+            }
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.start.len() - self.input.len()
+    }
+
+    // Le chemin n'est pas dépilé en cas d'erreur
+    fn breadcrumb(&self) -> String {
+        let mut breadcrumb = String::new();
+        for segment in &self.path {
+            let _ = match segment {
+                PathSegment::Field(field) if breadcrumb.is_empty() => {
+                    write!(breadcrumb, "{}", field)
+                }
+                PathSegment::Field(field) => write!(breadcrumb, ".{}", field),
+                PathSegment::Index(idx) => write!(breadcrumb, "[{}]", idx),
+            };
+        }
+        breadcrumb
     }
 
     fn read(&mut self, num_bytes: usize) -> Result<&[u8]> {
@@ -190,7 +245,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         const SIZE: usize = size_of::<u32>();
         let bytes = self.read(SIZE)?;
         let len = <u32>::from_le_bytes(bytes.try_into().map_err(Error::custom)?) as usize;
-        visitor.visit_seq(SizedSeqMap::new(&mut self, len))
+        visitor.visit_seq(SizedSeqMap::new(&mut self, len, None))
     }
 
     fn deserialize_map<V>(mut self, visitor: V) -> Result<V::Value>
@@ -200,14 +255,14 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         const SIZE: usize = size_of::<u32>();
         let bytes = self.read(SIZE)?;
         let len = <u32>::from_le_bytes(bytes.try_into().map_err(Error::custom)?) as usize;
-        visitor.visit_map(SizedSeqMap::new(&mut self, len))
+        visitor.visit_map(SizedSeqMap::new(&mut self, len, None))
     }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_seq(SizedSeqMap::new(self, len))
+        visitor.visit_seq(SizedSeqMap::new(self, len, None))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -220,12 +275,12 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     }
 
     fn deserialize_struct<V>(
-        self, name: &'static str, fields: &'static [&'static str], visitor: V,
+        self, _: &'static str, fields: &'static [&'static str], visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_tuple_struct(name, fields.len(), visitor)
+        visitor.visit_seq(SizedSeqMap::new(self, fields.len(), Some(fields)))
     }
 
     fn deserialize_enum<V>(
@@ -264,11 +319,24 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 struct SizedSeqMap<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
     len: usize,
+    idx: usize,
+    fields: Option<&'static [&'static str]>,
 }
 
 impl<'a, 'de> SizedSeqMap<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, len: usize) -> Self {
-        SizedSeqMap { de, len }
+    fn new(
+        de: &'a mut Deserializer<'de>, len: usize, fields: Option<&'static [&'static str]>,
+    ) -> Self {
+        SizedSeqMap { de, len, idx: 0, fields }
+    }
+
+    fn push_path(&mut self) {
+        let segment = match self.fields.and_then(|fields| fields.get(self.idx)) {
+            Some(field) => PathSegment::Field(field),
+            None => PathSegment::Index(self.idx),
+        };
+        self.de.path.push(segment);
+        self.idx += 1;
     }
 }
 
@@ -288,7 +356,10 @@ impl<'de, 'a> SeqAccess<'de> for SizedSeqMap<'a, 'de> {
         }
         self.len -= 1;
 
-        seed.deserialize(&mut *self.de).map(Some)
+        self.push_path();
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.path.pop();
+        Ok(Some(value))
     }
 }
 
@@ -308,6 +379,8 @@ impl<'de, 'a> MapAccess<'de> for SizedSeqMap<'a, 'de> {
         }
         self.len -= 1;
 
+        // Dépilé après la valeur
+        self.push_path();
         seed.deserialize(&mut *self.de).map(Some)
     }
 
@@ -315,7 +388,9 @@ impl<'de, 'a> MapAccess<'de> for SizedSeqMap<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut *self.de)
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.path.pop();
+        Ok(value)
     }
 }
 
@@ -1,5 +1,5 @@
 use serde::{de, ser};
-use std::fmt::{self, Display};
+use std::fmt::{self, Display, Write};
 
 mod serializer;
 pub use serializer::*;
@@ -13,6 +13,16 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Message(String),
     Eof,
+    Context(Box<ErrorContext>),
+}
+
+impl Error {
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::Context(context) => Some(context),
+            _ => None,
+        }
+    }
 }
 
 impl ser::Error for Error {
@@ -34,7 +44,91 @@ impl Display for Error {
             Error::Eof => formatter.write_str(
                 "unexpected end of file, some data in your save are unexpected or your save is corrupted ?\n\
                 Save again and retry. If this error persists, please report a bug with your save attached"),
+            Error::Context(context) => {
+                write!(formatter, "{}\nat offset 0x{:X}", context.error, context.offset)?;
+                if !context.path.is_empty() {
+                    write!(formatter, ", in `{}`", context.path)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// Contexte d'une erreur de désérialisation, sans données personnelles
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorContext {
+    pub error: Error,
+    pub offset: usize,
+    pub path: String,
+    pub input_len: usize,
+    header: Vec<u8>,
+    window_start: usize,
+    window: Vec<u8>,
+}
+
+impl ErrorContext {
+    const HEADER_LEN: usize = 16;
+    const WINDOW_LEN: usize = 64;
+
+    pub fn new(error: Error, input: &[u8], offset: usize, path: String) -> Self {
+        let header = sanitize(&input[..input.len().min(Self::HEADER_LEN)]);
+
+        let window_start = offset.saturating_sub(Self::WINDOW_LEN);
+        let window_end = input.len().min(offset + Self::WINDOW_LEN);
+        let window = sanitize(&input[window_start..window_end]);
+
+        Self { error, offset, path, input_len: input.len(), header, window_start, window }
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        let _ = writeln!(report, "Trilogy Save Editor v{}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(report, "Error: {}", self.error);
+        let _ = writeln!(report, "Offset: 0x{:08X} / 0x{:08X}", self.offset, self.input_len);
+        let _ = writeln!(report, "Field: {}", self.path);
+        let _ = writeln!(report, "\nHeader:");
+        hex_dump(&mut report, 0, &self.header, None);
+        let _ = writeln!(report, "\nContext:");
+        hex_dump(&mut report, self.window_start, &self.window, Some(self.offset));
+        report
+    }
+}
+
+// Masque les chaînes de caractères (noms, etc.)
+fn sanitize(bytes: &[u8]) -> Vec<u8> {
+    const MIN_STRING_LEN: usize = 3;
+
+    let is_text = |i: usize| {
+        let is_printable = |b: u8| b.is_ascii_graphic() || b == b' ';
+        // Ascii ou UTF-16
+        is_printable(bytes[i]) || (bytes[i] == 0 && i > 0 && is_printable(bytes[i - 1]))
+    };
+
+    let mut sanitized = bytes.to_vec();
+    let mut start = 0;
+    while start < bytes.len() {
+        let mut end = start;
+        while end < bytes.len() && is_text(end) {
+            end += 1;
+        }
+
+        if end - start >= MIN_STRING_LEN {
+            sanitized[start..end].iter_mut().for_each(|b| *b = b'*');
+        }
+        start = end + 1;
+    }
+    sanitized
+}
+
+fn hex_dump(output: &mut String, start: usize, bytes: &[u8], highlight: Option<usize>) {
+    for (i, line) in bytes.chunks(16).enumerate() {
+        let _ = write!(output, "{:08X} ", start + i * 16);
+        for (j, byte) in line.iter().enumerate() {
+            let separator = if highlight == Some(start + i * 16 + j) { '>' } else { ' ' };
+            let _ = write!(output, "{}{:02X}", separator, byte);
         }
+        let _ = writeln!(output);
     }
 }
 
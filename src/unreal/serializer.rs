@@ -8,6 +8,8 @@ use super::Result;
 
 pub struct Serializer {
     output: Vec<u8>,
+    depth: usize,
+    sections: Vec<(&'static str, usize)>,
 }
 
 impl Serializer {
@@ -16,10 +18,25 @@ impl Serializer {
     where
         T: Serialize,
     {
-        let mut serializer = Serializer { output: Vec::new() };
+        let mut serializer = Serializer { output: Vec::new(), depth: 0, sections: Vec::new() };
         value.serialize(&mut serializer)?;
+        tracing::debug!(
+            "Serialized {} ({} bytes)",
+            std::any::type_name::<T>(),
+            serializer.output.len()
+        );
         Ok(serializer.output)
     }
+
+    // Taille de chaque champ de la structure racine
+    pub fn section_sizes<T>(value: &T) -> Result<Vec<(&'static str, usize)>>
+    where
+        T: Serialize,
+    {
+        let mut serializer = Serializer { output: Vec::new(), depth: 0, sections: Vec::new() };
+        value.serialize(&mut serializer)?;
+        Ok(serializer.sections)
+    }
 }
 
 macro_rules! unimpl_serialize {
@@ -211,11 +228,19 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
     type Ok = ();
     type Error = super::Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, _: &'static str, value: &T) -> Result<()>
+    fn serialize_field<T: ?Sized>(&mut self, name: &'static str, value: &T) -> Result<()>
     where
         T: Serialize,
     {
-        value.serialize(&mut **self)
+        let start = self.output.len();
+        self.depth += 1;
+        value.serialize(&mut **self)?;
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            self.sections.push((name, self.output.len() - start));
+        }
+        Ok(())
     }
 
     fn end(self) -> Result<()> {